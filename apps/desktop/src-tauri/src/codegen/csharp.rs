@@ -0,0 +1,120 @@
+use super::{file_name, quote, MultipartPart, PreparedBody, PreparedRequest};
+
+/// Renders a request as a C# top-level program using `HttpClient`.
+pub fn generate(req: &PreparedRequest) -> String {
+    let mut out = String::from("using System.Net.Http.Headers;\n\n");
+    out.push_str("using var client = new HttpClient();\n");
+    out.push_str(&format!(
+        "var request = new HttpRequestMessage(new HttpMethod({}), {});\n",
+        quote(&req.method),
+        quote(&req.url)
+    ));
+    for (key, value) in &req.headers {
+        out.push_str(&format!(
+            "request.Headers.TryAddWithoutValidation({}, {});\n",
+            quote(key),
+            quote(value)
+        ));
+    }
+
+    // Content-Type belongs to the content, not the request headers
+    let content_type = req.content_type.as_deref();
+    match &req.body {
+        PreparedBody::None => {
+            if let Some(content_type) = content_type {
+                out.push_str(&format!(
+                    "request.Content = new ByteArrayContent(Array.Empty<byte>());\nrequest.Content.Headers.ContentType = MediaTypeHeaderValue.Parse({});\n",
+                    quote(content_type)
+                ));
+            }
+        }
+        PreparedBody::Raw(content) => {
+            out.push_str(&format!(
+                "request.Content = new StringContent({});\n",
+                quote(content)
+            ));
+            if let Some(content_type) = content_type {
+                out.push_str(&format!(
+                    "request.Content.Headers.ContentType = MediaTypeHeaderValue.Parse({});\n",
+                    quote(content_type)
+                ));
+            }
+        }
+        PreparedBody::Form(fields) => {
+            out.push_str("request.Content = new FormUrlEncodedContent(new[]\n{\n");
+            for (key, value) in fields {
+                out.push_str(&format!(
+                    "    new KeyValuePair<string, string>({}, {}),\n",
+                    quote(key),
+                    quote(value)
+                ));
+            }
+            out.push_str("});\n");
+            if let Some(content_type) =
+                content_type.filter(|c| *c != "application/x-www-form-urlencoded")
+            {
+                out.push_str(&format!(
+                    "request.Content.Headers.ContentType = MediaTypeHeaderValue.Parse({});\n",
+                    quote(content_type)
+                ));
+            }
+        }
+        PreparedBody::Multipart(parts) => {
+            out.push_str("var content = new MultipartFormDataContent();\n");
+            for part in parts {
+                match part {
+                    MultipartPart::Field { name, value } => {
+                        out.push_str(&format!(
+                            "content.Add(new StringContent({}), {});\n",
+                            quote(value),
+                            quote(name)
+                        ));
+                    }
+                    MultipartPart::File { name, path } => {
+                        out.push_str(&format!(
+                            "content.Add(new StreamContent(File.OpenRead({})), {}, {});\n",
+                            quote(path),
+                            quote(name),
+                            quote(file_name(path))
+                        ));
+                    }
+                }
+            }
+            out.push_str("request.Content = content;\n");
+        }
+    }
+
+    out.push_str(
+        "\nvar response = await client.SendAsync(request);\nConsole.WriteLine((int)response.StatusCode);\nConsole.WriteLine(await response.Content.ReadAsStringAsync());\n",
+    );
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::codegen::tests::request;
+    use crate::codegen::{generate, CodegenTarget};
+    use crate::models::request::{BodyType, HttpMethod};
+
+    #[test]
+    fn renders_json_and_multipart() {
+        let req = request(HttpMethod::POST, BodyType::Json, Some("{}"));
+        let code = generate(&req, CodegenTarget::CsharpHttpClient, None);
+        assert!(code.contains(
+            "request.Headers.TryAddWithoutValidation(\"Authorization\", \"Bearer {{token}}\");"
+        ));
+        assert!(code.contains("request.Content = new StringContent(\"{}\");"));
+        assert!(code.contains("MediaTypeHeaderValue.Parse(\"application/json\")"));
+
+        let req = request(
+            HttpMethod::POST,
+            BodyType::Multipart,
+            Some("a=1&f=@/tmp/f.txt"),
+        );
+        let code = generate(&req, CodegenTarget::CsharpHttpClient, None);
+        assert!(code.contains("content.Add(new StringContent(\"1\"), \"a\");"));
+        assert!(code.contains(
+            "content.Add(new StreamContent(File.OpenRead(\"/tmp/f.txt\")), \"f\", \"f.txt\");"
+        ));
+    }
+}
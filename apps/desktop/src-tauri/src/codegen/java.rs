@@ -0,0 +1,154 @@
+use super::{file_name, quote, MultipartPart, PreparedBody, PreparedRequest};
+
+/// Renders a request using `java.net.http.HttpClient` (Java 11+).
+pub fn generate(req: &PreparedRequest) -> String {
+    let mut imports = vec![
+        "java.net.URI",
+        "java.net.http.HttpClient",
+        "java.net.http.HttpRequest",
+        "java.net.http.HttpResponse",
+    ];
+    let mut setup = String::new();
+    let mut content_type = req.content_type.as_deref().map(quote);
+
+    let publisher = match &req.body {
+        PreparedBody::None => "HttpRequest.BodyPublishers.noBody()".to_string(),
+        PreparedBody::Raw(content) => {
+            format!("HttpRequest.BodyPublishers.ofString({})", quote(content))
+        }
+        PreparedBody::Form(fields) => {
+            imports.extend(["java.net.URLEncoder", "java.nio.charset.StandardCharsets"]);
+            let pairs: Vec<String> = fields
+                .iter()
+                .map(|(k, v)| {
+                    format!(
+                        "            URLEncoder.encode({}, StandardCharsets.UTF_8) + \"=\" + URLEncoder.encode({}, StandardCharsets.UTF_8)",
+                        quote(k),
+                        quote(v)
+                    )
+                })
+                .collect();
+            setup.push_str(&format!(
+                "        String form = String.join(\"&\",\n{});\n\n",
+                pairs.join(",\n")
+            ));
+            "HttpRequest.BodyPublishers.ofString(form)".to_string()
+        }
+        PreparedBody::Multipart(parts) => {
+            // HttpClient has no multipart support, so the body is assembled by hand
+            imports.extend([
+                "java.io.ByteArrayOutputStream",
+                "java.nio.charset.StandardCharsets",
+                "java.nio.file.Files",
+                "java.nio.file.Path",
+            ]);
+            setup.push_str(
+                "        String boundary = \"SteqBoundary\" + System.currentTimeMillis();\n",
+            );
+            setup.push_str("        ByteArrayOutputStream body = new ByteArrayOutputStream();\n");
+            for part in parts {
+                match part {
+                    MultipartPart::Field { name, value } => {
+                        let section = format!(
+                            "\r\nContent-Disposition: form-data; name=\"{}\"\r\n\r\n{}\r\n",
+                            name, value
+                        );
+                        setup.push_str(&format!(
+                            "        body.write((\"--\" + boundary + {}).getBytes(StandardCharsets.UTF_8));\n",
+                            quote(&section)
+                        ));
+                    }
+                    MultipartPart::File { name, path } => {
+                        let section = format!(
+                            "\r\nContent-Disposition: form-data; name=\"{}\"; filename=\"{}\"\r\nContent-Type: application/octet-stream\r\n\r\n",
+                            name,
+                            file_name(path)
+                        );
+                        setup.push_str(&format!(
+                            "        body.write((\"--\" + boundary + {}).getBytes(StandardCharsets.UTF_8));\n",
+                            quote(&section)
+                        ));
+                        setup.push_str(&format!(
+                            "        body.write(Files.readAllBytes(Path.of({})));\n",
+                            quote(path)
+                        ));
+                        setup.push_str(
+                            "        body.write(\"\\r\\n\".getBytes(StandardCharsets.UTF_8));\n",
+                        );
+                    }
+                }
+            }
+            setup.push_str("        body.write((\"--\" + boundary + \"--\\r\\n\").getBytes(StandardCharsets.UTF_8));\n\n");
+            content_type = Some("\"multipart/form-data; boundary=\" + boundary".to_string());
+            "HttpRequest.BodyPublishers.ofByteArray(body.toByteArray())".to_string()
+        }
+    };
+    imports.sort();
+    imports.dedup();
+
+    let mut out = String::new();
+    for import in imports {
+        out.push_str(&format!("import {};\n", import));
+    }
+    out.push_str(
+        "\npublic class Main {\n    public static void main(String[] args) throws Exception {\n",
+    );
+    out.push_str("        HttpClient client = HttpClient.newHttpClient();\n\n");
+    out.push_str(&setup);
+    out.push_str(&format!(
+        "        HttpRequest request = HttpRequest.newBuilder()\n            .uri(URI.create({}))\n",
+        quote(&req.url)
+    ));
+    for (key, value) in &req.headers {
+        out.push_str(&format!(
+            "            .header({}, {})\n",
+            quote(key),
+            quote(value)
+        ));
+    }
+    if let Some(content_type) = content_type {
+        out.push_str(&format!(
+            "            .header(\"Content-Type\", {})\n",
+            content_type
+        ));
+    }
+    out.push_str(&format!(
+        "            .method({}, {})\n            .build();\n\n",
+        quote(&req.method),
+        publisher
+    ));
+    out.push_str(
+        "        HttpResponse<String> response = client.send(request, HttpResponse.BodyHandlers.ofString());\n        System.out.println(response.statusCode());\n        System.out.println(response.body());\n    }\n}\n",
+    );
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::codegen::tests::request;
+    use crate::codegen::{generate, CodegenTarget};
+    use crate::models::request::{BodyType, HttpMethod};
+
+    #[test]
+    fn renders_none_form_and_multipart() {
+        let req = request(HttpMethod::DELETE, BodyType::None, None);
+        let code = generate(&req, CodegenTarget::JavaHttpClient, None);
+        assert!(code.contains(".method(\"DELETE\", HttpRequest.BodyPublishers.noBody())"));
+        assert!(!code.contains("Content-Type"));
+
+        let req = request(HttpMethod::POST, BodyType::FormUrlEncoded, Some("a=1"));
+        let code = generate(&req, CodegenTarget::JavaHttpClient, None);
+        assert!(code.contains("URLEncoder.encode(\"a\", StandardCharsets.UTF_8) + \"=\" + URLEncoder.encode(\"1\", StandardCharsets.UTF_8)"));
+
+        let req = request(
+            HttpMethod::POST,
+            BodyType::Multipart,
+            Some("a=1&f=@/tmp/f.txt"),
+        );
+        let code = generate(&req, CodegenTarget::JavaHttpClient, None);
+        assert!(code.contains("name=\\\"f\\\"; filename=\\\"f.txt\\\""));
+        assert!(code.contains("Files.readAllBytes(Path.of(\"/tmp/f.txt\"))"));
+        assert!(code
+            .contains(".header(\"Content-Type\", \"multipart/form-data; boundary=\" + boundary)"));
+    }
+}
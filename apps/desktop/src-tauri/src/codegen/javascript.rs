@@ -0,0 +1,105 @@
+use super::{quote, MultipartPart, PreparedBody, PreparedRequest};
+
+/// Renders a request for Node.js using axios.
+pub fn generate(req: &PreparedRequest) -> String {
+    let mut out = String::from("const axios = require('axios');\n");
+    let mut setup = String::new();
+    let mut data = None;
+    let mut spread_form_headers = false;
+
+    match &req.body {
+        PreparedBody::None => {}
+        PreparedBody::Raw(content) => data = Some(quote(content)),
+        PreparedBody::Form(fields) => {
+            setup.push_str("const data = new URLSearchParams();\n");
+            for (key, value) in fields {
+                setup.push_str(&format!("data.append({}, {});\n", quote(key), quote(value)));
+            }
+            data = Some("data".to_string());
+        }
+        PreparedBody::Multipart(parts) => {
+            out.push_str("const FormData = require('form-data');\n");
+            if parts
+                .iter()
+                .any(|p| matches!(p, MultipartPart::File { .. }))
+            {
+                out.push_str("const fs = require('fs');\n");
+            }
+            setup.push_str("const data = new FormData();\n");
+            for part in parts {
+                match part {
+                    MultipartPart::Field { name, value } => {
+                        setup.push_str(&format!(
+                            "data.append({}, {});\n",
+                            quote(name),
+                            quote(value)
+                        ));
+                    }
+                    MultipartPart::File { name, path } => {
+                        setup.push_str(&format!(
+                            "data.append({}, fs.createReadStream({}));\n",
+                            quote(name),
+                            quote(path)
+                        ));
+                    }
+                }
+            }
+            data = Some("data".to_string());
+            spread_form_headers = true;
+        }
+    }
+
+    out.push('\n');
+    if !setup.is_empty() {
+        out.push_str(&setup);
+        out.push('\n');
+    }
+
+    out.push_str("axios\n  .request({\n");
+    out.push_str(&format!("    method: {},\n", quote(&req.method)));
+    out.push_str(&format!("    url: {},\n", quote(&req.url)));
+    let headers = req.all_headers();
+    if !headers.is_empty() || spread_form_headers {
+        out.push_str("    headers: {\n");
+        if spread_form_headers {
+            out.push_str("      ...data.getHeaders(),\n");
+        }
+        for (key, value) in &headers {
+            out.push_str(&format!("      {}: {},\n", quote(key), quote(value)));
+        }
+        out.push_str("    },\n");
+    }
+    if let Some(data) = data {
+        out.push_str(&format!("    data: {},\n", data));
+    }
+    out.push_str(
+        "  })\n  .then((response) => {\n    console.log(response.status);\n    console.log(response.data);\n  })\n  .catch((error) => {\n    console.error(error);\n  });\n",
+    );
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::codegen::tests::request;
+    use crate::codegen::{generate, CodegenTarget};
+    use crate::models::request::{BodyType, HttpMethod};
+
+    #[test]
+    fn renders_graphql_and_multipart() {
+        let body = r#"{"query":"{ me { id } }","variables":"","operationName":""}"#;
+        let req = request(HttpMethod::POST, BodyType::GraphQL, Some(body));
+        let code = generate(&req, CodegenTarget::NodeAxios, None);
+        assert!(code.contains("    data: \"{\\\"query\\\":\\\"{ me { id } }\\\"}\",\n"));
+        assert!(code.contains("      \"Content-Type\": \"application/json\",\n"));
+
+        let req = request(
+            HttpMethod::POST,
+            BodyType::Multipart,
+            Some("a=1&f=@/tmp/f.txt"),
+        );
+        let code = generate(&req, CodegenTarget::NodeAxios, None);
+        assert!(code.contains("const fs = require('fs');"));
+        assert!(code.contains("data.append(\"f\", fs.createReadStream(\"/tmp/f.txt\"));"));
+        assert!(code.contains("...data.getHeaders(),"));
+    }
+}
@@ -0,0 +1,446 @@
+use serde::{Deserialize, Serialize};
+
+use crate::http::executor::{build_graphql_body, urlencoding_encode};
+use crate::io::percent_decode;
+use crate::models::request::{ApiRequest, BodyType, KeyValue};
+
+pub mod csharp;
+pub mod go;
+pub mod java;
+pub mod javascript;
+pub mod powershell;
+pub mod python;
+pub mod rust;
+
+/// Languages and HTTP clients a request can be rendered for.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CodegenTarget {
+    PythonRequests,
+    GoNetHttp,
+    NodeAxios,
+    RustReqwest,
+    JavaHttpClient,
+    CsharpHttpClient,
+    Powershell,
+}
+
+/// A request flattened into what every generator needs: enabled headers only,
+/// the final URL, and a body split by how clients have to build it.
+#[derive(Debug, Clone)]
+pub struct PreparedRequest {
+    pub method: String,
+    pub url: String,
+    /// Enabled headers without `Content-Type`, which is kept in `content_type`.
+    pub headers: Vec<(String, String)>,
+    /// `None` for multipart bodies, where the client sets the boundary itself.
+    pub content_type: Option<String>,
+    pub body: PreparedBody,
+}
+
+#[derive(Debug, Clone)]
+pub enum PreparedBody {
+    None,
+    /// Sent as-is; GraphQL bodies are already rendered to their JSON payload.
+    Raw(String),
+    Form(Vec<(String, String)>),
+    Multipart(Vec<MultipartPart>),
+}
+
+#[derive(Debug, Clone)]
+pub enum MultipartPart {
+    Field { name: String, value: String },
+    File { name: String, path: String },
+}
+
+impl PreparedRequest {
+    /// Headers with `Content-Type` appended, for clients that need it set explicitly.
+    pub fn all_headers(&self) -> Vec<(String, String)> {
+        let mut headers = self.headers.clone();
+        if let Some(content_type) = &self.content_type {
+            headers.push(("Content-Type".to_string(), content_type.clone()));
+        }
+        headers
+    }
+}
+
+/// Renders a request as a code snippet. With `variables`, `{{name}}` references
+/// are resolved first; references without a value are left in place.
+pub fn generate(
+    request: &ApiRequest,
+    target: CodegenTarget,
+    variables: Option<&[(String, String)]>,
+) -> String {
+    let prepared = prepare(request, variables.unwrap_or(&[]));
+    match target {
+        CodegenTarget::PythonRequests => python::generate(&prepared),
+        CodegenTarget::GoNetHttp => go::generate(&prepared),
+        CodegenTarget::NodeAxios => javascript::generate(&prepared),
+        CodegenTarget::RustReqwest => rust::generate(&prepared),
+        CodegenTarget::JavaHttpClient => java::generate(&prepared),
+        CodegenTarget::CsharpHttpClient => csharp::generate(&prepared),
+        CodegenTarget::Powershell => powershell::generate(&prepared),
+    }
+}
+
+pub fn prepare(request: &ApiRequest, variables: &[(String, String)]) -> PreparedRequest {
+    let resolve = |s: &str| resolve_string(s, variables);
+    let resolve_all = |values: &[KeyValue]| -> Vec<(String, String)> {
+        values
+            .iter()
+            .filter(|kv| kv.enabled && !kv.key.trim().is_empty())
+            .map(|kv| (resolve(&kv.key), resolve(&kv.value)))
+            .collect()
+    };
+
+    let url = build_url(
+        &resolve(&request.url),
+        &resolve_all(&request.path_params),
+        &resolve_all(&request.query_params),
+    );
+
+    let mut headers = resolve_all(&request.headers);
+    let explicit_content_type = headers
+        .iter()
+        .position(|(k, _)| k.eq_ignore_ascii_case("content-type"))
+        .map(|i| headers.remove(i).1);
+
+    let content = request.body_content.as_deref().filter(|c| !c.is_empty());
+    let (default_content_type, body) = match (&request.body_type, content) {
+        (BodyType::None, _) | (_, None) => (None, PreparedBody::None),
+        (BodyType::Json, Some(c)) => (Some("application/json"), PreparedBody::Raw(resolve(c))),
+        (BodyType::Text, Some(c)) => (Some("text/plain"), PreparedBody::Raw(resolve(c))),
+        (BodyType::GraphQL, Some(c)) => (
+            Some("application/json"),
+            PreparedBody::Raw(build_graphql_body(&resolve_graphql(c, variables))),
+        ),
+        (BodyType::FormUrlEncoded, Some(c)) => (
+            Some("application/x-www-form-urlencoded"),
+            PreparedBody::Form(
+                form_fields(c)
+                    .map(|(k, v)| (resolve(&k), resolve(&v)))
+                    .collect(),
+            ),
+        ),
+        (BodyType::Multipart, Some(c)) => (
+            None,
+            PreparedBody::Multipart(
+                form_fields(c)
+                    .map(|(name, value)| match value.strip_prefix('@') {
+                        Some(path) => MultipartPart::File {
+                            name: resolve(&name),
+                            path: resolve(path),
+                        },
+                        None => MultipartPart::Field {
+                            name: resolve(&name),
+                            value: resolve(&value),
+                        },
+                    })
+                    .collect(),
+            ),
+        ),
+    };
+
+    let content_type = match body {
+        PreparedBody::Multipart(_) => None,
+        PreparedBody::None => explicit_content_type,
+        _ => explicit_content_type.or(default_content_type.map(|s| s.to_string())),
+    };
+
+    PreparedRequest {
+        method: request.method.as_str().to_string(),
+        url,
+        headers,
+        content_type,
+        body,
+    }
+}
+
+/// Same rules as the frontend's `resolveString`: `{{name}}` with word characters
+/// only, unknown names untouched.
+pub fn resolve_string(template: &str, variables: &[(String, String)]) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let resolved = after.find("}}").and_then(|end| {
+            let name = &after[..end];
+            let is_word = !name.is_empty() && name.chars().all(|c| c.is_alphanumeric() || c == '_');
+            let value = variables.iter().find(|(k, _)| k == name).map(|(_, v)| v);
+            value.filter(|_| is_word).map(|v| (v, end))
+        });
+        match resolved {
+            Some((value, end)) => {
+                out.push_str(value);
+                rest = &after[end + 2..];
+            }
+            None => {
+                out.push_str("{{");
+                rest = after;
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Resolves inside the stored GraphQL fields so substituted values are JSON-escaped.
+fn resolve_graphql(content: &str, variables: &[(String, String)]) -> String {
+    match serde_json::from_str::<serde_json::Value>(content) {
+        Ok(serde_json::Value::Object(mut fields)) => {
+            for value in fields.values_mut() {
+                if let Some(s) = value.as_str() {
+                    *value = serde_json::Value::String(resolve_string(s, variables));
+                }
+            }
+            serde_json::Value::Object(fields).to_string()
+        }
+        _ => resolve_string(content, variables),
+    }
+}
+
+fn form_fields(content: &str) -> impl Iterator<Item = (String, String)> + '_ {
+    content.split('&').filter(|f| !f.is_empty()).map(|field| {
+        let (key, value) = field.split_once('=').unwrap_or((field, ""));
+        (percent_decode(key), percent_decode(value))
+    })
+}
+
+/// Builds the final URL like the executor does, but leaves `{{name}}`
+/// references readable instead of percent-encoding them.
+fn build_url(url: &str, path_params: &[(String, String)], query: &[(String, String)]) -> String {
+    let (base, rest) = match url.find(['?', '#']) {
+        Some(i) => url.split_at(i),
+        None => (url, ""),
+    };
+    let segments: Vec<String> = base
+        .split('/')
+        .map(|segment| {
+            segment
+                .strip_prefix(':')
+                .and_then(|name| path_params.iter().find(|(k, _)| k == name))
+                .map(|(_, v)| encode_preserving_variables(v))
+                .unwrap_or_else(|| segment.to_string())
+        })
+        .collect();
+    let mut url = format!("{}{}", segments.join("/"), rest);
+
+    if !query.is_empty() {
+        let query_string: Vec<String> = query
+            .iter()
+            .map(|(k, v)| {
+                format!(
+                    "{}={}",
+                    encode_preserving_variables(k),
+                    encode_preserving_variables(v)
+                )
+            })
+            .collect();
+        let separator = if url.contains('?') { "&" } else { "?" };
+        url = format!("{}{}{}", url, separator, query_string.join("&"));
+    }
+    url
+}
+
+fn encode_preserving_variables(s: &str) -> String {
+    let mut out = String::new();
+    let mut rest = s;
+    while let Some(start) = rest.find("{{") {
+        let Some(end) = rest[start..].find("}}") else {
+            break;
+        };
+        out.push_str(&urlencoding_encode(&rest[..start]));
+        out.push_str(&rest[start..start + end + 2]);
+        rest = &rest[start + end + 2..];
+    }
+    out.push_str(&urlencoding_encode(rest));
+    out
+}
+
+/// Double-quoted literal with C-style escapes. JSON string syntax is valid in
+/// Python, Go, JavaScript, Java and C#.
+pub(crate) fn quote(s: &str) -> String {
+    serde_json::to_string(s).unwrap_or_else(|_| format!("\"{}\"", s))
+}
+
+/// File name part of a path, for multipart filename parameters.
+pub(crate) fn file_name(path: &str) -> &str {
+    path.rsplit(['/', '\\']).next().unwrap_or(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::request::{HttpMethod, RequestKind};
+
+    pub(super) fn request(
+        method: HttpMethod,
+        body_type: BodyType,
+        body: Option<&str>,
+    ) -> ApiRequest {
+        ApiRequest {
+            id: "r1".to_string(),
+            name: "Sample".to_string(),
+            method,
+            url: "{{baseUrl}}/users/:id".to_string(),
+            headers: vec![
+                KeyValue {
+                    key: "Authorization".to_string(),
+                    value: "Bearer {{token}}".to_string(),
+                    enabled: true,
+                },
+                KeyValue {
+                    key: "X-Disabled".to_string(),
+                    value: "1".to_string(),
+                    enabled: false,
+                },
+            ],
+            query_params: vec![KeyValue {
+                key: "q".to_string(),
+                value: "a b".to_string(),
+                enabled: true,
+            }],
+            body_type,
+            body_content: body.map(|b| b.to_string()),
+            description: None,
+            path_params: vec![KeyValue {
+                key: "id".to_string(),
+                value: "{{userId}}".to_string(),
+                enabled: true,
+            }],
+            scripts: None,
+            kind: RequestKind::Http,
+            collection_id: None,
+            folder_id: None,
+            sort_order: 0,
+            created_at: String::new(),
+            updated_at: String::new(),
+        }
+    }
+
+    fn vars() -> Vec<(String, String)> {
+        vec![
+            ("baseUrl".to_string(), "https://api.example.com".to_string()),
+            ("token".to_string(), "abc".to_string()),
+            ("userId".to_string(), "7".to_string()),
+            ("name".to_string(), "Ada \\u00e9".to_string()),
+        ]
+    }
+
+    #[test]
+    fn resolve_string_leaves_unknown_references() {
+        let vars = vars();
+        assert_eq!(
+            resolve_string("{{baseUrl}}/{{missing}}", &vars),
+            "https://api.example.com/{{missing}}"
+        );
+        assert_eq!(resolve_string("{{ token }} {{", &vars), "{{ token }} {{");
+    }
+
+    #[test]
+    fn prepare_unresolved_keeps_variables_readable() {
+        let req = request(HttpMethod::GET, BodyType::None, None);
+        let prepared = prepare(&req, &[]);
+        assert_eq!(prepared.url, "{{baseUrl}}/users/{{userId}}?q=a%20b");
+        assert_eq!(
+            prepared.headers,
+            vec![("Authorization".to_string(), "Bearer {{token}}".to_string())]
+        );
+        assert!(prepared.content_type.is_none());
+        assert!(matches!(prepared.body, PreparedBody::None));
+    }
+
+    #[test]
+    fn prepare_resolves_variables_and_graphql_fields() {
+        let body = r#"{"query":"query($n: String) { user(name: $n) { id } }","variables":"{\"n\": \"{{name}}\"}","operationName":""}"#;
+        let req = request(HttpMethod::POST, BodyType::GraphQL, Some(body));
+        let prepared = prepare(&req, &vars());
+        assert_eq!(prepared.url, "https://api.example.com/users/7?q=a%20b");
+        assert_eq!(prepared.headers[0].1, "Bearer abc");
+        assert_eq!(prepared.content_type.as_deref(), Some("application/json"));
+        let PreparedBody::Raw(payload) = &prepared.body else {
+            panic!("expected raw body");
+        };
+        let payload: serde_json::Value = serde_json::from_str(payload).unwrap();
+        assert_eq!(payload["variables"]["n"], "Ada \u{e9}");
+        assert!(payload.get("operationName").is_none());
+    }
+
+    #[test]
+    fn prepare_splits_form_and_multipart_bodies() {
+        let mut req = request(
+            HttpMethod::POST,
+            BodyType::FormUrlEncoded,
+            Some("a=1&b=x%20y"),
+        );
+        req.headers.push(KeyValue {
+            key: "content-type".to_string(),
+            value: "application/x-www-form-urlencoded; charset=utf-8".to_string(),
+            enabled: true,
+        });
+        let prepared = prepare(&req, &[]);
+        assert_eq!(
+            prepared.content_type.as_deref(),
+            Some("application/x-www-form-urlencoded; charset=utf-8")
+        );
+        let PreparedBody::Form(fields) = &prepared.body else {
+            panic!("expected form body");
+        };
+        assert_eq!(fields[1], ("b".to_string(), "x y".to_string()));
+
+        req.body_type = BodyType::Multipart;
+        req.body_content = Some("note=hi&file=@/tmp/a.png".to_string());
+        let prepared = prepare(&req, &[]);
+        assert!(prepared.content_type.is_none());
+        let PreparedBody::Multipart(parts) = &prepared.body else {
+            panic!("expected multipart body");
+        };
+        assert!(
+            matches!(&parts[1], MultipartPart::File { name, path } if name == "file" && path == "/tmp/a.png")
+        );
+    }
+
+    #[test]
+    fn generate_covers_every_target() {
+        let targets = [
+            CodegenTarget::PythonRequests,
+            CodegenTarget::GoNetHttp,
+            CodegenTarget::NodeAxios,
+            CodegenTarget::RustReqwest,
+            CodegenTarget::JavaHttpClient,
+            CodegenTarget::CsharpHttpClient,
+            CodegenTarget::Powershell,
+        ];
+        let bodies = [
+            (BodyType::None, None),
+            (BodyType::Json, Some("{\"a\": 1}")),
+            (BodyType::Text, Some("hello")),
+            (BodyType::FormUrlEncoded, Some("a=1")),
+            (BodyType::Multipart, Some("a=1&f=@/tmp/f.txt")),
+            (
+                BodyType::GraphQL,
+                Some(r#"{"query":"{ me { id } }","variables":"","operationName":""}"#),
+            ),
+        ];
+        for target in targets {
+            for (body_type, body) in bodies.iter().cloned() {
+                let req = request(HttpMethod::POST, body_type.clone(), body);
+                let code = generate(&req, target, None);
+                assert!(
+                    code.contains("{{baseUrl}}/users/{{userId}}?q=a%20b"),
+                    "{:?} {:?}",
+                    target,
+                    body_type
+                );
+                assert!(!code.contains("X-Disabled"), "{:?}", target);
+                if let Some(body) = body.filter(|_| matches!(body_type, BodyType::Text)) {
+                    assert!(code.contains(body), "{:?}", target);
+                }
+                if matches!(body_type, BodyType::Multipart) {
+                    assert!(code.contains("/tmp/f.txt"), "{:?}", target);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,117 @@
+use super::{MultipartPart, PreparedBody, PreparedRequest};
+
+/// Renders a request using `Invoke-WebRequest`. Multipart bodies use `-Form`,
+/// which needs PowerShell 7; repeated field names are sent as arrays.
+pub fn generate(req: &PreparedRequest) -> String {
+    let mut out = String::new();
+    let mut args = vec![
+        format!("-Uri {}", quote(&req.url)),
+        format!("-Method {}", quote(&req.method)),
+    ];
+
+    if !req.headers.is_empty() {
+        out.push_str("$headers = @{\n");
+        for (key, value) in &req.headers {
+            out.push_str(&format!("    {} = {}\n", quote(key), quote(value)));
+        }
+        out.push_str("}\n");
+        args.push("-Headers $headers".to_string());
+    }
+    if let Some(content_type) = &req.content_type {
+        args.push(format!("-ContentType {}", quote(content_type)));
+    }
+
+    match &req.body {
+        PreparedBody::None => {}
+        PreparedBody::Raw(content) => {
+            out.push_str(&format!("$body = {}\n", quote(content)));
+            args.push("-Body $body".to_string());
+        }
+        PreparedBody::Form(fields) => {
+            out.push_str("$body = @(\n");
+            for (key, value) in fields {
+                out.push_str(&format!(
+                    "    [uri]::EscapeDataString({}) + '=' + [uri]::EscapeDataString({})\n",
+                    quote(key),
+                    quote(value)
+                ));
+            }
+            out.push_str(") -join '&'\n");
+            args.push("-Body $body".to_string());
+        }
+        PreparedBody::Multipart(parts) => {
+            out.push_str("$form = [ordered]@{\n");
+            let entries = parts.iter().map(|part| match part {
+                MultipartPart::Field { name, value } => (name, quote(value)),
+                MultipartPart::File { name, path } => {
+                    (name, format!("(Get-Item -Path {})", quote(path)))
+                }
+            });
+            for (name, values) in group(entries) {
+                out.push_str(&format!("    {} = {}\n", quote(name), values));
+            }
+            out.push_str("}\n");
+            args.push("-Form $form".to_string());
+        }
+    }
+
+    if !out.is_empty() {
+        out.push('\n');
+    }
+    out.push_str(&format!(
+        "$response = Invoke-WebRequest {}\n$response.StatusCode\n$response.Content\n",
+        args.join(" ")
+    ));
+    out
+}
+
+/// Single-quoted literal; only `'` needs escaping, so `$` and backticks stay literal.
+fn quote(s: &str) -> String {
+    format!("'{}'", s.replace('\'', "''"))
+}
+
+fn group<'a>(entries: impl Iterator<Item = (&'a String, String)>) -> Vec<(&'a String, String)> {
+    let mut grouped: Vec<(&String, Vec<String>)> = Vec::new();
+    for (key, value) in entries {
+        match grouped.iter_mut().find(|(k, _)| *k == key) {
+            Some((_, values)) => values.push(value),
+            None => grouped.push((key, vec![value])),
+        }
+    }
+    grouped
+        .into_iter()
+        .map(|(key, values)| (key, values.join(", ")))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::codegen::tests::request;
+    use crate::codegen::{generate, CodegenTarget};
+    use crate::models::request::{BodyType, HttpMethod};
+
+    #[test]
+    fn renders_quoted_body_form_and_multipart() {
+        let req = request(HttpMethod::POST, BodyType::Text, Some("it's $HOME"));
+        let code = generate(&req, CodegenTarget::Powershell, None);
+        assert!(code.contains("$body = 'it''s $HOME'\n"));
+        assert!(
+            code.contains("-Method 'POST' -Headers $headers -ContentType 'text/plain' -Body $body")
+        );
+
+        let req = request(HttpMethod::POST, BodyType::FormUrlEncoded, Some("a=x%20y"));
+        let code = generate(&req, CodegenTarget::Powershell, None);
+        assert!(code.contains("    [uri]::EscapeDataString('a') + '=' + [uri]::EscapeDataString('x y')\n) -join '&'\n"));
+
+        let req = request(
+            HttpMethod::POST,
+            BodyType::Multipart,
+            Some("a=1&a=2&f=@/tmp/f.txt"),
+        );
+        let code = generate(&req, CodegenTarget::Powershell, None);
+        assert!(code.contains("    'a' = '1', '2'\n"));
+        assert!(code.contains("    'f' = (Get-Item -Path '/tmp/f.txt')\n"));
+        assert!(code.contains("-Form $form"));
+        assert!(!code.contains("-ContentType"));
+    }
+}
@@ -0,0 +1,101 @@
+use super::{file_name, quote, MultipartPart, PreparedBody, PreparedRequest};
+
+/// Renders a request using the `requests` library.
+pub fn generate(req: &PreparedRequest) -> String {
+    let mut out = String::from("import requests\n\n");
+    out.push_str(&format!("url = {}\n", quote(&req.url)));
+
+    let headers = req.all_headers();
+    if !headers.is_empty() {
+        out.push_str("headers = {\n");
+        for (key, value) in &headers {
+            out.push_str(&format!("    {}: {},\n", quote(key), quote(value)));
+        }
+        out.push_str("}\n");
+    }
+
+    let mut args = vec!["url".to_string()];
+    if !headers.is_empty() {
+        args.push("headers=headers".to_string());
+    }
+
+    match &req.body {
+        PreparedBody::None => {}
+        PreparedBody::Raw(content) => {
+            out.push_str(&format!("payload = {}\n", quote(content)));
+            args.push("data=payload.encode(\"utf-8\")".to_string());
+        }
+        PreparedBody::Form(fields) => {
+            // A list of pairs keeps repeated keys
+            out.push_str("data = [\n");
+            for (key, value) in fields {
+                out.push_str(&format!("    ({}, {}),\n", quote(key), quote(value)));
+            }
+            out.push_str("]\n");
+            args.push("data=data".to_string());
+        }
+        PreparedBody::Multipart(parts) => {
+            let (fields, files): (Vec<_>, Vec<_>) = parts
+                .iter()
+                .partition(|p| matches!(p, MultipartPart::Field { .. }));
+            if !fields.is_empty() {
+                out.push_str("data = [\n");
+                for part in fields {
+                    if let MultipartPart::Field { name, value } = part {
+                        out.push_str(&format!("    ({}, {}),\n", quote(name), quote(value)));
+                    }
+                }
+                out.push_str("]\n");
+                args.push("data=data".to_string());
+            }
+            out.push_str("files = [\n");
+            for part in files {
+                if let MultipartPart::File { name, path } = part {
+                    out.push_str(&format!(
+                        "    ({}, ({}, open({}, \"rb\"))),\n",
+                        quote(name),
+                        quote(file_name(path)),
+                        quote(path)
+                    ));
+                }
+            }
+            out.push_str("]\n");
+            args.push("files=files".to_string());
+        }
+    }
+
+    out.push_str(&format!(
+        "\nresponse = requests.request({}, {})\n\nprint(response.status_code)\nprint(response.text)\n",
+        quote(&req.method),
+        args.join(", ")
+    ));
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::codegen::tests::request;
+    use crate::codegen::{generate, CodegenTarget};
+    use crate::models::request::{BodyType, HttpMethod};
+
+    #[test]
+    fn renders_json_and_multipart() {
+        let req = request(HttpMethod::POST, BodyType::Json, Some("{\"a\": \"b\"}"));
+        let code = generate(&req, CodegenTarget::PythonRequests, None);
+        assert!(code.contains("url = \"{{baseUrl}}/users/{{userId}}?q=a%20b\""));
+        assert!(code.contains("    \"Content-Type\": \"application/json\",\n"));
+        assert!(code.contains("payload = \"{\\\"a\\\": \\\"b\\\"}\""));
+        assert!(code.contains(
+            "requests.request(\"POST\", url, headers=headers, data=payload.encode(\"utf-8\"))"
+        ));
+
+        let req = request(
+            HttpMethod::POST,
+            BodyType::Multipart,
+            Some("a=1&f=@/tmp/f.txt"),
+        );
+        let code = generate(&req, CodegenTarget::PythonRequests, None);
+        assert!(code.contains("(\"f\", (\"f.txt\", open(\"/tmp/f.txt\", \"rb\"))),"));
+        assert!(!code.contains("Content-Type"));
+    }
+}
@@ -0,0 +1,105 @@
+use super::{MultipartPart, PreparedBody, PreparedRequest};
+
+/// Renders a request using reqwest's blocking client. Requires the `blocking`
+/// feature, plus `multipart` for multipart bodies.
+pub fn generate(req: &PreparedRequest) -> String {
+    let mut out = String::from("fn main() -> Result<(), Box<dyn std::error::Error>> {\n");
+    out.push_str("    let client = reqwest::blocking::Client::new();\n");
+
+    if let PreparedBody::Multipart(parts) = &req.body {
+        out.push_str("    let form = reqwest::blocking::multipart::Form::new()");
+        for part in parts {
+            match part {
+                MultipartPart::Field { name, value } => {
+                    out.push_str(&format!(
+                        "\n        .text({}, {})",
+                        quote(name),
+                        quote(value)
+                    ));
+                }
+                MultipartPart::File { name, path } => {
+                    out.push_str(&format!(
+                        "\n        .file({}, {})?",
+                        quote(name),
+                        quote(path)
+                    ));
+                }
+            }
+        }
+        out.push_str(";\n");
+    }
+
+    out.push_str(&format!(
+        "\n    let response = client\n        .request(reqwest::Method::{}, {})\n",
+        method_constant(&req.method),
+        quote(&req.url)
+    ));
+    for (key, value) in &req.all_headers() {
+        out.push_str(&format!(
+            "        .header({}, {})\n",
+            quote(key),
+            quote(value)
+        ));
+    }
+    match &req.body {
+        PreparedBody::None => {}
+        PreparedBody::Raw(content) => {
+            out.push_str(&format!("        .body({})\n", quote(content)));
+        }
+        PreparedBody::Form(fields) => {
+            let pairs: Vec<String> = fields
+                .iter()
+                .map(|(k, v)| format!("({}, {})", quote(k), quote(v)))
+                .collect();
+            out.push_str(&format!("        .form(&[{}])\n", pairs.join(", ")));
+        }
+        PreparedBody::Multipart(_) => out.push_str("        .multipart(form)\n"),
+    }
+    out.push_str(
+        "        .send()?;\n\n    println!(\"{}\", response.status());\n    println!(\"{}\", response.text()?);\n    Ok(())\n}\n",
+    );
+    out
+}
+
+/// Rust string literals escape non-ASCII as `\u{..}`, which `Debug` produces.
+fn quote(s: &str) -> String {
+    format!("{:?}", s)
+}
+
+fn method_constant(method: &str) -> &str {
+    match method {
+        "GET" | "POST" | "PUT" | "PATCH" | "DELETE" | "HEAD" | "OPTIONS" => method,
+        _ => "GET",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::codegen::tests::request;
+    use crate::codegen::{generate, CodegenTarget};
+    use crate::models::request::{BodyType, HttpMethod};
+
+    #[test]
+    fn renders_text_form_and_multipart() {
+        let req = request(HttpMethod::PATCH, BodyType::Text, Some("line\n\u{7f}"));
+        let code = generate(&req, CodegenTarget::RustReqwest, None);
+        assert!(code.contains(
+            ".request(reqwest::Method::PATCH, \"{{baseUrl}}/users/{{userId}}?q=a%20b\")"
+        ));
+        assert!(code.contains(".body(\"line\\n\\u{7f}\")"));
+        assert!(code.contains(".header(\"Content-Type\", \"text/plain\")"));
+
+        let req = request(HttpMethod::POST, BodyType::FormUrlEncoded, Some("a=1&b=2"));
+        let code = generate(&req, CodegenTarget::RustReqwest, None);
+        assert!(code.contains(".form(&[(\"a\", \"1\"), (\"b\", \"2\")])"));
+
+        let req = request(
+            HttpMethod::POST,
+            BodyType::Multipart,
+            Some("a=1&f=@/tmp/f.txt"),
+        );
+        let code = generate(&req, CodegenTarget::RustReqwest, None);
+        assert!(code.contains(".text(\"a\", \"1\")\n        .file(\"f\", \"/tmp/f.txt\")?;"));
+        assert!(code.contains(".multipart(form)"));
+    }
+}
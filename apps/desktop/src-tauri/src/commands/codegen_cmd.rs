@@ -0,0 +1,25 @@
+use std::sync::Mutex;
+use tauri::State;
+
+use crate::codegen::{self, CodegenTarget};
+use crate::db::Database;
+
+/// Renders a saved request as code. Variables are resolved from the active
+/// environment when `workspace_id` is given, and left as `{{name}}` otherwise.
+#[tauri::command]
+pub fn generate_code(
+    db: State<'_, Mutex<Database>>,
+    request_id: String,
+    target: CodegenTarget,
+    workspace_id: Option<String>,
+) -> Result<String, String> {
+    let db = db.lock().map_err(|e| format!("Lock error: {}", e))?;
+    let request = db
+        .get_request(&request_id)?
+        .ok_or_else(|| format!("Request not found: {}", request_id))?;
+    let variables = match workspace_id {
+        Some(workspace_id) => Some(db.get_resolved_variables(&workspace_id)?),
+        None => None,
+    };
+    Ok(codegen::generate(&request, target, variables.as_deref()))
+}
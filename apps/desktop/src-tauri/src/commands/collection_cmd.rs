@@ -1,10 +1,12 @@
 use std::sync::Mutex;
-use tauri::State;
+use tauri::{AppHandle, State};
 
+use crate::commands::storage_cmd::mirror_edit;
 use crate::db::Database;
 use crate::models::collection::{
     Collection, CollectionTree, CreateCollectionInput, CreateFolderInput, Folder, RenameInput,
 };
+use crate::storage::watcher::StorageWatchers;
 
 #[tauri::command]
 pub async fn create_collection(
@@ -35,45 +37,63 @@ pub async fn get_collection_tree(
 
 #[tauri::command]
 pub async fn rename_collection(
+    app: AppHandle,
     db: State<'_, Mutex<Database>>,
     input: RenameInput,
 ) -> Result<(), String> {
     let db = db.lock().map_err(|e| format!("Lock error: {}", e))?;
-    db.rename_collection(input)
+    let id = input.id.clone();
+    db.rename_collection(input)?;
+    mirror_edit(&app, &db, Some(&id));
+    Ok(())
 }
 
 #[tauri::command]
 pub async fn delete_collection(
     db: State<'_, Mutex<Database>>,
+    watchers: State<'_, StorageWatchers>,
     id: String,
 ) -> Result<(), String> {
+    // A mirrored directory is left on disk; only the watcher goes away
+    watchers.remove(&id)?;
     let db = db.lock().map_err(|e| format!("Lock error: {}", e))?;
     db.delete_collection(&id)
 }
 
 #[tauri::command]
 pub async fn create_folder(
+    app: AppHandle,
     db: State<'_, Mutex<Database>>,
     input: CreateFolderInput,
 ) -> Result<Folder, String> {
     let db = db.lock().map_err(|e| format!("Lock error: {}", e))?;
-    db.create_folder(input)
+    let folder = db.create_folder(input)?;
+    mirror_edit(&app, &db, Some(&folder.collection_id));
+    Ok(folder)
 }
 
 #[tauri::command]
 pub async fn rename_folder(
+    app: AppHandle,
     db: State<'_, Mutex<Database>>,
     input: RenameInput,
 ) -> Result<(), String> {
     let db = db.lock().map_err(|e| format!("Lock error: {}", e))?;
-    db.rename_folder(input)
+    let collection_id = db.get_folder(&input.id)?.map(|f| f.collection_id);
+    db.rename_folder(input)?;
+    mirror_edit(&app, &db, collection_id.as_deref());
+    Ok(())
 }
 
 #[tauri::command]
 pub async fn delete_folder(
+    app: AppHandle,
     db: State<'_, Mutex<Database>>,
     id: String,
 ) -> Result<(), String> {
     let db = db.lock().map_err(|e| format!("Lock error: {}", e))?;
-    db.delete_folder(&id)
+    let collection_id = db.get_folder(&id)?.map(|f| f.collection_id);
+    db.delete_folder(&id)?;
+    mirror_edit(&app, &db, collection_id.as_deref());
+    Ok(())
 }
@@ -0,0 +1,39 @@
+use std::sync::Mutex;
+
+use tauri::State;
+
+use crate::db::Database;
+use crate::models::example::{CreateExampleInput, RequestExample, UpdateExampleInput};
+
+#[tauri::command]
+pub fn list_examples(
+    db: State<'_, Mutex<Database>>,
+    request_id: String,
+) -> Result<Vec<RequestExample>, String> {
+    let db = db.lock().map_err(|e| format!("Lock error: {}", e))?;
+    db.list_examples(&request_id)
+}
+
+#[tauri::command]
+pub fn create_example(
+    db: State<'_, Mutex<Database>>,
+    input: CreateExampleInput,
+) -> Result<RequestExample, String> {
+    let db = db.lock().map_err(|e| format!("Lock error: {}", e))?;
+    db.create_example(input)
+}
+
+#[tauri::command]
+pub fn update_example(
+    db: State<'_, Mutex<Database>>,
+    input: UpdateExampleInput,
+) -> Result<RequestExample, String> {
+    let db = db.lock().map_err(|e| format!("Lock error: {}", e))?;
+    db.update_example(input)
+}
+
+#[tauri::command]
+pub fn delete_example(db: State<'_, Mutex<Database>>, id: String) -> Result<(), String> {
+    let db = db.lock().map_err(|e| format!("Lock error: {}", e))?;
+    db.delete_example(&id)
+}
@@ -0,0 +1,62 @@
+use std::sync::Mutex;
+use tauri::State;
+
+use crate::db::Database;
+use crate::graphql::{self, schema::Schema, validate};
+use crate::models::graphql::{GraphQLError, GraphQLSchema, ValidateGraphQLInput};
+use crate::models::request::ExecuteRequestInput;
+
+/// Runs the introspection query against the request's endpoint and caches the
+/// schema for its URL.
+#[tauri::command]
+pub async fn introspect_graphql_schema(
+    db: State<'_, Mutex<Database>>,
+    workspace_id: String,
+    input: ExecuteRequestInput,
+) -> Result<GraphQLSchema, String> {
+    let introspection = graphql::introspect(&input).await?;
+    let db = db.lock().map_err(|e| format!("Lock error: {}", e))?;
+    db.save_graphql_schema(&workspace_id, &input.url, &introspection)
+}
+
+#[tauri::command]
+pub async fn get_graphql_schema(
+    db: State<'_, Mutex<Database>>,
+    workspace_id: String,
+    url: String,
+) -> Result<Option<GraphQLSchema>, String> {
+    let db = db.lock().map_err(|e| format!("Lock error: {}", e))?;
+    db.get_graphql_schema(&workspace_id, &url)
+}
+
+#[tauri::command]
+pub async fn delete_graphql_schema(
+    db: State<'_, Mutex<Database>>,
+    workspace_id: String,
+    url: String,
+) -> Result<(), String> {
+    let db = db.lock().map_err(|e| format!("Lock error: {}", e))?;
+    db.delete_graphql_schema(&workspace_id, &url)
+}
+
+/// Checks the query and variables against the URL's cached schema. An empty
+/// list means the request is valid as far as the schema can tell.
+#[tauri::command]
+pub async fn validate_graphql(
+    db: State<'_, Mutex<Database>>,
+    input: ValidateGraphQLInput,
+) -> Result<Vec<GraphQLError>, String> {
+    let cached = {
+        let db = db.lock().map_err(|e| format!("Lock error: {}", e))?;
+        db.get_graphql_schema(&input.workspace_id, &input.url)?
+    };
+    let schema = cached
+        .map(|cached| Schema::from_introspection(&cached.introspection))
+        .transpose()?;
+    Ok(validate::validate(
+        schema.as_ref(),
+        &input.query,
+        &input.variables,
+        &input.operation_name,
+    ))
+}
@@ -0,0 +1,43 @@
+use tauri::{AppHandle, Emitter, State};
+use tokio::sync::oneshot;
+
+use crate::grpc;
+use crate::http::sse::EventStreams;
+use crate::models::grpc::{
+    GrpcCallInput, GrpcResult, GrpcServiceInfo, GrpcServicesInput, GrpcStreamMessage,
+};
+
+#[tauri::command]
+pub async fn list_grpc_services(input: GrpcServicesInput) -> Result<Vec<GrpcServiceInfo>, String> {
+    grpc::list_services(&input).await
+}
+
+/// Calls a unary or server-streaming method. With a `stream_id`, each response
+/// message is also emitted as `grpc-message` as it arrives, and
+/// `stop_request_stream` with the same id ends the call early.
+#[tauri::command]
+pub async fn execute_grpc(
+    app: AppHandle,
+    streams: State<'_, EventStreams>,
+    input: GrpcCallInput,
+    stream_id: Option<String>,
+) -> Result<GrpcResult, String> {
+    let Some(stream_id) = stream_id else {
+        let (_stop, stopped) = oneshot::channel();
+        return grpc::call(&input, stopped, |_| {}).await;
+    };
+
+    let stopped = streams.register(&stream_id)?;
+    let result = grpc::call(&input, stopped, |message| {
+        let _ = app.emit(
+            "grpc-message",
+            &GrpcStreamMessage {
+                stream_id: stream_id.clone(),
+                message,
+            },
+        );
+    })
+    .await;
+    streams.stop(&stream_id)?;
+    result
+}
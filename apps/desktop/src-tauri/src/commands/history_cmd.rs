@@ -1,9 +1,19 @@
 use std::sync::Mutex;
 
-use tauri::State;
+use tauri::{AppHandle, State};
 
+use crate::commands::storage_cmd::mirror_edit;
 use crate::db::Database;
-use crate::models::history::{HistoryEntry, HistoryQuery};
+use crate::history;
+use crate::history::diff::{self, ResponseSnapshot};
+use crate::history::retention::HistoryPruner;
+use crate::http::executor;
+use crate::models::diff::{DiffOptions, ReplayResult, ResponseDiff};
+use crate::models::history::{
+    HistoryEntry, HistoryQuery, HistoryRedaction, HistoryRetention, SaveHistoryInput, VacuumResult,
+};
+use crate::models::request::ApiRequest;
+use crate::redact;
 
 #[tauri::command]
 pub fn list_history(
@@ -31,3 +41,140 @@ pub fn delete_history_entry(
     let db = db.lock().map_err(|e| format!("Lock error: {}", e))?;
     db.delete_history_entry(&id)
 }
+
+#[tauri::command]
+pub fn get_history_retention(
+    db: State<'_, Mutex<Database>>,
+    workspace_id: String,
+) -> Result<HistoryRetention, String> {
+    let db = db.lock().map_err(|e| format!("Lock error: {}", e))?;
+    db.get_history_retention(&workspace_id)
+}
+
+#[tauri::command]
+pub fn set_history_retention(
+    db: State<'_, Mutex<Database>>,
+    retention: HistoryRetention,
+) -> Result<(), String> {
+    let db = db.lock().map_err(|e| format!("Lock error: {}", e))?;
+    db.set_history_retention(&retention)?;
+    db.prune_history(&retention.workspace_id)?;
+    Ok(())
+}
+
+#[tauri::command]
+pub fn vacuum(db: State<'_, Mutex<Database>>) -> Result<VacuumResult, String> {
+    let db = db.lock().map_err(|e| format!("Lock error: {}", e))?;
+    db.vacuum()
+}
+
+#[tauri::command]
+pub fn get_history_redaction(
+    db: State<'_, Mutex<Database>>,
+    workspace_id: String,
+) -> Result<HistoryRedaction, String> {
+    let db = db.lock().map_err(|e| format!("Lock error: {}", e))?;
+    db.get_history_redaction(&workspace_id)
+}
+
+#[tauri::command]
+pub fn set_history_redaction(
+    db: State<'_, Mutex<Database>>,
+    redaction: HistoryRedaction,
+) -> Result<(), String> {
+    let db = db.lock().map_err(|e| format!("Lock error: {}", e))?;
+    db.set_history_redaction(&redaction)
+}
+
+/// Returns a history entry with encrypted values decrypted. Masked values can't
+/// be recovered and stay masked.
+#[tauri::command]
+pub fn reveal_history_entry(
+    db: State<'_, Mutex<Database>>,
+    id: String,
+) -> Result<HistoryEntry, String> {
+    let db = db.lock().map_err(|e| format!("Lock error: {}", e))?;
+    let entry = db
+        .get_history_entry(&id)?
+        .ok_or_else(|| format!("History entry not found: {}", id))?;
+    redact::reveal_entry(entry)
+}
+
+/// Re-sends the request a history entry recorded, records the new response and
+/// diffs it against the original.
+#[tauri::command]
+pub async fn replay_history_entry(
+    db: State<'_, Mutex<Database>>,
+    pruner: State<'_, HistoryPruner>,
+    id: String,
+    options: Option<DiffOptions>,
+) -> Result<ReplayResult, String> {
+    let (original, input) = {
+        let db = db.lock().map_err(|e| format!("Lock error: {}", e))?;
+        let original = db
+            .get_history_entry(&id)?
+            .ok_or_else(|| format!("History entry not found: {}", id))?;
+        let input = history::replay_input(&db, &original)?;
+        (original, input)
+    };
+
+    let result = executor::execute(&input).await;
+
+    let entry = {
+        let db = db.lock().map_err(|e| format!("Lock error: {}", e))?;
+        history::record(
+            &db,
+            &original.workspace_id,
+            original.request_id.as_deref(),
+            &input,
+            &result,
+        )
+        .ok()
+    };
+    pruner.schedule(&original.workspace_id);
+
+    // Compare stored forms when possible so both sides are redacted the same way
+    let after = match &entry {
+        Some(entry) => ResponseSnapshot::from_entry(entry),
+        None => ResponseSnapshot::from_result(&result),
+    };
+    let diff = diff::diff_responses(
+        &ResponseSnapshot::from_entry(&original),
+        &after,
+        &options.unwrap_or_default(),
+    );
+    Ok(ReplayResult { result, entry, diff })
+}
+
+#[tauri::command]
+pub fn diff_history_entries(
+    db: State<'_, Mutex<Database>>,
+    before_id: String,
+    after_id: String,
+    options: Option<DiffOptions>,
+) -> Result<ResponseDiff, String> {
+    let db = db.lock().map_err(|e| format!("Lock error: {}", e))?;
+    let find = |id: &str| {
+        db.get_history_entry(id)?
+            .ok_or_else(|| format!("History entry not found: {}", id))
+    };
+    let before = find(&before_id)?;
+    let after = find(&after_id)?;
+    Ok(diff::diff_responses(
+        &ResponseSnapshot::from_entry(&before),
+        &ResponseSnapshot::from_entry(&after),
+        &options.unwrap_or_default(),
+    ))
+}
+
+#[tauri::command]
+pub fn save_history_to_collection(
+    app: AppHandle,
+    db: State<'_, Mutex<Database>>,
+    input: SaveHistoryInput,
+) -> Result<ApiRequest, String> {
+    let db = db.lock().map_err(|e| format!("Lock error: {}", e))?;
+    let request = history::save_to_collection(&db, &input)?;
+    mirror_edit(&app, &db, request.collection_id.as_deref());
+    Ok(request)
+}
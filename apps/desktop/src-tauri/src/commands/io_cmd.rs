@@ -1,9 +1,19 @@
 use std::sync::Mutex;
 
-use tauri::State;
+use tauri::{AppHandle, State};
 
+use crate::commands::storage_cmd::mirror_edit;
 use crate::db::Database;
-use crate::io::postman::{self, ImportResult};
+use crate::io::bruno;
+use crate::io::curl;
+use crate::io::har::{self, HarImportOptions};
+use crate::io::http_file::{self, HttpFileImportOptions};
+use crate::io::insomnia::{self, InsomniaImportResult};
+use crate::io::openapi::{self, OpenApiFormat};
+use crate::io::postman;
+use crate::io::ImportResult;
+use crate::models::environment::Environment;
+use crate::models::request::CreateRequestInput;
 
 #[tauri::command]
 pub fn import_postman(
@@ -22,8 +32,159 @@ pub fn export_postman(
     db: State<'_, Mutex<Database>>,
     collection_id: String,
     file_path: String,
+    environment_id: Option<String>,
 ) -> Result<(), String> {
     let db = db.lock().map_err(|e| format!("Lock error: {}", e))?;
-    let json = postman::export_postman_collection(&db, &collection_id)?;
+    let json =
+        postman::export_postman_collection(&db, &collection_id, environment_id.as_deref())?;
     std::fs::write(&file_path, json).map_err(|e| format!("Failed to write file: {}", e))
 }
+
+#[tauri::command]
+pub fn import_postman_environment(
+    db: State<'_, Mutex<Database>>,
+    file_path: String,
+    workspace_id: String,
+) -> Result<Environment, String> {
+    let content =
+        std::fs::read_to_string(&file_path).map_err(|e| format!("Failed to read file: {}", e))?;
+    let db = db.lock().map_err(|e| format!("Lock error: {}", e))?;
+    postman::import_postman_environment(&db, &content, &workspace_id)
+}
+
+#[tauri::command]
+pub fn export_postman_environment(
+    db: State<'_, Mutex<Database>>,
+    environment_id: String,
+    file_path: String,
+    include_secrets: bool,
+) -> Result<(), String> {
+    let db = db.lock().map_err(|e| format!("Lock error: {}", e))?;
+    let json = postman::export_postman_environment(&db, &environment_id, include_secrets)?;
+    std::fs::write(&file_path, json).map_err(|e| format!("Failed to write file: {}", e))
+}
+
+#[tauri::command]
+pub fn export_openapi(
+    db: State<'_, Mutex<Database>>,
+    collection_id: String,
+    file_path: String,
+    format: Option<String>,
+) -> Result<(), String> {
+    let format = match format {
+        Some(f) => OpenApiFormat::from_str(&f)?,
+        None => OpenApiFormat::from_path(&file_path),
+    };
+    let db = db.lock().map_err(|e| format!("Lock error: {}", e))?;
+    let document = openapi::export_openapi_document(&db, &collection_id, format)?;
+    std::fs::write(&file_path, document).map_err(|e| format!("Failed to write file: {}", e))
+}
+
+#[tauri::command]
+pub fn import_har(
+    db: State<'_, Mutex<Database>>,
+    file_path: String,
+    workspace_id: String,
+    options: HarImportOptions,
+) -> Result<ImportResult, String> {
+    let content =
+        std::fs::read_to_string(&file_path).map_err(|e| format!("Failed to read file: {}", e))?;
+    let db = db.lock().map_err(|e| format!("Lock error: {}", e))?;
+    har::import_har(&db, &content, &workspace_id, &options)
+}
+
+#[tauri::command]
+pub fn import_http_file(
+    app: AppHandle,
+    db: State<'_, Mutex<Database>>,
+    file_path: String,
+    workspace_id: String,
+    options: HttpFileImportOptions,
+) -> Result<ImportResult, String> {
+    let content =
+        std::fs::read_to_string(&file_path).map_err(|e| format!("Failed to read file: {}", e))?;
+    let mut options = options;
+    if options.collection_name.is_none() {
+        options.collection_name = std::path::Path::new(&file_path)
+            .file_stem()
+            .map(|s| s.to_string_lossy().to_string());
+    }
+    let db = db.lock().map_err(|e| format!("Lock error: {}", e))?;
+    let result = http_file::import_http_file(&db, &content, &workspace_id, &options)?;
+    // Imports into a linked collection would otherwise be dropped by its next sync
+    mirror_edit(&app, &db, options.collection_id.as_deref());
+    Ok(result)
+}
+
+#[tauri::command]
+pub fn export_http_file(
+    db: State<'_, Mutex<Database>>,
+    collection_id: String,
+    folder_id: Option<String>,
+    environment_id: Option<String>,
+    file_path: String,
+) -> Result<(), String> {
+    let db = db.lock().map_err(|e| format!("Lock error: {}", e))?;
+    let content = http_file::export_http_file(
+        &db,
+        &collection_id,
+        folder_id.as_deref(),
+        environment_id.as_deref(),
+    )?;
+    std::fs::write(&file_path, content).map_err(|e| format!("Failed to write file: {}", e))
+}
+
+#[tauri::command]
+pub fn import_insomnia(
+    db: State<'_, Mutex<Database>>,
+    file_path: String,
+    workspace_id: String,
+) -> Result<InsomniaImportResult, String> {
+    let content =
+        std::fs::read_to_string(&file_path).map_err(|e| format!("Failed to read file: {}", e))?;
+    let db = db.lock().map_err(|e| format!("Lock error: {}", e))?;
+    insomnia::import_insomnia_export(&db, &content, &workspace_id)
+}
+
+#[tauri::command]
+pub fn import_bruno(
+    db: State<'_, Mutex<Database>>,
+    dir_path: String,
+    workspace_id: String,
+) -> Result<ImportResult, String> {
+    let db = db.lock().map_err(|e| format!("Lock error: {}", e))?;
+    bruno::import_bruno_collection(&db, std::path::Path::new(&dir_path), &workspace_id)
+}
+
+#[tauri::command]
+pub fn export_bruno(
+    db: State<'_, Mutex<Database>>,
+    collection_id: String,
+    dir_path: String,
+    environment_ids: Vec<String>,
+) -> Result<(), String> {
+    let db = db.lock().map_err(|e| format!("Lock error: {}", e))?;
+    bruno::export_bruno_collection(
+        &db,
+        &collection_id,
+        std::path::Path::new(&dir_path),
+        &environment_ids,
+    )
+}
+
+#[tauri::command]
+pub fn export_har(
+    db: State<'_, Mutex<Database>>,
+    workspace_id: String,
+    entry_ids: Vec<String>,
+    file_path: String,
+) -> Result<(), String> {
+    let db = db.lock().map_err(|e| format!("Lock error: {}", e))?;
+    let json = har::export_history_har(&db, &workspace_id, &entry_ids)?;
+    std::fs::write(&file_path, json).map_err(|e| format!("Failed to write file: {}", e))
+}
+
+#[tauri::command]
+pub fn parse_curl(command: String) -> Result<CreateRequestInput, String> {
+    curl::parse_curl_command(&command)
+}
@@ -0,0 +1,66 @@
+use std::sync::Mutex;
+
+use tauri::{AppHandle, Emitter, State};
+
+use crate::db::Database;
+use crate::mock::{self, routes, MockServers};
+use crate::models::mock::{MockLogEntry, MockServerInfo, StartMockInput};
+
+/// Serves the collection's requests from a snapshot taken now; restart the server
+/// to pick up later edits. Each request is sent to the UI as a `mock-request` event.
+#[tauri::command]
+pub async fn start_mock_server(
+    app: AppHandle,
+    db: State<'_, Mutex<Database>>,
+    servers: State<'_, MockServers>,
+    input: StartMockInput,
+) -> Result<MockServerInfo, String> {
+    let routes = {
+        let db = db.lock().map_err(|e| format!("Lock error: {}", e))?;
+        let tree = db.get_collection_tree(&input.collection_id)?;
+        let examples = db.list_collection_examples(&input.collection_id)?;
+        routes::build_routes(&tree, examples, &input.routes)
+    };
+    // Free the port first in case the server is restarted on the same one
+    servers.remove(&input.collection_id)?;
+    let server = mock::start(
+        &input.collection_id,
+        input.port,
+        routes,
+        input.delay_ms,
+        move |entry| {
+            let _ = app.emit("mock-request", entry);
+        },
+    )
+    .await?;
+    servers.insert(server)
+}
+
+#[tauri::command]
+pub fn stop_mock_server(
+    servers: State<'_, MockServers>,
+    collection_id: String,
+) -> Result<(), String> {
+    servers.remove(&collection_id)
+}
+
+#[tauri::command]
+pub fn list_mock_servers(servers: State<'_, MockServers>) -> Result<Vec<MockServerInfo>, String> {
+    servers.list()
+}
+
+#[tauri::command]
+pub fn get_mock_log(
+    servers: State<'_, MockServers>,
+    collection_id: String,
+) -> Result<Vec<MockLogEntry>, String> {
+    servers.log(&collection_id)
+}
+
+#[tauri::command]
+pub fn clear_mock_log(
+    servers: State<'_, MockServers>,
+    collection_id: String,
+) -> Result<(), String> {
+    servers.clear_log(&collection_id)
+}
@@ -1,6 +1,15 @@
+pub mod codegen_cmd;
 pub mod collection_cmd;
 pub mod environment_cmd;
+pub mod example_cmd;
+pub mod graphql_cmd;
+pub mod grpc_cmd;
 pub mod history_cmd;
 pub mod io_cmd;
+pub mod mock_cmd;
+pub mod proxy_cmd;
 pub mod request_cmd;
+pub mod search_cmd;
+pub mod storage_cmd;
+pub mod websocket_cmd;
 pub mod workspace_cmd;
@@ -0,0 +1,133 @@
+use std::sync::Mutex;
+
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::db::Database;
+use crate::history;
+use crate::history::retention::HistoryPruner;
+use crate::models::capture::{
+    CaptureSession, CaptureToCollectionInput, ProxyInfo, StartProxyInput,
+};
+use crate::models::collection::Collection;
+use crate::models::history::HistoryEntry;
+use crate::proxy::{self, ca::LocalCa, ProxyState};
+
+/// Starts the capture proxy in a new capture session, replacing any proxy
+/// already running. Each exchange is recorded to history like a sent request
+/// and sent to the UI as a `proxy-capture` event.
+#[tauri::command]
+pub async fn start_proxy(
+    app: AppHandle,
+    db: State<'_, Mutex<Database>>,
+    state: State<'_, ProxyState>,
+    input: StartProxyInput,
+) -> Result<ProxyInfo, String> {
+    stop(&db, &state)?;
+    let (session, ca) = {
+        let db = db.lock().map_err(|e| format!("Lock error: {}", e))?;
+        let ca = match input.intercept_https {
+            true => Some(LocalCa::load_or_create(&db)?),
+            false => None,
+        };
+        let name = input
+            .session_name
+            .clone()
+            .filter(|n| !n.trim().is_empty())
+            .unwrap_or_else(|| {
+                format!("Capture {}", chrono::Local::now().format("%Y-%m-%d %H:%M"))
+            });
+        (db.create_capture_session(&input.workspace_id, &name)?, ca)
+    };
+
+    let session_id = session.id.clone();
+    let workspace_id = input.workspace_id.clone();
+    let started = proxy::start(
+        &session.id,
+        &input.workspace_id,
+        input.port,
+        input.listen_on_network,
+        ca,
+        move |request, result| {
+            let db = app.state::<Mutex<Database>>();
+            let Ok(db) = db.lock() else {
+                return;
+            };
+            if let Ok(entry) = history::record(&db, &workspace_id, None, request, result) {
+                let _ = db.add_capture_entry(&session_id, &entry.id);
+                app.state::<HistoryPruner>().schedule(&workspace_id);
+                let _ = app.emit("proxy-capture", &entry);
+            }
+        },
+    )
+    .await;
+    match started {
+        Ok(proxy) => state.set(proxy),
+        Err(e) => {
+            let db = db.lock().map_err(|e| format!("Lock error: {}", e))?;
+            db.delete_capture_session(&session.id)?;
+            Err(e)
+        }
+    }
+}
+
+#[tauri::command]
+pub fn stop_proxy(
+    db: State<'_, Mutex<Database>>,
+    state: State<'_, ProxyState>,
+) -> Result<(), String> {
+    stop(&db, &state)
+}
+
+fn stop(db: &Mutex<Database>, state: &ProxyState) -> Result<(), String> {
+    if let Some(info) = state.take()? {
+        let db = db.lock().map_err(|e| format!("Lock error: {}", e))?;
+        db.stop_capture_session(&info.session_id)?;
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_proxy_status(state: State<'_, ProxyState>) -> Result<Option<ProxyInfo>, String> {
+    state.info()
+}
+
+/// The PEM certificate to install as trusted before intercepting HTTPS. It's
+/// generated on first request.
+#[tauri::command]
+pub fn get_proxy_ca_certificate(db: State<'_, Mutex<Database>>) -> Result<String, String> {
+    let db = db.lock().map_err(|e| format!("Lock error: {}", e))?;
+    Ok(LocalCa::load_or_create(&db)?.certificate_pem().to_string())
+}
+
+#[tauri::command]
+pub fn list_capture_sessions(
+    db: State<'_, Mutex<Database>>,
+    workspace_id: String,
+) -> Result<Vec<CaptureSession>, String> {
+    let db = db.lock().map_err(|e| format!("Lock error: {}", e))?;
+    db.list_capture_sessions(&workspace_id)
+}
+
+#[tauri::command]
+pub fn list_capture_entries(
+    db: State<'_, Mutex<Database>>,
+    session_id: String,
+) -> Result<Vec<HistoryEntry>, String> {
+    let db = db.lock().map_err(|e| format!("Lock error: {}", e))?;
+    db.list_capture_entries(&session_id)
+}
+
+#[tauri::command]
+pub fn delete_capture_session(db: State<'_, Mutex<Database>>, id: String) -> Result<(), String> {
+    let db = db.lock().map_err(|e| format!("Lock error: {}", e))?;
+    db.delete_capture_session(&id)
+}
+
+#[tauri::command]
+pub fn capture_session_to_collection(
+    db: State<'_, Mutex<Database>>,
+    input: CaptureToCollectionInput,
+) -> Result<Collection, String> {
+    let db = db.lock().map_err(|e| format!("Lock error: {}", e))?;
+    history::capture_to_collection(&db, &input)
+}
@@ -1,18 +1,27 @@
 use std::sync::Mutex;
-use tauri::State;
+use tauri::{AppHandle, Emitter, State};
 
+use crate::commands::storage_cmd::mirror_edit;
 use crate::db::Database;
+use crate::history;
+use crate::history::retention::HistoryPruner;
 use crate::http::executor;
-use crate::models::execution::ExecutionResult;
-use crate::models::request::{ApiRequest, CreateRequestInput, ExecuteRequestInput, UpdateRequestInput};
+use crate::http::sse::{self, EventStreams};
+use crate::models::execution::{ExecutionResult, StreamEvent};
+use crate::models::request::{
+    ApiRequest, CreateRequestInput, ExecuteRequestInput, ExecuteStreamInput, UpdateRequestInput,
+};
 
 #[tauri::command]
 pub async fn create_request(
+    app: AppHandle,
     db: State<'_, Mutex<Database>>,
     input: CreateRequestInput,
 ) -> Result<ApiRequest, String> {
     let db = db.lock().map_err(|e| format!("Lock error: {}", e))?;
-    db.create_request(input)
+    let request = db.create_request(input)?;
+    mirror_edit(&app, &db, request.collection_id.as_deref());
+    Ok(request)
 }
 
 #[tauri::command]
@@ -26,20 +35,31 @@ pub async fn get_request(
 
 #[tauri::command]
 pub async fn update_request(
+    app: AppHandle,
     db: State<'_, Mutex<Database>>,
     input: UpdateRequestInput,
 ) -> Result<ApiRequest, String> {
     let db = db.lock().map_err(|e| format!("Lock error: {}", e))?;
-    db.update_request(input)
+    let previous = db.get_request(&input.id)?.and_then(|r| r.collection_id);
+    let request = db.update_request(input)?;
+    mirror_edit(&app, &db, request.collection_id.as_deref());
+    if previous != request.collection_id {
+        mirror_edit(&app, &db, previous.as_deref());
+    }
+    Ok(request)
 }
 
 #[tauri::command]
 pub async fn delete_request(
+    app: AppHandle,
     db: State<'_, Mutex<Database>>,
     id: String,
 ) -> Result<(), String> {
     let db = db.lock().map_err(|e| format!("Lock error: {}", e))?;
-    db.delete_request(&id)
+    let collection_id = db.get_request(&id)?.and_then(|r| r.collection_id);
+    db.delete_request(&id)?;
+    mirror_edit(&app, &db, collection_id.as_deref());
+    Ok(())
 }
 
 #[tauri::command]
@@ -54,33 +74,65 @@ pub async fn list_requests_by_collection(
 #[tauri::command]
 pub async fn execute_request(
     db: State<'_, Mutex<Database>>,
+    pruner: State<'_, HistoryPruner>,
     input: ExecuteRequestInput,
     workspace_id: String,
     request_id: Option<String>,
 ) -> Result<ExecutionResult, String> {
-    // Serialize the request for history snapshot
-    let snapshot = serde_json::to_string(&input)
-        .unwrap_or_else(|_| "{}".to_string());
-
     let result = executor::execute(&input).await;
 
     // Save to history (non-blocking on failure)
     if let Ok(db) = db.lock() {
-        let response_headers_json = serde_json::to_string(&result.headers).ok();
-        let _ = db.save_history_entry(
-            &workspace_id,
-            request_id.as_deref(),
-            input.method.as_str(),
-            &input.url,
-            &snapshot,
-            if result.status > 0 { Some(result.status as i32) } else { None },
-            response_headers_json.as_deref(),
-            Some(&result.body),
-            Some(result.size_bytes as i64),
-            Some(result.timing.total_ms as i64),
-            result.error.as_deref(),
+        let _ = history::record(&db, &workspace_id, request_id.as_deref(), &input, &result);
+    }
+    pruner.schedule(&workspace_id);
+
+    Ok(result)
+}
+
+/// Sends a request whose response is a Server-Sent Events stream, emitting
+/// each event as `sse-event` until the server closes the stream or
+/// `stop_request_stream` is called with the same id. The result's body is the
+/// JSON list of events, and that is what history keeps.
+#[tauri::command]
+pub async fn execute_request_stream(
+    app: AppHandle,
+    db: State<'_, Mutex<Database>>,
+    pruner: State<'_, HistoryPruner>,
+    streams: State<'_, EventStreams>,
+    input: ExecuteStreamInput,
+) -> Result<ExecutionResult, String> {
+    let ExecuteStreamInput {
+        request: input,
+        workspace_id,
+        request_id,
+        stream_id,
+    } = input;
+    let stopped = streams.register(&stream_id)?;
+    let result = sse::execute_stream(&input, stopped, |event| {
+        let _ = app.emit(
+            "sse-event",
+            &StreamEvent {
+                stream_id: stream_id.clone(),
+                event,
+            },
         );
+    })
+    .await;
+    streams.stop(&stream_id)?;
+
+    if let Ok(db) = db.lock() {
+        let _ = history::record(&db, &workspace_id, request_id.as_deref(), &input, &result);
     }
+    pruner.schedule(&workspace_id);
 
     Ok(result)
 }
+
+#[tauri::command]
+pub fn stop_request_stream(
+    streams: State<'_, EventStreams>,
+    stream_id: String,
+) -> Result<(), String> {
+    streams.stop(&stream_id)
+}
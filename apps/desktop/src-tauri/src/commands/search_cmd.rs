@@ -0,0 +1,15 @@
+use std::sync::Mutex;
+
+use tauri::State;
+
+use crate::db::Database;
+use crate::models::search::{SearchHit, SearchQuery};
+
+#[tauri::command]
+pub fn search(
+    db: State<'_, Mutex<Database>>,
+    query: SearchQuery,
+) -> Result<Vec<SearchHit>, String> {
+    let db = db.lock().map_err(|e| format!("Lock error: {}", e))?;
+    db.search(&query)
+}
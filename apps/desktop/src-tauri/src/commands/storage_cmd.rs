@@ -0,0 +1,131 @@
+use std::path::Path;
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::db::Database;
+use crate::models::collection::Collection;
+use crate::models::storage::{CollectionStorage, StorageSyncEvent};
+use crate::storage::{
+    self,
+    watcher::{self, StorageWatchers},
+};
+
+/// Re-syncs every linked directory and starts watching it. Files win over the
+/// database here since they may have changed (e.g. `git pull`) while Steq was closed.
+/// Failures are reported through `collection-storage-synced` rather than aborting startup.
+pub fn resume_storage(app: &AppHandle) -> Result<(), String> {
+    let links = {
+        let db = app.state::<Mutex<Database>>();
+        let db = db.lock().map_err(|e| format!("Lock error: {}", e))?;
+        db.list_collection_storage()?
+    };
+    for link in links {
+        let result = {
+            let db = app.state::<Mutex<Database>>();
+            let db = db.lock().map_err(|e| format!("Lock error: {}", e))?;
+            storage::sync_from_directory(&db, &link.collection_id)
+        }
+        .and_then(|_| start_watching(app, &link.collection_id, Path::new(&link.directory)));
+        if let Err(error) = result {
+            emit_synced(app, &link.collection_id, Some(error));
+        }
+    }
+    Ok(())
+}
+
+/// Rewrites a linked collection's directory after an edit in the app. Failures
+/// don't fail the edit; they're reported through `collection-storage-synced`
+/// and the next sync rewrites the directory.
+pub fn mirror_edit(app: &AppHandle, db: &Database, collection_id: Option<&str>) {
+    if let Some(collection_id) = collection_id {
+        if let Err(error) = storage::write_if_linked(db, Some(collection_id)) {
+            emit_synced(app, collection_id, Some(error));
+        }
+    }
+}
+
+fn emit_synced(app: &AppHandle, collection_id: &str, error: Option<String>) {
+    let _ = app.emit(
+        "collection-storage-synced",
+        StorageSyncEvent {
+            collection_id: collection_id.to_string(),
+            error,
+        },
+    );
+}
+
+fn start_watching(app: &AppHandle, collection_id: &str, dir: &Path) -> Result<(), String> {
+    let handle = app.clone();
+    let id = collection_id.to_string();
+    let watcher = watcher::watch_directory(dir, move || {
+        let db = handle.state::<Mutex<Database>>();
+        let result = match db.lock() {
+            Ok(db) => storage::sync_from_directory(&db, &id),
+            Err(e) => Err(format!("Lock error: {}", e)),
+        };
+        emit_synced(&handle, &id, result.err());
+    })?;
+    app.state::<StorageWatchers>()
+        .insert(collection_id, watcher)
+}
+
+#[tauri::command]
+pub fn link_collection_directory(
+    app: AppHandle,
+    db: State<'_, Mutex<Database>>,
+    collection_id: String,
+    directory: String,
+) -> Result<CollectionStorage, String> {
+    let storage = {
+        let db = db.lock().map_err(|e| format!("Lock error: {}", e))?;
+        storage::link_directory(&db, &collection_id, Path::new(&directory))?;
+        db.get_collection_storage(&collection_id)?
+            .ok_or_else(|| format!("Collection not linked: {}", collection_id))?
+    };
+    start_watching(&app, &collection_id, Path::new(&directory))?;
+    Ok(storage)
+}
+
+#[tauri::command]
+pub fn open_collection_directory(
+    app: AppHandle,
+    db: State<'_, Mutex<Database>>,
+    workspace_id: String,
+    directory: String,
+) -> Result<Collection, String> {
+    let collection = {
+        let db = db.lock().map_err(|e| format!("Lock error: {}", e))?;
+        storage::open_directory(&db, &workspace_id, Path::new(&directory))?
+    };
+    start_watching(&app, &collection.id, Path::new(&directory))?;
+    Ok(collection)
+}
+
+#[tauri::command]
+pub fn unlink_collection_directory(
+    db: State<'_, Mutex<Database>>,
+    watchers: State<'_, StorageWatchers>,
+    collection_id: String,
+) -> Result<(), String> {
+    watchers.remove(&collection_id)?;
+    let db = db.lock().map_err(|e| format!("Lock error: {}", e))?;
+    db.unlink_collection_directory(&collection_id)
+}
+
+#[tauri::command]
+pub fn get_collection_storage(
+    db: State<'_, Mutex<Database>>,
+    collection_id: String,
+) -> Result<Option<CollectionStorage>, String> {
+    let db = db.lock().map_err(|e| format!("Lock error: {}", e))?;
+    db.get_collection_storage(&collection_id)
+}
+
+#[tauri::command]
+pub fn sync_collection_directory(
+    db: State<'_, Mutex<Database>>,
+    collection_id: String,
+) -> Result<(), String> {
+    let db = db.lock().map_err(|e| format!("Lock error: {}", e))?;
+    storage::sync_from_directory(&db, &collection_id)
+}
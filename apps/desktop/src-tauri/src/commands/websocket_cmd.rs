@@ -0,0 +1,183 @@
+use std::sync::Mutex;
+
+use base64::Engine;
+use tauri::{AppHandle, Emitter, Manager, State};
+use tokio_tungstenite::tungstenite::Message;
+
+use crate::db::Database;
+use crate::models::websocket::{
+    ConnectWebSocketInput, MessageKind, SendWebSocketInput, WebSocketMessage, WebSocketSession,
+};
+use crate::redact::Redactor;
+use crate::websocket::{self, Command, SessionEvent, WebSocketSessions};
+
+/// Opens a connection and starts its transcript. Every frame is saved with
+/// credentials redacted like history and sent to the UI, as it went over the
+/// wire, as a `websocket-message` event; when the connection ends the final
+/// session is sent as `websocket-closed`. A failed handshake is saved as a
+/// closed session and returned as an error.
+#[tauri::command]
+pub async fn connect_websocket(
+    app: AppHandle,
+    db: State<'_, Mutex<Database>>,
+    sessions: State<'_, WebSocketSessions>,
+    input: ConnectWebSocketInput,
+) -> Result<WebSocketSession, String> {
+    let connected = websocket::connect(&input).await;
+
+    let (session, redactor) = {
+        let db = db.lock().map_err(|e| format!("Lock error: {}", e))?;
+        let redactor = Redactor::new(
+            &db.get_history_redaction(&input.workspace_id)?,
+            db.get_secret_values(&input.workspace_id)?,
+        );
+        let session = db.create_websocket_session(
+            &input.workspace_id,
+            input.request_id.as_deref(),
+            &redactor.url(&input.url)?,
+            connected
+                .as_ref()
+                .ok()
+                .and_then(|c| c.subprotocol.as_deref()),
+        )?;
+        if let Err(e) = &connected {
+            db.close_websocket_session(&session.id, None, None, Some(e.as_str()))?;
+        }
+        (session, redactor)
+    };
+    let connection = connected?;
+
+    let session_id = session.id.clone();
+    connection.run(&sessions, &session.id, move |event| {
+        let db = app.state::<Mutex<Database>>();
+        let Ok(db) = db.lock() else {
+            return;
+        };
+        match event {
+            SessionEvent::Message {
+                direction,
+                kind,
+                data,
+                size,
+            } => {
+                let stored = match kind {
+                    MessageKind::Text | MessageKind::Close => redactor.response_body(&data),
+                    // Base64 payloads can't be searched for secrets
+                    MessageKind::Binary | MessageKind::Ping | MessageKind::Pong => Ok(data.clone()),
+                };
+                // Like history, nothing is saved if redaction fails
+                let Ok(stored) = stored else {
+                    return;
+                };
+                if let Ok(message) =
+                    db.add_websocket_message(&session_id, direction, kind, &stored, size)
+                {
+                    let _ = app.emit("websocket-message", &WebSocketMessage { data, ..message });
+                }
+            }
+            SessionEvent::Closed {
+                code,
+                reason,
+                error,
+            } => {
+                let _ = app.state::<WebSocketSessions>().remove(&session_id);
+                let _ = db.close_websocket_session(
+                    &session_id,
+                    code,
+                    reason.as_deref(),
+                    error.as_deref(),
+                );
+                if let Ok(Some(session)) = db.get_websocket_session(&session_id) {
+                    let _ = app.emit("websocket-closed", &session);
+                }
+            }
+        }
+    })?;
+    Ok(session)
+}
+
+/// Sends a text frame, or a binary frame from base64 data.
+#[tauri::command]
+pub fn send_websocket_message(
+    sessions: State<'_, WebSocketSessions>,
+    input: SendWebSocketInput,
+) -> Result<(), String> {
+    let message = match input.kind {
+        MessageKind::Text => Message::Text(input.data),
+        MessageKind::Binary => Message::Binary(
+            base64::engine::general_purpose::STANDARD
+                .decode(input.data.trim())
+                .map_err(|e| format!("Binary data must be base64: {}", e))?,
+        ),
+        kind => return Err(format!("Cannot send a {} message", kind.as_str())),
+    };
+    sessions.send(&input.session_id, Command::Send(message))
+}
+
+/// The optional payload is sent as UTF-8. The server's pong arrives as a message.
+#[tauri::command]
+pub fn ping_websocket(
+    sessions: State<'_, WebSocketSessions>,
+    session_id: String,
+    payload: Option<String>,
+) -> Result<(), String> {
+    let payload = payload.unwrap_or_default().into_bytes();
+    if payload.len() > 125 {
+        return Err("Ping payload is limited to 125 bytes".to_string());
+    }
+    sessions.send(&session_id, Command::Send(Message::Ping(payload)))
+}
+
+/// Starts the closing handshake. The code defaults to 1000 (normal closure);
+/// otherwise it must be in the 3000-4999 range reserved for applications.
+#[tauri::command]
+pub fn close_websocket(
+    sessions: State<'_, WebSocketSessions>,
+    session_id: String,
+    code: Option<u16>,
+    reason: Option<String>,
+) -> Result<(), String> {
+    let code = code.unwrap_or(1000);
+    if code != 1000 && !(3000..=4999).contains(&code) {
+        return Err(format!(
+            "Close code {} is not allowed; use 1000 or 3000-4999",
+            code
+        ));
+    }
+    let reason = reason.unwrap_or_default();
+    if reason.len() > 123 {
+        return Err("Close reason is limited to 123 bytes".to_string());
+    }
+    sessions.send(&session_id, Command::Close { code, reason })
+}
+
+#[tauri::command]
+pub fn list_websocket_sessions(
+    db: State<'_, Mutex<Database>>,
+    workspace_id: String,
+    request_id: Option<String>,
+) -> Result<Vec<WebSocketSession>, String> {
+    let db = db.lock().map_err(|e| format!("Lock error: {}", e))?;
+    db.list_websocket_sessions(&workspace_id, request_id.as_deref())
+}
+
+#[tauri::command]
+pub fn get_websocket_transcript(
+    db: State<'_, Mutex<Database>>,
+    session_id: String,
+) -> Result<Vec<WebSocketMessage>, String> {
+    let db = db.lock().map_err(|e| format!("Lock error: {}", e))?;
+    db.list_websocket_messages(&session_id)
+}
+
+/// Deletes a session and its transcript, disconnecting it if still open.
+#[tauri::command]
+pub fn delete_websocket_session(
+    db: State<'_, Mutex<Database>>,
+    sessions: State<'_, WebSocketSessions>,
+    id: String,
+) -> Result<(), String> {
+    sessions.remove(&id)?;
+    let db = db.lock().map_err(|e| format!("Lock error: {}", e))?;
+    db.delete_websocket_session(&id)
+}
@@ -2,7 +2,7 @@ pub mod schema;
 pub mod repository;
 
 use rusqlite::Connection;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 pub struct Database {
     pub conn: Connection,
@@ -18,10 +18,29 @@ impl Database {
         Ok(Database { conn })
     }
 
+    /// Applies pending migrations from `schema::MIGRATIONS`, each in its own
+    /// transaction. The database file is backed up first if it already has data.
     pub fn run_migrations(&self) -> Result<(), String> {
+        let current = self.schema_version()?;
+        let latest = schema::MIGRATIONS.last().map_or(0, |m| m.version);
+        if current > latest {
+            return Err(format!(
+                "Database schema version {} is newer than this version of Steq supports ({})",
+                current, latest
+            ));
+        }
+
+        if current < latest && self.has_table("workspaces")? {
+            self.backup(current)?;
+        }
+
         self.conn
-            .execute_batch(schema::CREATE_TABLES)
-            .map_err(|e| format!("Failed to run migrations: {}", e))?;
+            .execute_batch(schema::CREATE_MIGRATIONS_TABLE)
+            .map_err(|e| format!("Failed to create migrations table: {}", e))?;
+
+        for migration in schema::MIGRATIONS.iter().filter(|m| m.version > current) {
+            self.apply_migration(migration)?;
+        }
 
         // Ensure a default workspace exists
         let count: i64 = self
@@ -42,4 +61,164 @@ impl Database {
 
         Ok(())
     }
+
+    /// The highest applied migration, or 0 for a new database or one created
+    /// before migrations were tracked.
+    pub fn schema_version(&self) -> Result<i64, String> {
+        if !self.has_table("schema_migrations")? {
+            return Ok(0);
+        }
+        self.conn
+            .query_row("SELECT COALESCE(MAX(version), 0) FROM schema_migrations", [], |row| row.get(0))
+            .map_err(|e| format!("Failed to read schema version: {}", e))
+    }
+
+    fn has_table(&self, name: &str) -> Result<bool, String> {
+        self.conn
+            .query_row(
+                "SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = ?1",
+                [name],
+                |row| row.get::<_, i64>(0),
+            )
+            .map(|count| count > 0)
+            .map_err(|e| format!("Failed to inspect schema: {}", e))
+    }
+
+    fn apply_migration(&self, migration: &schema::Migration) -> Result<(), String> {
+        let tx = self
+            .conn
+            .unchecked_transaction()
+            .map_err(|e| format!("Failed to start migration {}: {}", migration.version, e))?;
+        tx.execute_batch(migration.sql)
+            .map_err(|e| format!("Migration {} ({}) failed: {}", migration.version, migration.name, e))?;
+        tx.execute(
+            "INSERT INTO schema_migrations (version, name, applied_at) VALUES (?1, ?2, ?3)",
+            rusqlite::params![migration.version, migration.name, chrono::Utc::now().to_rfc3339()],
+        )
+        .map_err(|e| format!("Failed to record migration {}: {}", migration.version, e))?;
+        tx.commit()
+            .map_err(|e| format!("Failed to commit migration {}: {}", migration.version, e))
+    }
+
+    /// Writes a consistent copy of the database next to it, e.g. `steq.db.v1.bak`.
+    /// In-memory databases have no file and are not backed up.
+    fn backup(&self, version: i64) -> Result<(), String> {
+        let Some(path) = self.conn.path().filter(|p| !p.is_empty()) else {
+            return Ok(());
+        };
+        let backup_path = backup_path(Path::new(path), version);
+        if backup_path.exists() {
+            std::fs::remove_file(&backup_path)
+                .map_err(|e| format!("Failed to replace {}: {}", backup_path.display(), e))?;
+        }
+        self.conn
+            .execute("VACUUM INTO ?1", [backup_path.to_string_lossy()])
+            .map_err(|e| format!("Failed to back up database: {}", e))?;
+        Ok(())
+    }
+}
+
+pub fn backup_path(db_path: &Path, version: i64) -> PathBuf {
+    let mut name = db_path.file_name().unwrap_or_default().to_os_string();
+    name.push(format!(".v{}.bak", version));
+    db_path.with_file_name(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_db_path() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("steq-db-{}", uuid::Uuid::now_v7()));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir.join("steq.db")
+    }
+
+    fn has_column(db: &Database, table: &str, column: &str) -> bool {
+        db.conn
+            .query_row(
+                &format!("SELECT COUNT(*) FROM pragma_table_info('{}') WHERE name = ?1", table),
+                [column],
+                |row| row.get::<_, i64>(0),
+            )
+            .unwrap()
+            > 0
+    }
+
+    #[test]
+    fn upgrades_untracked_0_5_0_database() {
+        let path = temp_db_path();
+        {
+            // What 0.5.0 left on disk: the initial tables and no migration history
+            let db = Database::open(&path).unwrap();
+            db.conn.execute_batch(schema::MIGRATIONS[0].sql).unwrap();
+            db.conn
+                .execute_batch(
+                    "INSERT INTO workspaces VALUES ('w1', 'Mine', 't', 't');
+                     INSERT INTO collections VALUES ('c1', 'w1', 'API', NULL, 't', 't');
+                     INSERT INTO folders VALUES ('f1', 'c1', NULL, 'Users', 0, 't', 't');
+                     INSERT INTO requests (id, collection_id, folder_id, name, method, url, created_at, updated_at)
+                     VALUES ('r1', 'c1', 'f1', 'List', 'GET', 'https://api.test/users', 't', 't');",
+                )
+                .unwrap();
+        }
+
+        let db = Database::open(&path).unwrap();
+        db.run_migrations().unwrap();
+
+        assert_eq!(db.schema_version().unwrap(), schema::MIGRATIONS.last().unwrap().version);
+        assert!(has_column(&db, "folders", "description"));
+        assert!(has_column(&db, "requests", "scripts"));
+        assert!(db.has_table("collection_storage").unwrap());
+
+        let request = db.get_request("r1").unwrap().unwrap();
+        assert_eq!(request.url, "https://api.test/users");
+        assert!(request.path_params.is_empty());
+        assert_eq!(db.get_current_workspace().unwrap().id, "w1");
+        let hits = db
+            .search(&crate::models::search::SearchQuery {
+                workspace_id: "w1".to_string(),
+                query: "users".to_string(),
+                kinds: None,
+                limit: None,
+            })
+            .unwrap();
+        assert_eq!(hits.len(), 1, "existing rows are indexed");
+
+        let backup = Database::open(&backup_path(&path, 0)).unwrap();
+        assert!(!backup.has_table("schema_migrations").unwrap());
+        assert!(!has_column(&backup, "requests", "scripts"));
+
+        std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn migrating_twice_is_a_no_op() {
+        let path = temp_db_path();
+        let db = Database::open(&path).unwrap();
+        db.run_migrations().unwrap();
+        db.run_migrations().unwrap();
+
+        let applied: i64 = db
+            .conn
+            .query_row("SELECT COUNT(*) FROM schema_migrations", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(applied, schema::MIGRATIONS.len() as i64);
+        // A fresh database has nothing to lose, so no backup is taken
+        assert!(!backup_path(&path, 0).exists());
+
+        std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn rejects_database_from_newer_version() {
+        let db = Database { conn: Connection::open_in_memory().unwrap() };
+        db.run_migrations().unwrap();
+        db.conn
+            .execute("INSERT INTO schema_migrations VALUES (999, 'future', 't')", [])
+            .unwrap();
+
+        let err = db.run_migrations().unwrap_err();
+        assert!(err.contains("newer"));
+    }
 }
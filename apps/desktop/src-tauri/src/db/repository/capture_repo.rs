@@ -0,0 +1,254 @@
+use rusqlite::{params, OptionalExtension};
+
+use crate::crypto;
+use crate::db::repository::history_repo::{history_from_row, HISTORY_COLUMNS};
+use crate::db::Database;
+use crate::models::capture::CaptureSession;
+use crate::models::history::HistoryEntry;
+
+const SESSION_COLUMNS: &str = "s.id, s.workspace_id, s.name, s.started_at, s.stopped_at,
+    (SELECT COUNT(*) FROM capture_entries e WHERE e.session_id = s.id)";
+
+impl Database {
+    pub fn create_capture_session(
+        &self,
+        workspace_id: &str,
+        name: &str,
+    ) -> Result<CaptureSession, String> {
+        let id = uuid::Uuid::now_v7().to_string();
+        let now = chrono::Utc::now().to_rfc3339();
+        self.conn
+            .execute(
+                "INSERT INTO capture_sessions (id, workspace_id, name, started_at) VALUES (?1, ?2, ?3, ?4)",
+                params![id, workspace_id, name, now],
+            )
+            .map_err(|e| format!("Insert capture session: {}", e))?;
+
+        Ok(CaptureSession {
+            id,
+            workspace_id: workspace_id.to_string(),
+            name: name.to_string(),
+            started_at: now,
+            stopped_at: None,
+            entry_count: 0,
+        })
+    }
+
+    /// Marks the session as finished. Sessions already stopped keep their time.
+    pub fn stop_capture_session(&self, id: &str) -> Result<(), String> {
+        self.conn
+            .execute(
+                "UPDATE capture_sessions SET stopped_at = ?1 WHERE id = ?2 AND stopped_at IS NULL",
+                params![chrono::Utc::now().to_rfc3339(), id],
+            )
+            .map_err(|e| format!("Stop capture session: {}", e))?;
+        Ok(())
+    }
+
+    /// Sessions left open by a crash or shutdown are closed at their last entry.
+    pub fn close_open_capture_sessions(&self) -> Result<(), String> {
+        self.conn
+            .execute(
+                "UPDATE capture_sessions SET stopped_at = COALESCE(
+                     (SELECT MAX(h.executed_at) FROM capture_entries e
+                      JOIN history h ON h.id = e.history_id WHERE e.session_id = capture_sessions.id),
+                     started_at)
+                 WHERE stopped_at IS NULL",
+                [],
+            )
+            .map_err(|e| format!("Close capture sessions: {}", e))?;
+        Ok(())
+    }
+
+    pub fn get_capture_session(&self, id: &str) -> Result<Option<CaptureSession>, String> {
+        self.conn
+            .query_row(
+                &format!(
+                    "SELECT {} FROM capture_sessions s WHERE s.id = ?1",
+                    SESSION_COLUMNS
+                ),
+                params![id],
+                session_from_row,
+            )
+            .optional()
+            .map_err(|e| format!("Get capture session: {}", e))
+    }
+
+    /// Newest first.
+    pub fn list_capture_sessions(&self, workspace_id: &str) -> Result<Vec<CaptureSession>, String> {
+        let mut stmt = self
+            .conn
+            .prepare(&format!(
+                "SELECT {} FROM capture_sessions s WHERE s.workspace_id = ?1 ORDER BY s.started_at DESC",
+                SESSION_COLUMNS
+            ))
+            .map_err(|e| format!("Prepare list_capture_sessions: {}", e))?;
+        let sessions = stmt
+            .query_map(params![workspace_id], session_from_row)
+            .map_err(|e| format!("Query capture sessions: {}", e))?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("Read capture session: {}", e))?;
+        Ok(sessions)
+    }
+
+    /// Removes the session. Its entries stay in history.
+    pub fn delete_capture_session(&self, id: &str) -> Result<(), String> {
+        self.conn
+            .execute("DELETE FROM capture_sessions WHERE id = ?1", params![id])
+            .map_err(|e| format!("Delete capture session: {}", e))?;
+        Ok(())
+    }
+
+    pub fn add_capture_entry(&self, session_id: &str, history_id: &str) -> Result<(), String> {
+        self.conn
+            .execute(
+                "INSERT OR IGNORE INTO capture_entries (session_id, history_id) VALUES (?1, ?2)",
+                params![session_id, history_id],
+            )
+            .map_err(|e| format!("Insert capture entry: {}", e))?;
+        Ok(())
+    }
+
+    /// The session's history entries in the order they were captured. Entries
+    /// removed from history by retention or clearing are gone from the session too.
+    pub fn list_capture_entries(&self, session_id: &str) -> Result<Vec<HistoryEntry>, String> {
+        let mut stmt = self
+            .conn
+            .prepare(&format!(
+                "SELECT {} FROM history
+                 WHERE id IN (SELECT history_id FROM capture_entries WHERE session_id = ?1)
+                 ORDER BY executed_at ASC, id ASC",
+                HISTORY_COLUMNS
+            ))
+            .map_err(|e| format!("Prepare list_capture_entries: {}", e))?;
+        let entries = stmt
+            .query_map(params![session_id], history_from_row)
+            .map_err(|e| format!("Query capture entries: {}", e))?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("Read capture entry: {}", e))?;
+        Ok(entries)
+    }
+
+    /// The proxy's CA certificate and private key as PEM, if one was generated.
+    pub fn get_proxy_ca(&self) -> Result<Option<(String, String)>, String> {
+        let saved = self
+            .conn
+            .query_row(
+                "SELECT certificate, private_key FROM proxy_ca WHERE id = 1",
+                [],
+                |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)),
+            )
+            .optional()
+            .map_err(|e| format!("Get proxy CA: {}", e))?;
+        match saved {
+            Some((certificate, key)) => Ok(Some((certificate, crypto::decrypt(&key)?))),
+            None => Ok(None),
+        }
+    }
+
+    /// Stores the CA, encrypting the private key.
+    pub fn set_proxy_ca(&self, certificate: &str, private_key: &str) -> Result<(), String> {
+        self.conn
+            .execute(
+                "INSERT INTO proxy_ca (id, certificate, private_key, created_at) VALUES (1, ?1, ?2, ?3)
+                 ON CONFLICT(id) DO UPDATE SET
+                     certificate = excluded.certificate,
+                     private_key = excluded.private_key,
+                     created_at = excluded.created_at",
+                params![
+                    certificate,
+                    crypto::encrypt(private_key)?,
+                    chrono::Utc::now().to_rfc3339()
+                ],
+            )
+            .map_err(|e| format!("Save proxy CA: {}", e))?;
+        Ok(())
+    }
+}
+
+fn session_from_row(row: &rusqlite::Row) -> rusqlite::Result<CaptureSession> {
+    Ok(CaptureSession {
+        id: row.get(0)?,
+        workspace_id: row.get(1)?,
+        name: row.get(2)?,
+        started_at: row.get(3)?,
+        stopped_at: row.get(4)?,
+        entry_count: row.get(5)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rusqlite::Connection;
+
+    fn setup_test_db() -> (Database, String) {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch("PRAGMA foreign_keys=ON;").unwrap();
+        let db = Database { conn };
+        db.run_migrations().unwrap();
+        let workspace = db.get_current_workspace().unwrap();
+        (db, workspace.id)
+    }
+
+    fn save_entry(db: &Database, wid: &str, url: &str) -> HistoryEntry {
+        db.save_history_entry(
+            wid,
+            None,
+            "GET",
+            url,
+            "{}",
+            Some(200),
+            Some("[]"),
+            Some("ok"),
+            Some(2),
+            Some(1),
+            None,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn tracks_session_entries() {
+        let (db, wid) = setup_test_db();
+        let session = db.create_capture_session(&wid, "Login flow").unwrap();
+        let first = save_entry(&db, &wid, "https://api.test/login");
+        let second = save_entry(&db, &wid, "https://api.test/me");
+        db.add_capture_entry(&session.id, &first.id).unwrap();
+        db.add_capture_entry(&session.id, &second.id).unwrap();
+        db.add_capture_entry(&session.id, &second.id).unwrap();
+
+        let entries = db.list_capture_entries(&session.id).unwrap();
+        let urls: Vec<&str> = entries.iter().map(|e| e.url.as_str()).collect();
+        assert_eq!(urls, ["https://api.test/login", "https://api.test/me"]);
+
+        db.delete_history_entry(&first.id).unwrap();
+        let sessions = db.list_capture_sessions(&wid).unwrap();
+        assert_eq!(sessions[0].entry_count, 1);
+        assert!(sessions[0].stopped_at.is_none());
+
+        db.close_open_capture_sessions().unwrap();
+        let closed = db.get_capture_session(&session.id).unwrap().unwrap();
+        assert_eq!(closed.stopped_at, Some(second.executed_at));
+
+        db.delete_capture_session(&session.id).unwrap();
+        assert!(db.get_capture_session(&session.id).unwrap().is_none());
+        assert!(db.get_history_entry(&second.id).unwrap().is_some());
+    }
+
+    #[test]
+    fn encrypts_proxy_ca_key() {
+        let (db, _) = setup_test_db();
+        assert!(db.get_proxy_ca().unwrap().is_none());
+        db.set_proxy_ca("CERT", "KEY").unwrap();
+        let stored: String = db
+            .conn
+            .query_row("SELECT private_key FROM proxy_ca", [], |row| row.get(0))
+            .unwrap();
+        assert_ne!(stored, "KEY");
+        assert_eq!(
+            db.get_proxy_ca().unwrap(),
+            Some(("CERT".to_string(), "KEY".to_string()))
+        );
+    }
+}
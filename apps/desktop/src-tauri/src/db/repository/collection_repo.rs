@@ -1,4 +1,4 @@
-use rusqlite::params;
+use rusqlite::{params, OptionalExtension};
 
 use crate::db::Database;
 use crate::models::collection::{
@@ -83,9 +83,9 @@ impl Database {
 
         self.conn
             .execute(
-                "INSERT INTO folders (id, collection_id, parent_folder_id, name, sort_order, created_at, updated_at)
-                 VALUES (?1, ?2, ?3, ?4, 0, ?5, ?6)",
-                params![id, input.collection_id, input.parent_folder_id, input.name, now, now],
+                "INSERT INTO folders (id, collection_id, parent_folder_id, name, description, sort_order, created_at, updated_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, 0, ?6, ?7)",
+                params![id, input.collection_id, input.parent_folder_id, input.name, input.description, now, now],
             )
             .map_err(|e| format!("Insert folder: {}", e))?;
 
@@ -94,6 +94,7 @@ impl Database {
             collection_id: input.collection_id,
             parent_folder_id: input.parent_folder_id,
             name: input.name,
+            description: input.description,
             sort_order: 0,
             created_at: now.clone(),
             updated_at: now,
@@ -111,6 +112,29 @@ impl Database {
         Ok(())
     }
 
+    pub fn get_folder(&self, id: &str) -> Result<Option<Folder>, String> {
+        self.conn
+            .query_row(
+                "SELECT id, collection_id, parent_folder_id, name, sort_order, created_at, updated_at, description
+                 FROM folders WHERE id = ?1",
+                params![id],
+                |row| {
+                    Ok(Folder {
+                        id: row.get(0)?,
+                        collection_id: row.get(1)?,
+                        parent_folder_id: row.get(2)?,
+                        name: row.get(3)?,
+                        description: row.get(7)?,
+                        sort_order: row.get(4)?,
+                        created_at: row.get(5)?,
+                        updated_at: row.get(6)?,
+                    })
+                },
+            )
+            .optional()
+            .map_err(|e| format!("Get folder: {}", e))
+    }
+
     pub fn delete_folder(&self, id: &str) -> Result<(), String> {
         self.conn
             .execute("DELETE FROM folders WHERE id = ?1", params![id])
@@ -143,7 +167,7 @@ impl Database {
         let mut folder_stmt = self
             .conn
             .prepare(
-                "SELECT id, collection_id, parent_folder_id, name, sort_order, created_at, updated_at
+                "SELECT id, collection_id, parent_folder_id, name, sort_order, created_at, updated_at, description
                  FROM folders WHERE collection_id = ?1 ORDER BY sort_order ASC, name ASC",
             )
             .map_err(|e| format!("Prepare list_folders: {}", e))?;
@@ -155,6 +179,7 @@ impl Database {
                     collection_id: row.get(1)?,
                     parent_folder_id: row.get(2)?,
                     name: row.get(3)?,
+                    description: row.get(7)?,
                     sort_order: row.get(4)?,
                     created_at: row.get(5)?,
                     updated_at: row.get(6)?,
@@ -168,7 +193,7 @@ impl Database {
         let mut req_stmt = self
             .conn
             .prepare(
-                "SELECT id, collection_id, folder_id, name, method, url, headers, query_params, body_type, body_content, sort_order, created_at, updated_at
+                "SELECT id, collection_id, folder_id, name, method, url, headers, query_params, body_type, body_content, sort_order, created_at, updated_at, description, path_params, scripts, kind
                  FROM requests WHERE collection_id = ?1 ORDER BY sort_order ASC, name ASC",
             )
             .map_err(|e| format!("Prepare list_requests_tree: {}", e))?;
@@ -318,6 +343,7 @@ mod tests {
                 collection_id: coll.id.clone(),
                 parent_folder_id: None,
                 name: "Root Folder".to_string(),
+                description: None,
             })
             .unwrap();
 
@@ -342,6 +368,7 @@ mod tests {
                 collection_id: coll.id.clone(),
                 parent_folder_id: None,
                 name: "Parent".to_string(),
+                description: None,
             })
             .unwrap();
 
@@ -350,6 +377,7 @@ mod tests {
                 collection_id: coll.id.clone(),
                 parent_folder_id: Some(parent.id.clone()),
                 name: "Child".to_string(),
+                description: None,
             })
             .unwrap();
 
@@ -372,6 +400,7 @@ mod tests {
                 collection_id: coll.id.clone(),
                 parent_folder_id: None,
                 name: "Old".to_string(),
+                description: None,
             })
             .unwrap();
 
@@ -401,12 +430,18 @@ mod tests {
                 collection_id: coll.id.clone(),
                 parent_folder_id: None,
                 name: "Folder".to_string(),
+                description: None,
             })
             .unwrap();
 
+        assert_eq!(
+            db.get_folder(&folder.id).unwrap().unwrap().collection_id,
+            coll.id
+        );
         db.delete_folder(&folder.id).unwrap();
         let tree = db.get_collection_tree(&coll.id).unwrap();
         assert!(tree.root_folders.is_empty());
+        assert!(db.get_folder(&folder.id).unwrap().is_none());
     }
 
     #[test]
@@ -443,6 +478,7 @@ mod tests {
                 collection_id: coll.id.clone(),
                 parent_folder_id: None,
                 name: "Auth".to_string(),
+                description: None,
             })
             .unwrap();
 
@@ -451,6 +487,7 @@ mod tests {
                 collection_id: coll.id.clone(),
                 parent_folder_id: Some(root_folder.id.clone()),
                 name: "OAuth".to_string(),
+                description: None,
             })
             .unwrap();
 
@@ -465,6 +502,10 @@ mod tests {
             body_content: None,
             collection_id: Some(coll.id.clone()),
             folder_id: None,
+            description: None,
+            path_params: vec![],
+            scripts: None,
+            kind: RequestKind::Http,
         })
         .unwrap();
 
@@ -479,6 +520,10 @@ mod tests {
             body_content: Some("{\"user\":\"test\"}".to_string()),
             collection_id: Some(coll.id.clone()),
             folder_id: Some(root_folder.id.clone()),
+            description: None,
+            path_params: vec![],
+            scripts: None,
+            kind: RequestKind::Http,
         })
         .unwrap();
 
@@ -493,6 +538,10 @@ mod tests {
             body_content: Some("grant_type=client_credentials".to_string()),
             collection_id: Some(coll.id.clone()),
             folder_id: Some(nested_folder.id.clone()),
+            description: None,
+            path_params: vec![],
+            scripts: None,
+            kind: RequestKind::Http,
         })
         .unwrap();
 
@@ -527,6 +576,7 @@ mod tests {
                 collection_id: coll.id.clone(),
                 parent_folder_id: None,
                 name: "Folder".to_string(),
+                description: None,
             })
             .unwrap();
 
@@ -540,6 +590,10 @@ mod tests {
             body_content: None,
             collection_id: Some(coll.id.clone()),
             folder_id: Some(folder.id.clone()),
+            description: None,
+            path_params: vec![],
+            scripts: None,
+            kind: RequestKind::Http,
         })
         .unwrap();
 
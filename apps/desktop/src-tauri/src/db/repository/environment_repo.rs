@@ -281,6 +281,16 @@ impl Database {
     /// Returns a flat map of key→value for all enabled variables
     /// in the currently active environment for the given workspace.
     pub fn get_resolved_variables(&self, workspace_id: &str) -> Result<Vec<(String, String)>, String> {
+        self.active_variables(workspace_id, true)
+    }
+
+    /// Like `get_resolved_variables`, but secret values stay as `{{key}}`, for
+    /// documents that leave the app.
+    pub fn get_exported_variables(&self, workspace_id: &str) -> Result<Vec<(String, String)>, String> {
+        self.active_variables(workspace_id, false)
+    }
+
+    fn active_variables(&self, workspace_id: &str, reveal_secrets: bool) -> Result<Vec<(String, String)>, String> {
         let mut stmt = self
             .conn
             .prepare(
@@ -306,16 +316,42 @@ impl Database {
 
         let mut result = Vec::with_capacity(pairs.len());
         for (key, value, is_secret) in pairs {
-            let decrypted_value = if is_secret {
-                crypto::decrypt(&value).unwrap_or_else(|_| String::new())
-            } else {
-                value
+            let resolved_value = match (is_secret, reveal_secrets) {
+                (false, _) => value,
+                (true, true) => crypto::decrypt(&value).unwrap_or_else(|_| String::new()),
+                (true, false) => format!("{{{{{}}}}}", key),
             };
-            result.push((key, decrypted_value));
+            result.push((key, resolved_value));
         }
 
         Ok(result)
     }
+
+    /// Returns the decrypted values of every secret variable in the workspace, in
+    /// any environment, so they can be kept out of history.
+    pub fn get_secret_values(&self, workspace_id: &str) -> Result<Vec<String>, String> {
+        let mut stmt = self
+            .conn
+            .prepare(
+                "SELECT v.value
+                 FROM variables v
+                 INNER JOIN environments e ON v.environment_id = e.id
+                 WHERE e.workspace_id = ?1 AND v.is_secret = 1",
+            )
+            .map_err(|e| format!("Failed to prepare secret variables query: {}", e))?;
+
+        let values = stmt
+            .query_map(params![workspace_id], |row| row.get::<_, String>(0))
+            .map_err(|e| format!("Failed to query secret variables: {}", e))?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("Failed to read secret variables: {}", e))?;
+
+        Ok(values
+            .iter()
+            .filter_map(|value| crypto::decrypt(value).ok())
+            .filter(|value| !value.is_empty())
+            .collect())
+    }
 }
 
 use rusqlite::OptionalExtension;
@@ -644,6 +680,7 @@ mod tests {
         // The stored value should be decrypted back to the original
         assert_eq!(updated.variables[0].value, "super-secret-value");
         assert!(updated.variables[0].is_secret);
+        assert_eq!(db.get_secret_values(&wid).unwrap(), ["super-secret-value"]);
     }
 
     #[test]
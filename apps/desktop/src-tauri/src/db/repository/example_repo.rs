@@ -0,0 +1,287 @@
+use rusqlite::{params, OptionalExtension};
+
+use crate::db::Database;
+use crate::models::example::{CreateExampleInput, RequestExample, UpdateExampleInput};
+
+const EXAMPLE_COLUMNS: &str = "id, request_id, name, status, headers, body, original_request,
+    sort_order, created_at, updated_at";
+
+impl Database {
+    /// Adds an example after the request's existing ones.
+    pub fn create_example(&self, input: CreateExampleInput) -> Result<RequestExample, String> {
+        let id = uuid::Uuid::now_v7().to_string();
+        let now = chrono::Utc::now().to_rfc3339();
+        let headers_json = serde_json::to_string(&input.headers)
+            .map_err(|e| format!("Serialize headers: {}", e))?;
+        let original_json = input
+            .original_request
+            .as_ref()
+            .map(serde_json::to_string)
+            .transpose()
+            .map_err(|e| format!("Serialize original request: {}", e))?;
+        let sort_order: i32 = self
+            .conn
+            .query_row(
+                "SELECT COALESCE(MAX(sort_order) + 1, 0) FROM request_examples WHERE request_id = ?1",
+                params![input.request_id],
+                |row| row.get(0),
+            )
+            .map_err(|e| format!("Query example order: {}", e))?;
+
+        self.conn
+            .execute(
+                "INSERT INTO request_examples (id, request_id, name, status, headers, body, original_request, sort_order, created_at, updated_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+                params![
+                    id,
+                    input.request_id,
+                    input.name,
+                    input.status,
+                    headers_json,
+                    input.body,
+                    original_json,
+                    sort_order,
+                    now,
+                    now,
+                ],
+            )
+            .map_err(|e| format!("Insert example: {}", e))?;
+
+        Ok(RequestExample {
+            id,
+            request_id: input.request_id,
+            name: input.name,
+            status: input.status,
+            headers: input.headers,
+            body: input.body,
+            original_request: input.original_request,
+            sort_order,
+            created_at: now.clone(),
+            updated_at: now,
+        })
+    }
+
+    pub fn list_examples(&self, request_id: &str) -> Result<Vec<RequestExample>, String> {
+        let mut stmt = self
+            .conn
+            .prepare(&format!(
+                "SELECT {} FROM request_examples WHERE request_id = ?1 ORDER BY sort_order, created_at",
+                EXAMPLE_COLUMNS
+            ))
+            .map_err(|e| format!("Prepare list_examples: {}", e))?;
+
+        let examples = stmt
+            .query_map(params![request_id], example_from_row)
+            .map_err(|e| format!("Query list_examples: {}", e))?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("Parse example: {}", e))?;
+
+        Ok(examples)
+    }
+
+    /// Examples of every request in a collection, in request and example order.
+    pub fn list_collection_examples(
+        &self,
+        collection_id: &str,
+    ) -> Result<Vec<RequestExample>, String> {
+        let mut stmt = self
+            .conn
+            .prepare(&format!(
+                "SELECT {} FROM request_examples
+                 WHERE request_id IN (SELECT id FROM requests WHERE collection_id = ?1)
+                 ORDER BY request_id, sort_order, created_at",
+                EXAMPLE_COLUMNS
+            ))
+            .map_err(|e| format!("Prepare list_collection_examples: {}", e))?;
+
+        let examples = stmt
+            .query_map(params![collection_id], example_from_row)
+            .map_err(|e| format!("Query list_collection_examples: {}", e))?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("Parse example: {}", e))?;
+
+        Ok(examples)
+    }
+
+    pub fn get_example(&self, id: &str) -> Result<Option<RequestExample>, String> {
+        self.conn
+            .query_row(
+                &format!(
+                    "SELECT {} FROM request_examples WHERE id = ?1",
+                    EXAMPLE_COLUMNS
+                ),
+                params![id],
+                example_from_row,
+            )
+            .optional()
+            .map_err(|e| format!("Query get_example: {}", e))
+    }
+
+    pub fn update_example(&self, input: UpdateExampleInput) -> Result<RequestExample, String> {
+        let existing = self
+            .get_example(&input.id)?
+            .ok_or_else(|| format!("Example not found: {}", input.id))?;
+
+        let now = chrono::Utc::now().to_rfc3339();
+        let name = input.name.unwrap_or(existing.name);
+        let status = input.status.or(existing.status);
+        let headers = input.headers.unwrap_or(existing.headers);
+        let body = input.body.or(existing.body);
+        let original_request = input.original_request.or(existing.original_request);
+        let sort_order = input.sort_order.unwrap_or(existing.sort_order);
+
+        let headers_json =
+            serde_json::to_string(&headers).map_err(|e| format!("Serialize headers: {}", e))?;
+        let original_json = original_request
+            .as_ref()
+            .map(serde_json::to_string)
+            .transpose()
+            .map_err(|e| format!("Serialize original request: {}", e))?;
+
+        self.conn
+            .execute(
+                "UPDATE request_examples SET name=?1, status=?2, headers=?3, body=?4, original_request=?5, sort_order=?6, updated_at=?7
+                 WHERE id=?8",
+                params![
+                    name,
+                    status,
+                    headers_json,
+                    body,
+                    original_json,
+                    sort_order,
+                    now,
+                    input.id,
+                ],
+            )
+            .map_err(|e| format!("Update example: {}", e))?;
+
+        Ok(RequestExample {
+            id: input.id,
+            request_id: existing.request_id,
+            name,
+            status,
+            headers,
+            body,
+            original_request,
+            sort_order,
+            created_at: existing.created_at,
+            updated_at: now,
+        })
+    }
+
+    pub fn delete_example(&self, id: &str) -> Result<(), String> {
+        self.conn
+            .execute("DELETE FROM request_examples WHERE id = ?1", params![id])
+            .map_err(|e| format!("Delete example: {}", e))?;
+        Ok(())
+    }
+}
+
+fn example_from_row(row: &rusqlite::Row) -> rusqlite::Result<RequestExample> {
+    let headers: String = row.get(4)?;
+    let original: Option<String> = row.get(6)?;
+    Ok(RequestExample {
+        id: row.get(0)?,
+        request_id: row.get(1)?,
+        name: row.get(2)?,
+        status: row.get(3)?,
+        headers: serde_json::from_str(&headers).unwrap_or_default(),
+        body: row.get(5)?,
+        original_request: original.and_then(|o| serde_json::from_str(&o).ok()),
+        sort_order: row.get(7)?,
+        created_at: row.get(8)?,
+        updated_at: row.get(9)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::db::Database;
+    use crate::models::collection::CreateCollectionInput;
+    use crate::models::example::{CreateExampleInput, UpdateExampleInput};
+    use crate::models::request::{BodyType, CreateRequestInput, HttpMethod, KeyValue, RequestKind};
+    use rusqlite::Connection;
+
+    fn setup_test_db() -> (Database, String) {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch("PRAGMA foreign_keys=ON;").unwrap();
+        let db = Database { conn };
+        db.run_migrations().unwrap();
+        let workspace = db.get_current_workspace().unwrap();
+        (db, workspace.id)
+    }
+
+    fn example_input(request_id: &str, name: &str) -> CreateExampleInput {
+        CreateExampleInput {
+            request_id: request_id.to_string(),
+            name: name.to_string(),
+            status: Some(200),
+            headers: vec![KeyValue {
+                key: "Content-Type".to_string(),
+                value: "application/json".to_string(),
+                enabled: true,
+            }],
+            body: Some("{}".to_string()),
+            original_request: None,
+        }
+    }
+
+    #[test]
+    fn example_crud_and_cascade() {
+        let (db, wid) = setup_test_db();
+        let collection = db
+            .create_collection(CreateCollectionInput {
+                workspace_id: wid,
+                name: "API".to_string(),
+                description: None,
+            })
+            .unwrap();
+        let request = db
+            .create_request(CreateRequestInput {
+                name: "Users".to_string(),
+                method: HttpMethod::GET,
+                url: "https://api.test/users".to_string(),
+                headers: vec![],
+                query_params: vec![],
+                body_type: BodyType::None,
+                body_content: None,
+                description: None,
+                path_params: vec![],
+                scripts: None,
+                kind: RequestKind::Http,
+                collection_id: Some(collection.id.clone()),
+                folder_id: None,
+            })
+            .unwrap();
+
+        let ok = db.create_example(example_input(&request.id, "OK")).unwrap();
+        let missing = db
+            .create_example(example_input(&request.id, "Not found"))
+            .unwrap();
+        assert_eq!((ok.sort_order, missing.sort_order), (0, 1));
+
+        let updated = db
+            .update_example(UpdateExampleInput {
+                id: missing.id.clone(),
+                name: None,
+                status: Some(404),
+                headers: None,
+                body: Some("{\"error\":\"not found\"}".to_string()),
+                original_request: None,
+                sort_order: None,
+            })
+            .unwrap();
+        assert_eq!(updated.name, "Not found");
+        assert_eq!(updated.status, Some(404));
+        assert_eq!(updated.headers.len(), 1);
+
+        let listed = db.list_collection_examples(&collection.id).unwrap();
+        assert_eq!(listed.len(), 2);
+        assert_eq!(listed[1].body, updated.body);
+
+        db.delete_example(&ok.id).unwrap();
+        assert_eq!(db.list_examples(&request.id).unwrap().len(), 1);
+        db.delete_request(&request.id).unwrap();
+        assert!(db.get_example(&missing.id).unwrap().is_none());
+    }
+}
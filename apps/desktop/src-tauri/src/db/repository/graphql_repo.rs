@@ -0,0 +1,108 @@
+use rusqlite::{params, OptionalExtension};
+
+use crate::db::Database;
+use crate::models::graphql::GraphQLSchema;
+
+impl Database {
+    /// Caches the introspection result for the URL, replacing any earlier one.
+    pub fn save_graphql_schema(
+        &self,
+        workspace_id: &str,
+        url: &str,
+        introspection: &serde_json::Value,
+    ) -> Result<GraphQLSchema, String> {
+        let now = chrono::Utc::now().to_rfc3339();
+        self.conn
+            .execute(
+                "INSERT INTO graphql_schemas (workspace_id, url, introspection, fetched_at) VALUES (?1, ?2, ?3, ?4)
+                 ON CONFLICT(workspace_id, url) DO UPDATE SET introspection = excluded.introspection, fetched_at = excluded.fetched_at",
+                params![workspace_id, url, introspection.to_string(), now],
+            )
+            .map_err(|e| format!("Save graphql schema: {}", e))?;
+
+        Ok(GraphQLSchema {
+            workspace_id: workspace_id.to_string(),
+            url: url.to_string(),
+            introspection: introspection.clone(),
+            fetched_at: now,
+        })
+    }
+
+    pub fn get_graphql_schema(
+        &self,
+        workspace_id: &str,
+        url: &str,
+    ) -> Result<Option<GraphQLSchema>, String> {
+        let row = self
+            .conn
+            .query_row(
+                "SELECT introspection, fetched_at FROM graphql_schemas WHERE workspace_id = ?1 AND url = ?2",
+                params![workspace_id, url],
+                |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)),
+            )
+            .optional()
+            .map_err(|e| format!("Get graphql schema: {}", e))?;
+
+        row.map(|(introspection, fetched_at)| {
+            Ok(GraphQLSchema {
+                workspace_id: workspace_id.to_string(),
+                url: url.to_string(),
+                introspection: serde_json::from_str(&introspection)
+                    .map_err(|e| format!("Read graphql schema: {}", e))?,
+                fetched_at,
+            })
+        })
+        .transpose()
+    }
+
+    pub fn delete_graphql_schema(&self, workspace_id: &str, url: &str) -> Result<(), String> {
+        self.conn
+            .execute(
+                "DELETE FROM graphql_schemas WHERE workspace_id = ?1 AND url = ?2",
+                params![workspace_id, url],
+            )
+            .map_err(|e| format!("Delete graphql schema: {}", e))?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rusqlite::Connection;
+
+    fn setup_test_db() -> (Database, String) {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch("PRAGMA foreign_keys=ON;").unwrap();
+        let db = Database { conn };
+        db.run_migrations().unwrap();
+        let workspace = db.get_current_workspace().unwrap();
+        (db, workspace.id)
+    }
+
+    #[test]
+    fn test_schema_cache() {
+        let (db, wid) = setup_test_db();
+        let url = "https://api.example.com/graphql";
+        assert!(db.get_graphql_schema(&wid, url).unwrap().is_none());
+
+        db.save_graphql_schema(
+            &wid,
+            url,
+            &serde_json::json!({ "__schema": { "types": [] } }),
+        )
+        .unwrap();
+        let updated = serde_json::json!({ "__schema": { "types": [{ "name": "Query" }] } });
+        db.save_graphql_schema(&wid, url, &updated).unwrap();
+
+        let cached = db.get_graphql_schema(&wid, url).unwrap().unwrap();
+        assert_eq!(cached.introspection, updated);
+        assert!(db
+            .get_graphql_schema(&wid, "https://other.example.com/graphql")
+            .unwrap()
+            .is_none());
+
+        db.delete_graphql_schema(&wid, url).unwrap();
+        assert!(db.get_graphql_schema(&wid, url).unwrap().is_none());
+    }
+}
@@ -1,6 +1,7 @@
 use crate::db::Database;
-use crate::models::history::{HistoryEntry, HistoryQuery};
-use rusqlite::params;
+use crate::models::history::{HistoryEntry, HistoryQuery, HistoryRetention, HistorySort, LargeBodyMode};
+use rusqlite::types::Value;
+use rusqlite::{params, params_from_iter, OptionalExtension};
 
 impl Database {
     pub fn save_history_entry(
@@ -19,12 +20,22 @@ impl Database {
     ) -> Result<HistoryEntry, String> {
         let id = uuid::Uuid::now_v7().to_string();
         let now = chrono::Utc::now().to_rfc3339();
+        let retention = self.get_history_retention(workspace_id)?;
+        let stored = response_body.map(|body| store_body(body, &retention)).transpose()?;
+        let (text, zstd, truncated, body_bytes) = match &stored {
+            Some(StoredBody::Text { text, truncated }) => (Some(text.as_str()), None, *truncated, text.len()),
+            Some(StoredBody::Zstd { data, searchable }) => {
+                (Some(searchable.as_str()), Some(data.as_slice()), false, searchable.len() + data.len())
+            }
+            None => (None, None, false, 0),
+        };
 
         self.conn
             .execute(
                 "INSERT INTO history (id, request_id, workspace_id, method, url, request_snapshot,
-                 response_status, response_headers, response_body, response_size, duration_ms, error, executed_at)
-                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)",
+                 response_status, response_headers, response_body, response_size, duration_ms, error, executed_at,
+                 response_body_zstd, response_body_truncated, body_bytes)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16)",
                 params![
                     id,
                     request_id,
@@ -34,11 +45,14 @@ impl Database {
                     request_snapshot,
                     response_status,
                     response_headers,
-                    response_body,
+                    text,
                     response_size,
                     duration_ms,
                     error,
                     &now,
+                    zstd,
+                    truncated,
+                    body_bytes as i64,
                 ],
             )
             .map_err(|e| format!("Failed to save history entry: {}", e))?;
@@ -52,7 +66,12 @@ impl Database {
             request_snapshot: request_snapshot.to_string(),
             response_status,
             response_headers: response_headers.map(|s| s.to_string()),
-            response_body: response_body.map(|s| s.to_string()),
+            response_body: match zstd {
+                Some(_) => response_body,
+                None => text,
+            }
+            .map(|s| s.to_string()),
+            response_body_truncated: truncated,
             response_size,
             duration_ms,
             error: error.map(|s| s.to_string()),
@@ -61,40 +80,126 @@ impl Database {
     }
 
     pub fn list_history(&self, query: &HistoryQuery) -> Result<Vec<HistoryEntry>, String> {
-        let limit = query.limit.unwrap_or(50);
-        let offset = query.offset.unwrap_or(0);
+        let mut conditions = vec!["workspace_id = ?".to_string()];
+        let mut values: Vec<Value> = vec![query.workspace_id.clone().into()];
 
+        if !query.methods.is_empty() {
+            conditions.push(format!("upper(method) IN ({})", placeholders(query.methods.len())));
+            values.extend(query.methods.iter().map(|m| m.to_uppercase().into()));
+        }
+        if !query.status_codes.is_empty() || !query.status_classes.is_empty() {
+            conditions.push(format!(
+                "(response_status IN ({}) OR response_status / 100 IN ({}))",
+                placeholders(query.status_codes.len()),
+                placeholders(query.status_classes.len())
+            ));
+            values.extend(query.status_codes.iter().map(|&code| i64::from(code).into()));
+            values.extend(query.status_classes.iter().map(|&class| i64::from(class).into()));
+        }
+        if let Some(text) = query.url_contains.as_deref().filter(|t| !t.is_empty()) {
+            conditions.push("url LIKE ? ESCAPE '\\'".to_string());
+            values.push(format!("%{}%", escape_like(text)).into());
+        }
+        if let Some(host) = query.host.as_deref().filter(|h| !h.is_empty()) {
+            // The host follows `://` and ends at the first `/`, `:`, `?` or `#`
+            let patterns: Vec<String> = ["", "/%", ":%", "?%", "#%"]
+                .iter()
+                .map(|suffix| format!("%://{}{}", escape_like(host), suffix))
+                .collect();
+            conditions.push(format!(
+                "({})",
+                vec!["url LIKE ? ESCAPE '\\'"; patterns.len()].join(" OR ")
+            ));
+            values.extend(patterns.into_iter().map(Value::from));
+        }
+        if let Some(request_id) = &query.request_id {
+            conditions.push("request_id = ?".to_string());
+            values.push(request_id.clone().into());
+        }
+        if let Some(after) = &query.executed_after {
+            conditions.push("executed_at >= ?".to_string());
+            values.push(normalize_timestamp(after)?.into());
+        }
+        if let Some(before) = &query.executed_before {
+            conditions.push("executed_at <= ?".to_string());
+            values.push(normalize_timestamp(before)?.into());
+        }
+        if query.errors_only {
+            conditions.push("error IS NOT NULL".to_string());
+        }
+        if let Some(min) = query.min_duration_ms {
+            conditions.push("duration_ms >= ?".to_string());
+            values.push(min.into());
+        }
+
+        let (direction, comparison) = match query.sort {
+            HistorySort::NewestFirst => ("DESC", "<"),
+            HistorySort::OldestFirst => ("ASC", ">"),
+        };
+        if let Some(cursor) = &query.after {
+            // Ids are UUIDv7, so they break ties between entries recorded in the same instant
+            conditions.push(format!("(executed_at, id) {} (?, ?)", comparison));
+            values.push(cursor.executed_at.clone().into());
+            values.push(cursor.id.clone().into());
+        }
+        values.push(query.limit.unwrap_or(50).into());
+        values.push(query.offset.unwrap_or(0).into());
+
+        let sql = format!(
+            "SELECT {} FROM history
+             WHERE {}
+             ORDER BY executed_at {dir}, id {dir}
+             LIMIT ? OFFSET ?",
+            HISTORY_COLUMNS,
+            conditions.join(" AND "),
+            dir = direction
+        );
+        let mut stmt = self
+            .conn
+            .prepare(&sql)
+            .map_err(|e| format!("Failed to prepare history query: {}", e))?;
+
+        let entries = stmt
+            .query_map(params_from_iter(values), history_from_row)
+            .map_err(|e| format!("Failed to query history: {}", e))?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("Failed to read history: {}", e))?;
+
+        Ok(entries)
+    }
+
+    pub fn get_history_entry(&self, id: &str) -> Result<Option<HistoryEntry>, String> {
+        self.conn
+            .query_row(
+                &format!("SELECT {} FROM history WHERE id = ?1", HISTORY_COLUMNS),
+                params![id],
+                history_from_row,
+            )
+            .optional()
+            .map_err(|e| format!("Failed to get history entry: {}", e))
+    }
+
+    /// Returns the most recent history entries recorded for a saved request.
+    pub fn list_history_by_request(
+        &self,
+        request_id: &str,
+        limit: i64,
+    ) -> Result<Vec<HistoryEntry>, String> {
         let mut stmt = self
             .conn
             .prepare(
-                "SELECT id, request_id, workspace_id, method, url, request_snapshot,
-                        response_status, response_headers, response_body, response_size,
-                        duration_ms, error, executed_at
-                 FROM history
-                 WHERE workspace_id = ?1
+                &format!(
+                "SELECT {} FROM history
+                 WHERE request_id = ?1
                  ORDER BY executed_at DESC
-                 LIMIT ?2 OFFSET ?3",
+                 LIMIT ?2",
+                HISTORY_COLUMNS
+            ),
             )
             .map_err(|e| format!("Failed to prepare history query: {}", e))?;
 
         let entries = stmt
-            .query_map(params![query.workspace_id, limit, offset], |row| {
-                Ok(HistoryEntry {
-                    id: row.get(0)?,
-                    request_id: row.get(1)?,
-                    workspace_id: row.get(2)?,
-                    method: row.get(3)?,
-                    url: row.get(4)?,
-                    request_snapshot: row.get(5)?,
-                    response_status: row.get(6)?,
-                    response_headers: row.get(7)?,
-                    response_body: row.get(8)?,
-                    response_size: row.get(9)?,
-                    duration_ms: row.get(10)?,
-                    error: row.get(11)?,
-                    executed_at: row.get(12)?,
-                })
-            })
+            .query_map(params![request_id, limit], history_from_row)
             .map_err(|e| format!("Failed to query history: {}", e))?
             .collect::<Result<Vec<_>, _>>()
             .map_err(|e| format!("Failed to read history: {}", e))?;
@@ -117,10 +222,100 @@ impl Database {
     }
 }
 
+pub(crate) const HISTORY_COLUMNS: &str = "id, request_id, workspace_id, method, url, request_snapshot,
+    response_status, response_headers, response_body, response_size, duration_ms, error, executed_at,
+    response_body_zstd, response_body_truncated";
+
+pub(crate) fn history_from_row(row: &rusqlite::Row) -> rusqlite::Result<HistoryEntry> {
+    let text: Option<String> = row.get(8)?;
+    let compressed: Option<Vec<u8>> = row.get(13)?;
+    let response_body = match compressed {
+        Some(bytes) => Some(decompress_body(&bytes).map_err(|e| {
+            rusqlite::Error::FromSqlConversionFailure(13, rusqlite::types::Type::Blob, e.into())
+        })?),
+        None => text,
+    };
+    Ok(HistoryEntry {
+        id: row.get(0)?,
+        request_id: row.get(1)?,
+        workspace_id: row.get(2)?,
+        method: row.get(3)?,
+        url: row.get(4)?,
+        request_snapshot: row.get(5)?,
+        response_status: row.get(6)?,
+        response_headers: row.get(7)?,
+        response_body,
+        response_body_truncated: row.get(14)?,
+        response_size: row.get(9)?,
+        duration_ms: row.get(10)?,
+        error: row.get(11)?,
+        executed_at: row.get(12)?,
+    })
+}
+
+/// How much of a compressed body is also kept as text for full-text search.
+pub(crate) const SEARCHABLE_BODY_BYTES: usize = 16 * 1024;
+
+/// How a response body is kept in the `history` table.
+pub(crate) enum StoredBody {
+    Text { text: String, truncated: bool },
+    /// `searchable` is the start of the body, stored in `response_body` so the
+    /// search index covers it. Reads use the compressed copy.
+    Zstd { data: Vec<u8>, searchable: String },
+}
+
+/// Bodies up to the workspace's `large_body_bytes` are stored as-is; larger ones
+/// are compressed or cut to that size.
+pub(crate) fn store_body(body: &str, retention: &HistoryRetention) -> Result<StoredBody, String> {
+    let limit = retention.large_body_bytes.max(0) as usize;
+    if body.len() <= limit {
+        return Ok(StoredBody::Text { text: body.to_string(), truncated: false });
+    }
+    match retention.large_body_mode {
+        LargeBodyMode::Compress => zstd::encode_all(body.as_bytes(), 0)
+            .map(|data| StoredBody::Zstd {
+                data,
+                searchable: prefix(body, limit.min(SEARCHABLE_BODY_BYTES)).to_string(),
+            })
+            .map_err(|e| format!("Failed to compress response body: {}", e)),
+        LargeBodyMode::Truncate => Ok(StoredBody::Text { text: prefix(body, limit).to_string(), truncated: true }),
+    }
+}
+
+/// At most `max` bytes from the start of `text`, cut on a character boundary.
+fn prefix(text: &str, max: usize) -> &str {
+    let mut end = max.min(text.len());
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    &text[..end]
+}
+
+pub(crate) fn decompress_body(bytes: &[u8]) -> Result<String, String> {
+    let decoded = zstd::decode_all(bytes).map_err(|e| format!("Failed to decompress response body: {}", e))?;
+    String::from_utf8(decoded).map_err(|e| format!("Compressed response body is not UTF-8: {}", e))
+}
+
+fn placeholders(count: usize) -> String {
+    vec!["?"; count].join(", ")
+}
+
+fn escape_like(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_")
+}
+
+/// `executed_at` is stored as UTC RFC 3339, so bounds are converted to the same
+/// form for the text comparison to order correctly.
+fn normalize_timestamp(value: &str) -> Result<String, String> {
+    chrono::DateTime::parse_from_rfc3339(value)
+        .map(|time| time.with_timezone(&chrono::Utc).to_rfc3339())
+        .map_err(|e| format!("Invalid timestamp {}: {}", value, e))
+}
+
 #[cfg(test)]
 mod tests {
     use crate::db::Database;
-    use crate::models::history::HistoryQuery;
+    use crate::models::history::{HistoryCursor, HistoryQuery, HistorySort};
     use rusqlite::Connection;
 
     fn setup_test_db() -> (Database, String) {
@@ -145,6 +340,7 @@ mod tests {
                 workspace_id: wid,
                 limit: None,
                 offset: None,
+                ..Default::default()
             })
             .unwrap();
 
@@ -168,6 +364,7 @@ mod tests {
                 workspace_id: wid.clone(),
                 limit: Some(2),
                 offset: Some(1),
+                ..Default::default()
             })
             .unwrap();
 
@@ -184,12 +381,30 @@ mod tests {
                 workspace_id: wid,
                 limit: None,
                 offset: None,
+                ..Default::default()
             })
             .unwrap();
 
         assert!(entries.is_empty());
     }
 
+    #[test]
+    fn list_history_by_request() {
+        let (db, wid) = setup_test_db();
+
+        db.save_history_entry(&wid, Some("req-1"), "GET", "https://a.com", "{}", Some(200), None, None, None, None, None).unwrap();
+        db.save_history_entry(&wid, Some("req-2"), "GET", "https://b.com", "{}", Some(200), None, None, None, None, None).unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(10));
+        db.save_history_entry(&wid, Some("req-1"), "GET", "https://a.com", "{}", Some(404), None, None, None, None, None).unwrap();
+
+        let entries = db.list_history_by_request("req-1", 10).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].response_status, Some(404));
+
+        let limited = db.list_history_by_request("req-1", 1).unwrap();
+        assert_eq!(limited.len(), 1);
+    }
+
     #[test]
     fn delete_history_entry() {
         let (db, wid) = setup_test_db();
@@ -205,12 +420,26 @@ mod tests {
                 workspace_id: wid,
                 limit: None,
                 offset: None,
+                ..Default::default()
             })
             .unwrap();
 
         assert!(entries.is_empty());
     }
 
+    #[test]
+    fn get_history_entry() {
+        let (db, wid) = setup_test_db();
+
+        let entry = db
+            .save_history_entry(&wid, None, "GET", "https://example.com", "{}", Some(200), None, None, None, None, None)
+            .unwrap();
+
+        let found = db.get_history_entry(&entry.id).unwrap().unwrap();
+        assert_eq!(found.url, "https://example.com");
+        assert!(db.get_history_entry("missing").unwrap().is_none());
+    }
+
     #[test]
     fn clear_history() {
         let (db, wid) = setup_test_db();
@@ -225,6 +454,7 @@ mod tests {
                 workspace_id: wid,
                 limit: None,
                 offset: None,
+                ..Default::default()
             })
             .unwrap();
 
@@ -262,4 +492,137 @@ mod tests {
         assert!(entry.response_body.is_some());
         assert!(entry.error.is_none());
     }
+
+    fn save_at(db: &Database, wid: &str, method: &str, url: &str, status: Option<i32>, duration: Option<i64>, executed_at: &str) -> String {
+        let entry = db
+            .save_history_entry(wid, None, method, url, "{}", status, None, None, None, duration, None)
+            .unwrap();
+        db.conn
+            .execute("UPDATE history SET executed_at = ?1 WHERE id = ?2", rusqlite::params![executed_at, entry.id])
+            .unwrap();
+        entry.id
+    }
+
+    fn urls(entries: &[crate::models::history::HistoryEntry]) -> Vec<&str> {
+        entries.iter().map(|e| e.url.as_str()).collect()
+    }
+
+    #[test]
+    fn filter_by_method_status_and_url() {
+        let (db, wid) = setup_test_db();
+        save_at(&db, &wid, "GET", "https://api.test/users", Some(200), None, "2026-01-01T00:00:00+00:00");
+        save_at(&db, &wid, "POST", "https://api.test:8443/users", Some(201), None, "2026-01-01T00:00:01+00:00");
+        save_at(&db, &wid, "DELETE", "https://api.test.evil.com/users_1", Some(404), None, "2026-01-01T00:00:02+00:00");
+        save_at(&db, &wid, "GET", "http://other.test/health?q=api.test", Some(503), None, "2026-01-01T00:00:03+00:00");
+
+        let list = |query: HistoryQuery| db.list_history(&query).unwrap();
+        let base = HistoryQuery {
+            workspace_id: wid.clone(),
+            sort: HistorySort::OldestFirst,
+            ..Default::default()
+        };
+
+        let entries = list(HistoryQuery { methods: vec!["get".to_string()], ..base.clone() });
+        assert_eq!(urls(&entries), ["https://api.test/users", "http://other.test/health?q=api.test"]);
+
+        let entries = list(HistoryQuery { status_codes: vec![201], status_classes: vec![5], ..base.clone() });
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].response_status, Some(201));
+        assert_eq!(entries[1].response_status, Some(503));
+
+        let entries = list(HistoryQuery { url_contains: Some("users_".to_string()), ..base.clone() });
+        assert_eq!(urls(&entries), ["https://api.test.evil.com/users_1"]);
+
+        let entries = list(HistoryQuery { host: Some("api.test".to_string()), ..base.clone() });
+        assert_eq!(urls(&entries), ["https://api.test/users", "https://api.test:8443/users"]);
+    }
+
+    #[test]
+    fn filter_by_time_errors_and_duration() {
+        let (db, wid) = setup_test_db();
+        save_at(&db, &wid, "GET", "https://a.test/1", Some(200), Some(50), "2026-01-01T10:00:00+00:00");
+        let failed = save_at(&db, &wid, "GET", "https://a.test/2", None, Some(3000), "2026-01-02T10:00:00+00:00");
+        db.conn
+            .execute("UPDATE history SET error = 'timed out' WHERE id = ?1", [failed])
+            .unwrap();
+        save_at(&db, &wid, "GET", "https://a.test/3", Some(200), Some(900), "2026-01-03T10:00:00+00:00");
+
+        let list = |query: HistoryQuery| db.list_history(&query).unwrap();
+        let base = HistoryQuery {
+            workspace_id: wid.clone(),
+            ..Default::default()
+        };
+
+        // Bounds in another offset are compared in UTC
+        let entries = list(HistoryQuery {
+            executed_after: Some("2026-01-02T12:00:00+02:00".to_string()),
+            executed_before: Some("2026-01-03T10:00:00Z".to_string()),
+            ..base.clone()
+        });
+        assert_eq!(urls(&entries), ["https://a.test/3", "https://a.test/2"]);
+
+        let entries = list(HistoryQuery { errors_only: true, ..base.clone() });
+        assert_eq!(urls(&entries), ["https://a.test/2"]);
+
+        let entries = list(HistoryQuery { min_duration_ms: Some(900), ..base.clone() });
+        assert_eq!(urls(&entries), ["https://a.test/3", "https://a.test/2"]);
+
+        let err = db
+            .list_history(&HistoryQuery { executed_after: Some("yesterday".to_string()), ..base })
+            .unwrap_err();
+        assert!(err.contains("Invalid timestamp"));
+    }
+
+    #[test]
+    fn keyset_pagination() {
+        let (db, wid) = setup_test_db();
+        // Two entries share a timestamp, so the id must break the tie
+        for (i, at) in ["2026-01-01T00:00:00+00:00", "2026-01-01T00:00:01+00:00", "2026-01-01T00:00:01+00:00", "2026-01-01T00:00:02+00:00"]
+            .iter()
+            .enumerate()
+        {
+            save_at(&db, &wid, "GET", &format!("https://a.test/{}", i), None, None, at);
+        }
+
+        for sort in [HistorySort::NewestFirst, HistorySort::OldestFirst] {
+            let mut seen = Vec::new();
+            let mut after = None;
+            loop {
+                let page = db
+                    .list_history(&HistoryQuery {
+                        workspace_id: wid.clone(),
+                        limit: Some(3),
+                        sort,
+                        after: after.take(),
+                        ..Default::default()
+                    })
+                    .unwrap();
+                let Some(last) = page.last() else { break };
+                after = Some(HistoryCursor {
+                    executed_at: last.executed_at.clone(),
+                    id: last.id.clone(),
+                });
+                seen.extend(page.into_iter().map(|e| e.url));
+            }
+            let mut expected: Vec<String> = (0..4).map(|i| format!("https://a.test/{}", i)).collect();
+            if sort == HistorySort::NewestFirst {
+                expected.reverse();
+            }
+            assert_eq!(seen, expected);
+        }
+    }
+
+    #[test]
+    fn list_history_uses_workspace_index() {
+        let (db, wid) = setup_test_db();
+        let plan: Vec<String> = db
+            .conn
+            .prepare("EXPLAIN QUERY PLAN SELECT id FROM history WHERE workspace_id = ?1 ORDER BY executed_at DESC, id DESC")
+            .unwrap()
+            .query_map([wid], |row| row.get::<_, String>(3))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert!(plan.iter().any(|step| step.contains("idx_history_workspace_executed_at")));
+    }
 }
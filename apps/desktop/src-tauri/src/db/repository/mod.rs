@@ -1,5 +1,13 @@
+pub mod capture_repo;
 pub mod collection_repo;
 pub mod environment_repo;
+pub mod example_repo;
+pub mod graphql_repo;
 pub mod history_repo;
+pub mod redaction_repo;
 pub mod request_repo;
+pub mod retention_repo;
+pub mod search_repo;
+pub mod storage_repo;
+pub mod websocket_repo;
 pub mod workspace_repo;
@@ -0,0 +1,97 @@
+use crate::db::Database;
+use crate::models::history::{HistoryRedaction, RedactionMode};
+use rusqlite::{params, OptionalExtension};
+
+impl Database {
+    /// Returns the workspace's redaction rules, or the defaults if none were saved.
+    pub fn get_history_redaction(&self, workspace_id: &str) -> Result<HistoryRedaction, String> {
+        let saved = self
+            .conn
+            .query_row(
+                "SELECT header_patterns, field_patterns, mode FROM history_redaction WHERE workspace_id = ?1",
+                params![workspace_id],
+                |row| {
+                    Ok((
+                        row.get::<_, String>(0)?,
+                        row.get::<_, String>(1)?,
+                        row.get::<_, String>(2)?,
+                    ))
+                },
+            )
+            .optional()
+            .map_err(|e| format!("Failed to get history redaction: {}", e))?;
+
+        let Some((header_patterns, field_patterns, mode)) = saved else {
+            return Ok(HistoryRedaction::default_for(workspace_id));
+        };
+        Ok(HistoryRedaction {
+            workspace_id: workspace_id.to_string(),
+            header_patterns: serde_json::from_str(&header_patterns).unwrap_or_default(),
+            field_patterns: serde_json::from_str(&field_patterns).unwrap_or_default(),
+            mode: match mode.as_str() {
+                "encrypt" => RedactionMode::Encrypt,
+                _ => RedactionMode::Mask,
+            },
+        })
+    }
+
+    pub fn set_history_redaction(&self, redaction: &HistoryRedaction) -> Result<(), String> {
+        let header_patterns = serde_json::to_string(&redaction.header_patterns)
+            .map_err(|e| format!("Failed to serialize header patterns: {}", e))?;
+        let field_patterns = serde_json::to_string(&redaction.field_patterns)
+            .map_err(|e| format!("Failed to serialize field patterns: {}", e))?;
+        let mode = match redaction.mode {
+            RedactionMode::Mask => "mask",
+            RedactionMode::Encrypt => "encrypt",
+        };
+        self.conn
+            .execute(
+                "INSERT INTO history_redaction (workspace_id, header_patterns, field_patterns, mode, updated_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5)
+                 ON CONFLICT(workspace_id) DO UPDATE SET
+                     header_patterns = excluded.header_patterns,
+                     field_patterns = excluded.field_patterns,
+                     mode = excluded.mode,
+                     updated_at = excluded.updated_at",
+                params![
+                    redaction.workspace_id,
+                    header_patterns,
+                    field_patterns,
+                    mode,
+                    chrono::Utc::now().to_rfc3339(),
+                ],
+            )
+            .map_err(|e| format!("Failed to save history redaction: {}", e))?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::db::Database;
+    use crate::models::history::{HistoryRedaction, RedactionMode};
+    use rusqlite::Connection;
+
+    #[test]
+    fn redaction_defaults_and_round_trips() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch("PRAGMA foreign_keys=ON;").unwrap();
+        let db = Database { conn };
+        db.run_migrations().unwrap();
+        let wid = db.get_current_workspace().unwrap().id;
+
+        assert_eq!(
+            db.get_history_redaction(&wid).unwrap(),
+            HistoryRedaction::default_for(&wid)
+        );
+
+        let redaction = HistoryRedaction {
+            workspace_id: wid.clone(),
+            header_patterns: vec!["x-tenant-*".to_string()],
+            field_patterns: vec![],
+            mode: RedactionMode::Encrypt,
+        };
+        db.set_history_redaction(&redaction).unwrap();
+        assert_eq!(db.get_history_redaction(&wid).unwrap(), redaction);
+    }
+}
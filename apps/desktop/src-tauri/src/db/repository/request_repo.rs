@@ -1,7 +1,10 @@
 use rusqlite::params;
 
 use crate::db::Database;
-use crate::models::request::{ApiRequest, BodyType, CreateRequestInput, HttpMethod, KeyValue, UpdateRequestInput};
+use crate::models::request::{
+    ApiRequest, BodyType, CreateRequestInput, HttpMethod, KeyValue, RequestKind, RequestScripts,
+    UpdateRequestInput,
+};
 
 impl Database {
     pub fn create_request(&self, input: CreateRequestInput) -> Result<ApiRequest, String> {
@@ -11,11 +14,20 @@ impl Database {
             serde_json::to_string(&input.headers).map_err(|e| format!("Serialize headers: {}", e))?;
         let query_json = serde_json::to_string(&input.query_params)
             .map_err(|e| format!("Serialize query_params: {}", e))?;
+        let path_json = serde_json::to_string(&input.path_params)
+            .map_err(|e| format!("Serialize path_params: {}", e))?;
+        let scripts_json = input
+            .scripts
+            .as_ref()
+            .map(serde_json::to_string)
+            .transpose()
+            .map_err(|e| format!("Serialize scripts: {}", e))?;
+        let kind_json = kind_to_column(&input.kind)?;
 
         self.conn
             .execute(
-                "INSERT INTO requests (id, collection_id, folder_id, name, method, url, headers, query_params, body_type, body_content, sort_order, created_at, updated_at)
-                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, 0, ?11, ?12)",
+                "INSERT INTO requests (id, collection_id, folder_id, name, method, url, headers, query_params, body_type, body_content, sort_order, created_at, updated_at, description, path_params, scripts, kind)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, 0, ?11, ?12, ?13, ?14, ?15, ?16)",
                 params![
                     id,
                     input.collection_id,
@@ -29,6 +41,10 @@ impl Database {
                     input.body_content,
                     now,
                     now,
+                    input.description,
+                    path_json,
+                    scripts_json,
+                    kind_json,
                 ],
             )
             .map_err(|e| format!("Insert request: {}", e))?;
@@ -42,6 +58,10 @@ impl Database {
             query_params: input.query_params,
             body_type: input.body_type,
             body_content: input.body_content,
+            description: input.description,
+            path_params: input.path_params,
+            scripts: input.scripts,
+            kind: input.kind,
             collection_id: input.collection_id,
             folder_id: input.folder_id,
             sort_order: 0,
@@ -54,7 +74,7 @@ impl Database {
         let mut stmt = self
             .conn
             .prepare(
-                "SELECT id, collection_id, folder_id, name, method, url, headers, query_params, body_type, body_content, sort_order, created_at, updated_at
+                "SELECT id, collection_id, folder_id, name, method, url, headers, query_params, body_type, body_content, sort_order, created_at, updated_at, description, path_params, scripts, kind
                  FROM requests WHERE id = ?1",
             )
             .map_err(|e| format!("Prepare get_request: {}", e))?;
@@ -100,16 +120,36 @@ impl Database {
             existing.folder_id
         };
         let sort_order = input.sort_order.unwrap_or(existing.sort_order);
+        let description = if input.description.is_some() {
+            input.description
+        } else {
+            existing.description
+        };
+        let path_params = input.path_params.unwrap_or(existing.path_params);
+        let scripts = if input.scripts.is_some() {
+            input.scripts
+        } else {
+            existing.scripts
+        };
+        let kind = input.kind.unwrap_or(existing.kind);
 
         let headers_json =
             serde_json::to_string(&headers).map_err(|e| format!("Serialize headers: {}", e))?;
         let query_json = serde_json::to_string(&query_params)
             .map_err(|e| format!("Serialize query_params: {}", e))?;
+        let path_json = serde_json::to_string(&path_params)
+            .map_err(|e| format!("Serialize path_params: {}", e))?;
+        let scripts_json = scripts
+            .as_ref()
+            .map(serde_json::to_string)
+            .transpose()
+            .map_err(|e| format!("Serialize scripts: {}", e))?;
+        let kind_json = kind_to_column(&kind)?;
 
         self.conn
             .execute(
-                "UPDATE requests SET name=?1, method=?2, url=?3, headers=?4, query_params=?5, body_type=?6, body_content=?7, collection_id=?8, folder_id=?9, sort_order=?10, updated_at=?11
-                 WHERE id=?12",
+                "UPDATE requests SET name=?1, method=?2, url=?3, headers=?4, query_params=?5, body_type=?6, body_content=?7, collection_id=?8, folder_id=?9, sort_order=?10, updated_at=?11, description=?12, path_params=?13, scripts=?14, kind=?15
+                 WHERE id=?16",
                 params![
                     name,
                     method.as_str(),
@@ -122,6 +162,10 @@ impl Database {
                     folder_id,
                     sort_order,
                     now,
+                    description,
+                    path_json,
+                    scripts_json,
+                    kind_json,
                     input.id,
                 ],
             )
@@ -136,6 +180,10 @@ impl Database {
             query_params,
             body_type,
             body_content,
+            description,
+            path_params,
+            scripts,
+            kind,
             collection_id,
             folder_id,
             sort_order,
@@ -155,7 +203,7 @@ impl Database {
         let mut stmt = self
             .conn
             .prepare(
-                "SELECT id, collection_id, folder_id, name, method, url, headers, query_params, body_type, body_content, sort_order, created_at, updated_at
+                "SELECT id, collection_id, folder_id, name, method, url, headers, query_params, body_type, body_content, sort_order, created_at, updated_at, description, path_params, scripts, kind
                  FROM requests WHERE collection_id = ?1 ORDER BY sort_order ASC",
             )
             .map_err(|e| format!("Prepare list_requests: {}", e))?;
@@ -184,6 +232,20 @@ impl Database {
             serde_json::from_str(&headers_str).map_err(|e| format!("Parse headers: {}", e))?;
         let query_params: Vec<KeyValue> =
             serde_json::from_str(&query_str).map_err(|e| format!("Parse query_params: {}", e))?;
+        let path_str: String = row.get(14).map_err(|e| format!("Get path_params: {}", e))?;
+        let path_params: Vec<KeyValue> =
+            serde_json::from_str(&path_str).map_err(|e| format!("Parse path_params: {}", e))?;
+        let scripts_str: Option<String> = row.get(15).map_err(|e| format!("Get scripts: {}", e))?;
+        let scripts: Option<RequestScripts> = scripts_str
+            .map(|s| serde_json::from_str(&s))
+            .transpose()
+            .map_err(|e| format!("Parse scripts: {}", e))?;
+        let kind_str: Option<String> = row.get(16).map_err(|e| format!("Get kind: {}", e))?;
+        let kind: RequestKind = kind_str
+            .map(|s| serde_json::from_str(&s))
+            .transpose()
+            .map_err(|e| format!("Parse kind: {}", e))?
+            .unwrap_or_default();
 
         Ok(ApiRequest {
             id: row.get(0).map_err(|e| format!("Get id: {}", e))?,
@@ -196,6 +258,10 @@ impl Database {
             query_params,
             body_type: BodyType::from_str(&body_type_str),
             body_content: row.get(9).map_err(|e| format!("Get body_content: {}", e))?,
+            description: row.get(13).map_err(|e| format!("Get description: {}", e))?,
+            path_params,
+            scripts,
+            kind,
             sort_order: row.get(10).map_err(|e| format!("Get sort_order: {}", e))?,
             created_at: row.get(11).map_err(|e| format!("Get created_at: {}", e))?,
             updated_at: row.get(12).map_err(|e| format!("Get updated_at: {}", e))?,
@@ -203,6 +269,16 @@ impl Database {
     }
 }
 
+/// HTTP requests are stored as NULL, like rows written before kinds existed.
+pub(crate) fn kind_to_column(kind: &RequestKind) -> Result<Option<String>, String> {
+    match kind {
+        RequestKind::Http => Ok(None),
+        kind => serde_json::to_string(kind)
+            .map(Some)
+            .map_err(|e| format!("Serialize kind: {}", e)),
+    }
+}
+
 use rusqlite::OptionalExtension;
 
 #[cfg(test)]
@@ -250,6 +326,10 @@ mod tests {
             body_content: None,
             collection_id: Some(collection_id.to_string()),
             folder_id: None,
+            description: None,
+            path_params: vec![],
+            scripts: None,
+            kind: RequestKind::Http,
         }
     }
 
@@ -301,6 +381,10 @@ mod tests {
             collection_id: None,
             folder_id: None,
             sort_order: None,
+            description: None,
+            path_params: None,
+            scripts: None,
+            kind: None,
         };
 
         let updated = db.update_request(update).unwrap();
@@ -326,6 +410,10 @@ mod tests {
             collection_id: None,
             folder_id: None,
             sort_order: None,
+            description: None,
+            path_params: None,
+            scripts: None,
+            kind: None,
         };
         let result = db.update_request(update);
         assert!(result.is_err());
@@ -396,6 +484,10 @@ mod tests {
                 body_content: content.clone(),
                 collection_id: Some(cid.clone()),
                 folder_id: None,
+                description: None,
+                path_params: vec![],
+                scripts: None,
+                kind: RequestKind::Http,
             };
             let created = db.create_request(input).unwrap();
             let fetched = db.get_request(&created.id).unwrap().unwrap();
@@ -0,0 +1,451 @@
+use crate::db::repository::history_repo::{decompress_body, store_body, StoredBody};
+use crate::db::Database;
+use crate::models::history::{HistoryRetention, LargeBodyMode, VacuumResult};
+use rusqlite::{params, OptionalExtension};
+
+impl Database {
+    /// Returns the workspace's retention settings, or the defaults if none were saved.
+    pub fn get_history_retention(&self, workspace_id: &str) -> Result<HistoryRetention, String> {
+        let saved = self
+            .conn
+            .query_row(
+                "SELECT max_entries, max_age_days, max_body_bytes, large_body_bytes, large_body_mode
+                 FROM history_retention WHERE workspace_id = ?1",
+                params![workspace_id],
+                |row| {
+                    Ok(HistoryRetention {
+                        workspace_id: workspace_id.to_string(),
+                        max_entries: row.get(0)?,
+                        max_age_days: row.get(1)?,
+                        max_body_bytes: row.get(2)?,
+                        large_body_bytes: row.get(3)?,
+                        large_body_mode: match row.get::<_, String>(4)?.as_str() {
+                            "truncate" => LargeBodyMode::Truncate,
+                            _ => LargeBodyMode::Compress,
+                        },
+                    })
+                },
+            )
+            .optional()
+            .map_err(|e| format!("Failed to get history retention: {}", e))?;
+        Ok(saved.unwrap_or_else(|| HistoryRetention::default_for(workspace_id)))
+    }
+
+    pub fn set_history_retention(&self, retention: &HistoryRetention) -> Result<(), String> {
+        let mode = match retention.large_body_mode {
+            LargeBodyMode::Compress => "compress",
+            LargeBodyMode::Truncate => "truncate",
+        };
+        self.conn
+            .execute(
+                "INSERT INTO history_retention (workspace_id, max_entries, max_age_days, max_body_bytes,
+                 large_body_bytes, large_body_mode, updated_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+                 ON CONFLICT(workspace_id) DO UPDATE SET
+                     max_entries = excluded.max_entries,
+                     max_age_days = excluded.max_age_days,
+                     max_body_bytes = excluded.max_body_bytes,
+                     large_body_bytes = excluded.large_body_bytes,
+                     large_body_mode = excluded.large_body_mode,
+                     updated_at = excluded.updated_at",
+                params![
+                    retention.workspace_id,
+                    retention.max_entries,
+                    retention.max_age_days,
+                    retention.max_body_bytes,
+                    retention.large_body_bytes,
+                    mode,
+                    chrono::Utc::now().to_rfc3339(),
+                ],
+            )
+            .map_err(|e| format!("Failed to save history retention: {}", e))?;
+        Ok(())
+    }
+
+    /// Deletes history entries that fall outside the workspace's retention limits,
+    /// oldest first. Returns the number of entries removed.
+    pub fn prune_history(&self, workspace_id: &str) -> Result<usize, String> {
+        let retention = self.get_history_retention(workspace_id)?;
+        let mut removed = 0;
+
+        if let Some(days) = retention.max_age_days {
+            let cutoff = (chrono::Utc::now() - chrono::Duration::days(days)).to_rfc3339();
+            removed += self
+                .conn
+                .execute(
+                    "DELETE FROM history WHERE workspace_id = ?1 AND executed_at < ?2",
+                    params![workspace_id, cutoff],
+                )
+                .map_err(|e| format!("Failed to prune old history: {}", e))?;
+        }
+
+        if let Some(max) = retention.max_entries {
+            removed += self
+                .conn
+                .execute(
+                    "DELETE FROM history WHERE id IN (
+                         SELECT id FROM history WHERE workspace_id = ?1
+                         ORDER BY executed_at DESC, id DESC
+                         LIMIT -1 OFFSET ?2
+                     )",
+                    params![workspace_id, max.max(0)],
+                )
+                .map_err(|e| format!("Failed to prune history entries: {}", e))?;
+        }
+
+        if let Some(max) = retention.max_body_bytes {
+            removed += self
+                .conn
+                .execute(
+                    "DELETE FROM history WHERE id IN (
+                         SELECT id FROM (
+                             SELECT id, SUM(body_bytes) OVER (ORDER BY executed_at DESC, id DESC) AS total
+                             FROM history WHERE workspace_id = ?1
+                         )
+                         WHERE total > ?2
+                     )",
+                    params![workspace_id, max],
+                )
+                .map_err(|e| format!("Failed to prune history bodies: {}", e))?;
+        }
+
+        Ok(removed)
+    }
+
+    /// Compresses or truncates up to `limit` bodies stored before the current large
+    /// body limit applied, and restores the searchable start of bodies compressed
+    /// before it was kept. Returns how many were rewritten. Callers repeat it
+    /// until fewer than `limit` come back; each call is short enough to run between
+    /// other work on the connection.
+    pub fn compact_history_bodies(
+        &self,
+        workspace_id: &str,
+        limit: usize,
+    ) -> Result<usize, String> {
+        let retention = self.get_history_retention(workspace_id)?;
+        let mut stmt = self
+            .conn
+            .prepare(
+                "SELECT id, response_body, response_body_zstd FROM history
+                 WHERE workspace_id = ?1 AND response_body_truncated = 0 AND (
+                     (response_body_zstd IS NULL AND response_body IS NOT NULL AND body_bytes > ?2)
+                     OR (response_body_zstd IS NOT NULL AND response_body IS NULL)
+                 )
+                 LIMIT ?3",
+            )
+            .map_err(|e| format!("Failed to prepare history compaction: {}", e))?;
+        let large = stmt
+            .query_map(
+                params![workspace_id, retention.large_body_bytes, limit as i64],
+                |row| {
+                    Ok((
+                        row.get::<_, String>(0)?,
+                        row.get::<_, Option<String>>(1)?,
+                        row.get::<_, Option<Vec<u8>>>(2)?,
+                    ))
+                },
+            )
+            .map_err(|e| format!("Failed to query large history bodies: {}", e))?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("Failed to read large history bodies: {}", e))?;
+
+        let compacted = large.len();
+        for (id, text, compressed) in large {
+            let body = match compressed {
+                Some(bytes) => decompress_body(&bytes)?,
+                None => text.unwrap_or_default(),
+            };
+            let (text, zstd, truncated, bytes) = match store_body(&body, &retention)? {
+                StoredBody::Text { text, truncated } => {
+                    let bytes = text.len();
+                    (Some(text), None, truncated, bytes)
+                }
+                StoredBody::Zstd { data, searchable } => {
+                    let bytes = searchable.len() + data.len();
+                    (Some(searchable), Some(data), false, bytes)
+                }
+            };
+            self.conn
+                .execute(
+                    "UPDATE history SET response_body = ?1, response_body_zstd = ?2,
+                     response_body_truncated = ?3, body_bytes = ?4 WHERE id = ?5",
+                    params![text, zstd, truncated, bytes as i64, id],
+                )
+                .map_err(|e| format!("Failed to compact history entry: {}", e))?;
+        }
+        Ok(compacted)
+    }
+
+    /// Applies retention and compaction to every workspace in one go. Returns the
+    /// number of entries removed.
+    pub fn prune_all_history(&self) -> Result<usize, String> {
+        let mut removed = 0;
+        for workspace_id in self.list_workspace_ids()? {
+            removed += self.prune_history(&workspace_id)?;
+            while self.compact_history_bodies(&workspace_id, 500)? > 0 {}
+        }
+        Ok(removed)
+    }
+
+    /// Prunes all history, then rewrites the database file to release free pages.
+    pub fn vacuum(&self) -> Result<VacuumResult, String> {
+        let bytes_before = self.database_size()?;
+        let entries_pruned = self.prune_all_history()?;
+
+        self.conn
+            .execute_batch("VACUUM")
+            .map_err(|e| format!("Failed to vacuum database: {}", e))?;
+        // VACUUM may renumber the rowids the search indexes point at
+        self.conn
+            .execute_batch(
+                "INSERT INTO requests_fts (requests_fts) VALUES ('rebuild');
+                 INSERT INTO collections_fts (collections_fts) VALUES ('rebuild');
+                 INSERT INTO history_fts (history_fts) VALUES ('rebuild');",
+            )
+            .map_err(|e| format!("Failed to rebuild search index: {}", e))?;
+        // Fold the WAL back into the main file so its size reflects the vacuum
+        self.conn
+            .query_row("PRAGMA wal_checkpoint(TRUNCATE)", [], |_| Ok(()))
+            .map_err(|e| format!("Failed to checkpoint database: {}", e))?;
+
+        let bytes_after = self.database_size()?;
+        Ok(VacuumResult {
+            entries_pruned,
+            bytes_before,
+            bytes_after,
+            bytes_reclaimed: (bytes_before - bytes_after).max(0),
+        })
+    }
+
+    fn database_size(&self) -> Result<i64, String> {
+        self.conn
+            .query_row(
+                "SELECT page_count * page_size FROM pragma_page_count(), pragma_page_size()",
+                [],
+                |row| row.get(0),
+            )
+            .map_err(|e| format!("Failed to read database size: {}", e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::db::Database;
+    use crate::models::history::{HistoryQuery, HistoryRetention, LargeBodyMode};
+    use rusqlite::Connection;
+
+    fn setup_test_db() -> (Database, String) {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch("PRAGMA foreign_keys=ON;").unwrap();
+        let db = Database { conn };
+        db.run_migrations().unwrap();
+        let workspace = db.get_current_workspace().unwrap();
+        (db, workspace.id)
+    }
+
+    fn save(db: &Database, wid: &str, url: &str, body: &str, executed_at: &str) -> String {
+        let entry = db
+            .save_history_entry(
+                wid,
+                None,
+                "GET",
+                url,
+                "{}",
+                Some(200),
+                None,
+                Some(body),
+                None,
+                None,
+                None,
+            )
+            .unwrap();
+        db.conn
+            .execute(
+                "UPDATE history SET executed_at = ?1 WHERE id = ?2",
+                rusqlite::params![executed_at, entry.id],
+            )
+            .unwrap();
+        entry.id
+    }
+
+    fn urls(db: &Database, wid: &str) -> Vec<String> {
+        db.list_history(&HistoryQuery {
+            workspace_id: wid.to_string(),
+            ..Default::default()
+        })
+        .unwrap()
+        .into_iter()
+        .map(|e| e.url)
+        .collect()
+    }
+
+    #[test]
+    fn retention_defaults_and_round_trips() {
+        let (db, wid) = setup_test_db();
+        assert_eq!(
+            db.get_history_retention(&wid).unwrap(),
+            HistoryRetention::default_for(&wid)
+        );
+
+        let retention = HistoryRetention {
+            max_entries: None,
+            max_age_days: Some(30),
+            large_body_mode: LargeBodyMode::Truncate,
+            ..HistoryRetention::default_for(&wid)
+        };
+        db.set_history_retention(&retention).unwrap();
+        assert_eq!(db.get_history_retention(&wid).unwrap(), retention);
+    }
+
+    #[test]
+    fn prunes_by_count_age_and_body_size() {
+        let (db, wid) = setup_test_db();
+        let recent = chrono::Utc::now();
+        for i in 0..4 {
+            let at = (recent - chrono::Duration::minutes(4 - i)).to_rfc3339();
+            save(
+                &db,
+                &wid,
+                &format!("https://a.test/{}", i),
+                "0123456789",
+                &at,
+            );
+        }
+        save(
+            &db,
+            &wid,
+            "https://a.test/ancient",
+            "x",
+            "2020-01-01T00:00:00+00:00",
+        );
+
+        db.set_history_retention(&HistoryRetention {
+            max_entries: Some(4),
+            max_age_days: Some(365),
+            max_body_bytes: Some(25),
+            ..HistoryRetention::default_for(&wid)
+        })
+        .unwrap();
+
+        // The ancient entry goes by age, then the two oldest remaining bodies
+        // push the total past 25 bytes
+        assert_eq!(db.prune_history(&wid).unwrap(), 3);
+        assert_eq!(urls(&db, &wid), ["https://a.test/3", "https://a.test/2"]);
+    }
+
+    #[test]
+    fn large_bodies_are_compressed_or_truncated() {
+        let (db, wid) = setup_test_db();
+        let body = "é".repeat(100);
+        db.set_history_retention(&HistoryRetention {
+            large_body_bytes: 51,
+            ..HistoryRetention::default_for(&wid)
+        })
+        .unwrap();
+
+        let compressed = save(
+            &db,
+            &wid,
+            "https://a.test/zstd",
+            &body,
+            "2026-01-01T00:00:00+00:00",
+        );
+        let entry = db.get_history_entry(&compressed).unwrap().unwrap();
+        assert_eq!(entry.response_body.as_deref(), Some(body.as_str()));
+        assert!(!entry.response_body_truncated);
+        // Only the start is kept as text, for search
+        let raw = || -> Option<String> {
+            db.conn
+                .query_row(
+                    "SELECT response_body FROM history WHERE id = ?1",
+                    [&compressed],
+                    |row| row.get(0),
+                )
+                .unwrap()
+        };
+        assert_eq!(raw().as_deref(), Some("é".repeat(25).as_str()));
+
+        // Bodies compressed before the start was kept get it back when compacted
+        db.conn
+            .execute(
+                "UPDATE history SET response_body = NULL WHERE id = ?1",
+                [&compressed],
+            )
+            .unwrap();
+        assert_eq!(db.compact_history_bodies(&wid, 10).unwrap(), 1);
+        assert_eq!(raw().as_deref(), Some("é".repeat(25).as_str()));
+        assert_eq!(db.compact_history_bodies(&wid, 10).unwrap(), 0);
+
+        db.set_history_retention(&HistoryRetention {
+            large_body_bytes: 51,
+            large_body_mode: LargeBodyMode::Truncate,
+            ..HistoryRetention::default_for(&wid)
+        })
+        .unwrap();
+        let truncated = save(
+            &db,
+            &wid,
+            "https://a.test/cut",
+            &body,
+            "2026-01-01T00:00:01+00:00",
+        );
+        let entry = db.get_history_entry(&truncated).unwrap().unwrap();
+        // Cut on a character boundary below the limit
+        assert_eq!(
+            entry.response_body.as_deref(),
+            Some("é".repeat(25).as_str())
+        );
+        assert!(entry.response_body_truncated);
+    }
+
+    #[test]
+    fn compaction_and_vacuum_shrink_existing_history() {
+        let (db, wid) = setup_test_db();
+        let body =
+            "{\"items\":[".to_string() + &"{\"id\":1,\"name\":\"row\"},".repeat(2000) + "{}]}";
+        for i in 0..5 {
+            save(
+                &db,
+                &wid,
+                &format!("https://a.test/{}", i),
+                &body,
+                &format!("2026-01-01T00:00:0{}+00:00", i),
+            );
+        }
+        db.set_history_retention(&HistoryRetention {
+            large_body_bytes: 1024,
+            ..HistoryRetention::default_for(&wid)
+        })
+        .unwrap();
+
+        let result = db.vacuum().unwrap();
+        assert_eq!(result.entries_pruned, 0);
+        assert!(result.bytes_reclaimed > 0);
+        assert_eq!(
+            result.bytes_before - result.bytes_after,
+            result.bytes_reclaimed
+        );
+
+        let entries = db
+            .list_history(&HistoryQuery {
+                workspace_id: wid.clone(),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(entries.len(), 5);
+        assert!(entries
+            .iter()
+            .all(|e| e.response_body.as_deref() == Some(body.as_str())));
+
+        // Search still resolves after rowids may have moved
+        let hits = db
+            .search(&crate::models::search::SearchQuery {
+                workspace_id: wid,
+                query: "a.test".to_string(),
+                kinds: None,
+                limit: None,
+            })
+            .unwrap();
+        assert_eq!(hits.len(), 5);
+    }
+}
@@ -0,0 +1,420 @@
+use crate::db::Database;
+use crate::models::search::{SearchHit, SearchHitKind, SearchQuery, SnippetSegment};
+use rusqlite::params;
+
+/// Snippet markers around matched terms. Control characters can't appear in
+/// user-typed text, so splitting on them is unambiguous.
+const MATCH_START: char = '\u{2}';
+const MATCH_END: char = '\u{3}';
+
+const REQUEST_HITS: &str = "
+    SELECT 'request', r.id, r.name, r.method || ' ' || r.url, r.collection_id,
+           snippet(requests_fts, -1, char(2), char(3), '…', 16),
+           bm25(requests_fts, 10.0, 5.0, 2.0, 1.0, 1.0)
+    FROM requests_fts
+    JOIN requests r ON r.rowid = requests_fts.rowid
+    JOIN collections c ON c.id = r.collection_id
+    WHERE requests_fts MATCH ?1 AND c.workspace_id = ?2";
+
+const COLLECTION_HITS: &str = "
+    SELECT 'collection', c.id, c.name, NULL, c.id,
+           snippet(collections_fts, -1, char(2), char(3), '…', 16),
+           bm25(collections_fts, 10.0, 1.0)
+    FROM collections_fts
+    JOIN collections c ON c.rowid = collections_fts.rowid
+    WHERE collections_fts MATCH ?1 AND c.workspace_id = ?2";
+
+const HISTORY_HITS: &str = "
+    SELECT 'history', h.id, h.method || ' ' || h.url, h.executed_at, NULL,
+           snippet(history_fts, -1, char(2), char(3), '…', 16),
+           bm25(history_fts, 5.0, 1.0)
+    FROM history_fts
+    JOIN history h ON h.rowid = history_fts.rowid
+    WHERE history_fts MATCH ?1 AND h.workspace_id = ?2";
+
+impl Database {
+    /// Ranked full-text search over the workspace's requests, collections and
+    /// history. Every term must match, and the last term also matches as a prefix
+    /// so results appear while typing. Only the start of compressed history bodies
+    /// is indexed, up to `SEARCHABLE_BODY_BYTES`.
+    pub fn search(&self, query: &SearchQuery) -> Result<Vec<SearchHit>, String> {
+        let Some(fts_query) = fts_query(&query.query) else {
+            return Ok(Vec::new());
+        };
+        let wants = |kind| {
+            query
+                .kinds
+                .as_ref()
+                .is_none_or(|kinds| kinds.contains(&kind))
+        };
+        let parts: Vec<&str> = [
+            (SearchHitKind::Request, REQUEST_HITS),
+            (SearchHitKind::Collection, COLLECTION_HITS),
+            (SearchHitKind::History, HISTORY_HITS),
+        ]
+        .into_iter()
+        .filter(|(kind, _)| wants(*kind))
+        .map(|(_, sql)| sql)
+        .collect();
+        if parts.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let sql = format!("{} ORDER BY 7 LIMIT ?3", parts.join(" UNION ALL "));
+        let mut stmt = self
+            .conn
+            .prepare(&sql)
+            .map_err(|e| format!("Failed to prepare search: {}", e))?;
+
+        let hits = stmt
+            .query_map(
+                params![fts_query, query.workspace_id, query.limit.unwrap_or(50)],
+                |row| {
+                    let kind = match row.get::<_, String>(0)?.as_str() {
+                        "request" => SearchHitKind::Request,
+                        "collection" => SearchHitKind::Collection,
+                        _ => SearchHitKind::History,
+                    };
+                    Ok(SearchHit {
+                        kind,
+                        id: row.get(1)?,
+                        title: row.get(2)?,
+                        subtitle: row.get(3)?,
+                        collection_id: row.get(4)?,
+                        snippet: split_snippet(
+                            &row.get::<_, Option<String>>(5)?.unwrap_or_default(),
+                        ),
+                        rank: row.get(6)?,
+                    })
+                },
+            )
+            .map_err(|e| format!("Failed to search: {}", e))?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("Failed to read search results: {}", e))?;
+
+        Ok(hits)
+    }
+}
+
+/// Turns free text into an FTS5 query. Each term is quoted so characters like
+/// `-`, `:` or `/` are treated as separators rather than query syntax.
+fn fts_query(input: &str) -> Option<String> {
+    let terms: Vec<String> = input
+        .split_whitespace()
+        .map(|term| format!("\"{}\"", term.replace('"', "\"\"")))
+        .collect();
+    let last = terms.last()?;
+    let mut query = terms[..terms.len() - 1].join(" ");
+    if !query.is_empty() {
+        query.push(' ');
+    }
+    query.push_str(last);
+    query.push('*');
+    Some(query)
+}
+
+fn split_snippet(snippet: &str) -> Vec<SnippetSegment> {
+    let mut segments = Vec::new();
+    let mut text = String::new();
+    let mut highlight = false;
+    for c in snippet.chars() {
+        if c == MATCH_START || c == MATCH_END {
+            if !text.is_empty() {
+                segments.push(SnippetSegment {
+                    text: std::mem::take(&mut text),
+                    highlight,
+                });
+            }
+            highlight = c == MATCH_START;
+        } else {
+            text.push(c);
+        }
+    }
+    if !text.is_empty() {
+        segments.push(SnippetSegment { text, highlight });
+    }
+    segments
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::repository::history_repo::SEARCHABLE_BODY_BYTES;
+    use crate::models::collection::CreateCollectionInput;
+    use crate::models::history::HistoryRetention;
+    use crate::models::request::{
+        BodyType, CreateRequestInput, HttpMethod, KeyValue, RequestKind, UpdateRequestInput,
+    };
+    use rusqlite::Connection;
+
+    fn setup_test_db() -> (Database, String) {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch("PRAGMA foreign_keys=ON;").unwrap();
+        let db = Database { conn };
+        db.run_migrations().unwrap();
+        let workspace = db.get_current_workspace().unwrap();
+        (db, workspace.id)
+    }
+
+    fn create_collection(db: &Database, workspace_id: &str, name: &str) -> String {
+        db.create_collection(CreateCollectionInput {
+            workspace_id: workspace_id.to_string(),
+            name: name.to_string(),
+            description: None,
+        })
+        .unwrap()
+        .id
+    }
+
+    fn request_input(collection_id: &str, name: &str, url: &str) -> CreateRequestInput {
+        CreateRequestInput {
+            name: name.to_string(),
+            method: HttpMethod::GET,
+            url: url.to_string(),
+            headers: vec![],
+            query_params: vec![],
+            body_type: BodyType::None,
+            body_content: None,
+            description: None,
+            path_params: vec![],
+            scripts: None,
+            kind: RequestKind::Http,
+            collection_id: Some(collection_id.to_string()),
+            folder_id: None,
+        }
+    }
+
+    fn search(db: &Database, wid: &str, text: &str) -> Vec<SearchHit> {
+        db.search(&SearchQuery {
+            workspace_id: wid.to_string(),
+            query: text.to_string(),
+            kinds: None,
+            limit: None,
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn finds_requests_collections_and_history() {
+        let (db, wid) = setup_test_db();
+        let collection_id = create_collection(&db, &wid, "Billing API");
+        let mut input = request_input(
+            &collection_id,
+            "List invoices",
+            "https://api.test/billing/invoices",
+        );
+        input.headers = vec![KeyValue {
+            key: "X-Tenant".to_string(),
+            value: "acme".to_string(),
+            enabled: true,
+        }];
+        let request = db.create_request(input).unwrap();
+        db.save_history_entry(
+            &wid,
+            None,
+            "GET",
+            "https://api.test/users",
+            "{}",
+            Some(200),
+            None,
+            Some("{\"tenant\":\"acme\"}"),
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+
+        let hits = search(&db, &wid, "billing");
+        assert_eq!(hits.len(), 2);
+        assert!(hits
+            .iter()
+            .any(|h| h.kind == SearchHitKind::Collection && h.id == collection_id));
+        let hit = hits
+            .iter()
+            .find(|h| h.kind == SearchHitKind::Request)
+            .unwrap();
+        assert_eq!(hit.id, request.id);
+        assert_eq!(hit.collection_id.as_deref(), Some(collection_id.as_str()));
+        assert_eq!(
+            hit.subtitle.as_deref(),
+            Some("GET https://api.test/billing/invoices")
+        );
+
+        let hits = search(&db, &wid, "/billing X-Tenant");
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].kind, SearchHitKind::Request);
+
+        let hits = search(&db, &wid, "acm");
+        assert_eq!(hits.len(), 2);
+        let history = hits
+            .iter()
+            .find(|h| h.kind == SearchHitKind::History)
+            .unwrap();
+        assert_eq!(history.title, "GET https://api.test/users");
+        assert!(history
+            .snippet
+            .iter()
+            .any(|s| s.highlight && s.text == "acme"));
+    }
+
+    #[test]
+    fn compressed_history_bodies_are_searched_by_their_start() {
+        let (db, wid) = setup_test_db();
+        db.set_history_retention(&HistoryRetention {
+            large_body_bytes: 1024,
+            ..HistoryRetention::default_for(&wid)
+        })
+        .unwrap();
+        let body = format!(
+            "needle {} haystack",
+            "filler ".repeat(SEARCHABLE_BODY_BYTES / 4)
+        );
+        let entry = db
+            .save_history_entry(
+                &wid,
+                None,
+                "GET",
+                "https://api.test/export",
+                "{}",
+                Some(200),
+                None,
+                Some(&body),
+                None,
+                None,
+                None,
+            )
+            .unwrap();
+        assert_eq!(entry.response_body.as_deref(), Some(body.as_str()));
+
+        let hits = search(&db, &wid, "needle");
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].id, entry.id);
+        // Terms past the indexed start aren't found
+        assert!(search(&db, &wid, "haystack").is_empty());
+        assert_eq!(
+            db.get_history_entry(&entry.id)
+                .unwrap()
+                .unwrap()
+                .response_body
+                .as_deref(),
+            Some(body.as_str())
+        );
+    }
+
+    #[test]
+    fn index_follows_updates_and_deletes() {
+        let (db, wid) = setup_test_db();
+        let collection_id = create_collection(&db, &wid, "API");
+        let request = db
+            .create_request(request_input(
+                &collection_id,
+                "Old name",
+                "https://api.test",
+            ))
+            .unwrap();
+
+        db.update_request(UpdateRequestInput {
+            id: request.id.clone(),
+            name: Some("Fresh name".to_string()),
+            method: None,
+            url: None,
+            headers: None,
+            query_params: None,
+            body_type: None,
+            body_content: None,
+            description: None,
+            path_params: None,
+            scripts: None,
+            kind: None,
+            collection_id: None,
+            folder_id: None,
+            sort_order: None,
+        })
+        .unwrap();
+        assert!(search(&db, &wid, "old").is_empty());
+        assert_eq!(search(&db, &wid, "fresh").len(), 1);
+
+        db.delete_request(&request.id).unwrap();
+        assert!(search(&db, &wid, "fresh").is_empty());
+    }
+
+    #[test]
+    fn scoped_to_workspace_and_kinds() {
+        let (db, wid) = setup_test_db();
+        db.conn
+            .execute(
+                "INSERT INTO workspaces VALUES ('other', 'Other', 't', 't')",
+                [],
+            )
+            .unwrap();
+        db.save_history_entry(
+            &wid,
+            None,
+            "GET",
+            "https://api.test/orders",
+            "{}",
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+        db.save_history_entry(
+            "other",
+            None,
+            "GET",
+            "https://api.test/orders",
+            "{}",
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(search(&db, &wid, "orders").len(), 1);
+        let hits = db
+            .search(&SearchQuery {
+                workspace_id: wid,
+                query: "orders".to_string(),
+                kinds: Some(vec![SearchHitKind::Request]),
+                limit: None,
+            })
+            .unwrap();
+        assert!(hits.is_empty());
+    }
+
+    #[test]
+    fn query_syntax_is_escaped() {
+        assert_eq!(fts_query("  "), None);
+        assert_eq!(fts_query("a \"b"), Some("\"a\" \"\"\"b\"*".to_string()));
+
+        let (db, wid) = setup_test_db();
+        assert!(search(&db, &wid, "NOT AND ( * \"").is_empty());
+    }
+
+    #[test]
+    fn splits_snippet_on_markers() {
+        assert_eq!(
+            split_snippet("…get \u{2}acme\u{3} users"),
+            vec![
+                SnippetSegment {
+                    text: "…get ".to_string(),
+                    highlight: false
+                },
+                SnippetSegment {
+                    text: "acme".to_string(),
+                    highlight: true
+                },
+                SnippetSegment {
+                    text: " users".to_string(),
+                    highlight: false
+                },
+            ]
+        );
+    }
+}
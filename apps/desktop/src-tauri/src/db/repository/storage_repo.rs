@@ -0,0 +1,385 @@
+use std::collections::HashSet;
+
+use rusqlite::{params, OptionalExtension};
+
+use crate::db::repository::request_repo::kind_to_column;
+use crate::db::Database;
+use crate::models::collection::{CollectionTree, CollectionTreeNode, Folder};
+use crate::models::request::ApiRequest;
+use crate::models::storage::CollectionStorage;
+
+impl Database {
+    pub fn link_collection_directory(
+        &self,
+        collection_id: &str,
+        directory: &str,
+    ) -> Result<CollectionStorage, String> {
+        let now = chrono::Utc::now().to_rfc3339();
+        self.conn
+            .execute(
+                "INSERT INTO collection_storage (collection_id, directory, synced_at) VALUES (?1, ?2, ?3)
+                 ON CONFLICT(collection_id) DO UPDATE SET directory = excluded.directory, synced_at = excluded.synced_at",
+                params![collection_id, directory, now],
+            )
+            .map_err(|e| format!("Link collection directory: {}", e))?;
+
+        Ok(CollectionStorage {
+            collection_id: collection_id.to_string(),
+            directory: directory.to_string(),
+            synced_at: now,
+        })
+    }
+
+    pub fn unlink_collection_directory(&self, collection_id: &str) -> Result<(), String> {
+        self.conn
+            .execute(
+                "DELETE FROM collection_storage WHERE collection_id = ?1",
+                params![collection_id],
+            )
+            .map_err(|e| format!("Unlink collection directory: {}", e))?;
+        Ok(())
+    }
+
+    pub fn get_collection_storage(
+        &self,
+        collection_id: &str,
+    ) -> Result<Option<CollectionStorage>, String> {
+        self.conn
+            .query_row(
+                "SELECT collection_id, directory, synced_at FROM collection_storage WHERE collection_id = ?1",
+                params![collection_id],
+                |row| {
+                    Ok(CollectionStorage {
+                        collection_id: row.get(0)?,
+                        directory: row.get(1)?,
+                        synced_at: row.get(2)?,
+                    })
+                },
+            )
+            .optional()
+            .map_err(|e| format!("Get collection storage: {}", e))
+    }
+
+    pub fn list_collection_storage(&self) -> Result<Vec<CollectionStorage>, String> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT collection_id, directory, synced_at FROM collection_storage")
+            .map_err(|e| format!("Prepare list_collection_storage: {}", e))?;
+
+        let rows = stmt
+            .query_map([], |row| {
+                Ok(CollectionStorage {
+                    collection_id: row.get(0)?,
+                    directory: row.get(1)?,
+                    synced_at: row.get(2)?,
+                })
+            })
+            .map_err(|e| format!("Query list_collection_storage: {}", e))?;
+
+        rows.collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("Read collection storage row: {}", e))
+    }
+
+    pub fn mark_collection_synced(&self, collection_id: &str) -> Result<(), String> {
+        let now = chrono::Utc::now().to_rfc3339();
+        self.conn
+            .execute(
+                "UPDATE collection_storage SET synced_at = ?1 WHERE collection_id = ?2",
+                params![now, collection_id],
+            )
+            .map_err(|e| format!("Mark collection synced: {}", e))?;
+        Ok(())
+    }
+
+    /// Ids of the folders and requests that don't belong to `collection_id`,
+    /// which a directory being read must not take over.
+    pub fn ids_outside_collection(&self, collection_id: &str) -> Result<HashSet<String>, String> {
+        let mut stmt = self
+            .conn
+            .prepare(
+                "SELECT id FROM folders WHERE collection_id IS NOT ?1
+                 UNION ALL SELECT id FROM requests WHERE collection_id IS NOT ?1",
+            )
+            .map_err(|e| format!("Prepare ids_outside_collection: {}", e))?;
+        let rows = stmt
+            .query_map(params![collection_id], |row| row.get(0))
+            .map_err(|e| format!("Query ids_outside_collection: {}", e))?;
+        rows.collect::<Result<HashSet<String>, _>>()
+            .map_err(|e| format!("Read id row: {}", e))
+    }
+
+    /// Makes the stored folders and requests of a collection match `tree`, keyed by id.
+    /// Rows that are unchanged keep their `updated_at`; rows missing from the tree are
+    /// deleted. Rows with the same id in another collection are never taken over.
+    /// Runs in a single transaction.
+    pub fn replace_collection_contents(&self, tree: &CollectionTree) -> Result<(), String> {
+        let tx = self
+            .conn
+            .unchecked_transaction()
+            .map_err(|e| format!("Begin transaction: {}", e))?;
+        let collection = &tree.collection;
+
+        tx.execute(
+            "UPDATE collections SET name = ?1, description = ?2, updated_at = ?3
+             WHERE id = ?4 AND (name IS NOT ?1 OR description IS NOT ?2)",
+            params![
+                collection.name,
+                collection.description,
+                collection.updated_at,
+                collection.id
+            ],
+        )
+        .map_err(|e| format!("Update collection: {}", e))?;
+
+        let mut folders = Vec::new();
+        let mut requests: Vec<&ApiRequest> = tree.root_requests.iter().collect();
+        collect_nodes(&tree.root_folders, &mut folders, &mut requests);
+
+        for folder in &folders {
+            tx.execute(
+                "INSERT INTO folders (id, collection_id, parent_folder_id, name, description, sort_order, created_at, updated_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+                 ON CONFLICT(id) DO UPDATE SET
+                    parent_folder_id = excluded.parent_folder_id,
+                    name = excluded.name,
+                    description = excluded.description,
+                    sort_order = excluded.sort_order,
+                    updated_at = excluded.updated_at
+                 WHERE folders.collection_id = excluded.collection_id
+                    AND (parent_folder_id IS NOT excluded.parent_folder_id
+                    OR name IS NOT excluded.name
+                    OR description IS NOT excluded.description
+                    OR sort_order IS NOT excluded.sort_order)",
+                params![
+                    folder.id,
+                    collection.id,
+                    folder.parent_folder_id,
+                    folder.name,
+                    folder.description,
+                    folder.sort_order,
+                    folder.created_at,
+                    folder.updated_at,
+                ],
+            )
+            .map_err(|e| format!("Upsert folder: {}", e))?;
+        }
+
+        for request in &requests {
+            let headers_json = serde_json::to_string(&request.headers)
+                .map_err(|e| format!("Serialize headers: {}", e))?;
+            let query_json = serde_json::to_string(&request.query_params)
+                .map_err(|e| format!("Serialize query_params: {}", e))?;
+            let path_json = serde_json::to_string(&request.path_params)
+                .map_err(|e| format!("Serialize path_params: {}", e))?;
+            let scripts_json = request
+                .scripts
+                .as_ref()
+                .map(serde_json::to_string)
+                .transpose()
+                .map_err(|e| format!("Serialize scripts: {}", e))?;
+            let kind_json = kind_to_column(&request.kind)?;
+
+            tx.execute(
+                "INSERT INTO requests (id, collection_id, folder_id, name, method, url, headers, query_params, body_type, body_content, sort_order, created_at, updated_at, description, path_params, scripts, kind)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17)
+                 ON CONFLICT(id) DO UPDATE SET
+                    folder_id = excluded.folder_id,
+                    name = excluded.name,
+                    method = excluded.method,
+                    url = excluded.url,
+                    headers = excluded.headers,
+                    query_params = excluded.query_params,
+                    body_type = excluded.body_type,
+                    body_content = excluded.body_content,
+                    sort_order = excluded.sort_order,
+                    updated_at = excluded.updated_at,
+                    description = excluded.description,
+                    path_params = excluded.path_params,
+                    scripts = excluded.scripts,
+                    kind = excluded.kind
+                 WHERE requests.collection_id = excluded.collection_id
+                    AND (folder_id IS NOT excluded.folder_id
+                    OR name IS NOT excluded.name
+                    OR method IS NOT excluded.method
+                    OR url IS NOT excluded.url
+                    OR headers IS NOT excluded.headers
+                    OR query_params IS NOT excluded.query_params
+                    OR body_type IS NOT excluded.body_type
+                    OR body_content IS NOT excluded.body_content
+                    OR sort_order IS NOT excluded.sort_order
+                    OR description IS NOT excluded.description
+                    OR path_params IS NOT excluded.path_params
+                    OR scripts IS NOT excluded.scripts
+                    OR kind IS NOT excluded.kind)",
+                params![
+                    request.id,
+                    collection.id,
+                    request.folder_id,
+                    request.name,
+                    request.method.as_str(),
+                    request.url,
+                    headers_json,
+                    query_json,
+                    request.body_type.as_str(),
+                    request.body_content,
+                    request.sort_order,
+                    request.created_at,
+                    request.updated_at,
+                    request.description,
+                    path_json,
+                    scripts_json,
+                    kind_json,
+                ],
+            )
+            .map_err(|e| format!("Upsert request: {}", e))?;
+        }
+
+        // Remove whatever the tree no longer contains
+        let request_ids: Vec<&str> = requests.iter().map(|r| r.id.as_str()).collect();
+        let folder_ids: Vec<&str> = folders.iter().map(|f| f.id.as_str()).collect();
+        let request_ids_json = serde_json::to_string(&request_ids)
+            .map_err(|e| format!("Serialize request ids: {}", e))?;
+        let folder_ids_json = serde_json::to_string(&folder_ids)
+            .map_err(|e| format!("Serialize folder ids: {}", e))?;
+        tx.execute(
+            "DELETE FROM requests WHERE collection_id = ?1
+             AND id NOT IN (SELECT value FROM json_each(?2))",
+            params![collection.id, request_ids_json],
+        )
+        .map_err(|e| format!("Delete removed requests: {}", e))?;
+        tx.execute(
+            "DELETE FROM folders WHERE collection_id = ?1
+             AND id NOT IN (SELECT value FROM json_each(?2))",
+            params![collection.id, folder_ids_json],
+        )
+        .map_err(|e| format!("Delete removed folders: {}", e))?;
+
+        tx.commit()
+            .map_err(|e| format!("Commit transaction: {}", e))
+    }
+}
+
+fn collect_nodes<'a>(
+    nodes: &'a [CollectionTreeNode],
+    folders: &mut Vec<&'a Folder>,
+    requests: &mut Vec<&'a ApiRequest>,
+) {
+    for node in nodes {
+        folders.push(&node.folder);
+        requests.extend(node.requests.iter());
+        collect_nodes(&node.children, folders, requests);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::collection::{CreateCollectionInput, CreateFolderInput};
+    use crate::models::request::{BodyType, CreateRequestInput, HttpMethod, RequestKind};
+    use rusqlite::Connection;
+
+    fn setup_test_db() -> (Database, String) {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch("PRAGMA foreign_keys=ON;").unwrap();
+        let db = Database { conn };
+        db.run_migrations().unwrap();
+        let workspace = db.get_current_workspace().unwrap();
+        (db, workspace.id)
+    }
+
+    fn sample_collection(db: &Database, workspace_id: &str) -> String {
+        let coll = db
+            .create_collection(CreateCollectionInput {
+                workspace_id: workspace_id.to_string(),
+                name: "API".to_string(),
+                description: None,
+            })
+            .unwrap();
+        let folder = db
+            .create_folder(CreateFolderInput {
+                collection_id: coll.id.clone(),
+                parent_folder_id: None,
+                name: "Users".to_string(),
+                description: None,
+            })
+            .unwrap();
+        for (name, folder_id) in [("List", Some(folder.id.clone())), ("Health", None)] {
+            db.create_request(CreateRequestInput {
+                name: name.to_string(),
+                method: HttpMethod::GET,
+                url: "https://example.com".to_string(),
+                headers: vec![],
+                query_params: vec![],
+                body_type: BodyType::None,
+                body_content: None,
+                description: None,
+                path_params: vec![],
+                scripts: None,
+                kind: RequestKind::Http,
+                collection_id: Some(coll.id.clone()),
+                folder_id,
+            })
+            .unwrap();
+        }
+        coll.id
+    }
+
+    #[test]
+    fn link_and_unlink_directory() {
+        let (db, wid) = setup_test_db();
+        let coll_id = sample_collection(&db, &wid);
+
+        db.link_collection_directory(&coll_id, "/tmp/a").unwrap();
+        db.link_collection_directory(&coll_id, "/tmp/b").unwrap();
+        let storage = db.get_collection_storage(&coll_id).unwrap().unwrap();
+        assert_eq!(storage.directory, "/tmp/b");
+        assert_eq!(db.list_collection_storage().unwrap().len(), 1);
+
+        db.unlink_collection_directory(&coll_id).unwrap();
+        assert!(db.get_collection_storage(&coll_id).unwrap().is_none());
+
+        db.link_collection_directory(&coll_id, "/tmp/c").unwrap();
+        db.delete_collection(&coll_id).unwrap();
+        assert!(db.list_collection_storage().unwrap().is_empty());
+    }
+
+    #[test]
+    fn replace_contents_upserts_and_deletes() {
+        let (db, wid) = setup_test_db();
+        let coll_id = sample_collection(&db, &wid);
+        let mut tree = db.get_collection_tree(&coll_id).unwrap();
+        let untouched_at = tree.root_folders[0].requests[0].updated_at.clone();
+
+        tree.collection.description = Some("Docs".to_string());
+        tree.root_requests[0].url = "https://example.com/health".to_string();
+        tree.root_requests[0].updated_at = "2030-01-01T00:00:00Z".to_string();
+        tree.root_folders[0].requests[0].updated_at = "2030-01-01T00:00:00Z".to_string();
+        let mut added = tree.root_requests[0].clone();
+        added.id = "new-request".to_string();
+        added.name = "Added".to_string();
+        tree.root_requests.push(added);
+        db.replace_collection_contents(&tree).unwrap();
+
+        let after = db.get_collection_tree(&coll_id).unwrap();
+        assert_eq!(after.collection.description.as_deref(), Some("Docs"));
+        assert_eq!(after.root_requests.len(), 2);
+        let health = after
+            .root_requests
+            .iter()
+            .find(|r| r.name == "Health")
+            .unwrap();
+        assert_eq!(health.url, "https://example.com/health");
+        assert_eq!(health.updated_at, "2030-01-01T00:00:00Z");
+        // Unchanged rows keep their timestamp
+        assert_eq!(after.root_folders[0].requests[0].updated_at, untouched_at);
+
+        let mut tree = after;
+        tree.root_folders.clear();
+        tree.root_requests.retain(|r| r.name == "Added");
+        db.replace_collection_contents(&tree).unwrap();
+        let after = db.get_collection_tree(&coll_id).unwrap();
+        assert!(after.root_folders.is_empty());
+        assert_eq!(after.root_requests.len(), 1);
+        assert_eq!(db.list_requests_by_collection(&coll_id).unwrap().len(), 1);
+    }
+}
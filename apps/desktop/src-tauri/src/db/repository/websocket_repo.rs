@@ -0,0 +1,303 @@
+use rusqlite::{params, OptionalExtension};
+
+use crate::db::Database;
+use crate::models::websocket::{MessageDirection, MessageKind, WebSocketMessage, WebSocketSession};
+
+const SESSION_COLUMNS: &str = "s.id, s.workspace_id, s.request_id, s.url, s.subprotocol,
+    s.opened_at, s.closed_at, s.close_code, s.close_reason, s.error,
+    (SELECT COUNT(*) FROM websocket_messages m WHERE m.session_id = s.id)";
+
+impl Database {
+    pub fn create_websocket_session(
+        &self,
+        workspace_id: &str,
+        request_id: Option<&str>,
+        url: &str,
+        subprotocol: Option<&str>,
+    ) -> Result<WebSocketSession, String> {
+        let id = uuid::Uuid::now_v7().to_string();
+        let now = chrono::Utc::now().to_rfc3339();
+        self.conn
+            .execute(
+                "INSERT INTO websocket_sessions (id, workspace_id, request_id, url, subprotocol, opened_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![id, workspace_id, request_id, url, subprotocol, now],
+            )
+            .map_err(|e| format!("Insert websocket session: {}", e))?;
+
+        Ok(WebSocketSession {
+            id,
+            workspace_id: workspace_id.to_string(),
+            request_id: request_id.map(String::from),
+            url: url.to_string(),
+            subprotocol: subprotocol.map(String::from),
+            opened_at: now,
+            closed_at: None,
+            close_code: None,
+            close_reason: None,
+            error: None,
+            message_count: 0,
+        })
+    }
+
+    /// Records how the connection ended. Sessions already closed are unchanged.
+    pub fn close_websocket_session(
+        &self,
+        id: &str,
+        close_code: Option<u16>,
+        close_reason: Option<&str>,
+        error: Option<&str>,
+    ) -> Result<(), String> {
+        self.conn
+            .execute(
+                "UPDATE websocket_sessions
+                 SET closed_at = ?1, close_code = ?2, close_reason = ?3, error = ?4
+                 WHERE id = ?5 AND closed_at IS NULL",
+                params![
+                    chrono::Utc::now().to_rfc3339(),
+                    close_code,
+                    close_reason,
+                    error,
+                    id
+                ],
+            )
+            .map_err(|e| format!("Close websocket session: {}", e))?;
+        Ok(())
+    }
+
+    /// Sessions left open by a crash or shutdown are closed at their last message.
+    pub fn close_open_websocket_sessions(&self) -> Result<(), String> {
+        self.conn
+            .execute(
+                "UPDATE websocket_sessions SET closed_at = COALESCE(
+                     (SELECT MAX(m.created_at) FROM websocket_messages m
+                      WHERE m.session_id = websocket_sessions.id),
+                     opened_at)
+                 WHERE closed_at IS NULL",
+                [],
+            )
+            .map_err(|e| format!("Close websocket sessions: {}", e))?;
+        Ok(())
+    }
+
+    pub fn get_websocket_session(&self, id: &str) -> Result<Option<WebSocketSession>, String> {
+        self.conn
+            .query_row(
+                &format!(
+                    "SELECT {} FROM websocket_sessions s WHERE s.id = ?1",
+                    SESSION_COLUMNS
+                ),
+                params![id],
+                session_from_row,
+            )
+            .optional()
+            .map_err(|e| format!("Get websocket session: {}", e))
+    }
+
+    /// Newest first, optionally only those opened from one saved request.
+    pub fn list_websocket_sessions(
+        &self,
+        workspace_id: &str,
+        request_id: Option<&str>,
+    ) -> Result<Vec<WebSocketSession>, String> {
+        let mut stmt = self
+            .conn
+            .prepare(&format!(
+                "SELECT {} FROM websocket_sessions s
+                 WHERE s.workspace_id = ?1 AND (?2 IS NULL OR s.request_id = ?2)
+                 ORDER BY s.opened_at DESC",
+                SESSION_COLUMNS
+            ))
+            .map_err(|e| format!("Prepare list_websocket_sessions: {}", e))?;
+        let sessions = stmt
+            .query_map(params![workspace_id, request_id], session_from_row)
+            .map_err(|e| format!("Query websocket sessions: {}", e))?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("Read websocket session: {}", e))?;
+        Ok(sessions)
+    }
+
+    pub fn delete_websocket_session(&self, id: &str) -> Result<(), String> {
+        self.conn
+            .execute("DELETE FROM websocket_sessions WHERE id = ?1", params![id])
+            .map_err(|e| format!("Delete websocket session: {}", e))?;
+        Ok(())
+    }
+
+    pub fn add_websocket_message(
+        &self,
+        session_id: &str,
+        direction: MessageDirection,
+        kind: MessageKind,
+        data: &str,
+        size: usize,
+    ) -> Result<WebSocketMessage, String> {
+        let id = uuid::Uuid::now_v7().to_string();
+        let now = chrono::Utc::now().to_rfc3339();
+        self.conn
+            .execute(
+                "INSERT INTO websocket_messages (id, session_id, direction, kind, data, size, created_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                params![
+                    id,
+                    session_id,
+                    direction_to_str(direction),
+                    kind.as_str(),
+                    data,
+                    size as i64,
+                    now
+                ],
+            )
+            .map_err(|e| format!("Insert websocket message: {}", e))?;
+
+        Ok(WebSocketMessage {
+            id,
+            session_id: session_id.to_string(),
+            direction,
+            kind,
+            data: data.to_string(),
+            size: size as i64,
+            created_at: now,
+        })
+    }
+
+    /// The session's transcript, oldest first.
+    pub fn list_websocket_messages(
+        &self,
+        session_id: &str,
+    ) -> Result<Vec<WebSocketMessage>, String> {
+        let mut stmt = self
+            .conn
+            .prepare(
+                "SELECT id, session_id, direction, kind, data, size, created_at
+                 FROM websocket_messages WHERE session_id = ?1
+                 ORDER BY created_at ASC, id ASC",
+            )
+            .map_err(|e| format!("Prepare list_websocket_messages: {}", e))?;
+        let messages = stmt
+            .query_map(params![session_id], |row| {
+                let direction: String = row.get(2)?;
+                let kind: String = row.get(3)?;
+                Ok(WebSocketMessage {
+                    id: row.get(0)?,
+                    session_id: row.get(1)?,
+                    direction: match direction.as_str() {
+                        "sent" => MessageDirection::Sent,
+                        _ => MessageDirection::Received,
+                    },
+                    kind: MessageKind::from_str(&kind),
+                    data: row.get(4)?,
+                    size: row.get(5)?,
+                    created_at: row.get(6)?,
+                })
+            })
+            .map_err(|e| format!("Query websocket messages: {}", e))?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("Read websocket message: {}", e))?;
+        Ok(messages)
+    }
+}
+
+fn direction_to_str(direction: MessageDirection) -> &'static str {
+    match direction {
+        MessageDirection::Sent => "sent",
+        MessageDirection::Received => "received",
+    }
+}
+
+fn session_from_row(row: &rusqlite::Row) -> rusqlite::Result<WebSocketSession> {
+    Ok(WebSocketSession {
+        id: row.get(0)?,
+        workspace_id: row.get(1)?,
+        request_id: row.get(2)?,
+        url: row.get(3)?,
+        subprotocol: row.get(4)?,
+        opened_at: row.get(5)?,
+        closed_at: row.get(6)?,
+        close_code: row.get(7)?,
+        close_reason: row.get(8)?,
+        error: row.get(9)?,
+        message_count: row.get(10)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rusqlite::Connection;
+
+    fn setup_test_db() -> (Database, String) {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch("PRAGMA foreign_keys=ON;").unwrap();
+        let db = Database { conn };
+        db.run_migrations().unwrap();
+        let workspace = db.get_current_workspace().unwrap();
+        (db, workspace.id)
+    }
+
+    #[test]
+    fn test_session_transcript() {
+        let (db, wid) = setup_test_db();
+        let session = db
+            .create_websocket_session(&wid, None, "wss://echo.example.com", Some("chat"))
+            .unwrap();
+        db.add_websocket_message(
+            &session.id,
+            MessageDirection::Sent,
+            MessageKind::Text,
+            "hi",
+            2,
+        )
+        .unwrap();
+        db.add_websocket_message(
+            &session.id,
+            MessageDirection::Received,
+            MessageKind::Binary,
+            "AQI=",
+            2,
+        )
+        .unwrap();
+        db.close_websocket_session(&session.id, Some(1000), Some("bye"), None)
+            .unwrap();
+        // A later close doesn't overwrite the first
+        db.close_websocket_session(&session.id, None, None, Some("reset"))
+            .unwrap();
+
+        let saved = db.get_websocket_session(&session.id).unwrap().unwrap();
+        assert_eq!(saved.subprotocol.as_deref(), Some("chat"));
+        assert_eq!(saved.close_code, Some(1000));
+        assert_eq!(saved.close_reason.as_deref(), Some("bye"));
+        assert!(saved.error.is_none());
+        assert_eq!(saved.message_count, 2);
+
+        let messages = db.list_websocket_messages(&session.id).unwrap();
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[0].direction, MessageDirection::Sent);
+        assert_eq!(messages[0].data, "hi");
+        assert_eq!(messages[1].kind, MessageKind::Binary);
+
+        db.delete_websocket_session(&session.id).unwrap();
+        assert!(db.list_websocket_messages(&session.id).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_close_open_sessions() {
+        let (db, wid) = setup_test_db();
+        let open = db
+            .create_websocket_session(&wid, Some("req-1"), "ws://localhost:1", None)
+            .unwrap();
+        db.create_websocket_session(&wid, None, "ws://localhost:2", None)
+            .unwrap();
+        db.close_open_websocket_sessions().unwrap();
+
+        let saved = db.get_websocket_session(&open.id).unwrap().unwrap();
+        assert_eq!(saved.closed_at.as_deref(), Some(saved.opened_at.as_str()));
+        assert_eq!(db.list_websocket_sessions(&wid, None).unwrap().len(), 2);
+        assert_eq!(
+            db.list_websocket_sessions(&wid, Some("req-1"))
+                .unwrap()
+                .len(),
+            1
+        );
+    }
+}
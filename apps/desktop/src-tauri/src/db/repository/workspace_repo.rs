@@ -18,6 +18,19 @@ impl Database {
         })
         .map_err(|e| format!("Query get_workspace: {}", e))
     }
+
+    pub fn list_workspace_ids(&self) -> Result<Vec<String>, String> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT id FROM workspaces")
+            .map_err(|e| format!("Failed to prepare workspace query: {}", e))?;
+        let ids = stmt
+            .query_map([], |row| row.get::<_, String>(0))
+            .map_err(|e| format!("Failed to query workspaces: {}", e))?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("Failed to read workspaces: {}", e));
+        ids
+    }
 }
 
 #[cfg(test)]
@@ -1,4 +1,226 @@
-pub const CREATE_TABLES: &str = r#"
+/// A schema change, applied once and recorded in `schema_migrations`. Versions
+/// must be consecutive and existing migrations must never be edited once released.
+pub struct Migration {
+    pub version: i64,
+    pub name: &'static str,
+    pub sql: &'static str,
+}
+
+pub const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        name: "initial schema",
+        sql: INITIAL_SCHEMA,
+    },
+    Migration {
+        version: 2,
+        name: "descriptions, path params and scripts",
+        sql: "ALTER TABLE folders ADD COLUMN description TEXT;
+              ALTER TABLE requests ADD COLUMN description TEXT;
+              ALTER TABLE requests ADD COLUMN path_params TEXT NOT NULL DEFAULT '[]';
+              ALTER TABLE requests ADD COLUMN scripts TEXT;",
+    },
+    Migration {
+        version: 3,
+        name: "collection directory storage",
+        sql: "CREATE TABLE collection_storage (
+                  collection_id TEXT PRIMARY KEY REFERENCES collections(id) ON DELETE CASCADE,
+                  directory TEXT NOT NULL,
+                  synced_at TEXT NOT NULL
+              );",
+    },
+    Migration {
+        version: 4,
+        name: "full-text search",
+        sql: SEARCH_INDEXES,
+    },
+    Migration {
+        version: 5,
+        name: "history indexes",
+        sql: "CREATE INDEX idx_history_workspace_executed_at ON history (workspace_id, executed_at);
+              CREATE INDEX idx_history_request_executed_at ON history (request_id, executed_at);",
+    },
+    Migration {
+        version: 6,
+        name: "history retention",
+        sql: "CREATE TABLE history_retention (
+                  workspace_id TEXT PRIMARY KEY REFERENCES workspaces(id) ON DELETE CASCADE,
+                  max_entries INTEGER,
+                  max_age_days INTEGER,
+                  max_body_bytes INTEGER,
+                  large_body_bytes INTEGER NOT NULL,
+                  large_body_mode TEXT NOT NULL,
+                  updated_at TEXT NOT NULL
+              );
+              ALTER TABLE history ADD COLUMN response_body_zstd BLOB;
+              ALTER TABLE history ADD COLUMN response_body_truncated INTEGER NOT NULL DEFAULT 0;
+              ALTER TABLE history ADD COLUMN body_bytes INTEGER NOT NULL DEFAULT 0;
+              UPDATE history SET body_bytes = COALESCE(length(CAST(response_body AS BLOB)), 0);",
+    },
+    Migration {
+        version: 7,
+        name: "history redaction",
+        sql: "CREATE TABLE history_redaction (
+                  workspace_id TEXT PRIMARY KEY REFERENCES workspaces(id) ON DELETE CASCADE,
+                  header_patterns TEXT NOT NULL,
+                  field_patterns TEXT NOT NULL,
+                  mode TEXT NOT NULL,
+                  updated_at TEXT NOT NULL
+              );",
+    },
+    Migration {
+        version: 8,
+        name: "request examples",
+        sql: "CREATE TABLE request_examples (
+                  id TEXT PRIMARY KEY,
+                  request_id TEXT NOT NULL REFERENCES requests(id) ON DELETE CASCADE,
+                  name TEXT NOT NULL,
+                  status INTEGER,
+                  headers TEXT NOT NULL DEFAULT '[]',
+                  body TEXT,
+                  original_request TEXT,
+                  sort_order INTEGER NOT NULL DEFAULT 0,
+                  created_at TEXT NOT NULL,
+                  updated_at TEXT NOT NULL
+              );
+              CREATE INDEX idx_request_examples_request ON request_examples (request_id, sort_order);",
+    },
+    Migration {
+        version: 9,
+        name: "capture sessions",
+        sql: "CREATE TABLE capture_sessions (
+                  id TEXT PRIMARY KEY,
+                  workspace_id TEXT NOT NULL REFERENCES workspaces(id) ON DELETE CASCADE,
+                  name TEXT NOT NULL,
+                  started_at TEXT NOT NULL,
+                  stopped_at TEXT
+              );
+              CREATE TABLE capture_entries (
+                  session_id TEXT NOT NULL REFERENCES capture_sessions(id) ON DELETE CASCADE,
+                  history_id TEXT NOT NULL REFERENCES history(id) ON DELETE CASCADE,
+                  PRIMARY KEY (session_id, history_id)
+              );
+              CREATE INDEX idx_capture_entries_history ON capture_entries (history_id);
+              CREATE TABLE proxy_ca (
+                  id INTEGER PRIMARY KEY CHECK (id = 1),
+                  certificate TEXT NOT NULL,
+                  private_key TEXT NOT NULL,
+                  created_at TEXT NOT NULL
+              );",
+    },
+    Migration {
+        version: 10,
+        name: "websocket sessions",
+        sql: "ALTER TABLE requests ADD COLUMN kind TEXT;
+              CREATE TABLE websocket_sessions (
+                  id TEXT PRIMARY KEY,
+                  workspace_id TEXT NOT NULL REFERENCES workspaces(id) ON DELETE CASCADE,
+                  request_id TEXT,
+                  url TEXT NOT NULL,
+                  subprotocol TEXT,
+                  opened_at TEXT NOT NULL,
+                  closed_at TEXT,
+                  close_code INTEGER,
+                  close_reason TEXT,
+                  error TEXT
+              );
+              CREATE INDEX idx_websocket_sessions_workspace ON websocket_sessions (workspace_id, opened_at);
+              CREATE TABLE websocket_messages (
+                  id TEXT PRIMARY KEY,
+                  session_id TEXT NOT NULL REFERENCES websocket_sessions(id) ON DELETE CASCADE,
+                  direction TEXT NOT NULL,
+                  kind TEXT NOT NULL,
+                  data TEXT NOT NULL,
+                  size INTEGER NOT NULL,
+                  created_at TEXT NOT NULL
+              );
+              CREATE INDEX idx_websocket_messages_session ON websocket_messages (session_id, created_at);",
+    },
+    Migration {
+        version: 11,
+        name: "graphql schemas",
+        sql: "CREATE TABLE graphql_schemas (
+                  workspace_id TEXT NOT NULL REFERENCES workspaces(id) ON DELETE CASCADE,
+                  url TEXT NOT NULL,
+                  introspection TEXT NOT NULL,
+                  fetched_at TEXT NOT NULL,
+                  PRIMARY KEY (workspace_id, url)
+              );",
+    },
+];
+
+pub const CREATE_MIGRATIONS_TABLE: &str = r#"
+CREATE TABLE IF NOT EXISTS schema_migrations (
+    version INTEGER PRIMARY KEY,
+    name TEXT NOT NULL,
+    applied_at TEXT NOT NULL
+);
+"#;
+
+/// External-content FTS5 indexes over requests, collections and history, kept
+/// current by triggers. They reference source rows by rowid, which `VACUUM` may
+/// renumber, so the indexes must be rebuilt after vacuuming.
+const SEARCH_INDEXES: &str = r#"
+CREATE VIRTUAL TABLE requests_fts USING fts5(
+    name, url, headers, body_content, description,
+    content = 'requests', content_rowid = 'rowid'
+);
+CREATE TRIGGER requests_fts_insert AFTER INSERT ON requests BEGIN
+    INSERT INTO requests_fts (rowid, name, url, headers, body_content, description)
+    VALUES (new.rowid, new.name, new.url, new.headers, new.body_content, new.description);
+END;
+CREATE TRIGGER requests_fts_delete AFTER DELETE ON requests BEGIN
+    INSERT INTO requests_fts (requests_fts, rowid, name, url, headers, body_content, description)
+    VALUES ('delete', old.rowid, old.name, old.url, old.headers, old.body_content, old.description);
+END;
+CREATE TRIGGER requests_fts_update AFTER UPDATE OF name, url, headers, body_content, description ON requests BEGIN
+    INSERT INTO requests_fts (requests_fts, rowid, name, url, headers, body_content, description)
+    VALUES ('delete', old.rowid, old.name, old.url, old.headers, old.body_content, old.description);
+    INSERT INTO requests_fts (rowid, name, url, headers, body_content, description)
+    VALUES (new.rowid, new.name, new.url, new.headers, new.body_content, new.description);
+END;
+INSERT INTO requests_fts (requests_fts) VALUES ('rebuild');
+
+CREATE VIRTUAL TABLE collections_fts USING fts5(
+    name, description,
+    content = 'collections', content_rowid = 'rowid'
+);
+CREATE TRIGGER collections_fts_insert AFTER INSERT ON collections BEGIN
+    INSERT INTO collections_fts (rowid, name, description) VALUES (new.rowid, new.name, new.description);
+END;
+CREATE TRIGGER collections_fts_delete AFTER DELETE ON collections BEGIN
+    INSERT INTO collections_fts (collections_fts, rowid, name, description)
+    VALUES ('delete', old.rowid, old.name, old.description);
+END;
+CREATE TRIGGER collections_fts_update AFTER UPDATE OF name, description ON collections BEGIN
+    INSERT INTO collections_fts (collections_fts, rowid, name, description)
+    VALUES ('delete', old.rowid, old.name, old.description);
+    INSERT INTO collections_fts (rowid, name, description) VALUES (new.rowid, new.name, new.description);
+END;
+INSERT INTO collections_fts (collections_fts) VALUES ('rebuild');
+
+CREATE VIRTUAL TABLE history_fts USING fts5(
+    url, response_body,
+    content = 'history', content_rowid = 'rowid'
+);
+CREATE TRIGGER history_fts_insert AFTER INSERT ON history BEGIN
+    INSERT INTO history_fts (rowid, url, response_body) VALUES (new.rowid, new.url, new.response_body);
+END;
+CREATE TRIGGER history_fts_delete AFTER DELETE ON history BEGIN
+    INSERT INTO history_fts (history_fts, rowid, url, response_body)
+    VALUES ('delete', old.rowid, old.url, old.response_body);
+END;
+CREATE TRIGGER history_fts_update AFTER UPDATE OF url, response_body ON history BEGIN
+    INSERT INTO history_fts (history_fts, rowid, url, response_body)
+    VALUES ('delete', old.rowid, old.url, old.response_body);
+    INSERT INTO history_fts (rowid, url, response_body) VALUES (new.rowid, new.url, new.response_body);
+END;
+INSERT INTO history_fts (history_fts) VALUES ('rebuild');
+"#;
+
+/// The schema shipped with 0.5.0. Tables are created with `IF NOT EXISTS` so
+/// databases from before migrations were tracked can adopt this as version 1.
+const INITIAL_SCHEMA: &str = r#"
 CREATE TABLE IF NOT EXISTS workspaces (
     id TEXT PRIMARY KEY,
     name TEXT NOT NULL,
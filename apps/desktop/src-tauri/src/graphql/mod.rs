@@ -0,0 +1,281 @@
+pub mod schema;
+pub mod validate;
+
+use crate::http::executor;
+use crate::models::request::{BodyType, ExecuteRequestInput, HttpMethod};
+
+/// The standard introspection query: everything an editor needs to build a
+/// client schema for autocompletion.
+pub const INTROSPECTION_QUERY: &str = r#"query IntrospectionQuery {
+  __schema {
+    queryType { name }
+    mutationType { name }
+    subscriptionType { name }
+    types { ...FullType }
+    directives {
+      name
+      description
+      locations
+      args { ...InputValue }
+    }
+  }
+}
+
+fragment FullType on __Type {
+  kind
+  name
+  description
+  fields(includeDeprecated: true) {
+    name
+    description
+    args { ...InputValue }
+    type { ...TypeRef }
+    isDeprecated
+    deprecationReason
+  }
+  inputFields { ...InputValue }
+  interfaces { ...TypeRef }
+  enumValues(includeDeprecated: true) {
+    name
+    description
+    isDeprecated
+    deprecationReason
+  }
+  possibleTypes { ...TypeRef }
+}
+
+fragment InputValue on __InputValue {
+  name
+  description
+  type { ...TypeRef }
+  defaultValue
+}
+
+fragment TypeRef on __Type {
+  kind
+  name
+  ofType {
+    kind
+    name
+    ofType {
+      kind
+      name
+      ofType {
+        kind
+        name
+        ofType {
+          kind
+          name
+          ofType {
+            kind
+            name
+            ofType {
+              kind
+              name
+              ofType {
+                kind
+                name
+              }
+            }
+          }
+        }
+      }
+    }
+  }
+}"#;
+
+/// Posts the introspection query to the request's URL with its headers and
+/// query parameters, returning the response's `data`. The request's own method
+/// and body aren't used.
+pub async fn introspect(input: &ExecuteRequestInput) -> Result<serde_json::Value, String> {
+    let request = ExecuteRequestInput {
+        method: HttpMethod::POST,
+        body_type: BodyType::GraphQL,
+        body_content: Some(serde_json::json!({ "query": INTROSPECTION_QUERY }).to_string()),
+        ..input.clone()
+    };
+    let result = executor::execute(&request).await;
+    if let Some(error) = result.error {
+        return Err(error);
+    }
+
+    let response: serde_json::Value = serde_json::from_str(&result.body).map_err(|_| {
+        format!(
+            "Introspection failed: the {} response isn't JSON",
+            result.status
+        )
+    })?;
+    match response
+        .get("data")
+        .filter(|data| data["__schema"].is_object())
+    {
+        Some(data) => {
+            // Only cache what validation can read
+            schema::Schema::from_introspection(data)?;
+            Ok(data.clone())
+        }
+        None => {
+            let reason = response
+                .pointer("/errors/0/message")
+                .and_then(|message| message.as_str())
+                .map(String::from)
+                .unwrap_or_else(|| format!("HTTP {}", result.status));
+            Err(format!("Introspection failed: {}", reason))
+        }
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use serde_json::{json, Value};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    fn named(kind: &str, name: &str) -> Value {
+        json!({ "kind": kind, "name": name, "ofType": null })
+    }
+
+    fn non_null(of: Value) -> Value {
+        json!({ "kind": "NON_NULL", "name": null, "ofType": of })
+    }
+
+    fn list(of: Value) -> Value {
+        json!({ "kind": "LIST", "name": null, "ofType": of })
+    }
+
+    fn input_value(name: &str, ty: Value, default: Option<&str>) -> Value {
+        json!({ "name": name, "type": ty, "defaultValue": default })
+    }
+
+    fn field(name: &str, args: Vec<Value>, ty: Value) -> Value {
+        json!({ "name": name, "args": args, "type": ty })
+    }
+
+    /// A small schema of users, as an introspection response's `data`.
+    pub(crate) fn introspection() -> Value {
+        let scalar = |name: &str| json!({ "kind": "SCALAR", "name": name });
+        let user = || named("OBJECT", "User");
+        json!({
+            "__schema": {
+                "queryType": { "name": "Query" },
+                "mutationType": { "name": "Mutation" },
+                "subscriptionType": null,
+                "types": [
+                    scalar("ID"), scalar("Int"), scalar("Float"), scalar("String"),
+                    scalar("Boolean"), scalar("DateTime"),
+                    {
+                        "kind": "OBJECT", "name": "Query",
+                        "fields": [
+                            field("user", vec![input_value("id", non_null(named("SCALAR", "ID")), None)], user()),
+                            field(
+                                "users",
+                                vec![
+                                    input_value("first", named("SCALAR", "Int"), Some("10")),
+                                    input_value("role", named("ENUM", "Role"), None),
+                                ],
+                                non_null(list(non_null(user()))),
+                            ),
+                        ]
+                    },
+                    {
+                        "kind": "OBJECT", "name": "Mutation",
+                        "fields": [field(
+                            "createUser",
+                            vec![input_value("input", non_null(named("INPUT_OBJECT", "UserInput")), None)],
+                            user(),
+                        )]
+                    },
+                    {
+                        "kind": "OBJECT", "name": "User",
+                        "fields": [
+                            field("id", vec![], non_null(named("SCALAR", "ID"))),
+                            field("name", vec![], named("SCALAR", "String")),
+                            field("role", vec![], named("ENUM", "Role")),
+                            field("createdAt", vec![], named("SCALAR", "DateTime")),
+                            field("friends", vec![], non_null(list(non_null(user())))),
+                        ]
+                    },
+                    {
+                        "kind": "ENUM", "name": "Role",
+                        "enumValues": [{ "name": "ADMIN" }, { "name": "MEMBER" }]
+                    },
+                    {
+                        "kind": "INPUT_OBJECT", "name": "UserInput",
+                        "inputFields": [
+                            input_value("name", non_null(named("SCALAR", "String")), None),
+                            input_value("role", named("ENUM", "Role"), Some("MEMBER")),
+                        ]
+                    }
+                ],
+                "directives": [
+                    { "name": "include", "args": [input_value("if", non_null(named("SCALAR", "Boolean")), None)] },
+                    { "name": "skip", "args": [input_value("if", non_null(named("SCALAR", "Boolean")), None)] }
+                ]
+            }
+        })
+    }
+
+    /// Answers one request with `body` as JSON, returning the URL and the
+    /// request it received.
+    async fn graphql_server(body: Value) -> (String, tokio::sync::oneshot::Receiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/graphql", listener.local_addr().unwrap());
+        let (received, request) = tokio::sync::oneshot::channel();
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut buffer = vec![0u8; 64 * 1024];
+            let mut read = 0;
+            // The query is small enough to arrive before the server answers
+            while !String::from_utf8_lossy(&buffer[..read]).contains("IntrospectionQuery") {
+                read += socket.read(&mut buffer[read..]).await.unwrap();
+            }
+            let _ = received.send(String::from_utf8_lossy(&buffer[..read]).to_string());
+            let body = body.to_string();
+            let response = format!(
+                "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            );
+            socket.write_all(response.as_bytes()).await.unwrap();
+        });
+        (url, request)
+    }
+
+    fn input(url: &str) -> ExecuteRequestInput {
+        ExecuteRequestInput {
+            method: HttpMethod::GET,
+            url: url.to_string(),
+            headers: vec![crate::models::request::KeyValue {
+                key: "Authorization".to_string(),
+                value: "Bearer t".to_string(),
+                enabled: true,
+            }],
+            query_params: vec![],
+            path_params: vec![],
+            body_type: BodyType::None,
+            body_content: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_introspect() {
+        let (url, request) = graphql_server(json!({ "data": introspection() })).await;
+        let data = introspect(&input(&url)).await.unwrap();
+        assert_eq!(data["__schema"]["queryType"]["name"], "Query");
+
+        let request = request.await.unwrap();
+        assert!(request.starts_with("POST /graphql"));
+        assert!(request.to_lowercase().contains("authorization: bearer t"));
+
+        let (url, _) = graphql_server(json!({
+            "data": null,
+            "errors": [{ "message": "Introspection is disabled" }]
+        }))
+        .await;
+        assert_eq!(
+            introspect(&input(&url)).await.unwrap_err(),
+            "Introspection failed: Introspection is disabled"
+        );
+    }
+}
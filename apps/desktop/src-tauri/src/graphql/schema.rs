@@ -0,0 +1,203 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use async_graphql_parser::types::{BaseType, Type};
+use serde::Deserialize;
+
+/// The parts of an introspected schema that validation needs, with types and
+/// directives looked up by name.
+pub struct Schema {
+    pub query_type: Option<String>,
+    pub mutation_type: Option<String>,
+    pub subscription_type: Option<String>,
+    pub types: HashMap<String, TypeDef>,
+    pub directives: HashMap<String, DirectiveDef>,
+}
+
+impl Schema {
+    /// Reads the `data` of an introspection response.
+    pub fn from_introspection(introspection: &serde_json::Value) -> Result<Self, String> {
+        let raw = Introspection::deserialize(introspection)
+            .map_err(|e| format!("Invalid introspection result: {}", e))?
+            .schema;
+        Ok(Schema {
+            query_type: raw.query_type.map(|t| t.name),
+            mutation_type: raw.mutation_type.map(|t| t.name),
+            subscription_type: raw.subscription_type.map(|t| t.name),
+            types: raw.types.into_iter().map(|t| (t.name.clone(), t)).collect(),
+            directives: raw
+                .directives
+                .into_iter()
+                .map(|d| (d.name.clone(), d))
+                .collect(),
+        })
+    }
+}
+
+#[derive(Deserialize)]
+struct Introspection {
+    #[serde(rename = "__schema")]
+    schema: RawSchema,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawSchema {
+    query_type: Option<NamedRef>,
+    mutation_type: Option<NamedRef>,
+    subscription_type: Option<NamedRef>,
+    types: Vec<TypeDef>,
+    #[serde(default)]
+    directives: Vec<DirectiveDef>,
+}
+
+#[derive(Deserialize)]
+struct NamedRef {
+    name: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum TypeKind {
+    Scalar,
+    Object,
+    Interface,
+    Union,
+    Enum,
+    InputObject,
+    List,
+    NonNull,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TypeDef {
+    pub kind: TypeKind,
+    pub name: String,
+    pub fields: Option<Vec<FieldDef>>,
+    pub input_fields: Option<Vec<InputValueDef>>,
+    pub enum_values: Option<Vec<EnumValueDef>>,
+}
+
+impl TypeDef {
+    /// Objects, interfaces and unions, which need a selection of subfields.
+    pub fn is_composite(&self) -> bool {
+        matches!(
+            self.kind,
+            TypeKind::Object | TypeKind::Interface | TypeKind::Union
+        )
+    }
+
+    /// Types that can be used for arguments and variables.
+    pub fn is_input(&self) -> bool {
+        matches!(
+            self.kind,
+            TypeKind::Scalar | TypeKind::Enum | TypeKind::InputObject
+        )
+    }
+
+    pub fn field(&self, name: &str) -> Option<&FieldDef> {
+        self.fields.iter().flatten().find(|f| f.name == name)
+    }
+
+    pub fn input_fields(&self) -> &[InputValueDef] {
+        self.input_fields.as_deref().unwrap_or_default()
+    }
+
+    pub fn has_enum_value(&self, name: &str) -> bool {
+        self.enum_values.iter().flatten().any(|v| v.name == name)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct FieldDef {
+    pub name: String,
+    #[serde(default)]
+    pub args: Vec<InputValueDef>,
+    #[serde(rename = "type")]
+    pub type_ref: TypeRef,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InputValueDef {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub type_ref: TypeRef,
+    pub default_value: Option<String>,
+}
+
+impl InputValueDef {
+    /// Non-null without a default, so it must be given.
+    pub fn is_required(&self) -> bool {
+        self.type_ref.kind == TypeKind::NonNull && self.default_value.is_none()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct EnumValueDef {
+    pub name: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DirectiveDef {
+    pub name: String,
+    #[serde(default)]
+    pub args: Vec<InputValueDef>,
+}
+
+/// A possibly wrapped reference to a named type, e.g. `[String!]!`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TypeRef {
+    pub kind: TypeKind,
+    pub name: Option<String>,
+    pub of_type: Option<Box<TypeRef>>,
+}
+
+impl TypeRef {
+    /// The type a variable is declared with in the query. Named types are
+    /// left as scalars; their kind comes from the schema.
+    pub fn from_ast(ty: &Type) -> Self {
+        let inner = match &ty.base {
+            BaseType::Named(name) => TypeRef {
+                kind: TypeKind::Scalar,
+                name: Some(name.to_string()),
+                of_type: None,
+            },
+            BaseType::List(item) => TypeRef {
+                kind: TypeKind::List,
+                name: None,
+                of_type: Some(Box::new(TypeRef::from_ast(item))),
+            },
+        };
+        if ty.nullable {
+            inner
+        } else {
+            TypeRef {
+                kind: TypeKind::NonNull,
+                name: None,
+                of_type: Some(Box::new(inner)),
+            }
+        }
+    }
+
+    /// The named type inside any list and non-null wrappers.
+    pub fn named(&self) -> &str {
+        match (&self.name, &self.of_type) {
+            (Some(name), _) => name,
+            (None, Some(inner)) => inner.named(),
+            (None, None) => "",
+        }
+    }
+}
+
+impl fmt::Display for TypeRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.kind, &self.of_type) {
+            (TypeKind::NonNull, Some(inner)) => write!(f, "{}!", inner),
+            (TypeKind::List, Some(inner)) => write!(f, "[{}]", inner),
+            _ => f.write_str(self.named()),
+        }
+    }
+}
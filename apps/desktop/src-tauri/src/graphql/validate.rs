@@ -0,0 +1,728 @@
+use std::collections::{HashMap, HashSet};
+
+use async_graphql_parser::types::{
+    Directive, DocumentOperations, ExecutableDocument, Field, OperationDefinition, OperationType,
+    Selection, SelectionSet, TypeCondition,
+};
+use async_graphql_parser::{Pos, Positioned};
+use async_graphql_value::{Name, Value};
+
+use super::schema::{InputValueDef, Schema, TypeDef, TypeKind, TypeRef};
+use crate::models::graphql::{GraphQLError, GraphQLErrorSource};
+
+const START: Pos = Pos { line: 1, column: 1 };
+
+/// Checks a query and its variables before they're sent. Syntax, the
+/// variables JSON and required variables are always checked; fields,
+/// arguments, fragments, directives and variable types only with a `schema`.
+/// Errors are sorted by where they point.
+pub fn validate(
+    schema: Option<&Schema>,
+    query: &str,
+    variables: &str,
+    operation_name: &str,
+) -> Vec<GraphQLError> {
+    let mut errors = Vec::new();
+    let values = parse_variables(variables, &mut errors);
+
+    match async_graphql_parser::parse_query(query) {
+        Ok(document) => {
+            if let Some(schema) = schema {
+                let mut validator = Validator {
+                    schema,
+                    document: &document,
+                    errors: Vec::new(),
+                };
+                validator.document();
+                errors.extend(validator.errors);
+            }
+            let operation = select_operation(&document, operation_name, &mut errors);
+            if let (Some(operation), Some(values)) = (operation, values) {
+                check_variables(schema, &operation.node, &values, variables, &mut errors);
+            }
+        }
+        Err(e) => {
+            let pos = e.positions().next().unwrap_or(START);
+            errors.push(query_error(syntax_message(&e), pos));
+        }
+    }
+
+    errors.sort_by_key(|e| (e.source == GraphQLErrorSource::Variables, e.line, e.column));
+    errors
+}
+
+fn query_error(message: String, pos: Pos) -> GraphQLError {
+    GraphQLError {
+        message,
+        source: GraphQLErrorSource::Query,
+        line: pos.line,
+        column: pos.column,
+    }
+}
+
+/// The parser's message without the excerpt it draws under it.
+fn syntax_message(error: &async_graphql_parser::Error) -> String {
+    let message = error.to_string();
+    match message
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("= "))
+    {
+        Some(expected) => format!("Syntax error: {}", expected),
+        None => message,
+    }
+}
+
+/// `None` when the variables can't be read, so their values aren't checked.
+fn parse_variables(
+    text: &str,
+    errors: &mut Vec<GraphQLError>,
+) -> Option<serde_json::Map<String, serde_json::Value>> {
+    if text.trim().is_empty() {
+        return Some(serde_json::Map::new());
+    }
+    let error = |message: String, line: usize, column: usize| GraphQLError {
+        message,
+        source: GraphQLErrorSource::Variables,
+        line: line.max(1),
+        column: column.max(1),
+    };
+    match serde_json::from_str(text) {
+        Ok(serde_json::Value::Object(values)) => Some(values),
+        Ok(_) => {
+            errors.push(error("Variables must be a JSON object".to_string(), 1, 1));
+            None
+        }
+        Err(e) => {
+            // serde_json appends the position, which the error carries separately
+            let message = e.to_string();
+            let message = message.split(" at line ").next().unwrap_or_default();
+            errors.push(error(
+                format!("Invalid JSON: {}", message),
+                e.line(),
+                e.column(),
+            ));
+            None
+        }
+    }
+}
+
+/// The operation that will run: the named one, or the only one.
+fn select_operation<'a>(
+    document: &'a ExecutableDocument,
+    name: &str,
+    errors: &mut Vec<GraphQLError>,
+) -> Option<&'a Positioned<OperationDefinition>> {
+    let name = name.trim();
+    match &document.operations {
+        DocumentOperations::Single(operation) if name.is_empty() => Some(operation),
+        DocumentOperations::Multiple(operations) if name.is_empty() => {
+            if operations.len() == 1 {
+                return operations.values().next();
+            }
+            let first = operations.values().map(|op| op.pos).min().unwrap_or(START);
+            errors.push(query_error(
+                "The document has several operations; pick one to run by name".to_string(),
+                first,
+            ));
+            None
+        }
+        DocumentOperations::Multiple(operations) if operations.contains_key(name) => {
+            operations.get(name)
+        }
+        _ => {
+            errors.push(query_error(
+                format!("Unknown operation \"{}\"", name),
+                START,
+            ));
+            None
+        }
+    }
+}
+
+/// Checks the values given for the operation's variables. Values that don't
+/// fit point at their key in the variables; missing ones at the definition.
+fn check_variables(
+    schema: Option<&Schema>,
+    operation: &OperationDefinition,
+    values: &serde_json::Map<String, serde_json::Value>,
+    text: &str,
+    errors: &mut Vec<GraphQLError>,
+) {
+    for definition in &operation.variable_definitions {
+        let name = definition.node.name.node.as_str();
+        let ty = TypeRef::from_ast(&definition.node.var_type.node);
+        match values.get(name) {
+            None | Some(serde_json::Value::Null) => {
+                if ty.kind == TypeKind::NonNull && definition.node.default_value.is_none() {
+                    errors.push(query_error(
+                        format!("Variable \"${}\" of type \"{}\" is required", name, ty),
+                        definition.pos,
+                    ));
+                }
+            }
+            Some(value) => {
+                let (Some(schema), Ok(value)) = (schema, Value::from_json(value.clone())) else {
+                    continue;
+                };
+                if let Err(e) = check_value(schema, &value, &ty, true) {
+                    let (line, column) = key_position(text, name);
+                    errors.push(GraphQLError {
+                        message: format!("Variable \"${}\": {}", name, e),
+                        source: GraphQLErrorSource::Variables,
+                        line,
+                        column,
+                    });
+                }
+            }
+        }
+    }
+}
+
+/// Where `"key":` first appears in the variables, or the start.
+fn key_position(text: &str, key: &str) -> (usize, usize) {
+    let quoted = format!("\"{}\"", key);
+    let offset = text
+        .match_indices(&quoted)
+        .map(|(offset, _)| offset)
+        .find(|offset| text[offset + quoted.len()..].trim_start().starts_with(':'));
+    match offset {
+        Some(offset) => {
+            let before = &text[..offset];
+            let line_start = before.rfind('\n').map_or(0, |n| n + 1);
+            (
+                before.matches('\n').count() + 1,
+                before[line_start..].chars().count() + 1,
+            )
+        }
+        None => (1, 1),
+    }
+}
+
+/// Checks a value against `ty`, returning why it doesn't fit. Variables inside
+/// literals aren't resolved. From JSON, enum values arrive as strings.
+fn check_value(
+    schema: &Schema,
+    value: &Value,
+    ty: &TypeRef,
+    from_json: bool,
+) -> Result<(), String> {
+    if matches!(value, Value::Variable(_)) {
+        return Ok(());
+    }
+    match (ty.kind, ty.of_type.as_deref()) {
+        (TypeKind::NonNull, Some(inner)) => match value {
+            Value::Null => Err(format!("expected {}, found null", ty)),
+            _ => check_value(schema, value, inner, from_json),
+        },
+        _ if matches!(value, Value::Null) => Ok(()),
+        (TypeKind::List, Some(inner)) => match value {
+            Value::List(items) => items
+                .iter()
+                .try_for_each(|item| check_value(schema, item, inner, from_json)),
+            // A single value is coerced to a list of one
+            _ => check_value(schema, value, inner, from_json),
+        },
+        _ => check_named(schema, value, ty.named(), from_json),
+    }
+}
+
+fn check_named(schema: &Schema, value: &Value, name: &str, from_json: bool) -> Result<(), String> {
+    // Unknown types are reported where they're named
+    let Some(def) = schema.types.get(name) else {
+        return Ok(());
+    };
+    let fits = match def.kind {
+        TypeKind::Scalar => match name {
+            "Int" => {
+                matches!(value, Value::Number(n) if n.as_i64().is_some_and(|n| i32::try_from(n).is_ok()))
+            }
+            "Float" => matches!(value, Value::Number(_)),
+            "String" => matches!(value, Value::String(_)),
+            "Boolean" => matches!(value, Value::Boolean(_)),
+            "ID" => match value {
+                Value::String(_) => true,
+                Value::Number(n) => n.is_i64() || n.is_u64(),
+                _ => false,
+            },
+            // Custom scalars take any value
+            _ => true,
+        },
+        TypeKind::Enum => match value {
+            Value::Enum(v) if !from_json => def.has_enum_value(v),
+            Value::String(v) if from_json => def.has_enum_value(v),
+            _ => false,
+        },
+        TypeKind::InputObject => {
+            let Value::Object(fields) = value else {
+                return Err(format!("expected {}, found {}", name, value));
+            };
+            for (key, field_value) in fields {
+                let Some(field) = def.input_fields().iter().find(|f| f.name == key.as_str()) else {
+                    return Err(format!("{} has no field \"{}\"", name, key));
+                };
+                check_value(schema, field_value, &field.type_ref, from_json)
+                    .map_err(|e| format!("field \"{}\": {}", key, e))?;
+            }
+            if let Some(missing) = def
+                .input_fields()
+                .iter()
+                .find(|f| f.is_required() && !fields.contains_key(f.name.as_str()))
+            {
+                return Err(format!(
+                    "missing field \"{}\" of type \"{}\"",
+                    missing.name, missing.type_ref
+                ));
+            }
+            true
+        }
+        // Output types are reported where the variable is declared
+        _ => true,
+    };
+    if fits {
+        Ok(())
+    } else {
+        Err(format!("expected {}, found {}", name, value))
+    }
+}
+
+fn variables_in<'a>(value: &'a Value, pos: Pos, usages: &mut Usages<'a>) {
+    match value {
+        Value::Variable(name) => usages.variables.push((name.as_str(), pos)),
+        Value::List(items) => items
+            .iter()
+            .for_each(|item| variables_in(item, pos, usages)),
+        Value::Object(fields) => fields
+            .values()
+            .for_each(|field| variables_in(field, pos, usages)),
+        _ => {}
+    }
+}
+
+/// The variables an operation or fragment uses and the fragments it spreads.
+#[derive(Default)]
+struct Usages<'a> {
+    variables: Vec<(&'a str, Pos)>,
+    spreads: Vec<&'a str>,
+}
+
+struct Validator<'a> {
+    schema: &'a Schema,
+    document: &'a ExecutableDocument,
+    errors: Vec<GraphQLError>,
+}
+
+impl<'a> Validator<'a> {
+    fn error(&mut self, message: String, pos: Pos) {
+        self.errors.push(query_error(message, pos));
+    }
+
+    fn document(&mut self) {
+        let document = self.document;
+        let mut fragments: HashMap<&str, Usages> = HashMap::new();
+        for (name, fragment) in &document.fragments {
+            let mut usages = Usages::default();
+            self.directives(&fragment.node.directives, &mut usages);
+            if let Some(ty) = self.condition(&fragment.node.type_condition) {
+                self.selection_set(&fragment.node.selection_set.node, ty, &mut usages);
+            }
+            fragments.insert(name.as_str(), usages);
+        }
+
+        for (name, operation) in document.operations.iter() {
+            let mut usages = Usages::default();
+            self.operation(operation, &mut usages);
+
+            // Variables used inside spread fragments count for the operation
+            let mut used = usages.variables;
+            let mut pending = usages.spreads;
+            let mut seen = HashSet::new();
+            while let Some(spread) = pending.pop() {
+                if let Some(fragment) = fragments.get(spread).filter(|_| seen.insert(spread)) {
+                    used.extend(&fragment.variables);
+                    pending.extend(&fragment.spreads);
+                }
+            }
+
+            let by = name
+                .map(|n| format!(" by operation \"{}\"", n))
+                .unwrap_or_default();
+            let defined = &operation.node.variable_definitions;
+            for (variable, pos) in &used {
+                if !defined
+                    .iter()
+                    .any(|d| d.node.name.node.as_str() == *variable)
+                {
+                    self.error(
+                        format!("Variable \"${}\" is not defined{}", variable, by),
+                        *pos,
+                    );
+                }
+            }
+            for definition in defined {
+                let variable = definition.node.name.node.as_str();
+                if !used.iter().any(|(used, _)| *used == variable) {
+                    self.error(
+                        format!("Variable \"${}\" is never used{}", variable, by),
+                        definition.pos,
+                    );
+                }
+            }
+        }
+    }
+
+    fn operation(
+        &mut self,
+        operation: &'a Positioned<OperationDefinition>,
+        usages: &mut Usages<'a>,
+    ) {
+        let schema = self.schema;
+        for definition in &operation.node.variable_definitions {
+            let var_type = &definition.node.var_type;
+            let ty = TypeRef::from_ast(&var_type.node);
+            match schema.types.get(ty.named()) {
+                Some(def) if def.is_input() => {}
+                Some(_) => self.error(
+                    format!(
+                        "Variable \"${}\" cannot be of output type \"{}\"",
+                        definition.node.name.node, ty
+                    ),
+                    var_type.pos,
+                ),
+                None => self.error(format!("Unknown type \"{}\"", ty.named()), var_type.pos),
+            }
+            if let Some(default) = &definition.node.default_value {
+                if let Err(e) = check_value(schema, &default.node.clone().into_value(), &ty, false)
+                {
+                    self.error(
+                        format!("Default value of \"${}\": {}", definition.node.name.node, e),
+                        default.pos,
+                    );
+                }
+            }
+            self.directives(&definition.node.directives, usages);
+        }
+        self.directives(&operation.node.directives, usages);
+
+        let root = match operation.node.ty {
+            OperationType::Query => &schema.query_type,
+            OperationType::Mutation => &schema.mutation_type,
+            OperationType::Subscription => &schema.subscription_type,
+        };
+        match root.as_deref().and_then(|name| schema.types.get(name)) {
+            Some(root) => self.selection_set(&operation.node.selection_set.node, root, usages),
+            None => self.error(
+                format!("The schema has no {} type", operation.node.ty),
+                operation.pos,
+            ),
+        }
+    }
+
+    fn selection_set(
+        &mut self,
+        set: &'a SelectionSet,
+        parent: &'a TypeDef,
+        usages: &mut Usages<'a>,
+    ) {
+        for selection in &set.items {
+            match &selection.node {
+                Selection::Field(field) => self.field(&field.node, parent, usages),
+                Selection::FragmentSpread(spread) => {
+                    self.directives(&spread.node.directives, usages);
+                    let name = &spread.node.fragment_name;
+                    if self.document.fragments.contains_key(name.node.as_str()) {
+                        usages.spreads.push(name.node.as_str());
+                    } else {
+                        self.error(format!("Unknown fragment \"{}\"", name.node), name.pos);
+                    }
+                }
+                Selection::InlineFragment(inline) => {
+                    self.directives(&inline.node.directives, usages);
+                    let ty = match &inline.node.type_condition {
+                        Some(condition) => self.condition(condition),
+                        None => Some(parent),
+                    };
+                    if let Some(ty) = ty {
+                        self.selection_set(&inline.node.selection_set.node, ty, usages);
+                    }
+                }
+            }
+        }
+    }
+
+    fn condition(&mut self, condition: &'a Positioned<TypeCondition>) -> Option<&'a TypeDef> {
+        let name = &condition.node.on;
+        match self.schema.types.get(name.node.as_str()) {
+            Some(ty) if ty.is_composite() => Some(ty),
+            Some(_) => {
+                self.error(
+                    format!(
+                        "Fragment cannot condition on non composite type \"{}\"",
+                        name.node
+                    ),
+                    name.pos,
+                );
+                None
+            }
+            None => {
+                self.error(format!("Unknown type \"{}\"", name.node), name.pos);
+                None
+            }
+        }
+    }
+
+    fn field(&mut self, field: &'a Field, parent: &'a TypeDef, usages: &mut Usages<'a>) {
+        let schema = self.schema;
+        let name = field.name.node.as_str();
+        self.directives(&field.directives, usages);
+
+        let is_meta = name == "__typename"
+            || (name.starts_with("__")
+                && schema.query_type.as_deref() == Some(parent.name.as_str()));
+        let definition = match parent.field(name) {
+            Some(definition) if !is_meta => definition,
+            // Introspection fields aren't in the schema's own types
+            _ => {
+                if !is_meta {
+                    self.error(
+                        format!(
+                            "Cannot query field \"{}\" on type \"{}\"",
+                            name, parent.name
+                        ),
+                        field.name.pos,
+                    );
+                }
+                for (_, value) in &field.arguments {
+                    variables_in(&value.node, value.pos, usages);
+                }
+                return;
+            }
+        };
+
+        let owner = format!("field \"{}\"", name);
+        self.arguments(
+            &field.arguments,
+            &definition.args,
+            &owner,
+            field.name.pos,
+            usages,
+        );
+
+        let selection = &field.selection_set;
+        match schema.types.get(definition.type_ref.named()) {
+            Some(ty) if ty.is_composite() => {
+                if selection.node.items.is_empty() {
+                    self.error(
+                        format!(
+                            "Field \"{}\" of type \"{}\" must have a selection of subfields",
+                            name, definition.type_ref
+                        ),
+                        field.name.pos,
+                    );
+                } else {
+                    self.selection_set(&selection.node, ty, usages);
+                }
+            }
+            _ if !selection.node.items.is_empty() => self.error(
+                format!(
+                    "Field \"{}\" must not have a selection since type \"{}\" has no subfields",
+                    name, definition.type_ref
+                ),
+                selection.pos,
+            ),
+            _ => {}
+        }
+    }
+
+    fn directives(&mut self, directives: &'a [Positioned<Directive>], usages: &mut Usages<'a>) {
+        let schema = self.schema;
+        for directive in directives {
+            let name = directive.node.name.node.as_str();
+            match schema.directives.get(name) {
+                Some(definition) => {
+                    let owner = format!("directive \"@{}\"", name);
+                    self.arguments(
+                        &directive.node.arguments,
+                        &definition.args,
+                        &owner,
+                        directive.pos,
+                        usages,
+                    );
+                }
+                None => {
+                    self.error(format!("Unknown directive \"@{}\"", name), directive.pos);
+                    for (_, value) in &directive.node.arguments {
+                        variables_in(&value.node, value.pos, usages);
+                    }
+                }
+            }
+        }
+    }
+
+    fn arguments(
+        &mut self,
+        given: &'a [(Positioned<Name>, Positioned<Value>)],
+        defined: &[InputValueDef],
+        owner: &str,
+        pos: Pos,
+        usages: &mut Usages<'a>,
+    ) {
+        for (name, value) in given {
+            variables_in(&value.node, value.pos, usages);
+            match defined.iter().find(|arg| arg.name == name.node.as_str()) {
+                Some(arg) => {
+                    if let Err(e) = check_value(self.schema, &value.node, &arg.type_ref, false) {
+                        self.error(format!("Argument \"{}\": {}", name.node, e), value.pos);
+                    }
+                }
+                None => self.error(
+                    format!("Unknown argument \"{}\" on {}", name.node, owner),
+                    name.pos,
+                ),
+            }
+        }
+        for arg in defined.iter().filter(|arg| arg.is_required()) {
+            if !given.iter().any(|(name, _)| name.node.as_str() == arg.name) {
+                self.error(
+                    format!(
+                        "Missing argument \"{}\" of type \"{}\" on {}",
+                        arg.name, arg.type_ref, owner
+                    ),
+                    pos,
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graphql::tests::introspection;
+
+    fn check(query: &str, variables: &str) -> Vec<(String, usize, usize)> {
+        let schema = Schema::from_introspection(&introspection()).unwrap();
+        validate(Some(&schema), query, variables, "")
+            .into_iter()
+            .map(|e| (e.message, e.line, e.column))
+            .collect()
+    }
+
+    #[test]
+    fn test_valid_document() {
+        let query = r#"query Users($role: Role, $withFriends: Boolean!) {
+  users(role: $role) {
+    ...UserFields
+    friends @include(if: $withFriends) { id }
+  }
+  __schema { queryType { name } }
+}
+fragment UserFields on User { __typename id name createdAt }"#;
+        let variables = r#"{ "role": "ADMIN", "withFriends": true }"#;
+        assert_eq!(check(query, variables), vec![]);
+        assert_eq!(
+            check(
+                "mutation { createUser(input: { name: \"Ada\", role: ADMIN }) { id } }",
+                ""
+            ),
+            vec![]
+        );
+    }
+
+    #[test]
+    fn test_schema_errors() {
+        let query = r#"query ($unused: Int) {
+  user { id email }
+  users(first: "ten", sort: NAME) { name { length } friends }
+  ... on Role { id }
+  ...Missing
+  posts: users @live { id }
+}"#;
+        let errors = check(query, "");
+        assert_eq!(
+            errors,
+            vec![
+                ("Variable \"$unused\" is never used".to_string(), 1, 8),
+                ("Missing argument \"id\" of type \"ID!\" on field \"user\"".to_string(), 2, 3),
+                ("Cannot query field \"email\" on type \"User\"".to_string(), 2, 13),
+                ("Argument \"first\": expected Int, found \"ten\"".to_string(), 3, 16),
+                ("Unknown argument \"sort\" on field \"users\"".to_string(), 3, 23),
+                (
+                    "Field \"name\" must not have a selection since type \"String\" has no subfields"
+                        .to_string(),
+                    3,
+                    42
+                ),
+                (
+                    "Field \"friends\" of type \"[User!]!\" must have a selection of subfields"
+                        .to_string(),
+                    3,
+                    53
+                ),
+                (
+                    "Fragment cannot condition on non composite type \"Role\"".to_string(),
+                    4,
+                    10
+                ),
+                ("Unknown fragment \"Missing\"".to_string(), 5, 6),
+                ("Unknown directive \"@live\"".to_string(), 6, 16),
+            ]
+        );
+
+        let errors = check(
+            "query Find($id: ID!, $u: User) { user(id: $id) { id ...F } } fragment F on User { friends(first: $n) { id } }",
+            r#"{"id": 1}"#,
+        );
+        let messages: Vec<&str> = errors.iter().map(|e| e.0.as_str()).collect();
+        assert!(messages.contains(&"Variable \"$u\" cannot be of output type \"User\""));
+        assert!(messages.contains(&"Variable \"$n\" is not defined by operation \"Find\""));
+        assert!(messages.contains(&"Unknown argument \"first\" on field \"friends\""));
+    }
+
+    #[test]
+    fn test_syntax_and_variables() {
+        let errors = check("{\n  users(first: ) { id }\n}", "");
+        assert_eq!(errors.len(), 1);
+        assert_eq!((errors[0].1, errors[0].2), (2, 16));
+        assert!(
+            errors[0].0.starts_with("Syntax error: expected"),
+            "{}",
+            errors[0].0
+        );
+
+        let query = "mutation Create($input: UserInput!, $dryRun: Boolean!) { createUser(input: $input) @skip(if: $dryRun) { id } }";
+        let variables = "{\n  \"input\": { \"role\": \"OWNER\" }\n}";
+        let schema = Schema::from_introspection(&introspection()).unwrap();
+        let errors = validate(Some(&schema), query, variables, "Create");
+        assert_eq!(
+            errors,
+            vec![
+                GraphQLError {
+                    message: "Variable \"$dryRun\" of type \"Boolean!\" is required".to_string(),
+                    source: GraphQLErrorSource::Query,
+                    line: 1,
+                    column: 37,
+                },
+                GraphQLError {
+                    message: "Variable \"$input\": field \"role\": expected Role, found \"OWNER\""
+                        .to_string(),
+                    source: GraphQLErrorSource::Variables,
+                    line: 2,
+                    column: 3,
+                },
+            ]
+        );
+
+        // Without a schema, syntax and variables are still checked
+        let errors = validate(None, query, "{ \"input\": ", "Other");
+        let sources: Vec<_> = errors.iter().map(|e| (e.source, e.line)).collect();
+        assert_eq!(
+            sources,
+            vec![
+                (GraphQLErrorSource::Query, 1),
+                (GraphQLErrorSource::Variables, 1)
+            ]
+        );
+        assert_eq!(errors[0].message, "Unknown operation \"Other\"");
+        assert!(errors[1].message.starts_with("Invalid JSON: EOF"));
+    }
+}
@@ -0,0 +1,211 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use prost_reflect::{DescriptorPool, DynamicMessage, SerializeOptions};
+use protobuf::descriptor::{FileDescriptorProto, FileDescriptorSet};
+use protobuf::Message as _;
+use tonic::metadata::MetadataMap;
+use tonic::transport::Channel;
+use tonic::{Code, Status};
+
+use crate::models::grpc::{GrpcMethodInfo, GrpcServiceInfo};
+
+/// Reflection's own services, which every reflecting server lists.
+const REFLECTION_PACKAGE: &str = "grpc.reflection.";
+
+/// Parses `.proto` files with the built-in parser, so `protoc` isn't needed.
+/// The well-known `google/protobuf` imports are always available.
+pub fn from_proto_files(
+    files: &[String],
+    include_paths: &[String],
+) -> Result<DescriptorPool, String> {
+    if files.is_empty() {
+        return Err("No .proto files given".to_string());
+    }
+    let mut includes: Vec<PathBuf> = include_paths.iter().map(PathBuf::from).collect();
+    for file in files {
+        let parent = match Path::new(file).parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent.to_path_buf(),
+            _ => PathBuf::from("."),
+        };
+        if !includes.contains(&parent) {
+            includes.push(parent);
+        }
+    }
+    let parsed = protobuf_parse::Parser::new()
+        .pure()
+        .includes(&includes)
+        .inputs(files)
+        .parse_and_typecheck()
+        .map_err(|e| format!("Failed to parse .proto files: {:#}", e))?;
+    pool_from_files(parsed.file_descriptors)
+}
+
+/// Asks the server for the files defining `service`, or every service it
+/// lists, along with their imports. Servers without the `v1` reflection
+/// service are asked through `v1alpha`.
+pub async fn from_reflection(
+    channel: &Channel,
+    metadata: &MetadataMap,
+    service: Option<&str>,
+) -> Result<DescriptorPool, String> {
+    let services = match service {
+        Some(service) => vec![service.to_string()],
+        None => match reflect(channel, metadata, ReflectionRequest::ListServices).await? {
+            ReflectionResponse::Services(names) => names
+                .into_iter()
+                .filter(|name| !name.starts_with(REFLECTION_PACKAGE))
+                .collect(),
+            ReflectionResponse::Files(_) => {
+                return Err("Unexpected reflection response".to_string())
+            }
+        },
+    };
+
+    let mut files: HashMap<String, FileDescriptorProto> = HashMap::new();
+    let mut pending: Vec<ReflectionRequest> = services
+        .into_iter()
+        .map(ReflectionRequest::FileContainingSymbol)
+        .collect();
+    while let Some(request) = pending.pop() {
+        let ReflectionResponse::Files(found) = reflect(channel, metadata, request).await? else {
+            return Err("Unexpected reflection response".to_string());
+        };
+        for bytes in found {
+            let file = FileDescriptorProto::parse_from_bytes(&bytes)
+                .map_err(|e| format!("Invalid descriptor from server: {}", e))?;
+            files.entry(file.name().to_string()).or_insert(file);
+        }
+        // Servers may leave out files they consider already sent
+        for dependency in files.values().flat_map(|file| file.dependency.iter()) {
+            let request = ReflectionRequest::FileByFilename(dependency.clone());
+            if !files.contains_key(dependency) && !pending.contains(&request) {
+                pending.push(request);
+            }
+        }
+    }
+    pool_from_files(files.into_values().collect())
+}
+
+/// The services in the pool, sorted by name.
+pub fn services(pool: &DescriptorPool) -> Vec<GrpcServiceInfo> {
+    let options = SerializeOptions::new().skip_default_fields(false);
+    let mut services: Vec<GrpcServiceInfo> = pool
+        .services()
+        .filter(|service| !service.full_name().starts_with(REFLECTION_PACKAGE))
+        .map(|service| GrpcServiceInfo {
+            name: service.full_name().to_string(),
+            methods: service
+                .methods()
+                .map(|method| GrpcMethodInfo {
+                    name: method.name().to_string(),
+                    input_type: method.input().full_name().to_string(),
+                    output_type: method.output().full_name().to_string(),
+                    client_streaming: method.is_client_streaming(),
+                    server_streaming: method.is_server_streaming(),
+                    input_template: DynamicMessage::new(method.input())
+                        .serialize_with_options(serde_json::value::Serializer, &options)
+                        .unwrap_or_default(),
+                })
+                .collect(),
+        })
+        .collect();
+    services.sort_by(|a, b| a.name.cmp(&b.name));
+    services
+}
+
+fn pool_from_files(files: Vec<FileDescriptorProto>) -> Result<DescriptorPool, String> {
+    let mut set = FileDescriptorSet::new();
+    set.file = files;
+    let bytes = set
+        .write_to_bytes()
+        .map_err(|e| format!("Failed to encode descriptors: {}", e))?;
+    DescriptorPool::decode(bytes.as_slice())
+        .map_err(|e| format!("Invalid service definitions: {}", e))
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum ReflectionRequest {
+    ListServices,
+    FileContainingSymbol(String),
+    FileByFilename(String),
+}
+
+enum ReflectionResponse {
+    Services(Vec<String>),
+    /// Encoded `FileDescriptorProto`s.
+    Files(Vec<Vec<u8>>),
+}
+
+async fn reflect(
+    channel: &Channel,
+    metadata: &MetadataMap,
+    request: ReflectionRequest,
+) -> Result<ReflectionResponse, String> {
+    let response = match reflect_v1(channel.clone(), metadata, request.clone()).await {
+        Err(status) if status.code() == Code::Unimplemented => {
+            reflect_v1alpha(channel.clone(), metadata, request).await
+        }
+        response => response,
+    };
+    response.map_err(|status| match status.code() {
+        Code::Unimplemented => "The server doesn't support reflection".to_string(),
+        _ => format!("Reflection failed: {}", status.message()),
+    })
+}
+
+/// The two reflection versions have the same messages in different packages.
+macro_rules! reflection_client {
+    ($name:ident, $($pb:ident)::+) => {
+        async fn $name(
+            channel: Channel,
+            metadata: &MetadataMap,
+            request: ReflectionRequest,
+        ) -> Result<ReflectionResponse, Status> {
+            use $($pb)::+::server_reflection_client::ServerReflectionClient;
+            use $($pb)::+::server_reflection_request::MessageRequest;
+            use $($pb)::+::server_reflection_response::MessageResponse;
+            use $($pb)::+::ServerReflectionRequest;
+
+            let message_request = match request {
+                ReflectionRequest::ListServices => MessageRequest::ListServices(String::new()),
+                ReflectionRequest::FileContainingSymbol(symbol) => {
+                    MessageRequest::FileContainingSymbol(symbol)
+                }
+                ReflectionRequest::FileByFilename(name) => MessageRequest::FileByFilename(name),
+            };
+            let mut request = tonic::Request::new(futures_util::stream::iter(vec![
+                ServerReflectionRequest {
+                    host: String::new(),
+                    message_request: Some(message_request),
+                },
+            ]));
+            *request.metadata_mut() = metadata.clone();
+
+            let mut responses = ServerReflectionClient::new(channel)
+                .server_reflection_info(request)
+                .await?
+                .into_inner();
+            let response = responses
+                .message()
+                .await?
+                .ok_or_else(|| Status::unknown("No reflection response"))?;
+            match response.message_response {
+                Some(MessageResponse::ListServicesResponse(list)) => Ok(
+                    ReflectionResponse::Services(list.service.into_iter().map(|s| s.name).collect()),
+                ),
+                Some(MessageResponse::FileDescriptorResponse(files)) => {
+                    Ok(ReflectionResponse::Files(files.file_descriptor_proto))
+                }
+                Some(MessageResponse::ErrorResponse(error)) => Err(Status::new(
+                    Code::from(error.error_code),
+                    error.error_message,
+                )),
+                _ => Err(Status::unknown("Unexpected reflection response")),
+            }
+        }
+    };
+}
+
+reflection_client!(reflect_v1, tonic_reflection::pb::v1);
+reflection_client!(reflect_v1alpha, tonic_reflection::pb::v1alpha);
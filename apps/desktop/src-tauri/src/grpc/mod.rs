@@ -0,0 +1,556 @@
+pub mod descriptors;
+
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+use prost::Message as _;
+use prost_reflect::{DescriptorPool, DynamicMessage, MessageDescriptor, SerializeOptions};
+use tokio::sync::oneshot;
+use tonic::codec::{Codec, DecodeBuf, Decoder, EncodeBuf, Encoder};
+use tonic::codegen::http::uri::PathAndQuery;
+use tonic::codegen::http::{HeaderMap, HeaderName, HeaderValue};
+use tonic::metadata::MetadataMap;
+use tonic::transport::{Channel, ClientTlsConfig, Endpoint};
+use tonic::Status;
+
+use crate::models::execution::ExecutionTiming;
+use crate::models::grpc::{
+    GrpcCallInput, GrpcResult, GrpcSchemaSource, GrpcServiceInfo, GrpcServicesInput,
+};
+use crate::models::request::KeyValue;
+
+/// Lists the services and methods the schema source defines.
+pub async fn list_services(input: &GrpcServicesInput) -> Result<Vec<GrpcServiceInfo>, String> {
+    let pool = load_schema(&input.url, &input.metadata, &input.source, None).await?;
+    Ok(descriptors::services(&pool))
+}
+
+/// Makes a unary or server-streaming call, passing each response message to
+/// `on_message` as it arrives. Reading stops early when `stop` fires. Errors
+/// are returned only when the call can't be made; a failed call is a result
+/// with its status.
+pub async fn call<F>(
+    input: &GrpcCallInput,
+    mut stop: oneshot::Receiver<()>,
+    mut on_message: F,
+) -> Result<GrpcResult, String>
+where
+    F: FnMut(serde_json::Value),
+{
+    let pool = load_schema(
+        &input.url,
+        &input.metadata,
+        &input.source,
+        Some(&input.service),
+    )
+    .await?;
+    let service = pool
+        .get_service_by_name(&input.service)
+        .ok_or_else(|| format!("Service {} not found", input.service))?;
+    let method = service
+        .methods()
+        .find(|m| m.name() == input.method)
+        .ok_or_else(|| format!("Method {} not found in {}", input.method, input.service))?;
+    if method.is_client_streaming() {
+        return Err(format!(
+            "{}.{} is client-streaming, which isn't supported",
+            input.service, input.method
+        ));
+    }
+
+    let mut request = tonic::Request::new(parse_message(method.input(), &input.message)?);
+    *request.metadata_mut() = metadata_map(&input.metadata)?;
+    if let Some(timeout_ms) = input.timeout_ms {
+        request.set_timeout(Duration::from_millis(timeout_ms));
+    }
+    let path = PathAndQuery::from_str(&format!("/{}/{}", service.full_name(), method.name()))
+        .map_err(|e| format!("Invalid method path: {}", e))?;
+    let codec = DynamicCodec {
+        decodes: method.output(),
+    };
+    let mut grpc = tonic::client::Grpc::new(channel(&input.url)?);
+
+    let start = Instant::now();
+    let elapsed_ms = || start.elapsed().as_secs_f64() * 1000.0;
+    // Unary calls are read as a stream too, which keeps headers and trailers apart
+    let response = tokio::select! {
+        response = async {
+            grpc.ready()
+                .await
+                .map_err(|e| Status::unavailable(format!("Connection failed: {}", e)))?;
+            grpc.server_streaming(request, path, codec).await
+        } => response,
+        _ = &mut stop => Err(Status::cancelled("Call stopped")),
+    };
+    let (headers, mut stream) = match response {
+        Ok(response) => (metadata_to_kv(response.metadata()), response.into_inner()),
+        Err(status) => {
+            let total_ms = elapsed_ms();
+            return Ok(GrpcResult {
+                status_code: status.code() as i32,
+                status_name: format!("{:?}", status.code()),
+                status_message: status.message().to_string(),
+                headers: vec![],
+                // A trailers-only response carries its status and metadata here
+                trailers: metadata_to_kv(status.metadata()),
+                messages: vec![],
+                size_bytes: 0,
+                timing: timing(total_ms, total_ms),
+            });
+        }
+    };
+
+    let first_byte_ms = elapsed_ms();
+    let options = SerializeOptions::new().skip_default_fields(false);
+    let mut messages = Vec::new();
+    let mut size_bytes = 0;
+    let mut status = Status::ok("");
+    let mut trailers = None;
+    loop {
+        tokio::select! {
+            next = stream.message() => match next {
+                Ok(Some(message)) => {
+                    size_bytes += message.encoded_len() as u64;
+                    let json = message
+                        .serialize_with_options(serde_json::value::Serializer, &options)
+                        .unwrap_or_default();
+                    on_message(json.clone());
+                    messages.push(json);
+                }
+                Ok(None) => {
+                    trailers = stream.trailers().await.ok().flatten();
+                    break;
+                }
+                Err(error) => {
+                    trailers = Some(error.metadata().clone());
+                    status = error;
+                    break;
+                }
+            },
+            _ = &mut stop => {
+                status = Status::cancelled("Call stopped");
+                break;
+            }
+        }
+    }
+
+    Ok(GrpcResult {
+        status_code: status.code() as i32,
+        status_name: format!("{:?}", status.code()),
+        status_message: status.message().to_string(),
+        headers,
+        trailers: trailers.as_ref().map(metadata_to_kv).unwrap_or_default(),
+        messages,
+        size_bytes,
+        timing: timing(first_byte_ms, elapsed_ms()),
+    })
+}
+
+async fn load_schema(
+    url: &str,
+    metadata: &[KeyValue],
+    source: &GrpcSchemaSource,
+    service: Option<&str>,
+) -> Result<DescriptorPool, String> {
+    match source {
+        GrpcSchemaSource::ProtoFiles {
+            files,
+            include_paths,
+        } => descriptors::from_proto_files(files, include_paths),
+        GrpcSchemaSource::Reflection => {
+            descriptors::from_reflection(&channel(url)?, &metadata_map(metadata)?, service).await
+        }
+    }
+}
+
+/// Connects on first use. `grpc://` and bare `host:port` URLs are plaintext,
+/// `grpcs://` is TLS.
+fn channel(url: &str) -> Result<Channel, String> {
+    let url = url.trim().trim_end_matches('/');
+    let url = if let Some(rest) = url.strip_prefix("grpcs://") {
+        format!("https://{}", rest)
+    } else if let Some(rest) = url.strip_prefix("grpc://") {
+        format!("http://{}", rest)
+    } else if url.contains("://") {
+        url.to_string()
+    } else {
+        format!("http://{}", url)
+    };
+    let mut endpoint = Endpoint::from_shared(url.clone())
+        .map_err(|e| format!("Invalid gRPC URL {}: {}", url, e))?
+        .connect_timeout(Duration::from_secs(30));
+    if url.starts_with("https://") {
+        endpoint = endpoint
+            .tls_config(ClientTlsConfig::new().with_webpki_roots())
+            .map_err(|e| format!("Failed to configure TLS: {}", e))?;
+    }
+    Ok(endpoint.connect_lazy())
+}
+
+fn parse_message(descriptor: MessageDescriptor, json: &str) -> Result<DynamicMessage, String> {
+    let json = match json.trim() {
+        "" => "{}",
+        json => json,
+    };
+    let mut deserializer = serde_json::Deserializer::from_str(json);
+    let message = DynamicMessage::deserialize(descriptor.clone(), &mut deserializer)
+        .and_then(|message| deserializer.end().map(|_| message))
+        .map_err(|e| format!("Invalid {} message: {}", descriptor.full_name(), e))?;
+    Ok(message)
+}
+
+/// Enabled entries become request metadata. Keys ending in `-bin` carry
+/// base64 values, as on the wire.
+fn metadata_map(metadata: &[KeyValue]) -> Result<MetadataMap, String> {
+    let mut headers = HeaderMap::new();
+    for kv in metadata.iter().filter(|kv| kv.enabled) {
+        let name = HeaderName::from_bytes(kv.key.as_bytes())
+            .map_err(|e| format!("Invalid metadata key {}: {}", kv.key, e))?;
+        let value = HeaderValue::from_str(&kv.value)
+            .map_err(|e| format!("Invalid value for metadata {}: {}", kv.key, e))?;
+        headers.append(name, value);
+    }
+    Ok(MetadataMap::from_headers(headers))
+}
+
+fn metadata_to_kv(metadata: &MetadataMap) -> Vec<KeyValue> {
+    metadata
+        .as_ref()
+        .iter()
+        .map(|(name, value)| KeyValue {
+            key: name.to_string(),
+            value: value.to_str().unwrap_or("").to_string(),
+            enabled: true,
+        })
+        .collect()
+}
+
+fn timing(first_byte_ms: f64, total_ms: f64) -> ExecutionTiming {
+    ExecutionTiming {
+        dns_ms: None,
+        connect_ms: None,
+        tls_ms: None,
+        first_byte_ms,
+        total_ms,
+    }
+}
+
+/// Encodes any dynamic message and decodes messages of one type.
+#[derive(Clone)]
+struct DynamicCodec {
+    decodes: MessageDescriptor,
+}
+
+impl Codec for DynamicCodec {
+    type Encode = DynamicMessage;
+    type Decode = DynamicMessage;
+    type Encoder = DynamicCodec;
+    type Decoder = DynamicCodec;
+
+    fn encoder(&mut self) -> Self::Encoder {
+        self.clone()
+    }
+
+    fn decoder(&mut self) -> Self::Decoder {
+        self.clone()
+    }
+}
+
+impl Encoder for DynamicCodec {
+    type Item = DynamicMessage;
+    type Error = Status;
+
+    fn encode(&mut self, item: Self::Item, dst: &mut EncodeBuf<'_>) -> Result<(), Self::Error> {
+        item.encode(dst)
+            .map_err(|e| Status::internal(format!("Failed to encode message: {}", e)))
+    }
+}
+
+impl Decoder for DynamicCodec {
+    type Item = DynamicMessage;
+    type Error = Status;
+
+    fn decode(&mut self, src: &mut DecodeBuf<'_>) -> Result<Option<Self::Item>, Self::Error> {
+        DynamicMessage::decode(self.decodes.clone(), src)
+            .map(Some)
+            .map_err(|e| Status::internal(format!("Failed to decode message: {}", e)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::convert::Infallible;
+    use std::path::PathBuf;
+    use std::task::{Context, Poll};
+
+    use prost_reflect::Value;
+    use protobuf::Message as _;
+    use tokio::net::TcpListener;
+    use tonic::body::Body;
+    use tonic::codegen::http;
+    use tonic::codegen::BoxFuture;
+    use tonic::server::NamedService;
+
+    const PROTO: &str = r#"
+        syntax = "proto3";
+        package test;
+        import "google/protobuf/timestamp.proto";
+
+        message SayRequest {
+            string name = 1;
+            int32 times = 2;
+        }
+        message SayReply {
+            string text = 1;
+            google.protobuf.Timestamp at = 2;
+        }
+        service Echo {
+            rpc Say(SayRequest) returns (SayReply);
+            rpc SayMany(SayRequest) returns (stream SayReply);
+            rpc Collect(stream SayRequest) returns (SayReply);
+        }
+    "#;
+
+    fn write_proto() -> (PathBuf, String) {
+        let dir = std::env::temp_dir().join(format!("steq-grpc-{}", uuid::Uuid::now_v7()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("echo.proto");
+        std::fs::write(&path, PROTO).unwrap();
+        (dir, path.to_string_lossy().to_string())
+    }
+
+    /// Replies to `Say` with a greeting and `x-trace` trailer, and to `SayMany`
+    /// with `times` greetings. An empty name is rejected.
+    #[derive(Clone)]
+    struct Echo {
+        pool: DescriptorPool,
+    }
+
+    impl NamedService for Echo {
+        const NAME: &'static str = "test.Echo";
+    }
+
+    impl Echo {
+        fn reply(&self, request: &DynamicMessage, n: i32) -> DynamicMessage {
+            let name = request.get_field_by_name("name").unwrap();
+            let mut reply =
+                DynamicMessage::new(self.pool.get_message_by_name("test.SayReply").unwrap());
+            reply.set_field_by_name(
+                "text",
+                Value::String(format!("hi {} #{}", name.as_str().unwrap(), n)),
+            );
+            reply
+        }
+    }
+
+    struct Say(Echo);
+
+    impl tonic::server::UnaryService<DynamicMessage> for Say {
+        type Response = DynamicMessage;
+        type Future = BoxFuture<tonic::Response<DynamicMessage>, Status>;
+
+        fn call(&mut self, request: tonic::Request<DynamicMessage>) -> Self::Future {
+            let echo = self.0.clone();
+            Box::pin(async move {
+                let message = request.into_inner();
+                if message.get_field_by_name("name").unwrap().as_str() == Some("") {
+                    return Err(Status::invalid_argument("name is required"));
+                }
+                let mut response = tonic::Response::new(echo.reply(&message, 1));
+                response
+                    .metadata_mut()
+                    .insert("x-echo", "yes".parse().unwrap());
+                Ok(response)
+            })
+        }
+    }
+
+    struct SayMany(Echo);
+
+    type ReplyStream =
+        futures_util::stream::Iter<std::vec::IntoIter<Result<DynamicMessage, Status>>>;
+
+    impl tonic::server::ServerStreamingService<DynamicMessage> for SayMany {
+        type Response = DynamicMessage;
+        type ResponseStream = ReplyStream;
+        type Future = BoxFuture<tonic::Response<ReplyStream>, Status>;
+
+        fn call(&mut self, request: tonic::Request<DynamicMessage>) -> Self::Future {
+            let echo = self.0.clone();
+            Box::pin(async move {
+                let message = request.into_inner();
+                let times = message
+                    .get_field_by_name("times")
+                    .unwrap()
+                    .as_i32()
+                    .unwrap();
+                let replies: Vec<_> = (1..=times).map(|n| Ok(echo.reply(&message, n))).collect();
+                Ok(tonic::Response::new(futures_util::stream::iter(replies)))
+            })
+        }
+    }
+
+    impl tower_service::Service<http::Request<Body>> for Echo {
+        type Response = http::Response<Body>;
+        type Error = Infallible;
+        type Future = BoxFuture<Self::Response, Self::Error>;
+
+        fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, request: http::Request<Body>) -> Self::Future {
+            let echo = self.clone();
+            let codec = DynamicCodec {
+                decodes: self.pool.get_message_by_name("test.SayRequest").unwrap(),
+            };
+            Box::pin(async move {
+                let mut grpc = tonic::server::Grpc::new(codec);
+                Ok(match request.uri().path() {
+                    "/test.Echo/Say" => grpc.unary(Say(echo), request).await,
+                    "/test.Echo/SayMany" => grpc.server_streaming(SayMany(echo), request).await,
+                    _ => Status::unimplemented("").into_http(),
+                })
+            })
+        }
+    }
+
+    /// Serves `Echo` with reflection, returning its address.
+    async fn serve(
+        pool: DescriptorPool,
+        files: Vec<protobuf::descriptor::FileDescriptorProto>,
+    ) -> String {
+        let mut set = protobuf::descriptor::FileDescriptorSet::new();
+        set.file = files;
+        let reflection = tonic_reflection::server::Builder::configure()
+            .register_encoded_file_descriptor_set(&set.write_to_bytes().unwrap())
+            .build_v1()
+            .unwrap();
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        let incoming = futures_util::stream::unfold(listener, |listener| async move {
+            let accepted = listener.accept().await.map(|(socket, _)| socket);
+            Some((accepted, listener))
+        });
+        tokio::spawn(
+            tonic::transport::Server::builder()
+                .add_service(Echo { pool })
+                .add_service(reflection)
+                .serve_with_incoming(incoming),
+        );
+        format!("grpc://{}", address)
+    }
+
+    fn call_input(url: &str, method: &str, message: &str) -> GrpcCallInput {
+        GrpcCallInput {
+            url: url.to_string(),
+            service: "test.Echo".to_string(),
+            method: method.to_string(),
+            message: message.to_string(),
+            metadata: vec![KeyValue {
+                key: "x-token".to_string(),
+                value: "abc".to_string(),
+                enabled: true,
+            }],
+            source: GrpcSchemaSource::Reflection,
+            timeout_ms: Some(5000),
+        }
+    }
+
+    #[test]
+    fn test_proto_files() {
+        let (dir, path) = write_proto();
+        let pool = descriptors::from_proto_files(&[path], &[]).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+        let services = descriptors::services(&pool);
+        assert_eq!(services.len(), 1);
+        assert_eq!(services[0].name, "test.Echo");
+        let methods = &services[0].methods;
+        assert_eq!(methods.len(), 3);
+        assert!(!methods[0].server_streaming);
+        assert!(methods[1].server_streaming);
+        assert!(methods[2].client_streaming);
+        assert_eq!(methods[0].input_template["name"], "");
+        assert_eq!(methods[0].input_template["times"], 0);
+
+        let err = parse_message(
+            pool.get_message_by_name("test.SayRequest").unwrap(),
+            r#"{"nope": 1}"#,
+        )
+        .unwrap_err();
+        assert!(err.contains("test.SayRequest"), "{}", err);
+    }
+
+    #[tokio::test]
+    async fn test_calls_with_reflection() {
+        let (dir, path) = write_proto();
+        let parsed = protobuf_parse::Parser::new()
+            .pure()
+            .include(std::path::Path::new(&path).parent().unwrap())
+            .input(&path)
+            .parse_and_typecheck()
+            .unwrap();
+        let pool = descriptors::from_proto_files(&[path], &[]).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+        let url = serve(pool, parsed.file_descriptors).await;
+
+        let services = list_services(&GrpcServicesInput {
+            url: url.clone(),
+            metadata: vec![],
+            source: GrpcSchemaSource::Reflection,
+        })
+        .await
+        .unwrap();
+        assert_eq!(
+            services.iter().map(|s| s.name.as_str()).collect::<Vec<_>>(),
+            vec!["test.Echo"]
+        );
+
+        let (_stop, stopped) = oneshot::channel();
+        let result = call(
+            &call_input(&url, "Say", r#"{"name": "ada"}"#),
+            stopped,
+            |_| {},
+        )
+        .await
+        .unwrap();
+        assert_eq!(result.status_code, 0, "{}", result.status_message);
+        assert_eq!(result.messages.len(), 1);
+        assert_eq!(result.messages[0]["text"], "hi ada #1");
+        assert!(result
+            .headers
+            .iter()
+            .any(|h| h.key == "x-echo" && h.value == "yes"));
+        assert!(result
+            .trailers
+            .iter()
+            .any(|h| h.key == "grpc-status" && h.value == "0"));
+
+        let (_stop, stopped) = oneshot::channel();
+        let mut streamed = 0;
+        let result = call(
+            &call_input(&url, "SayMany", r#"{"name": "ada", "times": 3}"#),
+            stopped,
+            |_| streamed += 1,
+        )
+        .await
+        .unwrap();
+        assert_eq!(streamed, 3);
+        assert_eq!(result.messages[2]["text"], "hi ada #3");
+
+        let (_stop, stopped) = oneshot::channel();
+        let result = call(&call_input(&url, "Say", "{}"), stopped, |_| {})
+            .await
+            .unwrap();
+        assert_eq!(result.status_code, tonic::Code::InvalidArgument as i32);
+        assert_eq!(result.status_name, "InvalidArgument");
+        assert_eq!(result.status_message, "name is required");
+
+        let (_stop, stopped) = oneshot::channel();
+        let err = call(&call_input(&url, "Collect", "{}"), stopped, |_| {})
+            .await
+            .unwrap_err();
+        assert!(err.contains("client-streaming"), "{}", err);
+    }
+}
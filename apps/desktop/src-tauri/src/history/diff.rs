@@ -0,0 +1,333 @@
+use serde_json::Value;
+use similar::{ChangeTag, TextDiff};
+
+use crate::models::diff::{
+    BodyDiff, DiffOptions, HeaderChange, JsonChange, LineChangeKind, ResponseDiff, StatusChange,
+    TextChange,
+};
+use crate::models::execution::ExecutionResult;
+use crate::models::history::HistoryEntry;
+use crate::models::request::KeyValue;
+use crate::redact::{self, glob_match, matches_any};
+
+/// The parts of a response that are compared.
+pub struct ResponseSnapshot {
+    pub status: Option<i32>,
+    pub headers: Vec<KeyValue>,
+    pub body: String,
+}
+
+impl ResponseSnapshot {
+    /// Encrypted values are masked so entries recorded with fresh nonces compare
+    /// equal when the underlying values do.
+    pub fn from_entry(entry: &HistoryEntry) -> Self {
+        let headers = entry
+            .response_headers
+            .as_deref()
+            .and_then(|h| serde_json::from_str::<Vec<KeyValue>>(h).ok())
+            .unwrap_or_default()
+            .into_iter()
+            .map(|h| KeyValue {
+                value: redact::mask_encrypted(&h.value),
+                ..h
+            })
+            .collect();
+        ResponseSnapshot {
+            status: entry.response_status,
+            headers,
+            body: redact::mask_encrypted(entry.response_body.as_deref().unwrap_or_default()),
+        }
+    }
+
+    pub fn from_result(result: &ExecutionResult) -> Self {
+        ResponseSnapshot {
+            status: (result.status > 0).then_some(result.status as i32),
+            headers: result.headers.clone(),
+            body: result.body.clone(),
+        }
+    }
+}
+
+pub fn diff_responses(
+    before: &ResponseSnapshot,
+    after: &ResponseSnapshot,
+    options: &DiffOptions,
+) -> ResponseDiff {
+    let status = (before.status != after.status).then_some(StatusChange {
+        before: before.status,
+        after: after.status,
+    });
+    let headers = diff_headers(&before.headers, &after.headers, &options.ignore_headers);
+    let body = diff_bodies(&before.body, &after.body, &options.ignore_fields);
+    ResponseDiff {
+        identical: status.is_none() && headers.is_empty() && body.is_empty(),
+        status,
+        headers,
+        body,
+    }
+}
+
+fn diff_headers(before: &[KeyValue], after: &[KeyValue], ignore: &[String]) -> Vec<HeaderChange> {
+    let before = merge_headers(before, ignore);
+    let after = merge_headers(after, ignore);
+    let mut changes = Vec::new();
+    for (name, value) in &before {
+        let other = after.iter().find(|(n, _)| n == name).map(|(_, v)| v);
+        if other != Some(value) {
+            changes.push(HeaderChange {
+                name: name.clone(),
+                before: Some(value.clone()),
+                after: other.cloned(),
+            });
+        }
+    }
+    for (name, value) in &after {
+        if !before.iter().any(|(n, _)| n == name) {
+            changes.push(HeaderChange {
+                name: name.clone(),
+                before: None,
+                after: Some(value.clone()),
+            });
+        }
+    }
+    changes
+}
+
+/// Lowercases names and joins repeated headers, keeping first-seen order.
+fn merge_headers(headers: &[KeyValue], ignore: &[String]) -> Vec<(String, String)> {
+    let mut merged: Vec<(String, String)> = Vec::new();
+    for header in headers {
+        let name = header.key.to_lowercase();
+        if matches_any(ignore, &name) {
+            continue;
+        }
+        match merged.iter_mut().find(|(n, _)| *n == name) {
+            Some((_, value)) => {
+                value.push_str(", ");
+                value.push_str(&header.value);
+            }
+            None => merged.push((name, header.value.clone())),
+        }
+    }
+    merged
+}
+
+fn diff_bodies(before: &str, after: &str, ignore_fields: &[String]) -> BodyDiff {
+    match (
+        serde_json::from_str::<Value>(before),
+        serde_json::from_str::<Value>(after),
+    ) {
+        (Ok(before), Ok(after)) => {
+            let mut changes = Vec::new();
+            diff_json("", &before, &after, ignore_fields, &mut changes);
+            BodyDiff::Json { changes }
+        }
+        _ => BodyDiff::Text {
+            changes: diff_lines(before, after),
+        },
+    }
+}
+
+fn diff_json(
+    path: &str,
+    before: &Value,
+    after: &Value,
+    ignore_fields: &[String],
+    changes: &mut Vec<JsonChange>,
+) {
+    match (before, after) {
+        (Value::Object(a), Value::Object(b)) => {
+            for (key, value) in a {
+                let child = format!("{}/{}", path, escape_pointer(key));
+                if is_ignored(&child, key, ignore_fields) {
+                    continue;
+                }
+                match b.get(key) {
+                    Some(other) => diff_json(&child, value, other, ignore_fields, changes),
+                    None => changes.push(JsonChange {
+                        path: child,
+                        before: Some(value.clone()),
+                        after: None,
+                    }),
+                }
+            }
+            for (key, value) in b {
+                let child = format!("{}/{}", path, escape_pointer(key));
+                if !a.contains_key(key) && !is_ignored(&child, key, ignore_fields) {
+                    changes.push(JsonChange {
+                        path: child,
+                        before: None,
+                        after: Some(value.clone()),
+                    });
+                }
+            }
+        }
+        (Value::Array(a), Value::Array(b)) => {
+            for i in 0..a.len().max(b.len()) {
+                let child = format!("{}/{}", path, i);
+                match (a.get(i), b.get(i)) {
+                    (Some(x), Some(y)) => diff_json(&child, x, y, ignore_fields, changes),
+                    (x, y) => changes.push(JsonChange {
+                        path: child,
+                        before: x.cloned(),
+                        after: y.cloned(),
+                    }),
+                }
+            }
+        }
+        _ if before != after => changes.push(JsonChange {
+            path: path.to_string(),
+            before: Some(before.clone()),
+            after: Some(after.clone()),
+        }),
+        _ => {}
+    }
+}
+
+/// Patterns starting with `/` match the whole JSON Pointer path; others match
+/// the field name at any depth.
+fn is_ignored(path: &str, key: &str, ignore_fields: &[String]) -> bool {
+    ignore_fields.iter().any(|pattern| {
+        let pattern = pattern.to_lowercase();
+        if pattern.starts_with('/') {
+            glob_match(&pattern, &path.to_lowercase())
+        } else {
+            glob_match(&pattern, &key.to_lowercase())
+        }
+    })
+}
+
+/// Escapes a key as a JSON Pointer reference token (RFC 6901).
+fn escape_pointer(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
+
+fn diff_lines(before: &str, after: &str) -> Vec<TextChange> {
+    TextDiff::from_lines(before, after)
+        .iter_all_changes()
+        .filter_map(|change| {
+            let (kind, index) = match change.tag() {
+                ChangeTag::Delete => (LineChangeKind::Removed, change.old_index()?),
+                ChangeTag::Insert => (LineChangeKind::Added, change.new_index()?),
+                ChangeTag::Equal => return None,
+            };
+            Some(TextChange {
+                kind,
+                line: index + 1,
+                text: change.value().trim_end_matches(['\r', '\n']).to_string(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn header(key: &str, value: &str) -> KeyValue {
+        KeyValue {
+            key: key.to_string(),
+            value: value.to_string(),
+            enabled: true,
+        }
+    }
+
+    fn snapshot(status: i32, headers: Vec<KeyValue>, body: &str) -> ResponseSnapshot {
+        ResponseSnapshot {
+            status: Some(status),
+            headers,
+            body: body.to_string(),
+        }
+    }
+
+    #[test]
+    fn ignores_volatile_headers_and_fields() {
+        let before = snapshot(
+            200,
+            vec![
+                header("Date", "Mon"),
+                header("Content-Type", "application/json"),
+            ],
+            r#"{"id":1,"meta":{"request_id":"a","updatedAt":"t1"}}"#,
+        );
+        let after = snapshot(
+            200,
+            vec![
+                header("date", "Tue"),
+                header("content-type", "application/json"),
+            ],
+            r#"{"meta":{"updatedAt":"t2","request_id":"b"},"id":1}"#,
+        );
+        let diff = diff_responses(&before, &after, &DiffOptions::default());
+        assert!(diff.identical);
+    }
+
+    #[test]
+    fn reports_json_changes_by_pointer() {
+        let before = snapshot(200, vec![], r#"{"user":{"name":"a","a/b":1},"tags":["x"]}"#);
+        let after = snapshot(
+            201,
+            vec![header("X-New", "1")],
+            r#"{"user":{"name":"b"},"tags":["x","y"],"extra":true}"#,
+        );
+        let options = DiffOptions {
+            ignore_fields: vec![],
+            ignore_headers: vec![],
+        };
+        let diff = diff_responses(&before, &after, &options);
+
+        assert!(!diff.identical);
+        assert_eq!(
+            diff.status,
+            Some(StatusChange {
+                before: Some(200),
+                after: Some(201)
+            })
+        );
+        assert_eq!(diff.headers[0].name, "x-new");
+        let BodyDiff::Json { changes } = diff.body else {
+            panic!("expected a JSON diff");
+        };
+        let paths: Vec<&str> = changes.iter().map(|c| c.path.as_str()).collect();
+        assert_eq!(paths, vec!["/tags/1", "/user/a~1b", "/user/name", "/extra"]);
+        assert_eq!(changes[1].after, None);
+        assert_eq!(changes[2].after, Some(json!("b")));
+
+        let options = DiffOptions {
+            ignore_fields: vec!["/user/*".to_string()],
+            ignore_headers: vec!["x-*".to_string()],
+        };
+        let diff = diff_responses(&before, &after, &options);
+        assert!(diff.headers.is_empty());
+        let BodyDiff::Json { changes } = diff.body else {
+            panic!("expected a JSON diff");
+        };
+        assert_eq!(changes.len(), 2);
+    }
+
+    #[test]
+    fn falls_back_to_line_diff() {
+        let before = snapshot(200, vec![], "one\ntwo\nthree\n");
+        let after = snapshot(200, vec![], "one\n2\nthree\n");
+        let diff = diff_responses(&before, &after, &DiffOptions::default());
+        let BodyDiff::Text { changes } = diff.body else {
+            panic!("expected a text diff");
+        };
+        assert_eq!(
+            changes,
+            vec![
+                TextChange {
+                    kind: LineChangeKind::Removed,
+                    line: 2,
+                    text: "two".to_string()
+                },
+                TextChange {
+                    kind: LineChangeKind::Added,
+                    line: 2,
+                    text: "2".to_string()
+                },
+            ]
+        );
+    }
+}
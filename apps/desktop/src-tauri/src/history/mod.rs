@@ -0,0 +1,579 @@
+pub mod diff;
+pub mod retention;
+
+use std::collections::HashMap;
+
+use crate::codegen;
+use crate::db::Database;
+use crate::models::capture::CaptureToCollectionInput;
+use crate::models::collection::{Collection, CreateCollectionInput, CreateFolderInput};
+use crate::models::example::CreateExampleInput;
+use crate::models::execution::ExecutionResult;
+use crate::models::history::{HistoryEntry, SaveHistoryInput};
+use crate::models::request::{
+    ApiRequest, CreateRequestInput, ExecuteRequestInput, KeyValue, RequestKind,
+};
+use crate::redact::{self, Redactor, MASK};
+
+/// Records an execution with credentials redacted. Nothing is saved if redaction
+/// fails, so secrets never reach the table in plaintext.
+pub fn record(
+    db: &Database,
+    workspace_id: &str,
+    request_id: Option<&str>,
+    input: &ExecuteRequestInput,
+    result: &ExecutionResult,
+) -> Result<HistoryEntry, String> {
+    let redactor = Redactor::new(
+        &db.get_history_redaction(workspace_id)?,
+        db.get_secret_values(workspace_id)?,
+    );
+    let snapshot = serde_json::to_string(&redactor.request(input)?)
+        .map_err(|e| format!("Failed to serialize request snapshot: {}", e))?;
+    let response_headers = serde_json::to_string(&redactor.headers(&result.headers)?)
+        .map_err(|e| format!("Failed to serialize response headers: {}", e))?;
+    let response_body = redactor.response_body(&result.body)?;
+    db.save_history_entry(
+        workspace_id,
+        request_id,
+        input.method.as_str(),
+        &redactor.url(&input.url)?,
+        &snapshot,
+        if result.status > 0 {
+            Some(result.status as i32)
+        } else {
+            None
+        },
+        Some(&response_headers),
+        Some(&response_body),
+        Some(result.size_bytes as i64),
+        Some(result.timing.total_ms as i64),
+        result.error.as_deref(),
+    )
+}
+
+/// Rebuilds the request a history entry recorded. Encrypted values are decrypted.
+/// Masked headers and query parameters were never stored, so they are filled in
+/// from the saved request using the current environment.
+pub fn replay_input(db: &Database, entry: &HistoryEntry) -> Result<ExecuteRequestInput, String> {
+    let snapshot = redact::reveal(&entry.request_snapshot)?;
+    let mut input = parse_snapshot(&snapshot)?;
+    if snapshot.contains(MASK) {
+        if let Some(request) = saved_request(db, entry)? {
+            let variables = db.get_resolved_variables(&entry.workspace_id)?;
+            let resolve = |value: &str| codegen::resolve_string(value, &variables);
+            restore_masked(&mut input.headers, &request.headers, resolve);
+            restore_masked(&mut input.query_params, &request.query_params, resolve);
+        }
+    }
+    let restored =
+        serde_json::to_string(&input).map_err(|e| format!("Failed to serialize request: {}", e))?;
+    if restored.contains(MASK) {
+        return Err(
+            "This entry was recorded with masked credentials that can't be restored. \
+             Switch history redaction to encrypt mode to replay requests like it."
+                .to_string(),
+        );
+    }
+    Ok(input)
+}
+
+/// Creates a saved request from a history entry, optionally with the recorded
+/// response as its first example. Redacted values are never decrypted here;
+/// those that can't be restored as templates are kept as the mask so they stand
+/// out as needing to be filled in.
+pub fn save_to_collection(db: &Database, input: &SaveHistoryInput) -> Result<ApiRequest, String> {
+    let entry = db
+        .get_history_entry(&input.history_id)?
+        .ok_or_else(|| format!("History entry not found: {}", input.history_id))?;
+    if input.save_response && entry.response_status.is_none() {
+        return Err("History entry has no response to save as an example".to_string());
+    }
+    if let Some(folder_id) = &input.folder_id {
+        let folder = db
+            .get_folder(folder_id)?
+            .ok_or_else(|| format!("Folder not found: {}", folder_id))?;
+        if folder.collection_id != input.collection_id {
+            return Err("Folder belongs to a different collection".to_string());
+        }
+    }
+    let snapshot = snapshot_input(db, &entry)?;
+
+    let tx = db
+        .conn
+        .unchecked_transaction()
+        .map_err(|e| format!("Begin transaction: {}", e))?;
+    let name = input.name.clone().filter(|n| !n.trim().is_empty());
+    let request = create_request(
+        db,
+        &snapshot,
+        name,
+        &input.collection_id,
+        input.folder_id.clone(),
+    )?;
+    if input.save_response {
+        save_example(db, &request.id, &entry, snapshot)?;
+    }
+    tx.commit()
+        .map_err(|e| format!("Commit transaction: {}", e))?;
+    Ok(request)
+}
+
+/// Creates a collection from a capture session, with a folder per host. Repeated
+/// calls to the same method and path become one request, and each distinct
+/// response status it got is kept as an example.
+pub fn capture_to_collection(
+    db: &Database,
+    input: &CaptureToCollectionInput,
+) -> Result<Collection, String> {
+    let session = db
+        .get_capture_session(&input.session_id)?
+        .ok_or_else(|| format!("Capture session not found: {}", input.session_id))?;
+    let entries = db.list_capture_entries(&session.id)?;
+    if entries.is_empty() {
+        return Err("Capture session has no entries".to_string());
+    }
+
+    let tx = db
+        .conn
+        .unchecked_transaction()
+        .map_err(|e| format!("Begin transaction: {}", e))?;
+    let collection = db.create_collection(CreateCollectionInput {
+        workspace_id: session.workspace_id.clone(),
+        name: input
+            .name
+            .clone()
+            .filter(|n| !n.trim().is_empty())
+            .unwrap_or_else(|| session.name.clone()),
+        description: Some(format!("Captured by the proxy on {}", session.started_at)),
+    })?;
+    let mut folders: HashMap<String, String> = HashMap::new();
+    // (method, host, path) -> (request id, statuses saved as examples)
+    let mut requests: HashMap<(String, String, String), (String, Vec<i32>)> = HashMap::new();
+    for entry in &entries {
+        let snapshot = snapshot_input(db, entry)?;
+        let (host, path) = match reqwest::Url::parse(&snapshot.url) {
+            Ok(url) => (
+                url.host_str().unwrap_or("unknown").to_string(),
+                url.path().to_string(),
+            ),
+            Err(_) => ("unknown".to_string(), snapshot.url.clone()),
+        };
+        let key = (snapshot.method.as_str().to_string(), host.clone(), path);
+        if !requests.contains_key(&key) {
+            let folder_id = match folders.get(&host) {
+                Some(id) => id.clone(),
+                None => {
+                    let folder = db.create_folder(CreateFolderInput {
+                        collection_id: collection.id.clone(),
+                        parent_folder_id: None,
+                        name: host.clone(),
+                        description: None,
+                    })?;
+                    folders.insert(host, folder.id.clone());
+                    folder.id
+                }
+            };
+            let request = create_request(db, &snapshot, None, &collection.id, Some(folder_id))?;
+            requests.insert(key.clone(), (request.id, Vec::new()));
+        }
+        let Some((request_id, statuses)) = requests.get_mut(&key) else {
+            continue;
+        };
+        if let Some(status) = entry.response_status {
+            if input.save_responses && !statuses.contains(&status) {
+                save_example(db, request_id, entry, snapshot)?;
+                statuses.push(status);
+            }
+        }
+    }
+    tx.commit()
+        .map_err(|e| format!("Commit transaction: {}", e))?;
+    Ok(collection)
+}
+
+fn create_request(
+    db: &Database,
+    snapshot: &ExecuteRequestInput,
+    name: Option<String>,
+    collection_id: &str,
+    folder_id: Option<String>,
+) -> Result<ApiRequest, String> {
+    db.create_request(CreateRequestInput {
+        name: name.unwrap_or_else(|| default_name(snapshot)),
+        method: snapshot.method.clone(),
+        url: snapshot.url.clone(),
+        headers: snapshot.headers.clone(),
+        query_params: snapshot.query_params.clone(),
+        body_type: snapshot.body_type.clone(),
+        body_content: snapshot.body_content.clone(),
+        description: None,
+        path_params: snapshot.path_params.clone(),
+        scripts: None,
+        kind: RequestKind::Http,
+        collection_id: Some(collection_id.to_string()),
+        folder_id,
+    })
+}
+
+/// Adds the entry's response as an example named after its status code.
+fn save_example(
+    db: &Database,
+    request_id: &str,
+    entry: &HistoryEntry,
+    snapshot: ExecuteRequestInput,
+) -> Result<(), String> {
+    let headers = match entry.response_headers.as_deref() {
+        Some(headers) => serde_json::from_str(&redact::mask_encrypted(headers))
+            .map_err(|e| format!("Failed to read response headers: {}", e))?,
+        None => Vec::new(),
+    };
+    db.create_example(CreateExampleInput {
+        request_id: request_id.to_string(),
+        name: entry
+            .response_status
+            .map(|s| s.to_string())
+            .unwrap_or_default(),
+        status: entry.response_status,
+        headers,
+        body: entry.response_body.as_deref().map(redact::mask_encrypted),
+        original_request: Some(snapshot),
+    })?;
+    Ok(())
+}
+
+/// The recorded request for saving into a collection. Encrypted values are
+/// masked rather than decrypted, then masked values are replaced with the saved
+/// request's own templates where it still has them, so resolved tokens never
+/// reach collections, examples or mirrored directories.
+fn snapshot_input(db: &Database, entry: &HistoryEntry) -> Result<ExecuteRequestInput, String> {
+    let snapshot = redact::mask_encrypted(&entry.request_snapshot);
+    let mut input = parse_snapshot(&snapshot)?;
+    if snapshot.contains(MASK) {
+        if let Some(request) = saved_request(db, entry)? {
+            let template = |value: &str| value.to_string();
+            restore_masked(&mut input.headers, &request.headers, template);
+            restore_masked(&mut input.query_params, &request.query_params, template);
+            if input.url.contains(MASK) {
+                input.url = request.url;
+            }
+            if input
+                .body_content
+                .as_deref()
+                .is_some_and(|b| b.contains(MASK))
+            {
+                input.body_content = request.body_content;
+            }
+        }
+    }
+    Ok(input)
+}
+
+fn parse_snapshot(snapshot: &str) -> Result<ExecuteRequestInput, String> {
+    serde_json::from_str(snapshot).map_err(|e| format!("Failed to read request snapshot: {}", e))
+}
+
+fn saved_request(db: &Database, entry: &HistoryEntry) -> Result<Option<ApiRequest>, String> {
+    match entry.request_id.as_deref() {
+        Some(id) => db.get_request(id),
+        None => Ok(None),
+    }
+}
+
+/// `GET /users/42`, or the full URL if it doesn't parse.
+fn default_name(input: &ExecuteRequestInput) -> String {
+    let path = reqwest::Url::parse(&input.url)
+        .map(|url| url.path().to_string())
+        .unwrap_or_else(|_| input.url.clone());
+    format!("{} {}", input.method.as_str(), path)
+}
+
+/// Fills masked values from the saved request's value for the same key, passed
+/// through `fill`.
+fn restore_masked(values: &mut [KeyValue], saved: &[KeyValue], fill: impl Fn(&str) -> String) {
+    for value in values.iter_mut().filter(|v| v.value == MASK) {
+        if let Some(original) = saved
+            .iter()
+            .find(|s| s.enabled && s.key.eq_ignore_ascii_case(&value.key))
+        {
+            value.value = fill(&original.value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::execution::ExecutionTiming;
+    use crate::models::history::{HistoryRedaction, RedactionMode};
+    use crate::models::request::{BodyType, HttpMethod};
+    use rusqlite::Connection;
+
+    fn setup_test_db() -> (Database, String) {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch("PRAGMA foreign_keys=ON;").unwrap();
+        let db = Database { conn };
+        db.run_migrations().unwrap();
+        let workspace = db.get_current_workspace().unwrap();
+        (db, workspace.id)
+    }
+
+    fn header(key: &str, value: &str) -> KeyValue {
+        KeyValue {
+            key: key.to_string(),
+            value: value.to_string(),
+            enabled: true,
+        }
+    }
+
+    fn execution() -> ExecutionResult {
+        ExecutionResult {
+            status: 200,
+            status_text: "OK".to_string(),
+            headers: vec![header("Set-Cookie", "sid=1")],
+            body: "{\"ok\":true}".to_string(),
+            size_bytes: 11,
+            timing: ExecutionTiming {
+                dns_ms: None,
+                connect_ms: None,
+                tls_ms: None,
+                first_byte_ms: 1.0,
+                total_ms: 2.0,
+            },
+            error: None,
+        }
+    }
+
+    fn input(request_header: KeyValue) -> ExecuteRequestInput {
+        ExecuteRequestInput {
+            method: HttpMethod::GET,
+            url: "https://api.test/me".to_string(),
+            headers: vec![request_header, header("Accept", "application/json")],
+            query_params: vec![],
+            path_params: vec![],
+            body_type: BodyType::None,
+            body_content: None,
+        }
+    }
+
+    #[test]
+    fn replays_encrypted_entries_exactly() {
+        let (db, wid) = setup_test_db();
+        db.set_history_redaction(&HistoryRedaction {
+            mode: RedactionMode::Encrypt,
+            ..HistoryRedaction::default_for(&wid)
+        })
+        .unwrap();
+        let sent = input(header("Authorization", "Bearer abc"));
+        let entry = record(&db, &wid, None, &sent, &execution()).unwrap();
+        assert!(!entry.request_snapshot.contains("Bearer abc"));
+
+        let replay = replay_input(&db, &entry).unwrap();
+        assert_eq!(replay.headers[0].value, "Bearer abc");
+        assert_eq!(replay.url, sent.url);
+    }
+
+    #[test]
+    fn restores_masked_headers_from_saved_request() {
+        let (db, wid) = setup_test_db();
+        let collection = db
+            .create_collection(CreateCollectionInput {
+                workspace_id: wid.clone(),
+                name: "API".to_string(),
+                description: None,
+            })
+            .unwrap();
+        let request = db
+            .create_request(CreateRequestInput {
+                name: "Me".to_string(),
+                method: HttpMethod::GET,
+                url: "https://api.test/me".to_string(),
+                headers: vec![header("Authorization", "Bearer static-token")],
+                query_params: vec![],
+                body_type: BodyType::None,
+                body_content: None,
+                description: None,
+                path_params: vec![],
+                scripts: None,
+                kind: RequestKind::Http,
+                collection_id: Some(collection.id),
+                folder_id: None,
+            })
+            .unwrap();
+
+        let sent = input(header("Authorization", "Bearer static-token"));
+        let saved = record(&db, &wid, Some(&request.id), &sent, &execution()).unwrap();
+        assert!(saved.request_snapshot.contains(MASK));
+        let replay = replay_input(&db, &saved).unwrap();
+        assert_eq!(replay.headers[0].value, "Bearer static-token");
+
+        // Without a saved request there is nothing to restore from
+        let adhoc = record(&db, &wid, None, &sent, &execution()).unwrap();
+        let err = replay_input(&db, &adhoc).unwrap_err();
+        assert!(err.contains("masked credentials"));
+    }
+
+    #[test]
+    fn saves_entry_into_collection_with_example() {
+        let (db, wid) = setup_test_db();
+        db.set_history_redaction(&HistoryRedaction {
+            mode: RedactionMode::Encrypt,
+            ..HistoryRedaction::default_for(&wid)
+        })
+        .unwrap();
+        let collection = db
+            .create_collection(CreateCollectionInput {
+                workspace_id: wid.clone(),
+                name: "API".to_string(),
+                description: None,
+            })
+            .unwrap();
+        let sent = input(header("Authorization", "Bearer abc"));
+        let entry = record(&db, &wid, None, &sent, &execution()).unwrap();
+
+        let request = save_to_collection(
+            &db,
+            &SaveHistoryInput {
+                history_id: entry.id.clone(),
+                collection_id: collection.id.clone(),
+                folder_id: None,
+                name: None,
+                save_response: true,
+            },
+        )
+        .unwrap();
+        assert_eq!(request.name, "GET /me");
+        assert_eq!(
+            request.collection_id.as_deref(),
+            Some(collection.id.as_str())
+        );
+        // Encrypted values stay out of collections
+        assert_eq!(request.headers[0].value, MASK);
+
+        let examples = db.list_examples(&request.id).unwrap();
+        assert_eq!(examples.len(), 1);
+        assert_eq!(examples[0].status, Some(200));
+        assert_eq!(examples[0].headers[0].value, MASK);
+        assert_eq!(examples[0].body.as_deref(), Some("{\"ok\":true}"));
+        let original = examples[0].original_request.as_ref().unwrap();
+        assert_eq!(original.url, sent.url);
+        assert_eq!(original.headers[0].value, MASK);
+
+        // Entries from a saved request get its template back instead
+        let templated = db
+            .create_request(CreateRequestInput {
+                name: "Me".to_string(),
+                method: HttpMethod::GET,
+                url: sent.url.clone(),
+                headers: vec![header("Authorization", "Bearer {{token}}")],
+                query_params: vec![],
+                body_type: BodyType::None,
+                body_content: None,
+                description: None,
+                path_params: vec![],
+                scripts: None,
+                kind: RequestKind::Http,
+                collection_id: Some(collection.id.clone()),
+                folder_id: None,
+            })
+            .unwrap();
+        let from_request = record(&db, &wid, Some(&templated.id), &sent, &execution()).unwrap();
+        let copy = save_to_collection(
+            &db,
+            &SaveHistoryInput {
+                history_id: from_request.id,
+                collection_id: collection.id.clone(),
+                folder_id: None,
+                name: None,
+                save_response: true,
+            },
+        )
+        .unwrap();
+        assert_eq!(copy.headers[0].value, "Bearer {{token}}");
+        let example = &db.list_examples(&copy.id).unwrap()[0];
+        assert_eq!(
+            example.original_request.as_ref().unwrap().headers[0].value,
+            "Bearer {{token}}"
+        );
+
+        let other = db
+            .create_collection(CreateCollectionInput {
+                workspace_id: wid,
+                name: "Other".to_string(),
+                description: None,
+            })
+            .unwrap();
+        let folder = db
+            .create_folder(CreateFolderInput {
+                collection_id: other.id,
+                parent_folder_id: None,
+                name: "Users".to_string(),
+                description: None,
+            })
+            .unwrap();
+        let err = save_to_collection(
+            &db,
+            &SaveHistoryInput {
+                history_id: entry.id,
+                collection_id: collection.id,
+                folder_id: Some(folder.id),
+                name: None,
+                save_response: false,
+            },
+        )
+        .unwrap_err();
+        assert!(err.contains("different collection"));
+    }
+
+    #[test]
+    fn converts_capture_session_into_collection() {
+        let (db, wid) = setup_test_db();
+        let session = db.create_capture_session(&wid, "Checkout").unwrap();
+        let unauthorized = ExecutionResult {
+            status: 401,
+            ..execution()
+        };
+        let mut other_host = input(header("Accept", "*/*"));
+        other_host.url = "https://cdn.test/app.js".to_string();
+        for (sent, result) in [
+            (input(header("X-Trace", "1")), execution()),
+            (input(header("X-Trace", "2")), execution()),
+            (input(header("X-Trace", "3")), unauthorized),
+            (other_host, execution()),
+        ] {
+            let entry = record(&db, &wid, None, &sent, &result).unwrap();
+            db.add_capture_entry(&session.id, &entry.id).unwrap();
+        }
+
+        let collection = capture_to_collection(
+            &db,
+            &CaptureToCollectionInput {
+                session_id: session.id,
+                name: None,
+                save_responses: true,
+            },
+        )
+        .unwrap();
+        assert_eq!(collection.name, "Checkout");
+
+        let tree = db.get_collection_tree(&collection.id).unwrap();
+        let mut hosts: Vec<&str> = tree.root_folders.iter().map(|f| f.folder.name.as_str()).collect();
+        hosts.sort();
+        assert_eq!(hosts, ["api.test", "cdn.test"]);
+        let api = tree
+            .root_folders
+            .iter()
+            .find(|f| f.folder.name == "api.test")
+            .unwrap();
+        assert_eq!(api.requests.len(), 1);
+        assert_eq!(api.requests[0].name, "GET /me");
+        assert_eq!(api.requests[0].headers[0].value, "1");
+        let statuses: Vec<Option<i32>> = db
+            .list_examples(&api.requests[0].id)
+            .unwrap()
+            .iter()
+            .map(|e| e.status)
+            .collect();
+        assert_eq!(statuses, [Some(200), Some(401)]);
+    }
+}
@@ -0,0 +1,153 @@
+use std::collections::HashSet;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::db::Database;
+
+/// Bodies compacted per lock, so commands waiting on the database wait for one
+/// batch rather than the whole history.
+const COMPACTION_BATCH: usize = 50;
+
+/// How long recording must be quiet before a scheduled prune runs.
+const PRUNE_QUIET: Duration = Duration::from_millis(500);
+
+/// The longest a prune waits while history keeps being recorded, such as during
+/// a busy capture.
+const PRUNE_MAX_DELAY: Duration = Duration::from_secs(5);
+
+/// Applies retention and compaction to every workspace, taking the lock for each
+/// prune and each batch of bodies instead of for the whole pass. Returns the
+/// number of entries removed.
+pub fn prune_all(db: &Mutex<Database>) -> Result<usize, String> {
+    let lock = || db.lock().map_err(|e| format!("Lock error: {}", e));
+    let workspace_ids = lock()?.list_workspace_ids()?;
+    let mut removed = 0;
+    for workspace_id in workspace_ids {
+        removed += lock()?.prune_history(&workspace_id)?;
+        loop {
+            let compacted = lock()?.compact_history_bodies(&workspace_id, COMPACTION_BATCH)?;
+            if compacted < COMPACTION_BATCH {
+                break;
+            }
+        }
+    }
+    Ok(removed)
+}
+
+/// Prunes history in the background after it's recorded. Recording in quick
+/// succession is pruned once per workspace when it settles.
+pub struct HistoryPruner {
+    scheduled: mpsc::Sender<String>,
+}
+
+impl HistoryPruner {
+    /// Starts the pruning thread. `prune` is called with each workspace that had
+    /// history recorded; it stops when the pruner is dropped.
+    pub fn start<F>(prune: F) -> Self
+    where
+        F: Fn(&str) + Send + 'static,
+    {
+        let (scheduled, pending) = mpsc::channel::<String>();
+        std::thread::spawn(move || {
+            while let Ok(first) = pending.recv() {
+                let mut workspaces = HashSet::from([first]);
+                let deadline = Instant::now() + PRUNE_MAX_DELAY;
+                while let Some(left) = deadline.checked_duration_since(Instant::now()) {
+                    match pending.recv_timeout(PRUNE_QUIET.min(left)) {
+                        Ok(workspace_id) => {
+                            workspaces.insert(workspace_id);
+                        }
+                        Err(RecvTimeoutError::Timeout | RecvTimeoutError::Disconnected) => break,
+                    }
+                }
+                for workspace_id in &workspaces {
+                    prune(workspace_id);
+                }
+            }
+        });
+        HistoryPruner { scheduled }
+    }
+
+    pub fn schedule(&self, workspace_id: &str) {
+        let _ = self.scheduled.send(workspace_id.to_string());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::history::HistoryRetention;
+    use rusqlite::Connection;
+
+    #[test]
+    fn prunes_each_workspace_once_after_recording_settles() {
+        let (pruned, calls) = mpsc::channel();
+        let pruner = HistoryPruner::start(move |workspace_id| {
+            let _ = pruned.send(workspace_id.to_string());
+        });
+        for workspace_id in ["a", "b", "a", "a"] {
+            pruner.schedule(workspace_id);
+        }
+
+        let mut seen = vec![
+            calls.recv_timeout(Duration::from_secs(5)).unwrap(),
+            calls.recv_timeout(Duration::from_secs(5)).unwrap(),
+        ];
+        seen.sort();
+        assert_eq!(seen, ["a", "b"]);
+        assert!(calls.recv_timeout(PRUNE_QUIET * 2).is_err());
+    }
+
+    #[test]
+    fn compacts_in_batches_between_locks() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch("PRAGMA foreign_keys=ON;").unwrap();
+        let db = Database { conn };
+        db.run_migrations().unwrap();
+        let wid = db.get_current_workspace().unwrap().id;
+        let body = "x".repeat(2048);
+        for _ in 0..COMPACTION_BATCH + 5 {
+            db.save_history_entry(
+                &wid,
+                None,
+                "GET",
+                "https://a.test",
+                "{}",
+                Some(200),
+                None,
+                Some(&body),
+                None,
+                None,
+                None,
+            )
+            .unwrap();
+        }
+        db.set_history_retention(&HistoryRetention {
+            large_body_bytes: 1024,
+            ..HistoryRetention::default_for(&wid)
+        })
+        .unwrap();
+
+        assert_eq!(
+            db.compact_history_bodies(&wid, COMPACTION_BATCH).unwrap(),
+            COMPACTION_BATCH
+        );
+        let db = Mutex::new(db);
+        assert_eq!(prune_all(&db).unwrap(), 0);
+        let db = db.into_inner().unwrap();
+        assert_eq!(
+            db.compact_history_bodies(&wid, COMPACTION_BATCH).unwrap(),
+            0
+        );
+        let compressed: i64 = db
+            .conn
+            .query_row(
+                "SELECT COUNT(*) FROM history WHERE response_body_zstd IS NOT NULL",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(compressed, COMPACTION_BATCH as i64 + 5);
+    }
+}
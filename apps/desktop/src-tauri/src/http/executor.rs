@@ -25,7 +25,7 @@ struct GraphQLBody {
     operation_name: Option<String>,
 }
 
-fn build_graphql_body(content: &str) -> String {
+pub(crate) fn build_graphql_body(content: &str) -> String {
     let input: GraphQLInput = match serde_json::from_str(content) {
         Ok(i) => i,
         Err(_) => return content.to_string(),
@@ -78,84 +78,7 @@ pub async fn execute(input: &ExecuteRequestInput) -> ExecutionResult {
         }
     };
 
-    let method = match input.method.as_str() {
-        "GET" => Method::GET,
-        "POST" => Method::POST,
-        "PUT" => Method::PUT,
-        "PATCH" => Method::PATCH,
-        "DELETE" => Method::DELETE,
-        "HEAD" => Method::HEAD,
-        "OPTIONS" => Method::OPTIONS,
-        _ => Method::GET,
-    };
-
-    // Build URL with query params
-    let mut url = input.url.clone();
-    let enabled_params: Vec<&KeyValue> = input.query_params.iter().filter(|kv| kv.enabled).collect();
-    if !enabled_params.is_empty() {
-        let query_string: Vec<String> = enabled_params
-            .iter()
-            .map(|kv| {
-                format!(
-                    "{}={}",
-                    urlencoding_encode(&kv.key),
-                    urlencoding_encode(&kv.value)
-                )
-            })
-            .collect();
-        let separator = if url.contains('?') { "&" } else { "?" };
-        url = format!("{}{}{}", url, separator, query_string.join("&"));
-    }
-
-    // Build headers
-    let mut header_map = HeaderMap::new();
-    for kv in &input.headers {
-        if !kv.enabled {
-            continue;
-        }
-        if let (Ok(name), Ok(value)) = (
-            HeaderName::from_bytes(kv.key.as_bytes()),
-            HeaderValue::from_str(&kv.value),
-        ) {
-            header_map.insert(name, value);
-        }
-    }
-
-    let mut request_builder = client.request(method, &url).headers(header_map);
-
-    // Add body
-    match &input.body_type {
-        BodyType::Json => {
-            if let Some(ref content) = input.body_content {
-                request_builder = request_builder
-                    .header("Content-Type", "application/json")
-                    .body(content.clone());
-            }
-        }
-        BodyType::Text => {
-            if let Some(ref content) = input.body_content {
-                request_builder = request_builder
-                    .header("Content-Type", "text/plain")
-                    .body(content.clone());
-            }
-        }
-        BodyType::FormUrlEncoded => {
-            if let Some(ref content) = input.body_content {
-                request_builder = request_builder
-                    .header("Content-Type", "application/x-www-form-urlencoded")
-                    .body(content.clone());
-            }
-        }
-        BodyType::GraphQL => {
-            if let Some(ref content) = input.body_content {
-                let graphql_body = build_graphql_body(content);
-                request_builder = request_builder
-                    .header("Content-Type", "application/json")
-                    .body(graphql_body);
-            }
-        }
-        _ => {}
-    }
+    let request_builder = build_request(&client, input);
 
     let start = Instant::now();
 
@@ -250,7 +173,117 @@ pub async fn execute(input: &ExecuteRequestInput) -> ExecutionResult {
     }
 }
 
-fn urlencoding_encode(s: &str) -> String {
+/// The request `input` describes: path and query parameters applied, enabled
+/// headers set and the body encoded for its type.
+pub(crate) fn build_request(
+    client: &reqwest::Client,
+    input: &ExecuteRequestInput,
+) -> reqwest::RequestBuilder {
+    let method = match input.method.as_str() {
+        "GET" => Method::GET,
+        "POST" => Method::POST,
+        "PUT" => Method::PUT,
+        "PATCH" => Method::PATCH,
+        "DELETE" => Method::DELETE,
+        "HEAD" => Method::HEAD,
+        "OPTIONS" => Method::OPTIONS,
+        _ => Method::GET,
+    };
+
+    // Build URL with path and query params
+    let mut url = apply_path_params(&input.url, &input.path_params);
+    let enabled_params: Vec<&KeyValue> = input.query_params.iter().filter(|kv| kv.enabled).collect();
+    if !enabled_params.is_empty() {
+        let query_string: Vec<String> = enabled_params
+            .iter()
+            .map(|kv| {
+                format!(
+                    "{}={}",
+                    urlencoding_encode(&kv.key),
+                    urlencoding_encode(&kv.value)
+                )
+            })
+            .collect();
+        let separator = if url.contains('?') { "&" } else { "?" };
+        url = format!("{}{}{}", url, separator, query_string.join("&"));
+    }
+
+    // Build headers
+    let mut header_map = HeaderMap::new();
+    for kv in &input.headers {
+        if !kv.enabled {
+            continue;
+        }
+        if let (Ok(name), Ok(value)) = (
+            HeaderName::from_bytes(kv.key.as_bytes()),
+            HeaderValue::from_str(&kv.value),
+        ) {
+            header_map.insert(name, value);
+        }
+    }
+
+    let mut request_builder = client.request(method, &url).headers(header_map);
+
+    // Add body
+    match &input.body_type {
+        BodyType::Json => {
+            if let Some(ref content) = input.body_content {
+                request_builder = request_builder
+                    .header("Content-Type", "application/json")
+                    .body(content.clone());
+            }
+        }
+        BodyType::Text => {
+            if let Some(ref content) = input.body_content {
+                request_builder = request_builder
+                    .header("Content-Type", "text/plain")
+                    .body(content.clone());
+            }
+        }
+        BodyType::FormUrlEncoded => {
+            if let Some(ref content) = input.body_content {
+                request_builder = request_builder
+                    .header("Content-Type", "application/x-www-form-urlencoded")
+                    .body(content.clone());
+            }
+        }
+        BodyType::GraphQL => {
+            if let Some(ref content) = input.body_content {
+                let graphql_body = build_graphql_body(content);
+                request_builder = request_builder
+                    .header("Content-Type", "application/json")
+                    .body(graphql_body);
+            }
+        }
+        _ => {}
+    }
+
+    request_builder
+}
+
+/// Replaces `:name` path segments with the matching enabled path param value.
+fn apply_path_params(url: &str, params: &[KeyValue]) -> String {
+    if params.iter().all(|p| !p.enabled) {
+        return url.to_string();
+    }
+    let (base, rest) = match url.find(['?', '#']) {
+        Some(i) => url.split_at(i),
+        None => (url, ""),
+    };
+    let segments: Vec<String> = base
+        .split('/')
+        .map(|segment| {
+            segment
+                .strip_prefix(':')
+                .and_then(|name| params.iter().find(|p| p.enabled && p.key == name))
+                .map(|p| urlencoding_encode(&p.value))
+                .unwrap_or_else(|| segment.to_string())
+        })
+        .collect();
+    format!("{}{}", segments.join("/"), rest)
+}
+
+pub(crate) fn urlencoding_encode(s: &str) -> String {
     let mut result = String::new();
     for byte in s.bytes() {
         match byte {
@@ -267,7 +300,8 @@ fn urlencoding_encode(s: &str) -> String {
 
 #[cfg(test)]
 mod tests {
-    use super::{build_graphql_body, urlencoding_encode};
+    use super::{apply_path_params, build_graphql_body, urlencoding_encode};
+    use crate::models::request::KeyValue;
 
     #[test]
     fn encode_no_special_chars() {
@@ -326,4 +360,25 @@ mod tests {
         let result = build_graphql_body(input);
         assert_eq!(result, "not valid json");
     }
+
+    #[test]
+    fn path_params_replace_segments() {
+        let params = vec![
+            KeyValue {
+                key: "id".to_string(),
+                value: "a b".to_string(),
+                enabled: true,
+            },
+            KeyValue {
+                key: "tab".to_string(),
+                value: "x".to_string(),
+                enabled: false,
+            },
+        ];
+        assert_eq!(
+            apply_path_params("https://h.test:8080/users/:id/:tab?q=:id", &params),
+            "https://h.test:8080/users/a%20b/:tab?q=:id"
+        );
+        assert_eq!(apply_path_params("https://h.test/x", &[]), "https://h.test/x");
+    }
 }
@@ -0,0 +1,293 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Instant;
+
+use eventsource_stream::Eventsource;
+use futures_util::StreamExt;
+use tokio::sync::oneshot;
+
+use super::executor::build_request;
+use crate::models::execution::{ExecutionResult, ExecutionTiming, SseEvent};
+use crate::models::request::{ExecuteRequestInput, KeyValue};
+
+/// Streams that are still being read, by the id the frontend gave them.
+/// Removing a stream's entry stops it.
+#[derive(Default)]
+pub struct EventStreams {
+    streams: Mutex<HashMap<String, oneshot::Sender<()>>>,
+}
+
+impl EventStreams {
+    /// Reserves `stream_id`, returning the receiver `execute_stream` stops on.
+    pub fn register(&self, stream_id: &str) -> Result<oneshot::Receiver<()>, String> {
+        let mut streams = self
+            .streams
+            .lock()
+            .map_err(|e| format!("Lock error: {}", e))?;
+        if streams.contains_key(stream_id) {
+            return Err(format!("Stream {} is already running", stream_id));
+        }
+        let (stop, stopped) = oneshot::channel();
+        streams.insert(stream_id.to_string(), stop);
+        Ok(stopped)
+    }
+
+    /// Stops the stream. Streams that already ended are ignored.
+    pub fn stop(&self, stream_id: &str) -> Result<(), String> {
+        let mut streams = self
+            .streams
+            .lock()
+            .map_err(|e| format!("Lock error: {}", e))?;
+        if let Some(stop) = streams.remove(stream_id) {
+            let _ = stop.send(());
+        }
+        Ok(())
+    }
+}
+
+/// Sends the request and reads a `text/event-stream` response event by event,
+/// passing each to `on_event` as it arrives. Reading ends when the server
+/// closes the stream or `stop` fires; there's no overall timeout. The result's
+/// body is the JSON list of every event received. Other responses are read
+/// whole, as `execute` would.
+pub async fn execute_stream<F>(
+    input: &ExecuteRequestInput,
+    mut stop: oneshot::Receiver<()>,
+    mut on_event: F,
+) -> ExecutionResult
+where
+    F: FnMut(SseEvent),
+{
+    let client = match reqwest::Client::builder()
+        .connect_timeout(std::time::Duration::from_secs(30))
+        .redirect(reqwest::redirect::Policy::limited(10))
+        .build()
+    {
+        Ok(c) => c,
+        Err(e) => return failed(format!("Failed to create HTTP client: {}", e), 0.0),
+    };
+
+    let mut request_builder = build_request(&client, input);
+    if !input
+        .headers
+        .iter()
+        .any(|kv| kv.enabled && kv.key.eq_ignore_ascii_case("accept"))
+    {
+        request_builder = request_builder.header("Accept", "text/event-stream");
+    }
+
+    let start = Instant::now();
+    let elapsed_ms = || start.elapsed().as_secs_f64() * 1000.0;
+    let response = tokio::select! {
+        sent = request_builder.send() => match sent {
+            Ok(response) => response,
+            Err(e) if e.is_connect() => {
+                return failed(format!("Connection failed: {}", e), elapsed_ms())
+            }
+            Err(e) => return failed(format!("Request failed: {}", e), elapsed_ms()),
+        },
+        _ = &mut stop => return failed("Request stopped".to_string(), elapsed_ms()),
+    };
+
+    let first_byte_ms = elapsed_ms();
+    let status = response.status();
+    let headers: Vec<KeyValue> = response
+        .headers()
+        .iter()
+        .map(|(name, value)| KeyValue {
+            key: name.to_string(),
+            value: value.to_str().unwrap_or("").to_string(),
+            enabled: true,
+        })
+        .collect();
+    let is_event_stream = response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.trim_start().starts_with("text/event-stream"));
+
+    let size_bytes = AtomicU64::new(0);
+    let mut events = Vec::new();
+    let (body, error) = if is_event_stream {
+        let mut stream = response
+            .bytes_stream()
+            .inspect(|chunk| {
+                if let Ok(chunk) = chunk {
+                    size_bytes.fetch_add(chunk.len() as u64, Ordering::Relaxed);
+                }
+            })
+            .eventsource();
+        let error = loop {
+            tokio::select! {
+                next = stream.next() => match next {
+                    Some(Ok(event)) => {
+                        let event = SseEvent {
+                            id: Some(event.id).filter(|id| !id.is_empty()),
+                            event: event.event,
+                            data: event.data,
+                            retry_ms: event.retry.map(|retry| retry.as_millis() as u64),
+                            received_ms: elapsed_ms(),
+                        };
+                        on_event(event.clone());
+                        events.push(event);
+                    }
+                    Some(Err(e)) => break Some(format!("Failed to read event stream: {}", e)),
+                    None => break None,
+                },
+                _ = &mut stop => break None,
+            }
+        };
+        let body = serde_json::to_string_pretty(&events)
+            .map_err(|e| format!("Failed to serialize events: {}", e));
+        match body {
+            Ok(body) => (body, error),
+            Err(e) => (String::new(), Some(e)),
+        }
+    } else {
+        tokio::select! {
+            read = response.bytes() => match read {
+                Ok(bytes) => {
+                    size_bytes.store(bytes.len() as u64, Ordering::Relaxed);
+                    (String::from_utf8_lossy(&bytes).to_string(), None)
+                }
+                Err(e) => (String::new(), Some(format!("Failed to read response body: {}", e))),
+            },
+            _ = &mut stop => (String::new(), Some("Request stopped".to_string())),
+        }
+    };
+
+    ExecutionResult {
+        status: status.as_u16(),
+        status_text: status.canonical_reason().unwrap_or("").to_string(),
+        headers,
+        body,
+        size_bytes: size_bytes.into_inner(),
+        timing: ExecutionTiming {
+            dns_ms: None,
+            connect_ms: None,
+            tls_ms: None,
+            first_byte_ms,
+            total_ms: elapsed_ms(),
+        },
+        error,
+    }
+}
+
+fn failed(error: String, total_ms: f64) -> ExecutionResult {
+    ExecutionResult {
+        status: 0,
+        status_text: String::new(),
+        headers: vec![],
+        body: String::new(),
+        size_bytes: 0,
+        timing: ExecutionTiming {
+            dns_ms: None,
+            connect_ms: None,
+            tls_ms: None,
+            first_byte_ms: 0.0,
+            total_ms,
+        },
+        error: Some(error),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::request::{BodyType, HttpMethod};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    /// Answers one request with an event stream made of `chunks`, then keeps
+    /// the connection open when `hold_open` is set.
+    async fn sse_server(chunks: Vec<&'static str>, hold_open: bool) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/events", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut request = [0u8; 1024];
+            let _ = socket.read(&mut request).await.unwrap();
+            socket
+                .write_all(
+                    b"HTTP/1.1 200 OK\r\ncontent-type: text/event-stream\r\nconnection: close\r\n\r\n",
+                )
+                .await
+                .unwrap();
+            for chunk in chunks {
+                socket.write_all(chunk.as_bytes()).await.unwrap();
+                socket.flush().await.unwrap();
+                tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+            }
+            if hold_open {
+                tokio::time::sleep(std::time::Duration::from_secs(30)).await;
+            }
+        });
+        url
+    }
+
+    fn input(url: &str) -> ExecuteRequestInput {
+        ExecuteRequestInput {
+            method: HttpMethod::GET,
+            url: url.to_string(),
+            headers: vec![],
+            query_params: vec![],
+            path_params: vec![],
+            body_type: BodyType::None,
+            body_content: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_stream_until_closed() {
+        let url = sse_server(
+            vec![
+                "retry: 3000\nid: 1\nevent: update\ndata: first\n\n",
+                "data: sec",
+                "ond\ndata: line\n\n: comment\n\n",
+            ],
+            false,
+        )
+        .await;
+        let (_stop, stopped) = oneshot::channel();
+        let mut received = Vec::new();
+        let result = execute_stream(&input(&url), stopped, |event| received.push(event)).await;
+
+        assert!(result.error.is_none(), "{:?}", result.error);
+        assert_eq!(result.status, 200);
+        assert_eq!(received.len(), 2);
+        assert_eq!(received[0].id.as_deref(), Some("1"));
+        assert_eq!(received[0].event, "update");
+        assert_eq!(received[0].retry_ms, Some(3000));
+        assert_eq!(received[1].event, "message");
+        assert_eq!(received[1].data, "second\nline");
+        // The id carries over to events that don't set one
+        assert_eq!(received[1].id.as_deref(), Some("1"));
+
+        let body: Vec<SseEvent> = serde_json::from_str(&result.body).unwrap();
+        assert_eq!(body.len(), 2);
+        assert!(result.size_bytes > 0);
+    }
+
+    #[tokio::test]
+    async fn test_stop_stream() {
+        let url = sse_server(vec!["data: one\n\n"], true).await;
+        let streams = EventStreams::default();
+        let stopped = streams.register("s1").unwrap();
+        assert!(streams.register("s1").is_err());
+
+        let mut count = 0;
+        let result = execute_stream(&input(&url), stopped, |_| {
+            count += 1;
+            streams.stop("s1").unwrap();
+        })
+        .await;
+
+        assert_eq!(count, 1);
+        assert!(result.error.is_none());
+        let body: Vec<SseEvent> = serde_json::from_str(&result.body).unwrap();
+        assert_eq!(body[0].data, "one");
+        // The id is free again once stopped
+        assert!(streams.register("s1").is_ok());
+    }
+}
@@ -0,0 +1,1321 @@
+use std::path::Path;
+
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use serde::Deserialize;
+
+use super::{file_stem, ImportResult};
+use crate::db::Database;
+use crate::models::collection::{CollectionTreeNode, CreateCollectionInput, CreateFolderInput};
+use crate::models::environment::{CreateEnvironmentInput, UpdateEnvironmentInput, Variable};
+use crate::models::request::{
+    ApiRequest, BodyType, CreateRequestInput, HttpMethod, KeyValue, RequestKind, RequestScripts,
+    UpdateRequestInput,
+};
+
+const METHOD_BLOCKS: [&str; 7] = ["get", "post", "put", "patch", "delete", "head", "options"];
+
+/// Blocks whose content is free text rather than `key: value` pairs.
+const TEXT_BLOCKS: [&str; 10] = [
+    "body:json",
+    "body:text",
+    "body:xml",
+    "body:sparql",
+    "body:graphql",
+    "body:graphql:vars",
+    "script:pre-request",
+    "script:post-response",
+    "tests",
+    "docs",
+];
+
+/// A parsed `.bru` file: an ordered list of named blocks.
+#[derive(Debug, Clone, Default)]
+pub struct BruFile {
+    pub blocks: Vec<BruBlock>,
+}
+
+#[derive(Debug, Clone)]
+pub struct BruBlock {
+    pub name: String,
+    pub body: BruBody,
+}
+
+#[derive(Debug, Clone)]
+pub enum BruBody {
+    Pairs(Vec<BruPair>),
+    Text(String),
+    List(Vec<String>),
+}
+
+#[derive(Debug, Clone)]
+pub struct BruPair {
+    pub key: String,
+    pub value: String,
+    pub enabled: bool,
+}
+
+impl BruFile {
+    fn block(&self, name: &str) -> Option<&BruBody> {
+        self.blocks.iter().find(|b| b.name == name).map(|b| &b.body)
+    }
+
+    fn pairs(&self, name: &str) -> &[BruPair] {
+        match self.block(name) {
+            Some(BruBody::Pairs(pairs)) => pairs,
+            _ => &[],
+        }
+    }
+
+    fn text(&self, name: &str) -> Option<&str> {
+        match self.block(name) {
+            Some(BruBody::Text(text)) if !text.trim().is_empty() => Some(text),
+            _ => None,
+        }
+    }
+
+    fn list(&self, name: &str) -> &[String] {
+        match self.block(name) {
+            Some(BruBody::List(items)) => items,
+            _ => &[],
+        }
+    }
+
+    fn value(&self, block: &str, key: &str) -> Option<&str> {
+        self.pairs(block)
+            .iter()
+            .find(|p| p.enabled && p.key == key)
+            .map(|p| p.value.as_str())
+    }
+
+    fn push_pairs(&mut self, name: &str, pairs: Vec<BruPair>) {
+        if !pairs.is_empty() {
+            self.blocks.push(BruBlock {
+                name: name.to_string(),
+                body: BruBody::Pairs(pairs),
+            });
+        }
+    }
+
+    fn push_text(&mut self, name: &str, text: Option<&str>) {
+        if let Some(text) = text.filter(|t| !t.trim().is_empty()) {
+            self.blocks.push(BruBlock {
+                name: name.to_string(),
+                body: BruBody::Text(text.to_string()),
+            });
+        }
+    }
+}
+
+impl BruPair {
+    fn new(key: &str, value: &str) -> Self {
+        BruPair {
+            key: key.to_string(),
+            value: value.to_string(),
+            enabled: true,
+        }
+    }
+}
+
+/// Parses the block syntax of `.bru` files.
+pub fn parse_bru(content: &str) -> Result<BruFile, String> {
+    let mut file = BruFile::default();
+    let mut lines = content.lines();
+
+    while let Some(line) = lines.next() {
+        let header = line.trim_end();
+        if header.trim().is_empty() {
+            continue;
+        }
+
+        let (name, closing) = if let Some(name) = header.strip_suffix(" {") {
+            (name.trim(), "}")
+        } else if let Some(name) = header.strip_suffix(" [") {
+            (name.trim(), "]")
+        } else {
+            return Err(format!("Unexpected line in .bru file: {}", header));
+        };
+
+        let mut content = Vec::new();
+        loop {
+            let line = lines
+                .next()
+                .ok_or_else(|| format!("Unterminated block: {}", name))?;
+            if line.trim_end() == closing {
+                break;
+            }
+            content.push(line);
+        }
+
+        let body = if closing == "]" {
+            BruBody::List(
+                content
+                    .iter()
+                    .flat_map(|l| l.split(','))
+                    .map(str::trim)
+                    .filter(|item| !item.is_empty())
+                    .map(|item| item.to_string())
+                    .collect(),
+            )
+        } else if TEXT_BLOCKS.contains(&name) {
+            let text: Vec<&str> = content
+                .iter()
+                .map(|l| l.strip_prefix("  ").unwrap_or(l))
+                .collect();
+            BruBody::Text(text.join("\n").trim_end().to_string())
+        } else {
+            BruBody::Pairs(content.iter().filter_map(|l| parse_pair(l)).collect())
+        };
+
+        file.blocks.push(BruBlock {
+            name: name.to_string(),
+            body,
+        });
+    }
+
+    Ok(file)
+}
+
+fn parse_pair(line: &str) -> Option<BruPair> {
+    let trimmed = line.trim();
+    if trimmed.is_empty() {
+        return None;
+    }
+    let (key, value) = trimmed.split_once(':').unwrap_or((trimmed, ""));
+    let key = key.trim();
+    let (key, enabled) = match key.strip_prefix('~') {
+        Some(key) => (key, false),
+        None => (key, true),
+    };
+    Some(BruPair {
+        key: key.to_string(),
+        value: value.trim().to_string(),
+        enabled,
+    })
+}
+
+/// Writes blocks back out in `.bru` syntax.
+pub fn write_bru(file: &BruFile) -> String {
+    let mut out = String::new();
+    for (i, block) in file.blocks.iter().enumerate() {
+        if i > 0 {
+            out.push('\n');
+        }
+        match &block.body {
+            BruBody::Pairs(pairs) => {
+                out.push_str(&format!("{} {{\n", block.name));
+                for pair in pairs {
+                    let prefix = if pair.enabled { "" } else { "~" };
+                    out.push_str(&format!("  {}{}: {}\n", prefix, pair.key, pair.value));
+                }
+                out.push_str("}\n");
+            }
+            BruBody::Text(text) => {
+                out.push_str(&format!("{} {{\n", block.name));
+                for line in text.lines() {
+                    if line.is_empty() {
+                        out.push('\n');
+                    } else {
+                        out.push_str(&format!("  {}\n", line));
+                    }
+                }
+                out.push_str("}\n");
+            }
+            BruBody::List(items) => {
+                out.push_str(&format!("{} [\n", block.name));
+                for item in items {
+                    out.push_str(&format!("  {}\n", item));
+                }
+                out.push_str("]\n");
+            }
+        }
+    }
+    out
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct BrunoConfig {
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    ignore: Vec<String>,
+}
+
+/// Auth settings resolved from a request, folder or collection file.
+#[derive(Debug, Clone)]
+struct BrunoAuth {
+    mode: String,
+    params: Vec<BruPair>,
+}
+
+impl BrunoAuth {
+    /// Requests name their mode in the method block; folders and the collection
+    /// use an `auth { mode: ... }` block.
+    fn from_file(file: &BruFile, mode: Option<&str>) -> Option<BrunoAuth> {
+        let mode = mode.or_else(|| file.value("auth", "mode"))?;
+        Some(BrunoAuth {
+            mode: mode.to_string(),
+            params: file.pairs(&format!("auth:{}", mode)).to_vec(),
+        })
+    }
+
+    fn param(&self, key: &str) -> String {
+        self.params
+            .iter()
+            .find(|p| p.key == key)
+            .map(|p| p.value.clone())
+            .unwrap_or_default()
+    }
+}
+
+/// Imports a Bruno collection directory (`bruno.json`, folders, `.bru` request
+/// files and `environments/*.bru`). Nothing is kept if any file fails to import.
+pub fn import_bruno_collection(
+    db: &Database,
+    dir: &Path,
+    workspace_id: &str,
+) -> Result<ImportResult, String> {
+    let config_path = dir.join("bruno.json");
+    let config: BrunoConfig = match std::fs::read_to_string(&config_path) {
+        Ok(json) => {
+            serde_json::from_str(&json).map_err(|e| format!("Invalid bruno.json: {}", e))?
+        }
+        Err(_) => {
+            return Err(format!(
+                "Not a Bruno collection: {} is missing",
+                config_path.display()
+            ))
+        }
+    };
+
+    let collection_name = config
+        .name
+        .clone()
+        .or_else(|| dir.file_name().map(|n| n.to_string_lossy().to_string()))
+        .unwrap_or_else(|| "Bruno Collection".to_string());
+
+    let mut result = ImportResult {
+        collection_name: collection_name.clone(),
+        request_count: 0,
+        folder_count: 0,
+        warnings: vec![],
+    };
+
+    let collection_file = read_bru_if_exists(&dir.join("collection.bru"))?.unwrap_or_default();
+    warn_unsupported_blocks(&collection_file, "collection", &mut result.warnings);
+
+    let tx = db
+        .conn
+        .unchecked_transaction()
+        .map_err(|e| format!("Begin transaction: {}", e))?;
+    let collection = db.create_collection(CreateCollectionInput {
+        workspace_id: workspace_id.to_string(),
+        name: collection_name,
+        description: collection_file.text("docs").map(|d| d.to_string()),
+    })?;
+
+    let mut ignore = config.ignore.clone();
+    ignore.push("environments".to_string());
+    let auth = BrunoAuth::from_file(&collection_file, None);
+    import_dir(
+        db,
+        dir,
+        &collection.id,
+        None,
+        auth.as_ref(),
+        &ignore,
+        &mut result,
+    )?;
+
+    let env_dir = dir.join("environments");
+    if env_dir.is_dir() {
+        for path in sorted_entries(&env_dir)? {
+            if path.extension().is_some_and(|e| e == "bru") {
+                import_environment(db, &path, workspace_id)?;
+            }
+        }
+    }
+    tx.commit()
+        .map_err(|e| format!("Commit transaction: {}", e))?;
+
+    Ok(result)
+}
+
+fn import_dir(
+    db: &Database,
+    dir: &Path,
+    collection_id: &str,
+    folder_id: Option<&str>,
+    inherited_auth: Option<&BrunoAuth>,
+    ignore: &[String],
+    result: &mut ImportResult,
+) -> Result<(), String> {
+    for path in sorted_entries(dir)? {
+        let file_name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        if file_name.starts_with('.') || ignore.contains(&file_name) {
+            continue;
+        }
+
+        // A linked directory could lead back into the collection
+        if path.is_symlink() && path.is_dir() {
+            result
+                .warnings
+                .push(format!("Skipped linked directory '{}'", file_name));
+        } else if path.is_dir() {
+            let folder_file = read_bru_if_exists(&path.join("folder.bru"))?.unwrap_or_default();
+            let name = folder_file
+                .value("meta", "name")
+                .map(|n| n.to_string())
+                .unwrap_or_else(|| file_name.clone());
+            warn_unsupported_blocks(
+                &folder_file,
+                &format!("folder '{}'", name),
+                &mut result.warnings,
+            );
+
+            let folder = db.create_folder(CreateFolderInput {
+                collection_id: collection_id.to_string(),
+                parent_folder_id: folder_id.map(|s| s.to_string()),
+                name,
+                description: folder_file.text("docs").map(|d| d.to_string()),
+            })?;
+            result.folder_count += 1;
+
+            let auth = BrunoAuth::from_file(&folder_file, None)
+                .filter(|a| a.mode != "inherit")
+                .or_else(|| inherited_auth.cloned());
+            // Nested folders only ignore hidden entries
+            import_dir(
+                db,
+                &path,
+                collection_id,
+                Some(&folder.id),
+                auth.as_ref(),
+                &[],
+                result,
+            )?;
+        } else if path.extension().is_some_and(|e| e == "bru")
+            && file_name != "folder.bru"
+            && file_name != "collection.bru"
+        {
+            let content = std::fs::read_to_string(&path)
+                .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+            let file = parse_bru(&content).map_err(|e| format!("{}: {}", file_name, e))?;
+
+            let (mut input, seq) =
+                request_from_bru(&file, &file_name, inherited_auth, &mut result.warnings);
+            input.collection_id = Some(collection_id.to_string());
+            input.folder_id = folder_id.map(|s| s.to_string());
+            let request = db.create_request(input)?;
+            if let Some(seq) = seq {
+                db.update_request(UpdateRequestInput {
+                    id: request.id,
+                    name: None,
+                    method: None,
+                    url: None,
+                    headers: None,
+                    query_params: None,
+                    body_type: None,
+                    body_content: None,
+                    description: None,
+                    path_params: None,
+                    scripts: None,
+                    kind: None,
+                    collection_id: None,
+                    folder_id: None,
+                    sort_order: Some(seq),
+                })?;
+            }
+            result.request_count += 1;
+        }
+    }
+    Ok(())
+}
+
+fn request_from_bru(
+    file: &BruFile,
+    file_name: &str,
+    inherited_auth: Option<&BrunoAuth>,
+    warnings: &mut Vec<String>,
+) -> (CreateRequestInput, Option<i32>) {
+    let name = file
+        .value("meta", "name")
+        .map(|n| n.to_string())
+        .unwrap_or_else(|| file_name.trim_end_matches(".bru").to_string());
+    let seq = file.value("meta", "seq").and_then(|s| s.parse().ok());
+
+    let method_block = METHOD_BLOCKS
+        .iter()
+        .find(|m| file.block(m).is_some())
+        .copied()
+        .unwrap_or("get");
+    let method = HttpMethod::from_str(method_block).unwrap_or(HttpMethod::GET);
+    let url = file.value(method_block, "url").unwrap_or_default();
+
+    let to_key_values = |pairs: &[BruPair]| -> Vec<KeyValue> {
+        pairs
+            .iter()
+            .map(|p| KeyValue {
+                key: p.key.clone(),
+                value: p.value.clone(),
+                enabled: p.enabled,
+            })
+            .collect()
+    };
+
+    let query_params = to_key_values(file.pairs("params:query"));
+    // The url repeats the query; keep it only in query_params
+    let url = if query_params.is_empty() {
+        url.to_string()
+    } else {
+        url.split('?').next().unwrap_or_default().to_string()
+    };
+    let mut headers = to_key_values(file.pairs("headers"));
+
+    let body_mode = file.value(method_block, "body").unwrap_or("none");
+    let (body_type, body_content) = body_from_bru(file, body_mode, &name, &mut headers, warnings);
+
+    let mut input = CreateRequestInput {
+        name,
+        method,
+        url,
+        headers,
+        query_params,
+        body_type,
+        body_content,
+        description: file.text("docs").map(|d| d.to_string()),
+        path_params: to_key_values(file.pairs("params:path")),
+        scripts: scripts_from_bru(file),
+        kind: RequestKind::Http,
+        collection_id: None,
+        folder_id: None,
+    };
+
+    let auth = match file.value(method_block, "auth") {
+        Some("inherit") => inherited_auth.cloned(),
+        mode => BrunoAuth::from_file(file, mode),
+    };
+    if let Some(auth) = auth {
+        apply_auth(&auth, &mut input, warnings);
+    }
+
+    for block in ["vars:pre-request", "vars:post-response", "assert"] {
+        if !file.pairs(block).is_empty() {
+            warnings.push(format!(
+                "Request '{}': {} block is not supported and was dropped",
+                input.name, block
+            ));
+        }
+    }
+
+    (input, seq)
+}
+
+fn body_from_bru(
+    file: &BruFile,
+    mode: &str,
+    name: &str,
+    headers: &mut Vec<KeyValue>,
+    warnings: &mut Vec<String>,
+) -> (BodyType, Option<String>) {
+    let text = |block: &str| file.text(block).map(|t| t.to_string());
+    match mode {
+        "json" => (BodyType::Json, text("body:json")),
+        "text" => (BodyType::Text, text("body:text")),
+        "xml" | "sparql" => {
+            let content_type = if mode == "xml" {
+                "application/xml"
+            } else {
+                "application/sparql-query"
+            };
+            if !headers
+                .iter()
+                .any(|h| h.key.eq_ignore_ascii_case("content-type"))
+            {
+                headers.push(KeyValue {
+                    key: "Content-Type".to_string(),
+                    value: content_type.to_string(),
+                    enabled: true,
+                });
+            }
+            (BodyType::Text, text(&format!("body:{}", mode)))
+        }
+        "formUrlEncoded" => {
+            let fields = form_fields(file.pairs("body:form-urlencoded"), name, warnings);
+            (BodyType::FormUrlEncoded, Some(fields.join("&")))
+        }
+        "multipartForm" => {
+            let fields = form_fields(file.pairs("body:multipart-form"), name, warnings);
+            (BodyType::Multipart, Some(fields.join("&")))
+        }
+        "graphql" => {
+            let content = serde_json::json!({
+                "query": text("body:graphql").unwrap_or_default(),
+                "variables": text("body:graphql:vars").unwrap_or_default(),
+                "operationName": "",
+            });
+            (BodyType::GraphQL, Some(content.to_string()))
+        }
+        _ => (BodyType::None, None),
+    }
+}
+
+/// Converts form pairs into Steq's `key=value` / `key=@path` fields. Multi-file
+/// values (`@file(a|b)`) become one field per file; disabled pairs are dropped.
+fn form_fields(pairs: &[BruPair], name: &str, warnings: &mut Vec<String>) -> Vec<String> {
+    let mut fields = Vec::new();
+    for pair in pairs {
+        if !pair.enabled {
+            warnings.push(format!(
+                "Request '{}': disabled form field '{}' was dropped",
+                name, pair.key
+            ));
+            continue;
+        }
+        match pair
+            .value
+            .strip_prefix("@file(")
+            .and_then(|v| v.strip_suffix(')'))
+        {
+            Some(paths) => {
+                for path in paths.split('|') {
+                    fields.push(format!("{}=@{}", pair.key, path));
+                }
+            }
+            None => fields.push(format!("{}={}", pair.key, pair.value)),
+        }
+    }
+    fields
+}
+
+/// Bruno's `tests` block runs after the response like a post-response script,
+/// so both end up in `post_response`.
+fn scripts_from_bru(file: &BruFile) -> Option<RequestScripts> {
+    let post_response = match (file.text("script:post-response"), file.text("tests")) {
+        (Some(script), Some(tests)) => Some(format!("{}\n\n{}", script, tests)),
+        (script, tests) => script.or(tests).map(|s| s.to_string()),
+    };
+    let scripts = RequestScripts {
+        pre_request: file.text("script:pre-request").map(|s| s.to_string()),
+        post_response,
+    };
+    (scripts.pre_request.is_some() || scripts.post_response.is_some()).then_some(scripts)
+}
+
+/// Steq has no auth settings, so supported schemes become headers or query params.
+fn apply_auth(auth: &BrunoAuth, input: &mut CreateRequestInput, warnings: &mut Vec<String>) {
+    let has_authorization = input
+        .headers
+        .iter()
+        .any(|h| h.key.eq_ignore_ascii_case("authorization"));
+
+    match auth.mode.as_str() {
+        "none" | "inherit" => {}
+        "bearer" | "basic" if has_authorization => {}
+        "bearer" => input.headers.push(KeyValue {
+            key: "Authorization".to_string(),
+            value: format!("Bearer {}", auth.param("token")),
+            enabled: true,
+        }),
+        "basic" => {
+            let (username, password) = (auth.param("username"), auth.param("password"));
+            if username.contains("{{") || password.contains("{{") {
+                warnings.push(format!(
+                    "Request '{}': basic auth uses variables and could not be encoded",
+                    input.name
+                ));
+                return;
+            }
+            input.headers.push(KeyValue {
+                key: "Authorization".to_string(),
+                value: format!(
+                    "Basic {}",
+                    BASE64.encode(format!("{}:{}", username, password))
+                ),
+                enabled: true,
+            });
+        }
+        "apikey" => {
+            let entry = KeyValue {
+                key: auth.param("key"),
+                value: auth.param("value"),
+                enabled: true,
+            };
+            if auth.param("placement") == "queryparams" {
+                input.query_params.push(entry);
+            } else {
+                input.headers.push(entry);
+            }
+        }
+        other => warnings.push(format!(
+            "Request '{}': {} auth is not supported",
+            input.name, other
+        )),
+    }
+}
+
+/// Folder and collection files can carry headers, scripts and variables that
+/// apply to every request; Steq only keeps these per request.
+fn warn_unsupported_blocks(file: &BruFile, owner: &str, warnings: &mut Vec<String>) {
+    for block in &file.blocks {
+        let supported = matches!(block.name.as_str(), "meta" | "auth" | "docs")
+            || block.name.starts_with("auth:");
+        let empty = match &block.body {
+            BruBody::Pairs(pairs) => pairs.is_empty(),
+            BruBody::Text(text) => text.trim().is_empty(),
+            BruBody::List(items) => items.is_empty(),
+        };
+        if !supported && !empty {
+            warnings.push(format!(
+                "The {} block on {} was not imported",
+                block.name, owner
+            ));
+        }
+    }
+}
+
+fn import_environment(db: &Database, path: &Path, workspace_id: &str) -> Result<(), String> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let file = parse_bru(&content)?;
+    let name = path
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    let env = db.create_environment(&CreateEnvironmentInput {
+        workspace_id: workspace_id.to_string(),
+        name,
+    })?;
+
+    // Bruno keeps secret values outside the collection, so only the names are known
+    let secrets = file.list("vars:secret").iter().map(|key| BruPair {
+        key: key.trim_start_matches('~').to_string(),
+        value: String::new(),
+        enabled: !key.starts_with('~'),
+    });
+    let variables = file
+        .pairs("vars")
+        .iter()
+        .cloned()
+        .map(|p| (p, false))
+        .chain(secrets.map(|p| (p, true)))
+        .enumerate()
+        .map(|(i, (pair, is_secret))| Variable {
+            id: String::new(),
+            environment_id: env.id.clone(),
+            key: pair.key,
+            value: pair.value,
+            is_secret,
+            enabled: pair.enabled,
+            sort_order: i as i32,
+            created_at: String::new(),
+            updated_at: String::new(),
+        })
+        .collect();
+
+    db.update_environment(&UpdateEnvironmentInput {
+        id: env.id,
+        name: None,
+        variables: Some(variables),
+    })?;
+    Ok(())
+}
+
+fn read_bru_if_exists(path: &Path) -> Result<Option<BruFile>, String> {
+    if !path.is_file() {
+        return Ok(None);
+    }
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    parse_bru(&content).map(Some)
+}
+
+fn sorted_entries(dir: &Path) -> Result<Vec<std::path::PathBuf>, String> {
+    let mut entries: Vec<_> = std::fs::read_dir(dir)
+        .map_err(|e| format!("Failed to read {}: {}", dir.display(), e))?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .collect();
+    entries.sort();
+    Ok(entries)
+}
+
+/// Writes a collection out as a Bruno directory. Secret variable values of the
+/// given environments are not written; Bruno stores those outside the collection.
+pub fn export_bruno_collection(
+    db: &Database,
+    collection_id: &str,
+    dir: &Path,
+    environment_ids: &[String],
+) -> Result<(), String> {
+    let tree = db.get_collection_tree(collection_id)?;
+    create_dir(dir)?;
+
+    let config = serde_json::json!({
+        "version": "1",
+        "name": tree.collection.name,
+        "type": "collection",
+        "ignore": ["node_modules", ".git"]
+    });
+    let config =
+        serde_json::to_string_pretty(&config).map_err(|e| format!("Failed to serialize: {}", e))?;
+    write_file(&dir.join("bruno.json"), &config)?;
+
+    if let Some(description) = &tree.collection.description {
+        let mut file = BruFile::default();
+        file.push_text("docs", Some(description));
+        write_file(&dir.join("collection.bru"), &write_bru(&file))?;
+    }
+
+    export_items(dir, &tree.root_folders, &tree.root_requests)?;
+
+    if !environment_ids.is_empty() {
+        let env_dir = dir.join("environments");
+        create_dir(&env_dir)?;
+        for id in environment_ids {
+            let env = db
+                .get_environment(id)?
+                .ok_or_else(|| format!("Environment not found: {}", id))?;
+            let mut file = BruFile::default();
+            let vars = env
+                .variables
+                .iter()
+                .filter(|v| !v.is_secret)
+                .map(|v| BruPair {
+                    key: v.key.clone(),
+                    value: v.value.clone(),
+                    enabled: v.enabled,
+                })
+                .collect();
+            file.push_pairs("vars", vars);
+            let secrets: Vec<String> = env
+                .variables
+                .iter()
+                .filter(|v| v.is_secret)
+                .map(|v| format!("{}{}", if v.enabled { "" } else { "~" }, v.key))
+                .collect();
+            if !secrets.is_empty() {
+                file.blocks.push(BruBlock {
+                    name: "vars:secret".to_string(),
+                    body: BruBody::List(secrets),
+                });
+            }
+            let path = env_dir.join(format!("{}.bru", file_stem(&env.name)));
+            write_file(&path, &write_bru(&file))?;
+        }
+    }
+
+    Ok(())
+}
+
+fn export_items(
+    dir: &Path,
+    folders: &[CollectionTreeNode],
+    requests: &[ApiRequest],
+) -> Result<(), String> {
+    let mut used_names: Vec<String> = Vec::new();
+    let mut unique = |name: &str, extension: &str| {
+        let stem = file_stem(name);
+        let mut candidate = format!("{}{}", stem, extension);
+        let mut n = 2;
+        while used_names
+            .iter()
+            .any(|u| u.eq_ignore_ascii_case(&candidate))
+        {
+            candidate = format!("{} {}{}", stem, n, extension);
+            n += 1;
+        }
+        used_names.push(candidate.clone());
+        candidate
+    };
+
+    for (i, node) in folders.iter().enumerate() {
+        let folder_dir = dir.join(unique(&node.folder.name, ""));
+        create_dir(&folder_dir)?;
+
+        let mut file = BruFile::default();
+        file.push_pairs(
+            "meta",
+            vec![
+                BruPair::new("name", &node.folder.name),
+                BruPair::new("seq", &(i + 1).to_string()),
+            ],
+        );
+        file.push_text("docs", node.folder.description.as_deref());
+        write_file(&folder_dir.join("folder.bru"), &write_bru(&file))?;
+
+        export_items(&folder_dir, &node.children, &node.requests)?;
+    }
+
+    for (i, request) in requests.iter().enumerate() {
+        let path = dir.join(unique(&request.name, ".bru"));
+        write_file(&path, &request_to_bru(request, i as i32 + 1))?;
+    }
+    Ok(())
+}
+
+/// Renders a request as a `.bru` file.
+pub fn request_to_bru(request: &ApiRequest, seq: i32) -> String {
+    let to_pairs = |values: &[KeyValue]| -> Vec<BruPair> {
+        values
+            .iter()
+            .map(|kv| BruPair {
+                key: kv.key.clone(),
+                value: kv.value.clone(),
+                enabled: kv.enabled,
+            })
+            .collect()
+    };
+
+    let is_graphql = matches!(request.body_type, BodyType::GraphQL);
+    let mut file = BruFile::default();
+    file.push_pairs(
+        "meta",
+        vec![
+            BruPair::new("name", &request.name),
+            BruPair::new("type", if is_graphql { "graphql" } else { "http" }),
+            BruPair::new("seq", &seq.to_string()),
+        ],
+    );
+
+    let enabled_query: Vec<String> = request
+        .query_params
+        .iter()
+        .filter(|q| q.enabled)
+        .map(|q| format!("{}={}", q.key, q.value))
+        .collect();
+    let url = if enabled_query.is_empty() {
+        request.url.clone()
+    } else {
+        format!("{}?{}", request.url, enabled_query.join("&"))
+    };
+
+    let content = request.body_content.as_deref().filter(|c| !c.is_empty());
+    let body_mode = match (&request.body_type, content) {
+        (_, None) | (BodyType::None, _) => "none",
+        (BodyType::Json, _) => "json",
+        (BodyType::Text, _) => "text",
+        (BodyType::FormUrlEncoded, _) => "formUrlEncoded",
+        (BodyType::Multipart, _) => "multipartForm",
+        (BodyType::GraphQL, _) => "graphql",
+    };
+
+    file.push_pairs(
+        &request.method.as_str().to_lowercase(),
+        vec![
+            BruPair::new("url", &url),
+            BruPair::new("body", body_mode),
+            BruPair::new("auth", "none"),
+        ],
+    );
+    file.push_pairs("params:query", to_pairs(&request.query_params));
+    file.push_pairs("params:path", to_pairs(&request.path_params));
+    file.push_pairs("headers", to_pairs(&request.headers));
+
+    if let Some(content) = content {
+        let fields = || {
+            content
+                .split('&')
+                .filter(|f| !f.is_empty())
+                .map(|f| f.split_once('=').unwrap_or((f, "")))
+        };
+        match request.body_type {
+            BodyType::None => {}
+            BodyType::Json => file.push_text("body:json", Some(content)),
+            BodyType::Text => file.push_text("body:text", Some(content)),
+            BodyType::FormUrlEncoded => file.push_pairs(
+                "body:form-urlencoded",
+                fields().map(|(k, v)| BruPair::new(k, v)).collect(),
+            ),
+            BodyType::Multipart => file.push_pairs(
+                "body:multipart-form",
+                fields()
+                    .map(|(k, v)| match v.strip_prefix('@') {
+                        Some(path) => BruPair::new(k, &format!("@file({})", path)),
+                        None => BruPair::new(k, v),
+                    })
+                    .collect(),
+            ),
+            BodyType::GraphQL => {
+                let value: serde_json::Value = serde_json::from_str(content).unwrap_or_default();
+                file.push_text("body:graphql", value["query"].as_str().or(Some(content)));
+                file.push_text("body:graphql:vars", value["variables"].as_str());
+            }
+        }
+    }
+
+    if let Some(scripts) = &request.scripts {
+        file.push_text("script:pre-request", scripts.pre_request.as_deref());
+        file.push_text("script:post-response", scripts.post_response.as_deref());
+    }
+    file.push_text("docs", request.description.as_deref());
+
+    write_bru(&file)
+}
+
+fn create_dir(path: &Path) -> Result<(), String> {
+    std::fs::create_dir_all(path).map_err(|e| format!("Failed to create {}: {}", path.display(), e))
+}
+
+fn write_file(path: &Path, content: &str) -> Result<(), String> {
+    std::fs::write(path, content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rusqlite::Connection;
+    use std::path::PathBuf;
+
+    fn setup_test_db() -> (Database, String) {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch("PRAGMA foreign_keys=ON;").unwrap();
+        let db = Database { conn };
+        db.run_migrations().unwrap();
+        let workspace = db.get_current_workspace().unwrap();
+        (db, workspace.id)
+    }
+
+    fn temp_dir() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("steq-bruno-{}", uuid::Uuid::now_v7()));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    const GET_USER: &str = r#"meta {
+  name: Get User
+  type: http
+  seq: 2
+}
+
+get {
+  url: {{baseUrl}}/users/:id?expand=true
+  body: none
+  auth: inherit
+}
+
+params:query {
+  expand: true
+  ~debug: 1
+}
+
+params:path {
+  id: 42
+}
+
+headers {
+  Accept: application/json
+}
+
+script:pre-request {
+  bru.setVar("ts", Date.now());
+}
+
+tests {
+  test("ok", function() {
+    expect(res.status).to.equal(200);
+  });
+}
+
+docs {
+  Fetches one user.
+
+  Requires a token.
+}
+"#;
+
+    const CREATE_USER: &str = r#"meta {
+  name: Create User
+  type: http
+  seq: 1
+}
+
+post {
+  url: {{baseUrl}}/users
+  body: json
+  auth: basic
+}
+
+auth:basic {
+  username: ada
+  password: pw
+}
+
+body:json {
+  {
+    "name": "Ada"
+  }
+}
+
+vars:pre-request {
+  a: 1
+}
+"#;
+
+    const UPLOAD: &str = r#"meta {
+  name: Upload
+  type: http
+  seq: 1
+}
+
+post {
+  url: {{baseUrl}}/upload
+  body: multipartForm
+  auth: none
+}
+
+body:multipart-form {
+  note: hi
+  files: @file(/tmp/a.png|/tmp/b.png)
+  ~old: x
+}
+"#;
+
+    const QUERY: &str = r#"meta {
+  name: Me
+  type: graphql
+  seq: 3
+}
+
+post {
+  url: {{baseUrl}}/graphql
+  body: graphql
+  auth: none
+}
+
+body:graphql {
+  query {
+    me { id }
+  }
+}
+
+body:graphql:vars {
+  {"a": 1}
+}
+"#;
+
+    fn write_sample_collection(dir: &Path) {
+        std::fs::write(
+            dir.join("bruno.json"),
+            r#"{ "version": "1", "name": "Users API", "type": "collection", "ignore": ["node_modules"] }"#,
+        )
+        .unwrap();
+        std::fs::write(
+            dir.join("collection.bru"),
+            "auth {\n  mode: bearer\n}\n\nauth:bearer {\n  token: {{token}}\n}\n\ndocs {\n  Users service\n}\n",
+        )
+        .unwrap();
+        std::fs::write(dir.join("Get User.bru"), GET_USER).unwrap();
+        std::fs::write(dir.join("Create User.bru"), CREATE_USER).unwrap();
+        std::fs::write(dir.join("Me.bru"), QUERY).unwrap();
+
+        let files = dir.join("files");
+        std::fs::create_dir_all(&files).unwrap();
+        std::fs::write(
+            files.join("folder.bru"),
+            "meta {\n  name: File Uploads\n  seq: 1\n}\n\nheaders {\n  X-Folder: 1\n}\n",
+        )
+        .unwrap();
+        std::fs::write(files.join("Upload.bru"), UPLOAD).unwrap();
+
+        std::fs::create_dir_all(dir.join("node_modules/pkg")).unwrap();
+        std::fs::write(dir.join("node_modules/pkg/x.bru"), "meta {\n  name: x\n}\n").unwrap();
+
+        let envs = dir.join("environments");
+        std::fs::create_dir_all(&envs).unwrap();
+        std::fs::write(
+            envs.join("Local.bru"),
+            "vars {\n  baseUrl: http://localhost:3000\n  ~debug: true\n}\nvars:secret [\n  token,\n  apiKey\n]\n",
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn parse_and_write_bru_blocks() {
+        let file = parse_bru(GET_USER).unwrap();
+        assert_eq!(file.value("meta", "name"), Some("Get User"));
+        assert_eq!(
+            file.value("get", "url"),
+            Some("{{baseUrl}}/users/:id?expand=true")
+        );
+        assert!(!file.pairs("params:query")[1].enabled);
+        assert_eq!(
+            file.text("docs"),
+            Some("Fetches one user.\n\nRequires a token.")
+        );
+
+        let rewritten = write_bru(&file);
+        assert_eq!(rewritten, GET_USER);
+    }
+
+    #[test]
+    fn parse_rejects_malformed_files() {
+        assert!(parse_bru("meta {\n  name: x\n").is_err());
+        assert!(parse_bru("name: x\n").is_err());
+    }
+
+    #[test]
+    fn import_collection_directory() {
+        let dir = temp_dir();
+        write_sample_collection(&dir);
+        let (db, wid) = setup_test_db();
+
+        let result = import_bruno_collection(&db, &dir, &wid).unwrap();
+        assert_eq!(result.collection_name, "Users API");
+        assert_eq!(result.request_count, 4);
+        assert_eq!(result.folder_count, 1);
+
+        let colls = db.list_collections(&wid).unwrap();
+        assert_eq!(colls[0].description.as_deref(), Some("Users service"));
+        let tree = db.get_collection_tree(&colls[0].id).unwrap();
+        let names: Vec<&str> = tree.root_requests.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(names, vec!["Create User", "Get User", "Me"]);
+
+        let get_user = &tree.root_requests[1];
+        assert_eq!(get_user.url, "{{baseUrl}}/users/:id");
+        assert_eq!(get_user.query_params.len(), 2);
+        assert_eq!(get_user.path_params[0].value, "42");
+        assert_eq!(get_user.headers[1].value, "Bearer {{token}}");
+        let scripts = get_user.scripts.as_ref().unwrap();
+        assert!(scripts
+            .pre_request
+            .as_deref()
+            .unwrap()
+            .contains("bru.setVar"));
+        assert!(scripts
+            .post_response
+            .as_deref()
+            .unwrap()
+            .starts_with("test(\"ok\""));
+
+        let create = &tree.root_requests[0];
+        assert_eq!(create.body_type.as_str(), "json");
+        assert_eq!(
+            create.body_content.as_deref(),
+            Some("{\n  \"name\": \"Ada\"\n}")
+        );
+        assert_eq!(create.headers[0].value, "Basic YWRhOnB3");
+
+        let me = &tree.root_requests[2];
+        let content: serde_json::Value =
+            serde_json::from_str(me.body_content.as_deref().unwrap()).unwrap();
+        assert_eq!(content["query"], "query {\n  me { id }\n}");
+        assert_eq!(content["variables"], "{\"a\": 1}");
+
+        let folder = &tree.root_folders[0];
+        assert_eq!(folder.folder.name, "File Uploads");
+        let upload = &folder.requests[0];
+        assert_eq!(
+            upload.body_content.as_deref(),
+            Some("note=hi&files=@/tmp/a.png&files=@/tmp/b.png")
+        );
+
+        let warnings = result.warnings.join("\n");
+        assert!(warnings.contains("vars:pre-request"));
+        assert!(warnings.contains("headers block on folder 'File Uploads'"));
+        assert!(warnings.contains("disabled form field 'old'"));
+
+        let envs = db.list_environments(&wid).unwrap();
+        let local = envs.iter().find(|e| e.name == "Local").unwrap();
+        assert_eq!(local.variables.len(), 4);
+        assert!(!local.variables[1].enabled);
+        assert!(local.variables[2].is_secret);
+        assert_eq!(local.variables[3].key, "apiKey");
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn import_keeps_nothing_when_a_file_is_malformed() {
+        let dir = temp_dir();
+        write_sample_collection(&dir);
+        std::fs::write(dir.join("files/Broken.bru"), "meta {\n  name: Broken\n").unwrap();
+        let (db, wid) = setup_test_db();
+
+        let err = import_bruno_collection(&db, &dir, &wid).unwrap_err();
+        assert!(err.contains("Broken.bru"));
+        assert!(db.list_collections(&wid).unwrap().is_empty());
+        assert!(db.list_environments(&wid).unwrap().is_empty());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn import_skips_linked_directories() {
+        let dir = temp_dir();
+        write_sample_collection(&dir);
+        std::os::unix::fs::symlink(&dir, dir.join("files/loop")).unwrap();
+        let (db, wid) = setup_test_db();
+
+        let result = import_bruno_collection(&db, &dir, &wid).unwrap();
+        assert_eq!(result.request_count, 4);
+        assert_eq!(result.folder_count, 1);
+        assert!(result
+            .warnings
+            .iter()
+            .any(|w| w.contains("linked directory 'loop'")));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn import_requires_bruno_json() {
+        let dir = temp_dir();
+        let (db, wid) = setup_test_db();
+        let err = import_bruno_collection(&db, &dir, &wid).unwrap_err();
+        assert!(err.contains("bruno.json"));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn export_and_reimport_roundtrip() {
+        let source = temp_dir();
+        write_sample_collection(&source);
+        let (db, wid) = setup_test_db();
+        import_bruno_collection(&db, &source, &wid).unwrap();
+        let coll = db.list_collections(&wid).unwrap().remove(0);
+        let env = db.list_environments(&wid).unwrap().remove(0);
+
+        let target = temp_dir();
+        export_bruno_collection(&db, &coll.id, &target, std::slice::from_ref(&env.id)).unwrap();
+        assert!(target.join("bruno.json").is_file());
+        assert!(target.join("File Uploads/folder.bru").is_file());
+        let env_file = std::fs::read_to_string(target.join("environments/Local.bru")).unwrap();
+        assert!(env_file.contains("vars:secret [\n  token\n  apiKey\n]"));
+
+        let (db2, wid2) = setup_test_db();
+        let result = import_bruno_collection(&db2, &target, &wid2).unwrap();
+        assert_eq!(result.request_count, 4);
+
+        let before = db.get_collection_tree(&coll.id).unwrap();
+        let coll2 = db2.list_collections(&wid2).unwrap().remove(0);
+        let after = db2.get_collection_tree(&coll2.id).unwrap();
+        assert_eq!(after.collection.description, before.collection.description);
+        for (a, b) in before.root_requests.iter().zip(&after.root_requests) {
+            assert_eq!(a.name, b.name);
+            assert_eq!(a.url, b.url);
+            assert_eq!(a.body_type.as_str(), b.body_type.as_str());
+            assert_eq!(a.body_content, b.body_content);
+            assert_eq!(a.headers.len(), b.headers.len());
+            assert_eq!(a.query_params.len(), b.query_params.len());
+            assert_eq!(a.path_params.len(), b.path_params.len());
+            assert_eq!(a.description, b.description);
+        }
+        assert_eq!(
+            after.root_folders[0].requests[0].body_content,
+            before.root_folders[0].requests[0].body_content
+        );
+
+        std::fs::remove_dir_all(&source).unwrap();
+        std::fs::remove_dir_all(&target).unwrap();
+    }
+
+    #[test]
+    fn file_stem_sanitizes_names() {
+        assert_eq!(file_stem("Get /users/:id"), "Get -users--id");
+        assert_eq!(file_stem("  ..  "), "untitled");
+    }
+}
@@ -0,0 +1,558 @@
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+
+use super::{graphql_content, percent_decode, split_query, url_path};
+use crate::models::request::{BodyType, CreateRequestInput, HttpMethod, KeyValue, RequestKind};
+
+/// Options that consume the following argument. Any of them left unhandled
+/// below still has its argument skipped, so it can't be mistaken for the URL.
+const OPTIONS_WITH_VALUE: &[&str] = &[
+    "-X", "--request", "-H", "--header", "-d", "--data", "--data-ascii", "--data-raw",
+    "--data-binary", "--data-urlencode", "--json", "-F", "--form", "--form-string", "-u",
+    "--user", "-b", "--cookie", "-A", "--user-agent", "-e", "--referer", "--url", "-o",
+    "--output", "-m", "--max-time", "--connect-timeout", "--retry", "--retry-delay",
+    "--retry-max-time", "-w", "--write-out", "--cacert", "--capath", "-E", "--cert", "--key",
+    "-x", "--proxy", "-U", "--proxy-user", "--resolve", "--connect-to", "--interface",
+    "--limit-rate", "--max-redirs", "-c", "--cookie-jar", "--oauth2-bearer", "-D",
+    "--dump-header", "-T", "--upload-file", "-r", "--range", "-K", "--config", "-C",
+    "--continue-at", "-Y", "--speed-limit", "-y", "--speed-time", "-z", "--time-cond", "-t",
+    "--telnet-option", "-Q", "--quote", "-P", "--ftp-port", "--noproxy", "--cert-type",
+    "--key-type", "--pass", "--ciphers", "--tls13-ciphers", "--curves", "--tls-max",
+    "--pinnedpubkey", "--crlfile", "--aws-sigv4", "--request-target", "--url-query",
+    "--proxy-header", "--proxy-cacert", "--proxy-capath", "--proxy-cert", "--proxy-cert-type",
+    "--proxy-key", "--proxy-key-type", "--proxy-pass", "--proxy-ciphers", "--preproxy",
+    "--socks4", "--socks4a", "--socks5", "--socks5-hostname", "--unix-socket",
+    "--abstract-unix-socket", "--output-dir", "--stderr", "--trace", "--trace-ascii",
+    "--trace-config", "--local-port", "--keepalive-time", "--expect100-timeout",
+    "--happy-eyeballs-timeout-ms", "--max-filesize", "--rate", "--dns-servers",
+    "--dns-interface", "--doh-url", "--alt-svc", "--hsts", "--etag-compare", "--etag-save",
+    "--netrc-file", "--proto", "--proto-default", "--proto-redir", "--service-name",
+    "--login-options", "--sasl-authzid", "--variable", "--libcurl", "--create-file-mode",
+    "--ip-tos", "--mail-from", "--mail-rcpt", "--mail-auth",
+];
+
+/// Parses a cURL command line into a request.
+///
+/// Supports shell quoting (single, double and `$'...'`), line continuations,
+/// `-X`, `-H`, `-d`/`--data-raw`/`--data-binary`/`--data-urlencode`, `-F`,
+/// `-u`, `--oauth2-bearer`, `-b`, `-A`, `-e`, `-G`, `-I`, `-T` and `--compressed`.
+/// Flags that only affect transport, such as `-k`, `-L`, `-s` or `-D`, are
+/// accepted and ignored.
+pub fn parse_curl_command(command: &str) -> Result<CreateRequestInput, String> {
+    let mut tokens = tokenize(command)?.into_iter();
+
+    match tokens.next() {
+        Some(first) if first == "curl" || first == "curl.exe" || first.ends_with("/curl") => {}
+        Some(_) => return Err("Command must start with curl".to_string()),
+        None => return Err("Empty cURL command".to_string()),
+    }
+
+    let mut args = expand_short_flags(tokens.collect()).into_iter();
+
+    let mut method: Option<String> = None;
+    let mut url: Option<String> = None;
+    let mut headers: Vec<KeyValue> = Vec::new();
+    let mut data: Vec<String> = Vec::new();
+    let mut form: Vec<String> = Vec::new();
+    let mut use_get = false;
+    let mut head = false;
+    let mut upload = false;
+    let mut compressed = false;
+
+    while let Some(token) = args.next() {
+        let (flag, inline_value) = split_option(&token);
+        let takes_value = inline_value.is_some() || OPTIONS_WITH_VALUE.contains(&flag.as_str());
+        let value = if takes_value {
+            match inline_value {
+                Some(v) => v,
+                None => args
+                    .next()
+                    .ok_or_else(|| format!("Missing value for {}", flag))?,
+            }
+        } else {
+            String::new()
+        };
+
+        match flag.as_str() {
+            "-X" | "--request" => method = Some(value.to_uppercase()),
+            "-H" | "--header" => {
+                if let Some((key, val)) = value.split_once(':') {
+                    headers.push(KeyValue {
+                        key: key.trim().to_string(),
+                        value: val.trim().to_string(),
+                        enabled: true,
+                    });
+                }
+            }
+            "-d" | "--data" | "--data-ascii" | "--data-raw" | "--data-binary" => data.push(value),
+            "--data-urlencode" => data.push(encode_data_urlencode(&value)),
+            "--json" => {
+                data.push(value);
+                set_header_if_missing(&mut headers, "Content-Type", "application/json");
+                set_header_if_missing(&mut headers, "Accept", "application/json");
+            }
+            "-F" | "--form" | "--form-string" => form.push(value),
+            "-u" | "--user" => {
+                let encoded = BASE64.encode(value.as_bytes());
+                set_header_if_missing(&mut headers, "Authorization", &format!("Basic {}", encoded));
+            }
+            "--oauth2-bearer" => {
+                set_header_if_missing(&mut headers, "Authorization", &format!("Bearer {}", value));
+            }
+            "-b" | "--cookie" => {
+                // Without '=' the argument names a cookie file, which we cannot read
+                if value.contains('=') {
+                    set_header_if_missing(&mut headers, "Cookie", &value);
+                }
+            }
+            "-A" | "--user-agent" => set_header_if_missing(&mut headers, "User-Agent", &value),
+            "-e" | "--referer" => set_header_if_missing(&mut headers, "Referer", &value),
+            "--url" => url = Some(value),
+            "-G" | "--get" => use_get = true,
+            "-I" | "--head" => head = true,
+            // The file can't be read here, but it still makes the request a PUT
+            "-T" | "--upload-file" => upload = true,
+            "--compressed" => compressed = true,
+            f if f.starts_with('-') && f.len() > 1 => {}
+            _ => {
+                if url.is_none() {
+                    url = Some(token);
+                }
+            }
+        }
+    }
+
+    let url = url.ok_or_else(|| "No URL found in cURL command".to_string())?;
+    let (base_url, mut query_params) = split_query(&url);
+
+    if compressed {
+        set_header_if_missing(&mut headers, "Accept-Encoding", "gzip, deflate, br");
+    }
+
+    let content_type = headers
+        .iter()
+        .find(|h| h.key.eq_ignore_ascii_case("content-type"))
+        .map(|h| h.value.to_lowercase());
+
+    let (body_type, body_content) = if use_get {
+        for pair in data.iter().flat_map(|d| d.split('&')) {
+            if pair.is_empty() {
+                continue;
+            }
+            let (key, val) = pair.split_once('=').unwrap_or((pair, ""));
+            query_params.push(KeyValue {
+                key: percent_decode(key),
+                value: percent_decode(val),
+                enabled: true,
+            });
+        }
+        (BodyType::None, None)
+    } else if !form.is_empty() {
+        (BodyType::Multipart, Some(form.join("&")))
+    } else if !data.is_empty() {
+        let body = data.join("&");
+        (infer_body_type(&body, content_type.as_deref(), &base_url), Some(body))
+    } else {
+        (BodyType::None, None)
+    };
+
+    let body_content = match (&body_type, body_content) {
+        (BodyType::GraphQL, Some(body)) => Some(graphql_content(&body)),
+        (_, body) => body,
+    };
+
+    let method = match method {
+        Some(m) => m,
+        None if head => "HEAD".to_string(),
+        None if upload => "PUT".to_string(),
+        None if use_get => "GET".to_string(),
+        None if !matches!(body_type, BodyType::None) => "POST".to_string(),
+        None => "GET".to_string(),
+    };
+    let method = HttpMethod::from_str(&method)?;
+
+    let name = format!("{} {}", method.as_str(), url_path(&base_url));
+
+    Ok(CreateRequestInput {
+        name,
+        method,
+        url: base_url,
+        headers,
+        query_params,
+        body_type,
+        body_content,
+        collection_id: None,
+        folder_id: None,
+        description: None,
+        path_params: vec![],
+        scripts: None,
+        kind: RequestKind::Http,
+    })
+}
+
+/// Splits bundled short flags (`-sSL`) and attached values (`-XPOST`, `-H'Accept: */*'`)
+/// into separate arguments.
+fn expand_short_flags(tokens: Vec<String>) -> Vec<String> {
+    let mut out = Vec::with_capacity(tokens.len());
+    let mut expect_value = false;
+
+    for token in tokens {
+        if expect_value {
+            out.push(token);
+            expect_value = false;
+            continue;
+        }
+        if token.starts_with("--") {
+            expect_value = !token.contains('=') && OPTIONS_WITH_VALUE.contains(&token.as_str());
+            out.push(token);
+            continue;
+        }
+        if token.starts_with('-') && token.len() > 1 {
+            let chars: Vec<char> = token[1..].chars().collect();
+            for (i, c) in chars.iter().enumerate() {
+                let flag = format!("-{}", c);
+                let takes_value = OPTIONS_WITH_VALUE.contains(&flag.as_str());
+                out.push(flag);
+                if takes_value {
+                    let attached: String = chars[i + 1..].iter().collect();
+                    if attached.is_empty() {
+                        expect_value = true;
+                    } else {
+                        out.push(attached);
+                    }
+                    break;
+                }
+            }
+            continue;
+        }
+        out.push(token);
+    }
+    out
+}
+
+/// Splits shell-style words, honouring quotes, escapes and line continuations.
+fn tokenize(input: &str) -> Result<Vec<String>, String> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut in_token = false;
+    let mut chars = input.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' => {
+                in_token = true;
+                loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some(ch) => current.push(ch),
+                        None => return Err("Unterminated single quote".to_string()),
+                    }
+                }
+            }
+            '"' => {
+                in_token = true;
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some('\n') => {}
+                            Some(ch @ ('"' | '\\' | '$' | '`')) => current.push(ch),
+                            Some(ch) => {
+                                current.push('\\');
+                                current.push(ch);
+                            }
+                            None => return Err("Unterminated double quote".to_string()),
+                        },
+                        Some(ch) => current.push(ch),
+                        None => return Err("Unterminated double quote".to_string()),
+                    }
+                }
+            }
+            '$' if chars.peek() == Some(&'\'') => {
+                chars.next();
+                in_token = true;
+                loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some('\\') => match chars.next() {
+                            Some('n') => current.push('\n'),
+                            Some('t') => current.push('\t'),
+                            Some('r') => current.push('\r'),
+                            Some(ch) => current.push(ch),
+                            None => return Err("Unterminated $'' quote".to_string()),
+                        },
+                        Some(ch) => current.push(ch),
+                        None => return Err("Unterminated $'' quote".to_string()),
+                    }
+                }
+            }
+            '\\' => match chars.next() {
+                Some('\n') => {}
+                Some('\r') if chars.peek() == Some(&'\n') => {
+                    chars.next();
+                }
+                Some(ch) => {
+                    in_token = true;
+                    current.push(ch);
+                }
+                None => {}
+            },
+            // Windows cmd (^) and PowerShell (`) line continuations
+            '^' | '`' if matches!(chars.peek(), Some('\n') | Some('\r')) => {
+                while matches!(chars.peek(), Some('\n') | Some('\r')) {
+                    chars.next();
+                }
+            }
+            c if c.is_whitespace() => {
+                if in_token {
+                    tokens.push(std::mem::take(&mut current));
+                    in_token = false;
+                }
+            }
+            c => {
+                in_token = true;
+                current.push(c);
+            }
+        }
+    }
+
+    if in_token {
+        tokens.push(current);
+    }
+    Ok(tokens)
+}
+
+/// Splits `--name=value` into the option and its inline value.
+fn split_option(token: &str) -> (String, Option<String>) {
+    if token.starts_with("--") {
+        if let Some((name, value)) = token.split_once('=') {
+            return (name.to_string(), Some(value.to_string()));
+        }
+    }
+    (token.to_string(), None)
+}
+
+fn set_header_if_missing(headers: &mut Vec<KeyValue>, key: &str, value: &str) {
+    if !headers.iter().any(|h| h.key.eq_ignore_ascii_case(key)) {
+        headers.push(KeyValue {
+            key: key.to_string(),
+            value: value.to_string(),
+            enabled: true,
+        });
+    }
+}
+
+/// Applies `--data-urlencode` semantics: `name=content` encodes only the content.
+fn encode_data_urlencode(value: &str) -> String {
+    match value.split_once('=') {
+        Some((name, content)) if !name.is_empty() => {
+            format!("{}={}", name, crate::http::executor::urlencoding_encode(content))
+        }
+        Some((_, content)) => crate::http::executor::urlencoding_encode(content),
+        None => crate::http::executor::urlencoding_encode(value),
+    }
+}
+
+fn infer_body_type(body: &str, content_type: Option<&str>, url: &str) -> BodyType {
+    let looks_like_json = {
+        let trimmed = body.trim_start();
+        (trimmed.starts_with('{') || trimmed.starts_with('['))
+            && serde_json::from_str::<serde_json::Value>(body).is_ok()
+    };
+
+    let is_json = match content_type {
+        Some(ct) => ct.contains("json"),
+        None => looks_like_json,
+    };
+    if is_json {
+        let is_graphql = url.to_lowercase().contains("graphql")
+            && serde_json::from_str::<serde_json::Value>(body)
+                .ok()
+                .and_then(|v| v.get("query").map(|q| q.is_string()))
+                .unwrap_or(false);
+        return if is_graphql {
+            BodyType::GraphQL
+        } else {
+            BodyType::Json
+        };
+    }
+
+    match content_type {
+        Some(ct) if ct.contains("x-www-form-urlencoded") => BodyType::FormUrlEncoded,
+        Some(ct) if ct.contains("multipart/form-data") => BodyType::Multipart,
+        Some(_) => BodyType::Text,
+        // curl sends -d data as application/x-www-form-urlencoded by default
+        None => BodyType::FormUrlEncoded,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn header<'a>(input: &'a CreateRequestInput, key: &str) -> Option<&'a str> {
+        input
+            .headers
+            .iter()
+            .find(|h| h.key.eq_ignore_ascii_case(key))
+            .map(|h| h.value.as_str())
+    }
+
+    #[test]
+    fn parse_simple_get() {
+        let req = parse_curl_command("curl https://api.example.com/users").unwrap();
+        assert_eq!(req.method.as_str(), "GET");
+        assert_eq!(req.url, "https://api.example.com/users");
+        assert_eq!(req.name, "GET /users");
+        assert_eq!(req.body_type.as_str(), "none");
+    }
+
+    #[test]
+    fn parse_method_headers_and_json_body() {
+        let cmd = r#"curl -X PUT 'https://api.example.com/users/1' \
+  -H 'Content-Type: application/json' \
+  -H "Authorization: Bearer abc" \
+  --data-raw '{"name":"O'\''Brien"}'"#;
+        let req = parse_curl_command(cmd).unwrap();
+        assert_eq!(req.method.as_str(), "PUT");
+        assert_eq!(req.url, "https://api.example.com/users/1");
+        assert_eq!(header(&req, "authorization"), Some("Bearer abc"));
+        assert_eq!(req.body_type.as_str(), "json");
+        assert_eq!(req.body_content.as_deref(), Some(r#"{"name":"O'Brien"}"#));
+    }
+
+    #[test]
+    fn parse_data_defaults_to_post_form() {
+        let req = parse_curl_command("curl -d 'a=1' -d 'b=2' https://example.com/form").unwrap();
+        assert_eq!(req.method.as_str(), "POST");
+        assert_eq!(req.body_type.as_str(), "form_url_encoded");
+        assert_eq!(req.body_content.as_deref(), Some("a=1&b=2"));
+    }
+
+    #[test]
+    fn parse_json_without_content_type() {
+        let req = parse_curl_command(r#"curl https://example.com --data-binary '{"a":1}'"#).unwrap();
+        assert_eq!(req.body_type.as_str(), "json");
+    }
+
+    #[test]
+    fn parse_text_body() {
+        let req =
+            parse_curl_command("curl -H 'Content-Type: text/plain' -d hello https://example.com")
+                .unwrap();
+        assert_eq!(req.body_type.as_str(), "text");
+        assert_eq!(req.body_content.as_deref(), Some("hello"));
+    }
+
+    #[test]
+    fn parse_multipart_form() {
+        let req = parse_curl_command(
+            "curl -F 'name=steq' -F 'file=@/tmp/a.png' https://example.com/upload",
+        )
+        .unwrap();
+        assert_eq!(req.method.as_str(), "POST");
+        assert_eq!(req.body_type.as_str(), "multipart");
+        assert_eq!(req.body_content.as_deref(), Some("name=steq&file=@/tmp/a.png"));
+    }
+
+    #[test]
+    fn parse_basic_auth_cookie_and_compressed() {
+        let req = parse_curl_command(
+            "curl -u admin:secret -b 'session=xyz; theme=dark' --compressed -k https://example.com",
+        )
+        .unwrap();
+        assert_eq!(header(&req, "Authorization"), Some("Basic YWRtaW46c2VjcmV0"));
+        assert_eq!(header(&req, "Cookie"), Some("session=xyz; theme=dark"));
+        assert_eq!(header(&req, "Accept-Encoding"), Some("gzip, deflate, br"));
+        assert_eq!(req.url, "https://example.com");
+    }
+
+    #[test]
+    fn parse_bundled_and_attached_flags() {
+        let req = parse_curl_command("curl -sSL -XDELETE -H'X-Id: 7' https://example.com/items/7")
+            .unwrap();
+        assert_eq!(req.method.as_str(), "DELETE");
+        assert_eq!(header(&req, "X-Id"), Some("7"));
+    }
+
+    #[test]
+    fn parse_query_string_into_params() {
+        let req = parse_curl_command("curl 'https://example.com/search?q=hello%20world&page=2'")
+            .unwrap();
+        assert_eq!(req.url, "https://example.com/search");
+        assert_eq!(req.query_params.len(), 2);
+        assert_eq!(req.query_params[0].value, "hello world");
+    }
+
+    #[test]
+    fn parse_get_with_data() {
+        let req = parse_curl_command("curl -G -d 'q=rust' --data-urlencode 'tag=a b' https://example.com")
+            .unwrap();
+        assert_eq!(req.method.as_str(), "GET");
+        assert_eq!(req.body_type.as_str(), "none");
+        assert_eq!(req.query_params[1].key, "tag");
+        assert_eq!(req.query_params[1].value, "a b");
+    }
+
+    #[test]
+    fn parse_graphql_body() {
+        let cmd = r#"curl https://example.com/graphql -H 'Content-Type: application/json' -d '{"query":"{ me { id } }","variables":{"a":1}}'"#;
+        let req = parse_curl_command(cmd).unwrap();
+        assert_eq!(req.body_type.as_str(), "graphql");
+        let content: serde_json::Value =
+            serde_json::from_str(req.body_content.as_deref().unwrap()).unwrap();
+        assert_eq!(content["query"], "{ me { id } }");
+        assert!(content["variables"].as_str().unwrap().contains("\"a\""));
+    }
+
+    #[test]
+    fn parse_head_and_long_options_with_equals() {
+        let req = parse_curl_command("curl -I --max-time 5 --url=https://example.com").unwrap();
+        assert_eq!(req.method.as_str(), "HEAD");
+        assert_eq!(req.url, "https://example.com");
+    }
+
+    #[test]
+    fn parse_double_quote_escapes() {
+        let req = parse_curl_command(r#"curl -d "{\"a\":\"\$HOME\"}" https://example.com"#).unwrap();
+        assert_eq!(req.body_content.as_deref(), Some(r#"{"a":"$HOME"}"#));
+    }
+
+    #[test]
+    fn parse_skips_arguments_of_ignored_options() {
+        let req = parse_curl_command("curl -sS -D - -o /dev/null https://example.com/health").unwrap();
+        assert_eq!(req.url, "https://example.com/health");
+        assert_eq!(req.method.as_str(), "GET");
+
+        let req = parse_curl_command(
+            "curl -r 0-99 -C - -Y 1000 -y 30 --noproxy '*' --cert-type PEM https://example.com/file",
+        )
+        .unwrap();
+        assert_eq!(req.url, "https://example.com/file");
+
+        let req = parse_curl_command(
+            "curl --aws-sigv4 'aws:amz:us-east-1:s3' --request-target '*' -K ~/.curlrc https://s3.example.com",
+        )
+        .unwrap();
+        assert_eq!(req.url, "https://s3.example.com");
+    }
+
+    #[test]
+    fn parse_upload_and_bearer_token() {
+        let req = parse_curl_command(
+            "curl -T ./report.csv --oauth2-bearer 'tok-123' https://example.com/upload",
+        )
+        .unwrap();
+        assert_eq!(req.url, "https://example.com/upload");
+        assert_eq!(req.method.as_str(), "PUT");
+        assert_eq!(header(&req, "Authorization"), Some("Bearer tok-123"));
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert!(parse_curl_command("").is_err());
+        assert!(parse_curl_command("wget https://example.com").is_err());
+        assert!(parse_curl_command("curl -X POST").is_err());
+        assert!(parse_curl_command("curl 'https://example.com").is_err());
+        assert!(parse_curl_command("curl -H").is_err());
+    }
+}
@@ -0,0 +1,758 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::{url_path, ImportResult};
+use crate::db::Database;
+use crate::http::executor::urlencoding_encode;
+use crate::models::collection::{CreateCollectionInput, CreateFolderInput};
+use crate::models::history::{HistoryEntry, HistoryQuery};
+use crate::models::request::{
+    BodyType, CreateRequestInput, ExecuteRequestInput, HttpMethod, KeyValue, RequestKind,
+};
+
+/// HTTP Archive 1.2 format types
+#[derive(Debug, Serialize, Deserialize)]
+pub struct HarFile {
+    pub log: HarLog,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct HarLog {
+    pub version: String,
+    pub creator: HarCreator,
+    pub entries: Vec<HarEntry>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct HarCreator {
+    pub name: String,
+    pub version: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HarEntry {
+    pub started_date_time: String,
+    pub time: f64,
+    pub request: HarRequest,
+    pub response: HarResponse,
+    #[serde(default)]
+    pub cache: serde_json::Value,
+    #[serde(default)]
+    pub timings: Option<HarTimings>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HarRequest {
+    pub method: String,
+    pub url: String,
+    #[serde(default)]
+    pub http_version: String,
+    #[serde(default)]
+    pub headers: Vec<HarNameValue>,
+    #[serde(default)]
+    pub query_string: Vec<HarNameValue>,
+    #[serde(default)]
+    pub cookies: Vec<HarNameValue>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub post_data: Option<HarPostData>,
+    #[serde(default = "unknown_size")]
+    pub headers_size: i64,
+    #[serde(default = "unknown_size")]
+    pub body_size: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HarResponse {
+    pub status: i64,
+    #[serde(default)]
+    pub status_text: String,
+    #[serde(default)]
+    pub http_version: String,
+    #[serde(default)]
+    pub headers: Vec<HarNameValue>,
+    #[serde(default)]
+    pub cookies: Vec<HarNameValue>,
+    #[serde(default)]
+    pub content: HarContent,
+    #[serde(default, rename = "redirectURL")]
+    pub redirect_url: String,
+    #[serde(default = "unknown_size")]
+    pub headers_size: i64,
+    #[serde(default = "unknown_size")]
+    pub body_size: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HarNameValue {
+    pub name: String,
+    #[serde(default)]
+    pub value: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HarPostData {
+    #[serde(default)]
+    pub mime_type: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub params: Vec<HarNameValue>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HarContent {
+    #[serde(default)]
+    pub size: i64,
+    #[serde(default)]
+    pub mime_type: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub encoding: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct HarTimings {
+    #[serde(default)]
+    pub send: f64,
+    #[serde(default)]
+    pub wait: f64,
+    #[serde(default)]
+    pub receive: f64,
+}
+
+fn unknown_size() -> i64 {
+    -1
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HarImportOptions {
+    /// Name of the created collection; defaults to "HAR Import".
+    pub collection_name: Option<String>,
+    /// Create one folder per host instead of importing all requests at the root.
+    #[serde(default)]
+    pub group_by_host: bool,
+}
+
+/// Imports the entries of a HAR file as requests in a new collection.
+pub fn import_har(
+    db: &Database,
+    json_content: &str,
+    workspace_id: &str,
+    options: &HarImportOptions,
+) -> Result<ImportResult, String> {
+    let har: HarFile =
+        serde_json::from_str(json_content).map_err(|e| format!("Invalid HAR JSON: {}", e))?;
+
+    let collection_name = options
+        .collection_name
+        .clone()
+        .filter(|n| !n.trim().is_empty())
+        .unwrap_or_else(|| "HAR Import".to_string());
+    let collection = db.create_collection(CreateCollectionInput {
+        workspace_id: workspace_id.to_string(),
+        name: collection_name.clone(),
+        description: Some(format!("Imported from HAR ({})", har.log.creator.name)),
+    })?;
+
+    let mut host_folders: HashMap<String, String> = HashMap::new();
+    let mut request_count = 0;
+
+    for entry in &har.log.entries {
+        let folder_id = if options.group_by_host {
+            let host = url_host(&entry.request.url);
+            match host_folders.get(&host) {
+                Some(id) => Some(id.clone()),
+                None => {
+                    let folder = db.create_folder(CreateFolderInput {
+                        collection_id: collection.id.clone(),
+                        parent_folder_id: None,
+                        name: host.clone(),
+                        description: None,
+                    })?;
+                    host_folders.insert(host, folder.id.clone());
+                    Some(folder.id)
+                }
+            }
+        } else {
+            None
+        };
+
+        let mut input = har_request_to_input(&entry.request);
+        input.collection_id = Some(collection.id.clone());
+        input.folder_id = folder_id;
+        db.create_request(input)?;
+        request_count += 1;
+    }
+
+    Ok(ImportResult {
+        collection_name,
+        request_count,
+        folder_count: host_folders.len(),
+        warnings: vec![],
+    })
+}
+
+fn har_request_to_input(request: &HarRequest) -> CreateRequestInput {
+    let method = HttpMethod::from_str(&request.method).unwrap_or(HttpMethod::GET);
+
+    // Keep the query in the URL only when the archive did not break it out
+    let url = if request.query_string.is_empty() {
+        request.url.clone()
+    } else {
+        request.url.split('?').next().unwrap_or_default().to_string()
+    };
+
+    let headers: Vec<KeyValue> = request
+        .headers
+        .iter()
+        // HTTP/2 pseudo-headers and transport headers are recomputed on send
+        .filter(|h| !h.name.starts_with(':'))
+        .filter(|h| !h.name.eq_ignore_ascii_case("content-length"))
+        .map(|h| KeyValue {
+            key: h.name.clone(),
+            value: h.value.clone(),
+            enabled: true,
+        })
+        .collect();
+
+    let query_params: Vec<KeyValue> = request
+        .query_string
+        .iter()
+        .map(|q| KeyValue {
+            key: q.name.clone(),
+            value: q.value.clone(),
+            enabled: true,
+        })
+        .collect();
+
+    let (body_type, body_content) = match &request.post_data {
+        Some(post) => har_post_data_to_body(post),
+        None => (BodyType::None, None),
+    };
+
+    let name = format!("{} {}", method.as_str(), url_path(&url));
+
+    CreateRequestInput {
+        name,
+        method,
+        url,
+        headers,
+        query_params,
+        body_type,
+        body_content,
+        collection_id: None,
+        folder_id: None,
+        description: None,
+        path_params: vec![],
+        scripts: None,
+        kind: RequestKind::Http,
+    }
+}
+
+fn har_post_data_to_body(post: &HarPostData) -> (BodyType, Option<String>) {
+    let mime = post.mime_type.to_lowercase();
+    let params_text = || {
+        post.params
+            .iter()
+            .map(|p| format!("{}={}", p.name, p.value))
+            .collect::<Vec<_>>()
+            .join("&")
+    };
+
+    if mime.contains("x-www-form-urlencoded") {
+        let content = post.text.clone().unwrap_or_else(params_text);
+        (BodyType::FormUrlEncoded, Some(content))
+    } else if mime.contains("multipart/form-data") {
+        let content = if post.params.is_empty() {
+            post.text.clone().unwrap_or_default()
+        } else {
+            params_text()
+        };
+        (BodyType::Multipart, Some(content))
+    } else if mime.contains("json") {
+        (BodyType::Json, post.text.clone())
+    } else if post.text.as_deref().is_none_or(str::is_empty) {
+        (BodyType::None, None)
+    } else {
+        (BodyType::Text, post.text.clone())
+    }
+}
+
+fn url_host(url: &str) -> String {
+    let after_scheme = url.split("://").nth(1).unwrap_or(url);
+    let host = after_scheme
+        .split(['/', '?', '#'])
+        .next()
+        .unwrap_or_default();
+    if host.is_empty() {
+        "unknown".to_string()
+    } else {
+        host.to_string()
+    }
+}
+
+/// Exports history entries as a HAR 1.2 document. An empty `entry_ids` list
+/// exports the whole history of the workspace.
+pub fn export_history_har(
+    db: &Database,
+    workspace_id: &str,
+    entry_ids: &[String],
+) -> Result<String, String> {
+    let entries: Vec<HistoryEntry> = if entry_ids.is_empty() {
+        // SQLite treats a negative LIMIT as "no limit"
+        db.list_history(&HistoryQuery {
+            workspace_id: workspace_id.to_string(),
+            limit: Some(-1),
+            ..Default::default()
+        })?
+    } else {
+        let mut found = Vec::with_capacity(entry_ids.len());
+        for id in entry_ids {
+            // Entries of other workspaces are reported like missing ones
+            let entry = db
+                .get_history_entry(id)?
+                .filter(|entry| entry.workspace_id == workspace_id)
+                .ok_or_else(|| format!("History entry not found: {}", id))?;
+            found.push(entry);
+        }
+        found
+    };
+
+    let har = HarFile {
+        log: HarLog {
+            version: "1.2".to_string(),
+            creator: HarCreator {
+                name: "Steq".to_string(),
+                version: env!("CARGO_PKG_VERSION").to_string(),
+            },
+            entries: entries.iter().map(history_to_har_entry).collect(),
+        },
+    };
+
+    serde_json::to_string_pretty(&har).map_err(|e| format!("Failed to serialize: {}", e))
+}
+
+fn history_to_har_entry(entry: &HistoryEntry) -> HarEntry {
+    let snapshot: Option<ExecuteRequestInput> = serde_json::from_str(&entry.request_snapshot).ok();
+
+    let request = match &snapshot {
+        Some(input) => snapshot_to_har_request(input),
+        None => HarRequest {
+            method: entry.method.clone(),
+            url: entry.url.clone(),
+            http_version: "HTTP/1.1".to_string(),
+            headers: vec![],
+            query_string: vec![],
+            cookies: vec![],
+            post_data: None,
+            headers_size: -1,
+            body_size: -1,
+        },
+    };
+
+    let response_headers: Vec<HarNameValue> = entry
+        .response_headers
+        .as_deref()
+        .and_then(|h| serde_json::from_str::<Vec<KeyValue>>(h).ok())
+        .unwrap_or_default()
+        .into_iter()
+        .map(|kv| HarNameValue {
+            name: kv.key,
+            value: kv.value,
+        })
+        .collect();
+    let mime_type = response_headers
+        .iter()
+        .find(|h| h.name.eq_ignore_ascii_case("content-type"))
+        .map(|h| h.value.clone())
+        .unwrap_or_default();
+
+    let status = entry.response_status.unwrap_or(0);
+    let status_text = u16::try_from(status)
+        .ok()
+        .and_then(|s| reqwest::StatusCode::from_u16(s).ok())
+        .and_then(|s| s.canonical_reason())
+        .unwrap_or("")
+        .to_string();
+    let body = entry.response_body.clone().unwrap_or_default();
+    let body_size = entry.response_size.unwrap_or(body.len() as i64);
+    let duration = entry.duration_ms.unwrap_or(0) as f64;
+
+    HarEntry {
+        started_date_time: entry.executed_at.clone(),
+        time: duration,
+        request,
+        response: HarResponse {
+            status: status as i64,
+            status_text,
+            http_version: "HTTP/1.1".to_string(),
+            headers: response_headers,
+            cookies: vec![],
+            content: HarContent {
+                size: body_size,
+                mime_type,
+                text: Some(body),
+                encoding: None,
+            },
+            redirect_url: String::new(),
+            headers_size: -1,
+            body_size,
+        },
+        cache: serde_json::json!({}),
+        timings: Some(HarTimings {
+            send: 0.0,
+            wait: duration,
+            receive: 0.0,
+        }),
+        comment: entry.error.clone(),
+    }
+}
+
+fn snapshot_to_har_request(input: &ExecuteRequestInput) -> HarRequest {
+    let enabled_params: Vec<&KeyValue> = input.query_params.iter().filter(|kv| kv.enabled).collect();
+    let mut url = input.url.clone();
+    if !enabled_params.is_empty() {
+        let query: Vec<String> = enabled_params
+            .iter()
+            .map(|kv| format!("{}={}", urlencoding_encode(&kv.key), urlencoding_encode(&kv.value)))
+            .collect();
+        let separator = if url.contains('?') { "&" } else { "?" };
+        url = format!("{}{}{}", url, separator, query.join("&"));
+    }
+
+    let mut headers: Vec<HarNameValue> = input
+        .headers
+        .iter()
+        .filter(|h| h.enabled)
+        .map(|h| HarNameValue {
+            name: h.key.clone(),
+            value: h.value.clone(),
+        })
+        .collect();
+
+    let mime_type = match input.body_type {
+        BodyType::Json | BodyType::GraphQL => "application/json",
+        BodyType::Text => "text/plain",
+        BodyType::FormUrlEncoded => "application/x-www-form-urlencoded",
+        BodyType::Multipart => "multipart/form-data",
+        BodyType::None => "",
+    };
+    let post_data = match (&input.body_type, &input.body_content) {
+        (BodyType::None, _) | (_, None) => None,
+        (_, Some(text)) => {
+            if !headers.iter().any(|h| h.name.eq_ignore_ascii_case("content-type")) {
+                headers.push(HarNameValue {
+                    name: "Content-Type".to_string(),
+                    value: mime_type.to_string(),
+                });
+            }
+            Some(HarPostData {
+                mime_type: mime_type.to_string(),
+                text: Some(text.clone()),
+                params: vec![],
+            })
+        }
+    };
+    let body_size = post_data
+        .as_ref()
+        .and_then(|p| p.text.as_ref())
+        .map_or(0, |t| t.len() as i64);
+
+    HarRequest {
+        method: input.method.as_str().to_string(),
+        url,
+        http_version: "HTTP/1.1".to_string(),
+        headers,
+        query_string: enabled_params
+            .iter()
+            .map(|kv| HarNameValue {
+                name: kv.key.clone(),
+                value: kv.value.clone(),
+            })
+            .collect(),
+        cookies: vec![],
+        post_data,
+        headers_size: -1,
+        body_size,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rusqlite::Connection;
+
+    fn setup_test_db() -> (Database, String) {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch("PRAGMA foreign_keys=ON;").unwrap();
+        let db = Database { conn };
+        db.run_migrations().unwrap();
+        let workspace = db.get_current_workspace().unwrap();
+        (db, workspace.id)
+    }
+
+    const SAMPLE_HAR: &str = r#"{
+        "log": {
+            "version": "1.2",
+            "creator": { "name": "WebInspector", "version": "537.36" },
+            "entries": [
+                {
+                    "startedDateTime": "2024-05-01T10:00:00.000Z",
+                    "time": 120.5,
+                    "request": {
+                        "method": "GET",
+                        "url": "https://api.example.com/users?page=2",
+                        "httpVersion": "HTTP/2",
+                        "headers": [
+                            { "name": ":authority", "value": "api.example.com" },
+                            { "name": "Accept", "value": "application/json" }
+                        ],
+                        "queryString": [{ "name": "page", "value": "2" }],
+                        "cookies": [],
+                        "headersSize": -1,
+                        "bodySize": 0
+                    },
+                    "response": {
+                        "status": 200,
+                        "statusText": "OK",
+                        "httpVersion": "HTTP/2",
+                        "headers": [],
+                        "cookies": [],
+                        "content": { "size": 2, "mimeType": "application/json", "text": "[]" },
+                        "redirectURL": "",
+                        "headersSize": -1,
+                        "bodySize": 2
+                    },
+                    "cache": {},
+                    "timings": { "send": 1, "wait": 100, "receive": 19.5 }
+                },
+                {
+                    "startedDateTime": "2024-05-01T10:00:01.000Z",
+                    "time": 80,
+                    "request": {
+                        "method": "POST",
+                        "url": "https://auth.example.com/login",
+                        "httpVersion": "HTTP/1.1",
+                        "headers": [
+                            { "name": "Content-Type", "value": "application/json" },
+                            { "name": "Content-Length", "value": "17" }
+                        ],
+                        "queryString": [],
+                        "cookies": [],
+                        "postData": { "mimeType": "application/json", "text": "{\"user\":\"ada\"}" },
+                        "headersSize": -1,
+                        "bodySize": 17
+                    },
+                    "response": {
+                        "status": 204,
+                        "statusText": "No Content",
+                        "httpVersion": "HTTP/1.1",
+                        "headers": [],
+                        "cookies": [],
+                        "content": { "size": 0, "mimeType": "" },
+                        "redirectURL": "",
+                        "headersSize": -1,
+                        "bodySize": 0
+                    },
+                    "cache": {},
+                    "timings": { "send": 1, "wait": 70, "receive": 9 }
+                },
+                {
+                    "startedDateTime": "2024-05-01T10:00:02.000Z",
+                    "time": 50,
+                    "request": {
+                        "method": "POST",
+                        "url": "https://api.example.com/search",
+                        "httpVersion": "HTTP/1.1",
+                        "headers": [],
+                        "queryString": [],
+                        "cookies": [],
+                        "postData": {
+                            "mimeType": "application/x-www-form-urlencoded",
+                            "params": [{ "name": "q", "value": "steq" }]
+                        },
+                        "headersSize": -1,
+                        "bodySize": 6
+                    },
+                    "response": {
+                        "status": 200,
+                        "statusText": "OK",
+                        "httpVersion": "HTTP/1.1",
+                        "headers": [],
+                        "cookies": [],
+                        "content": { "size": 0, "mimeType": "text/html" },
+                        "redirectURL": "",
+                        "headersSize": -1,
+                        "bodySize": 0
+                    },
+                    "cache": {}
+                }
+            ]
+        }
+    }"#;
+
+    #[test]
+    fn import_har_flat() {
+        let (db, wid) = setup_test_db();
+        let options = HarImportOptions {
+            collection_name: Some("Captured".to_string()),
+            group_by_host: false,
+        };
+
+        let result = import_har(&db, SAMPLE_HAR, &wid, &options).unwrap();
+        assert_eq!(result.collection_name, "Captured");
+        assert_eq!(result.request_count, 3);
+        assert_eq!(result.folder_count, 0);
+
+        let colls = db.list_collections(&wid).unwrap();
+        let tree = db.get_collection_tree(&colls[0].id).unwrap();
+        let get = tree.root_requests.iter().find(|r| r.name == "GET /users").unwrap();
+        assert_eq!(get.url, "https://api.example.com/users");
+        assert_eq!(get.query_params[0].key, "page");
+        assert_eq!(get.headers.len(), 1);
+
+        let login = tree.root_requests.iter().find(|r| r.name == "POST /login").unwrap();
+        assert_eq!(login.body_type.as_str(), "json");
+        assert!(!login.headers.iter().any(|h| h.key == "Content-Length"));
+
+        let search = tree.root_requests.iter().find(|r| r.name == "POST /search").unwrap();
+        assert_eq!(search.body_type.as_str(), "form_url_encoded");
+        assert_eq!(search.body_content.as_deref(), Some("q=steq"));
+    }
+
+    #[test]
+    fn import_har_grouped_by_host() {
+        let (db, wid) = setup_test_db();
+        let options = HarImportOptions {
+            collection_name: None,
+            group_by_host: true,
+        };
+
+        let result = import_har(&db, SAMPLE_HAR, &wid, &options).unwrap();
+        assert_eq!(result.collection_name, "HAR Import");
+        assert_eq!(result.folder_count, 2);
+
+        let colls = db.list_collections(&wid).unwrap();
+        let tree = db.get_collection_tree(&colls[0].id).unwrap();
+        assert!(tree.root_requests.is_empty());
+        let api = tree
+            .root_folders
+            .iter()
+            .find(|f| f.folder.name == "api.example.com")
+            .unwrap();
+        assert_eq!(api.requests.len(), 2);
+    }
+
+    #[test]
+    fn import_har_invalid_json() {
+        let (db, wid) = setup_test_db();
+        let options = HarImportOptions {
+            collection_name: None,
+            group_by_host: false,
+        };
+        assert!(import_har(&db, "{\"log\": 1}", &wid, &options).is_err());
+    }
+
+    #[test]
+    fn export_history_as_har() {
+        let (db, wid) = setup_test_db();
+        let snapshot = serde_json::to_string(&ExecuteRequestInput {
+            method: HttpMethod::POST,
+            url: "https://api.example.com/items".to_string(),
+            headers: vec![KeyValue {
+                key: "X-Trace".to_string(),
+                value: "abc".to_string(),
+                enabled: true,
+            }],
+            query_params: vec![KeyValue {
+                key: "dry run".to_string(),
+                value: "true".to_string(),
+                enabled: true,
+            }],
+            body_type: BodyType::Json,
+            body_content: Some("{\"name\":\"x\"}".to_string()),
+            path_params: vec![],
+        })
+        .unwrap();
+
+        let entry = db
+            .save_history_entry(
+                &wid,
+                None,
+                "POST",
+                "https://api.example.com/items",
+                &snapshot,
+                Some(201),
+                Some(r#"[{"key":"content-type","value":"application/json","enabled":true}]"#),
+                Some("{\"id\":7}"),
+                Some(8),
+                Some(42),
+                None,
+            )
+            .unwrap();
+        db.save_history_entry(&wid, None, "GET", "https://other.com", "{}", None, None, None, None, None, Some("Connection failed"))
+            .unwrap();
+
+        let har = export_history_har(&db, &wid, std::slice::from_ref(&entry.id)).unwrap();
+        let parsed: HarFile = serde_json::from_str(&har).unwrap();
+        assert_eq!(parsed.log.version, "1.2");
+        assert_eq!(parsed.log.entries.len(), 1);
+
+        let exported = &parsed.log.entries[0];
+        assert_eq!(exported.request.url, "https://api.example.com/items?dry%20run=true");
+        assert_eq!(exported.request.post_data.as_ref().unwrap().mime_type, "application/json");
+        assert!(exported.request.headers.iter().any(|h| h.name == "Content-Type"));
+        assert_eq!(exported.response.status, 201);
+        assert_eq!(exported.response.status_text, "Created");
+        assert_eq!(exported.response.content.text.as_deref(), Some("{\"id\":7}"));
+        assert_eq!(exported.time, 42.0);
+
+        let all = export_history_har(&db, &wid, &[]).unwrap();
+        let parsed_all: HarFile = serde_json::from_str(&all).unwrap();
+        assert_eq!(parsed_all.log.entries.len(), 2);
+    }
+
+    #[test]
+    fn export_missing_entry_fails() {
+        let (db, wid) = setup_test_db();
+        assert!(export_history_har(&db, &wid, &["nope".to_string()]).is_err());
+    }
+
+    #[test]
+    fn export_entry_of_other_workspace_fails() {
+        let (db, wid) = setup_test_db();
+        db.conn
+            .execute("INSERT INTO workspaces VALUES ('other', 'Other', 't', 't')", [])
+            .unwrap();
+        let entry = db
+            .save_history_entry("other", None, "GET", "https://a.test", "{}", Some(200), None, Some("secret"), None, None, None)
+            .unwrap();
+        let ids = [entry.id];
+        assert!(export_history_har(&db, &wid, &ids).is_err());
+        assert!(export_history_har(&db, "other", &ids).is_ok());
+    }
+
+    #[test]
+    fn har_roundtrip_through_import() {
+        let (db, wid) = setup_test_db();
+        db.save_history_entry(&wid, None, "GET", "https://api.example.com/ping", "{}", Some(200), None, Some("pong"), None, Some(5), None)
+            .unwrap();
+
+        let har = export_history_har(&db, &wid, &[]).unwrap();
+        let options = HarImportOptions {
+            collection_name: None,
+            group_by_host: false,
+        };
+        let result = import_har(&db, &har, &wid, &options).unwrap();
+        assert_eq!(result.request_count, 1);
+    }
+}
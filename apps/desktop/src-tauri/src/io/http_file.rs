@@ -0,0 +1,899 @@
+use std::collections::HashMap;
+
+use serde::Deserialize;
+
+use super::{split_query, url_path, ImportResult};
+use crate::db::Database;
+use crate::models::collection::{CollectionTreeNode, CreateCollectionInput, CreateFolderInput};
+use crate::models::environment::{CreateEnvironmentInput, UpdateEnvironmentInput, Variable};
+use crate::models::request::{
+    ApiRequest, BodyType, CreateRequestInput, HttpMethod, KeyValue, RequestKind,
+};
+
+const MULTIPART_BOUNDARY: &str = "----SteqFormBoundary";
+
+/// A parsed `.http` / `.rest` file (VS Code REST Client and JetBrains HTTP Client format).
+#[derive(Debug, Clone)]
+pub struct HttpFile {
+    pub variables: Vec<(String, String)>,
+    pub requests: Vec<HttpFileRequest>,
+}
+
+#[derive(Debug, Clone)]
+pub struct HttpFileRequest {
+    /// Folder path taken from a `# @folder a/b` annotation, if any.
+    pub folder: Option<String>,
+    pub input: CreateRequestInput,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct HttpFileImportOptions {
+    /// Name of the collection to create; ignored when `collection_id` is set.
+    pub collection_name: Option<String>,
+    /// Import into an existing collection instead of creating one.
+    pub collection_id: Option<String>,
+    /// Parent folder inside `collection_id` to import under.
+    pub folder_id: Option<String>,
+    /// Environment receiving the file's `@var` declarations. A new environment
+    /// named after the collection is created when unset.
+    pub environment_id: Option<String>,
+}
+
+/// Parses `.http` content into variables and requests.
+pub fn parse_http_file(content: &str) -> Result<HttpFile, String> {
+    let mut variables = Vec::new();
+    let mut requests = Vec::new();
+
+    let mut block_title: Option<String> = None;
+    let mut block: Vec<&str> = Vec::new();
+    for line in content.lines() {
+        if let Some(title) = line.trim_start().strip_prefix("###") {
+            if let Some(request) = parse_block(block_title.take(), &block, &mut variables)? {
+                requests.push(request);
+            }
+            block.clear();
+            let title = title.trim();
+            block_title = (!title.is_empty()).then(|| title.to_string());
+        } else {
+            block.push(line);
+        }
+    }
+    if let Some(request) = parse_block(block_title, &block, &mut variables)? {
+        requests.push(request);
+    }
+
+    Ok(HttpFile {
+        variables,
+        requests,
+    })
+}
+
+fn parse_block(
+    title: Option<String>,
+    lines: &[&str],
+    variables: &mut Vec<(String, String)>,
+) -> Result<Option<HttpFileRequest>, String> {
+    let mut name = title;
+    let mut folder = None;
+    let mut iter = lines.iter().map(|l| l.trim_end()).peekable();
+
+    // Variables, comments and annotations before the request line
+    let request_line = loop {
+        let Some(line) = iter.next() else {
+            return Ok(None);
+        };
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+        if let Some(comment) = strip_comment(trimmed) {
+            if let Some((tag, value)) = parse_annotation(comment) {
+                match tag {
+                    "name" => name = Some(value.to_string()),
+                    "folder" => folder = Some(value.to_string()).filter(|f| !f.is_empty()),
+                    _ => {}
+                }
+            }
+            continue;
+        }
+        if let Some(decl) = trimmed.strip_prefix('@') {
+            let (key, value) = decl
+                .split_once('=')
+                .ok_or_else(|| format!("Invalid variable declaration: {}", trimmed))?;
+            variables.push((key.trim().to_string(), value.trim().to_string()));
+            continue;
+        }
+        break trimmed;
+    };
+
+    let mut parts = request_line.split_whitespace();
+    let first = parts.next().unwrap_or_default();
+    let (method, mut url) = match HttpMethod::from_str(first) {
+        Ok(method) => (
+            method,
+            parts
+                .next()
+                .ok_or_else(|| format!("Missing URL in request line: {}", request_line))?
+                .to_string(),
+        ),
+        Err(_) => (HttpMethod::GET, first.to_string()),
+    };
+
+    // Multi-line query continuation (`?a=1` / `&b=2` on their own lines)
+    while let Some(next) = iter.peek() {
+        let trimmed = next.trim();
+        if trimmed.starts_with('?') || trimmed.starts_with('&') {
+            url.push_str(trimmed);
+            iter.next();
+        } else {
+            break;
+        }
+    }
+
+    let mut headers = Vec::new();
+    for line in iter.by_ref() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            break;
+        }
+        if strip_comment(trimmed).is_some() {
+            continue;
+        }
+        let (key, value) = trimmed
+            .split_once(':')
+            .ok_or_else(|| format!("Invalid header line: {}", trimmed))?;
+        headers.push(KeyValue {
+            key: key.trim().to_string(),
+            value: value.trim().to_string(),
+            enabled: true,
+        });
+    }
+
+    let body = strip_response_handlers(iter);
+    let (url, query_params) = split_query(&url);
+    let (body_type, body_content) = body_to_steq(&body, &mut headers);
+
+    let name = name.unwrap_or_else(|| format!("{} {}", method.as_str(), url_path(&url)));
+
+    Ok(Some(HttpFileRequest {
+        folder,
+        input: CreateRequestInput {
+            name,
+            method,
+            url,
+            headers,
+            query_params,
+            body_type,
+            body_content,
+            collection_id: None,
+            folder_id: None,
+            description: None,
+            path_params: vec![],
+            scripts: None,
+            kind: RequestKind::Http,
+        },
+    }))
+}
+
+fn strip_comment(line: &str) -> Option<&str> {
+    line.strip_prefix('#')
+        .or_else(|| line.strip_prefix("//"))
+        .map(str::trim)
+}
+
+/// Parses `@name value` / `@name=value` annotations inside comments.
+fn parse_annotation(comment: &str) -> Option<(&str, &str)> {
+    let rest = comment.strip_prefix('@')?;
+    let end = rest
+        .find(|c: char| c.is_whitespace() || c == '=')
+        .unwrap_or(rest.len());
+    let value = rest[end..].trim_start().trim_start_matches('=').trim();
+    Some((&rest[..end], value))
+}
+
+/// Collects body lines, dropping JetBrains response handlers (`> {% ... %}`,
+/// `> file.js`) and response redirects (`>> file`, `<> file`).
+fn strip_response_handlers<'a>(lines: impl Iterator<Item = &'a str>) -> String {
+    let mut body = Vec::new();
+    let mut in_handler = false;
+    for line in lines {
+        let trimmed = line.trim_start();
+        if in_handler {
+            in_handler = !trimmed.contains("%}");
+            continue;
+        }
+        if trimmed.starts_with("> {%") {
+            in_handler = !trimmed.contains("%}");
+            continue;
+        }
+        if trimmed.starts_with("<> ") || trimmed.starts_with(">> ") || trimmed.starts_with("> ") {
+            continue;
+        }
+        body.push(line);
+    }
+    while body.last().is_some_and(|l| l.trim().is_empty()) {
+        body.pop();
+    }
+    body.join("\n")
+}
+
+fn body_to_steq(body: &str, headers: &mut Vec<KeyValue>) -> (BodyType, Option<String>) {
+    let is_graphql =
+        take_header(headers, "x-request-type").is_some_and(|v| v.eq_ignore_ascii_case("graphql"));
+    if body.trim().is_empty() {
+        return (BodyType::None, None);
+    }
+
+    if is_graphql {
+        let (query, variables) = match body.find("\n\n") {
+            Some(i) => (&body[..i], body[i + 2..].trim()),
+            None => (body, ""),
+        };
+        let content = serde_json::json!({
+            "query": query.trim(),
+            "variables": variables,
+            "operationName": "",
+        });
+        return (BodyType::GraphQL, Some(content.to_string()));
+    }
+
+    let raw_content_type = headers
+        .iter()
+        .find(|h| h.key.eq_ignore_ascii_case("content-type"))
+        .map(|h| h.value.clone())
+        .unwrap_or_default();
+    let content_type = raw_content_type.to_lowercase();
+
+    if content_type.starts_with("multipart/form-data") {
+        let boundary = raw_content_type
+            .split(';')
+            .find_map(|p| p.trim().strip_prefix("boundary="))
+            .map(|b| b.trim_matches('"').to_string());
+        if let Some(boundary) = boundary {
+            // The boundary is regenerated on send
+            take_header(headers, "content-type");
+            return (BodyType::Multipart, Some(parse_multipart(body, &boundary)));
+        }
+    }
+
+    if content_type.contains("x-www-form-urlencoded") {
+        let joined: String = body.lines().map(str::trim).collect();
+        return (BodyType::FormUrlEncoded, Some(joined));
+    }
+
+    let trimmed = body.trim_start();
+    if content_type.contains("json")
+        || (content_type.is_empty() && (trimmed.starts_with('{') || trimmed.starts_with('[')))
+    {
+        return (BodyType::Json, Some(body.to_string()));
+    }
+
+    (BodyType::Text, Some(body.to_string()))
+}
+
+fn take_header(headers: &mut Vec<KeyValue>, name: &str) -> Option<String> {
+    let index = headers
+        .iter()
+        .position(|h| h.key.eq_ignore_ascii_case(name))?;
+    Some(headers.remove(index).value)
+}
+
+/// Converts a multipart body into Steq's `key=value&file=@path` form.
+fn parse_multipart(body: &str, boundary: &str) -> String {
+    let delimiter = format!("--{}", boundary);
+    let mut fields = Vec::new();
+
+    for part in body.split(delimiter.as_str()).skip(1) {
+        if part.trim_start().starts_with("--") {
+            break;
+        }
+        let part = part.trim_start_matches(['\r', '\n']);
+        let (head, content) = part.split_once("\n\n").unwrap_or((part, ""));
+
+        let Some(name) = head
+            .lines()
+            .find(|l| l.to_lowercase().starts_with("content-disposition"))
+            .and_then(|l| disposition_param(l, "name"))
+        else {
+            continue;
+        };
+
+        let content = content.trim_end_matches(['\r', '\n']);
+        let value = match content.trim().strip_prefix("< ") {
+            Some(path) => format!("@{}", path.trim()),
+            None => content.to_string(),
+        };
+        fields.push(format!("{}={}", name, value));
+    }
+
+    fields.join("&")
+}
+
+fn disposition_param(line: &str, param: &str) -> Option<String> {
+    line.split(';').find_map(|p| {
+        let (key, value) = p.trim().split_once('=')?;
+        (key.trim() == param).then(|| value.trim().trim_matches('"').to_string())
+    })
+}
+
+/// Writes variables and requests in `.http` format. Each request is paired with
+/// its folder path relative to the exported root.
+pub fn write_http_file(
+    variables: &[(String, String)],
+    requests: &[(String, &ApiRequest)],
+) -> String {
+    let mut out = String::new();
+
+    for (key, value) in variables {
+        out.push_str(&format!("@{} = {}\n", key, value));
+    }
+    if !variables.is_empty() {
+        out.push('\n');
+    }
+
+    for (i, (folder, request)) in requests.iter().enumerate() {
+        if i > 0 {
+            out.push('\n');
+        }
+        write_request(&mut out, folder, request);
+    }
+
+    out
+}
+
+fn write_request(out: &mut String, folder: &str, request: &ApiRequest) {
+    out.push_str(&format!("### {}\n", request.name));
+    if !request.name.is_empty() && !request.name.contains(char::is_whitespace) {
+        out.push_str(&format!("# @name {}\n", request.name));
+    }
+    if !folder.is_empty() {
+        out.push_str(&format!("# @folder {}\n", folder));
+    }
+
+    let query: Vec<String> = request
+        .query_params
+        .iter()
+        .filter(|q| q.enabled)
+        .map(|q| {
+            format!(
+                "{}={}",
+                encode_query_part(&q.key),
+                encode_query_part(&q.value)
+            )
+        })
+        .collect();
+    let url = if query.is_empty() {
+        request.url.clone()
+    } else {
+        format!("{}?{}", request.url, query.join("&"))
+    };
+    out.push_str(&format!("{} {}\n", request.method.as_str(), url));
+
+    let has_header = |name: &str| {
+        request
+            .headers
+            .iter()
+            .any(|h| h.enabled && h.key.eq_ignore_ascii_case(name))
+    };
+    for header in request.headers.iter().filter(|h| h.enabled) {
+        out.push_str(&format!("{}: {}\n", header.key, header.value));
+    }
+
+    let content = request.body_content.as_deref().unwrap_or("");
+    match request.body_type {
+        BodyType::None => {}
+        _ if content.is_empty() => {}
+        BodyType::GraphQL => {
+            out.push_str("X-REQUEST-TYPE: GraphQL\n\n");
+            let value: serde_json::Value = serde_json::from_str(content).unwrap_or_default();
+            out.push_str(value["query"].as_str().unwrap_or(content).trim());
+            out.push('\n');
+            if let Some(vars) = value["variables"].as_str().filter(|v| !v.trim().is_empty()) {
+                out.push('\n');
+                out.push_str(vars.trim());
+                out.push('\n');
+            }
+        }
+        BodyType::Multipart => {
+            out.push_str(&format!(
+                "Content-Type: multipart/form-data; boundary={}\n\n",
+                MULTIPART_BOUNDARY
+            ));
+            for field in content.split('&').filter(|f| !f.is_empty()) {
+                let (key, value) = field.split_once('=').unwrap_or((field, ""));
+                out.push_str(&format!("--{}\n", MULTIPART_BOUNDARY));
+                match value.strip_prefix('@') {
+                    Some(path) => {
+                        let file_name = path.rsplit(['/', '\\']).next().unwrap_or(path);
+                        out.push_str(&format!(
+                            "Content-Disposition: form-data; name=\"{}\"; filename=\"{}\"\n\n< {}\n",
+                            key, file_name, path
+                        ));
+                    }
+                    None => out.push_str(&format!(
+                        "Content-Disposition: form-data; name=\"{}\"\n\n{}\n",
+                        key, value
+                    )),
+                }
+            }
+            out.push_str(&format!("--{}--\n", MULTIPART_BOUNDARY));
+        }
+        BodyType::FormUrlEncoded => {
+            if !has_header("content-type") {
+                out.push_str("Content-Type: application/x-www-form-urlencoded\n");
+            }
+            out.push('\n');
+            out.push_str(content);
+            out.push('\n');
+        }
+        BodyType::Json | BodyType::Text => {
+            if matches!(request.body_type, BodyType::Json) && !has_header("content-type") {
+                out.push_str("Content-Type: application/json\n");
+            }
+            out.push('\n');
+            out.push_str(content.trim_end());
+            out.push('\n');
+        }
+    }
+}
+
+/// Escapes only the characters that would break the query on re-parse,
+/// leaving `{{variables}}` readable.
+fn encode_query_part(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '%' | '&' | '=' | '#' | '+' | ' ' | '\n' => out.push_str(&format!("%{:02X}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// Imports a `.http` file. `# @folder` annotations recreate folders, and
+/// file-level `@var` declarations are merged into an environment.
+pub fn import_http_file(
+    db: &Database,
+    content: &str,
+    workspace_id: &str,
+    options: &HttpFileImportOptions,
+) -> Result<ImportResult, String> {
+    let file = parse_http_file(content)?;
+
+    let (collection_id, collection_name) = match &options.collection_id {
+        Some(id) => {
+            let collection = db
+                .list_collections(workspace_id)?
+                .into_iter()
+                .find(|c| &c.id == id)
+                .ok_or_else(|| format!("Collection not found: {}", id))?;
+            (collection.id, collection.name)
+        }
+        None => {
+            let name = options
+                .collection_name
+                .clone()
+                .unwrap_or_else(|| "HTTP File Import".to_string());
+            let collection = db.create_collection(CreateCollectionInput {
+                workspace_id: workspace_id.to_string(),
+                name: name.clone(),
+                description: None,
+            })?;
+            (collection.id, name)
+        }
+    };
+
+    let mut folders: HashMap<String, String> = HashMap::new();
+    let mut request_count = 0;
+    for request in file.requests {
+        let folder_id = match &request.folder {
+            Some(path) => Some(ensure_folder_path(
+                db,
+                &collection_id,
+                options.folder_id.as_deref(),
+                path,
+                &mut folders,
+            )?),
+            None => options.folder_id.clone(),
+        };
+
+        let mut input = request.input;
+        input.collection_id = Some(collection_id.clone());
+        input.folder_id = folder_id;
+        db.create_request(input)?;
+        request_count += 1;
+    }
+
+    if !file.variables.is_empty() {
+        merge_variables(db, workspace_id, options, &collection_name, &file.variables)?;
+    }
+
+    Ok(ImportResult {
+        collection_name,
+        request_count,
+        folder_count: folders.len(),
+        warnings: vec![],
+    })
+}
+
+fn ensure_folder_path(
+    db: &Database,
+    collection_id: &str,
+    root_folder_id: Option<&str>,
+    path: &str,
+    folders: &mut HashMap<String, String>,
+) -> Result<String, String> {
+    let mut parent = root_folder_id.map(|s| s.to_string());
+    let mut current_path = String::new();
+    for segment in path.split('/').map(str::trim).filter(|s| !s.is_empty()) {
+        if !current_path.is_empty() {
+            current_path.push('/');
+        }
+        current_path.push_str(segment);
+
+        let id = match folders.get(&current_path) {
+            Some(id) => id.clone(),
+            None => {
+                let folder = db.create_folder(CreateFolderInput {
+                    collection_id: collection_id.to_string(),
+                    parent_folder_id: parent.clone(),
+                    name: segment.to_string(),
+                    description: None,
+                })?;
+                folders.insert(current_path.clone(), folder.id.clone());
+                folder.id
+            }
+        };
+        parent = Some(id);
+    }
+    parent.ok_or_else(|| format!("Invalid folder path: {}", path))
+}
+
+fn merge_variables(
+    db: &Database,
+    workspace_id: &str,
+    options: &HttpFileImportOptions,
+    collection_name: &str,
+    declared: &[(String, String)],
+) -> Result<(), String> {
+    let env = match &options.environment_id {
+        Some(id) => db
+            .get_environment(id)?
+            .ok_or_else(|| format!("Environment not found: {}", id))?,
+        None => db.create_environment(&CreateEnvironmentInput {
+            workspace_id: workspace_id.to_string(),
+            name: collection_name.to_string(),
+        })?,
+    };
+
+    let mut variables = env.variables;
+    for (key, value) in declared {
+        match variables.iter_mut().find(|v| &v.key == key) {
+            Some(existing) => existing.value = value.clone(),
+            None => variables.push(Variable {
+                id: String::new(),
+                environment_id: env.id.clone(),
+                key: key.clone(),
+                value: value.clone(),
+                is_secret: false,
+                enabled: true,
+                sort_order: variables.len() as i32,
+                created_at: String::new(),
+                updated_at: String::new(),
+            }),
+        }
+    }
+
+    db.update_environment(&UpdateEnvironmentInput {
+        id: env.id,
+        name: None,
+        variables: Some(variables),
+    })?;
+    Ok(())
+}
+
+/// Exports a collection, or one folder of it, as `.http` content. Enabled,
+/// non-secret variables of `environment_id` are written as `@var` declarations.
+pub fn export_http_file(
+    db: &Database,
+    collection_id: &str,
+    folder_id: Option<&str>,
+    environment_id: Option<&str>,
+) -> Result<String, String> {
+    let tree = db.get_collection_tree(collection_id)?;
+
+    let mut requests: Vec<(String, &ApiRequest)> = Vec::new();
+    match folder_id {
+        Some(id) => {
+            let node = find_folder(&tree.root_folders, id)
+                .ok_or_else(|| format!("Folder not found: {}", id))?;
+            collect_requests(node, "", &mut requests);
+        }
+        None => {
+            requests.extend(tree.root_requests.iter().map(|r| (String::new(), r)));
+            for node in &tree.root_folders {
+                collect_requests(node, &node.folder.name, &mut requests);
+            }
+        }
+    }
+
+    let variables: Vec<(String, String)> = match environment_id {
+        Some(id) => db
+            .get_environment(id)?
+            .ok_or_else(|| format!("Environment not found: {}", id))?
+            .variables
+            .into_iter()
+            // Secrets stay out of files that end up in repositories
+            .filter(|v| v.enabled && !v.is_secret)
+            .map(|v| (v.key, v.value))
+            .collect(),
+        None => vec![],
+    };
+
+    Ok(write_http_file(&variables, &requests))
+}
+
+fn find_folder<'a>(nodes: &'a [CollectionTreeNode], id: &str) -> Option<&'a CollectionTreeNode> {
+    nodes.iter().find_map(|node| {
+        if node.folder.id == id {
+            Some(node)
+        } else {
+            find_folder(&node.children, id)
+        }
+    })
+}
+
+fn collect_requests<'a>(
+    node: &'a CollectionTreeNode,
+    path: &str,
+    out: &mut Vec<(String, &'a ApiRequest)>,
+) {
+    out.extend(node.requests.iter().map(|r| (path.to_string(), r)));
+    for child in &node.children {
+        let child_path = if path.is_empty() {
+            child.folder.name.clone()
+        } else {
+            format!("{}/{}", path, child.folder.name)
+        };
+        collect_requests(child, &child_path, out);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rusqlite::Connection;
+
+    fn setup_test_db() -> (Database, String) {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch("PRAGMA foreign_keys=ON;").unwrap();
+        let db = Database { conn };
+        db.run_migrations().unwrap();
+        let workspace = db.get_current_workspace().unwrap();
+        (db, workspace.id)
+    }
+
+    const SAMPLE: &str = r#"@baseUrl = https://api.example.com
+@token = abc123
+
+### List users
+GET {{baseUrl}}/users?page=1
+    &tag=a%20b
+Authorization: Bearer {{token}}
+
+### Create user
+# @name createUser
+POST {{baseUrl}}/users HTTP/1.1
+Content-Type: application/json
+
+{
+  "name": "Ada"
+}
+
+> {%
+  client.global.set("id", response.body.id);
+%}
+
+###
+// @folder Admin/Uploads
+POST {{baseUrl}}/upload
+Content-Type: multipart/form-data; boundary=WebKitBoundary
+
+--WebKitBoundary
+Content-Disposition: form-data; name="note"
+
+hello
+--WebKitBoundary
+Content-Disposition: form-data; name="file"; filename="a.png"
+Content-Type: image/png
+
+< ./a.png
+--WebKitBoundary--
+
+### Query
+# @folder Admin
+POST {{baseUrl}}/graphql
+X-REQUEST-TYPE: GraphQL
+
+query Users($n: Int) {
+  users(first: $n) { id }
+}
+
+{"n": 5}
+"#;
+
+    #[test]
+    fn parse_variables_and_requests() {
+        let file = parse_http_file(SAMPLE).unwrap();
+        assert_eq!(
+            file.variables,
+            vec![
+                ("baseUrl".to_string(), "https://api.example.com".to_string()),
+                ("token".to_string(), "abc123".to_string()),
+            ]
+        );
+        assert_eq!(file.requests.len(), 4);
+
+        let list = &file.requests[0].input;
+        assert_eq!(list.name, "List users");
+        assert_eq!(list.url, "{{baseUrl}}/users");
+        assert_eq!(list.query_params[1].value, "a b");
+        assert_eq!(list.headers[0].value, "Bearer {{token}}");
+        assert_eq!(list.body_type.as_str(), "none");
+
+        let create = &file.requests[1].input;
+        assert_eq!(create.name, "createUser");
+        assert_eq!(create.body_type.as_str(), "json");
+        assert_eq!(
+            create.body_content.as_deref(),
+            Some("{\n  \"name\": \"Ada\"\n}")
+        );
+    }
+
+    #[test]
+    fn parse_multipart_and_graphql() {
+        let file = parse_http_file(SAMPLE).unwrap();
+
+        let upload = &file.requests[2];
+        assert_eq!(upload.folder.as_deref(), Some("Admin/Uploads"));
+        assert_eq!(upload.input.name, "POST /upload");
+        assert_eq!(upload.input.body_type.as_str(), "multipart");
+        assert_eq!(
+            upload.input.body_content.as_deref(),
+            Some("note=hello&file=@./a.png")
+        );
+        assert!(upload.input.headers.is_empty());
+
+        let query = &file.requests[3].input;
+        assert_eq!(query.body_type.as_str(), "graphql");
+        assert!(query.headers.is_empty());
+        let content: serde_json::Value =
+            serde_json::from_str(query.body_content.as_deref().unwrap()).unwrap();
+        assert!(content["query"]
+            .as_str()
+            .unwrap()
+            .starts_with("query Users"));
+        assert_eq!(content["variables"], "{\"n\": 5}");
+    }
+
+    #[test]
+    fn parse_drops_url_fragment() {
+        let file = parse_http_file("GET https://x.test/docs?page=2#install\n").unwrap();
+        let input = &file.requests[0].input;
+        assert_eq!(input.url, "https://x.test/docs");
+        assert_eq!(input.query_params[0].value, "2");
+        assert_eq!(input.name, "GET /docs");
+    }
+
+    #[test]
+    fn parse_rejects_bad_header() {
+        assert!(parse_http_file("GET https://x.test\nnot a header\n").is_err());
+    }
+
+    #[test]
+    fn import_creates_folders_and_environment() {
+        let (db, wid) = setup_test_db();
+        let options = HttpFileImportOptions {
+            collection_name: Some("api".to_string()),
+            ..Default::default()
+        };
+        let result = import_http_file(&db, SAMPLE, &wid, &options).unwrap();
+        assert_eq!(result.request_count, 4);
+        assert_eq!(result.folder_count, 2);
+
+        let colls = db.list_collections(&wid).unwrap();
+        let tree = db.get_collection_tree(&colls[0].id).unwrap();
+        assert_eq!(tree.root_requests.len(), 2);
+        let admin = &tree.root_folders[0];
+        assert_eq!(admin.folder.name, "Admin");
+        assert_eq!(admin.requests[0].name, "Query");
+        assert_eq!(admin.children[0].folder.name, "Uploads");
+
+        let envs = db.list_environments(&wid).unwrap();
+        let env = envs.iter().find(|e| e.name == "api").unwrap();
+        assert_eq!(env.variables.len(), 2);
+        assert_eq!(env.variables[1].key, "token");
+    }
+
+    #[test]
+    fn import_merges_into_existing_environment() {
+        let (db, wid) = setup_test_db();
+        let env = db
+            .create_environment(&CreateEnvironmentInput {
+                workspace_id: wid.clone(),
+                name: "Dev".to_string(),
+            })
+            .unwrap();
+        let options = HttpFileImportOptions {
+            environment_id: Some(env.id.clone()),
+            ..Default::default()
+        };
+        import_http_file(&db, "@host = a\n\nGET {{host}}/x\n", &wid, &options).unwrap();
+        import_http_file(
+            &db,
+            "@host = b\n@port = 1\n\nGET {{host}}/y\n",
+            &wid,
+            &options,
+        )
+        .unwrap();
+
+        let env = db.get_environment(&env.id).unwrap().unwrap();
+        let pairs: Vec<(&str, &str)> = env
+            .variables
+            .iter()
+            .map(|v| (v.key.as_str(), v.value.as_str()))
+            .collect();
+        assert_eq!(pairs, vec![("host", "b"), ("port", "1")]);
+    }
+
+    #[test]
+    fn collection_round_trip() {
+        let (db, wid) = setup_test_db();
+        let options = HttpFileImportOptions {
+            collection_name: Some("api".to_string()),
+            ..Default::default()
+        };
+        import_http_file(&db, SAMPLE, &wid, &options).unwrap();
+        let coll = db.list_collections(&wid).unwrap().remove(0);
+        let env = db.list_environments(&wid).unwrap().remove(0);
+
+        let exported = export_http_file(&db, &coll.id, None, Some(&env.id)).unwrap();
+        assert!(exported.starts_with("@baseUrl = https://api.example.com\n"));
+
+        let original = parse_http_file(SAMPLE).unwrap();
+        let reparsed = parse_http_file(&exported).unwrap();
+        assert_eq!(reparsed.variables, original.variables);
+        assert_eq!(reparsed.requests.len(), original.requests.len());
+        for a in &original.requests {
+            let b = reparsed
+                .requests
+                .iter()
+                .find(|r| r.input.name == a.input.name)
+                .unwrap();
+            assert_eq!(a.folder, b.folder);
+            assert_eq!(a.input.url, b.input.url);
+            assert_eq!(a.input.body_type.as_str(), b.input.body_type.as_str());
+            assert_eq!(a.input.body_content, b.input.body_content);
+            assert_eq!(a.input.query_params.len(), b.input.query_params.len());
+        }
+    }
+
+    #[test]
+    fn folder_export_is_relative() {
+        let (db, wid) = setup_test_db();
+        let options = HttpFileImportOptions {
+            collection_name: Some("api".to_string()),
+            ..Default::default()
+        };
+        import_http_file(&db, SAMPLE, &wid, &options).unwrap();
+        let coll = db.list_collections(&wid).unwrap().remove(0);
+        let tree = db.get_collection_tree(&coll.id).unwrap();
+        let admin = &tree.root_folders[0];
+
+        let exported = export_http_file(&db, &coll.id, Some(&admin.folder.id), None).unwrap();
+        let file = parse_http_file(&exported).unwrap();
+        assert_eq!(file.requests.len(), 2);
+        assert_eq!(file.requests[0].folder, None);
+        assert_eq!(file.requests[1].folder.as_deref(), Some("Uploads"));
+    }
+}
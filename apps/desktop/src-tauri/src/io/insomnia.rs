@@ -0,0 +1,596 @@
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use serde::{Deserialize, Serialize};
+
+use super::{graphql_content, ImportResult};
+use crate::db::Database;
+use crate::models::collection::{CreateCollectionInput, CreateFolderInput};
+use crate::models::environment::{CreateEnvironmentInput, UpdateEnvironmentInput, Variable};
+use crate::models::request::{BodyType, CreateRequestInput, HttpMethod, KeyValue, RequestKind};
+
+/// Insomnia v4 export format types
+#[derive(Debug, Deserialize)]
+pub struct InsomniaExport {
+    #[serde(rename = "__export_format")]
+    pub export_format: u32,
+    pub resources: Vec<InsomniaResource>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct InsomniaResource {
+    #[serde(rename = "_id")]
+    pub id: String,
+    #[serde(rename = "_type")]
+    pub resource_type: String,
+    #[serde(rename = "parentId", default)]
+    pub parent_id: Option<String>,
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(rename = "metaSortKey", default)]
+    pub meta_sort_key: Option<f64>,
+    // Request fields
+    #[serde(default)]
+    pub method: Option<String>,
+    #[serde(default)]
+    pub url: Option<String>,
+    #[serde(default)]
+    pub body: Option<InsomniaBody>,
+    #[serde(default)]
+    pub headers: Vec<InsomniaPair>,
+    #[serde(default)]
+    pub parameters: Vec<InsomniaPair>,
+    #[serde(default)]
+    pub authentication: Option<serde_json::Value>,
+    // Environment fields
+    #[serde(default)]
+    pub data: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct InsomniaBody {
+    #[serde(rename = "mimeType", default)]
+    pub mime_type: Option<String>,
+    #[serde(default)]
+    pub text: Option<String>,
+    #[serde(default)]
+    pub params: Vec<InsomniaPair>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct InsomniaPair {
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub value: String,
+    #[serde(default)]
+    pub disabled: bool,
+    #[serde(rename = "type", default)]
+    pub pair_type: Option<String>,
+    #[serde(rename = "fileName", default)]
+    pub file_name: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InsomniaImportResult {
+    pub collections: Vec<ImportResult>,
+    pub environment_count: usize,
+}
+
+/// Imports an Insomnia v4 JSON export. Each workspace becomes a collection,
+/// request groups become folders and environments become Steq environments.
+pub fn import_insomnia_export(
+    db: &Database,
+    json_content: &str,
+    workspace_id: &str,
+) -> Result<InsomniaImportResult, String> {
+    let export: InsomniaExport =
+        serde_json::from_str(json_content).map_err(|e| format!("Invalid Insomnia JSON: {}", e))?;
+    if export.export_format != 4 {
+        return Err(format!(
+            "Unsupported Insomnia export format: {}",
+            export.export_format
+        ));
+    }
+
+    let mut resources: Vec<&InsomniaResource> = export.resources.iter().collect();
+    resources.sort_by(|a, b| {
+        a.meta_sort_key
+            .unwrap_or(0.0)
+            .partial_cmp(&b.meta_sort_key.unwrap_or(0.0))
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+
+    let workspaces: Vec<&InsomniaResource> = resources
+        .iter()
+        .copied()
+        .filter(|r| r.resource_type == "workspace")
+        .collect();
+
+    let mut collections = Vec::new();
+    for ws in &workspaces {
+        let collection = db.create_collection(CreateCollectionInput {
+            workspace_id: workspace_id.to_string(),
+            name: ws.name.clone(),
+            description: ws.description.clone().filter(|d| !d.is_empty()),
+        })?;
+
+        let mut result = ImportResult {
+            collection_name: ws.name.clone(),
+            request_count: 0,
+            folder_count: 0,
+            warnings: vec![],
+        };
+        import_children(db, &resources, &ws.id, &collection.id, None, &mut result)?;
+        collections.push(result);
+    }
+
+    let mut environment_count = 0;
+    for ws in &workspaces {
+        environment_count += import_environments(db, &resources, &ws.id, workspace_id)?;
+    }
+
+    Ok(InsomniaImportResult {
+        collections,
+        environment_count,
+    })
+}
+
+fn import_children(
+    db: &Database,
+    resources: &[&InsomniaResource],
+    parent_id: &str,
+    collection_id: &str,
+    folder_id: Option<&str>,
+    result: &mut ImportResult,
+) -> Result<(), String> {
+    for resource in resources
+        .iter()
+        .filter(|r| r.parent_id.as_deref() == Some(parent_id))
+    {
+        match resource.resource_type.as_str() {
+            "request_group" => {
+                let folder = db.create_folder(CreateFolderInput {
+                    collection_id: collection_id.to_string(),
+                    parent_folder_id: folder_id.map(|s| s.to_string()),
+                    name: resource.name.clone(),
+                    description: None,
+                })?;
+                result.folder_count += 1;
+                import_children(
+                    db,
+                    resources,
+                    &resource.id,
+                    collection_id,
+                    Some(&folder.id),
+                    result,
+                )?;
+            }
+            "request" => {
+                let mut input = request_to_input(resource);
+                input.collection_id = Some(collection_id.to_string());
+                input.folder_id = folder_id.map(|s| s.to_string());
+                db.create_request(input)?;
+                result.request_count += 1;
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+fn request_to_input(resource: &InsomniaResource) -> CreateRequestInput {
+    let method = resource
+        .method
+        .as_deref()
+        .and_then(|m| HttpMethod::from_str(m).ok())
+        .unwrap_or(HttpMethod::GET);
+
+    let mut headers: Vec<KeyValue> = resource
+        .headers
+        .iter()
+        .filter(|h| !h.name.is_empty())
+        .map(|h| KeyValue {
+            key: convert_template(&h.name),
+            value: convert_template(&h.value),
+            enabled: !h.disabled,
+        })
+        .collect();
+    if let Some(auth) = auth_header(resource.authentication.as_ref()) {
+        if !headers
+            .iter()
+            .any(|h| h.key.eq_ignore_ascii_case("authorization"))
+        {
+            headers.push(auth);
+        }
+    }
+
+    let query_params: Vec<KeyValue> = resource
+        .parameters
+        .iter()
+        .filter(|p| !p.name.is_empty())
+        .map(|p| KeyValue {
+            key: convert_template(&p.name),
+            value: convert_template(&p.value),
+            enabled: !p.disabled,
+        })
+        .collect();
+
+    let (body_type, body_content) = match &resource.body {
+        Some(body) => body_to_steq(body),
+        None => (BodyType::None, None),
+    };
+
+    CreateRequestInput {
+        name: resource.name.clone(),
+        method,
+        url: convert_template(resource.url.as_deref().unwrap_or("")),
+        headers,
+        query_params,
+        body_type,
+        body_content,
+        collection_id: None,
+        folder_id: None,
+        description: None,
+        path_params: vec![],
+        scripts: None,
+        kind: RequestKind::Http,
+    }
+}
+
+fn body_to_steq(body: &InsomniaBody) -> (BodyType, Option<String>) {
+    let mime = body.mime_type.as_deref().unwrap_or("").to_lowercase();
+    let text = body.text.as_deref().map(convert_template);
+    let params = || {
+        body.params
+            .iter()
+            .filter(|p| !p.disabled && !p.name.is_empty())
+            .map(|p| {
+                let value = if p.pair_type.as_deref() == Some("file") {
+                    format!("@{}", p.file_name.as_deref().unwrap_or(""))
+                } else {
+                    convert_template(&p.value)
+                };
+                format!("{}={}", convert_template(&p.name), value)
+            })
+            .collect::<Vec<_>>()
+            .join("&")
+    };
+
+    match mime.as_str() {
+        "" if text.as_deref().is_none_or(str::is_empty) => (BodyType::None, None),
+        "application/x-www-form-urlencoded" => (BodyType::FormUrlEncoded, Some(params())),
+        "multipart/form-data" => (BodyType::Multipart, Some(params())),
+        "application/graphql" => {
+            let content = text.as_deref().map(graphql_content);
+            (BodyType::GraphQL, content)
+        }
+        m if m.contains("json") => (BodyType::Json, text),
+        _ => (BodyType::Text, text),
+    }
+}
+
+fn auth_header(auth: Option<&serde_json::Value>) -> Option<KeyValue> {
+    let auth = auth?;
+    if auth
+        .get("disabled")
+        .and_then(|d| d.as_bool())
+        .unwrap_or(false)
+    {
+        return None;
+    }
+    let field =
+        |name: &str| convert_template(auth.get(name).and_then(|v| v.as_str()).unwrap_or_default());
+    let value = match auth.get("type").and_then(|t| t.as_str())? {
+        "bearer" => {
+            let prefix = auth
+                .get("prefix")
+                .and_then(|p| p.as_str())
+                .filter(|p| !p.is_empty())
+                .unwrap_or("Bearer");
+            format!("{} {}", prefix, field("token"))
+        }
+        "basic" => {
+            let credentials = format!("{}:{}", field("username"), field("password"));
+            format!("Basic {}", BASE64.encode(credentials.as_bytes()))
+        }
+        _ => return None,
+    };
+    Some(KeyValue {
+        key: "Authorization".to_string(),
+        value,
+        enabled: true,
+    })
+}
+
+/// Imports the environments of one Insomnia workspace. The base environment's
+/// data is merged into every sub-environment, matching Insomnia's inheritance.
+fn import_environments(
+    db: &Database,
+    resources: &[&InsomniaResource],
+    insomnia_workspace_id: &str,
+    workspace_id: &str,
+) -> Result<usize, String> {
+    let Some(base) = resources.iter().find(|r| {
+        r.resource_type == "environment" && r.parent_id.as_deref() == Some(insomnia_workspace_id)
+    }) else {
+        return Ok(0);
+    };
+
+    let mut base_vars = Vec::new();
+    if let Some(data) = &base.data {
+        flatten_data("", data, &mut base_vars);
+    }
+
+    let subs: Vec<&&InsomniaResource> = resources
+        .iter()
+        .filter(|r| r.resource_type == "environment" && r.parent_id.as_deref() == Some(&base.id))
+        .collect();
+
+    let mut targets: Vec<(String, Vec<(String, String)>)> = Vec::new();
+    if subs.is_empty() {
+        if !base_vars.is_empty() {
+            targets.push((base.name.clone(), base_vars));
+        }
+    } else {
+        for sub in subs {
+            let mut sub_vars = Vec::new();
+            if let Some(data) = &sub.data {
+                flatten_data("", data, &mut sub_vars);
+            }
+            let mut merged = base_vars.clone();
+            for (key, value) in sub_vars {
+                match merged.iter_mut().find(|(k, _)| *k == key) {
+                    Some(existing) => existing.1 = value,
+                    None => merged.push((key, value)),
+                }
+            }
+            targets.push((sub.name.clone(), merged));
+        }
+    }
+
+    for (name, vars) in &targets {
+        let env = db.create_environment(&CreateEnvironmentInput {
+            workspace_id: workspace_id.to_string(),
+            name: name.clone(),
+        })?;
+        let variables = vars
+            .iter()
+            .enumerate()
+            .map(|(i, (key, value))| Variable {
+                id: String::new(),
+                environment_id: env.id.clone(),
+                key: key.clone(),
+                value: value.clone(),
+                is_secret: false,
+                enabled: true,
+                sort_order: i as i32,
+                created_at: String::new(),
+                updated_at: String::new(),
+            })
+            .collect();
+        db.update_environment(&UpdateEnvironmentInput {
+            id: env.id,
+            name: None,
+            variables: Some(variables),
+        })?;
+    }
+
+    Ok(targets.len())
+}
+
+/// Flattens nested environment data into dotted keys, e.g. `{"api": {"host": "x"}}`
+/// becomes `api.host = x`, matching how `{{ _.api.host }}` references it.
+fn flatten_data(prefix: &str, value: &serde_json::Value, out: &mut Vec<(String, String)>) {
+    match value {
+        serde_json::Value::Object(map) => {
+            for (key, child) in map {
+                let path = if prefix.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{}.{}", prefix, key)
+                };
+                flatten_data(&path, child, out);
+            }
+        }
+        serde_json::Value::String(s) => out.push((prefix.to_string(), convert_template(s))),
+        serde_json::Value::Null => out.push((prefix.to_string(), String::new())),
+        other => out.push((prefix.to_string(), other.to_string())),
+    }
+}
+
+/// Rewrites Insomnia `{{ _.name }}` references into Steq `{{name}}` syntax.
+fn convert_template(input: &str) -> String {
+    let mut out = String::with_capacity(input.len());
+    let mut rest = input;
+
+    while let Some(start) = rest.find("{{") {
+        let Some(end) = rest[start..].find("}}") else {
+            break;
+        };
+        let inner = rest[start + 2..start + end].trim();
+        let inner = inner.strip_prefix("_.").unwrap_or(inner);
+        out.push_str(&rest[..start]);
+        out.push_str("{{");
+        out.push_str(inner);
+        out.push_str("}}");
+        rest = &rest[start + end + 2..];
+    }
+    out.push_str(rest);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rusqlite::Connection;
+
+    fn setup_test_db() -> (Database, String) {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch("PRAGMA foreign_keys=ON;").unwrap();
+        let db = Database { conn };
+        db.run_migrations().unwrap();
+        let workspace = db.get_current_workspace().unwrap();
+        (db, workspace.id)
+    }
+
+    const SAMPLE_EXPORT: &str = r#"{
+        "_type": "export",
+        "__export_format": 4,
+        "__export_source": "insomnia.desktop.app:v2023.5.8",
+        "resources": [
+            { "_id": "wrk_1", "_type": "workspace", "parentId": null, "name": "Billing API", "description": "" },
+            { "_id": "fld_1", "_type": "request_group", "parentId": "wrk_1", "name": "Invoices", "metaSortKey": -2 },
+            { "_id": "fld_2", "_type": "request_group", "parentId": "fld_1", "name": "Drafts", "metaSortKey": -1 },
+            {
+                "_id": "req_1", "_type": "request", "parentId": "fld_1", "name": "List invoices",
+                "method": "GET", "url": "{{ _.baseUrl }}/invoices",
+                "headers": [{ "name": "X-Tenant", "value": "{{ _.tenant.id }}" }],
+                "parameters": [{ "name": "status", "value": "open" }, { "name": "old", "value": "1", "disabled": true }],
+                "authentication": { "type": "bearer", "token": "{{ _.token }}" },
+                "body": {}
+            },
+            {
+                "_id": "req_2", "_type": "request", "parentId": "fld_2", "name": "Create draft",
+                "method": "POST", "url": "{{ _.baseUrl }}/invoices",
+                "headers": [{ "name": "Content-Type", "value": "application/json" }],
+                "parameters": [],
+                "authentication": {},
+                "body": { "mimeType": "application/json", "text": "{\"amount\": 10}" }
+            },
+            {
+                "_id": "req_3", "_type": "request", "parentId": "wrk_1", "name": "Upload",
+                "method": "POST", "url": "https://files.example.com/upload",
+                "headers": [], "parameters": [],
+                "authentication": { "type": "basic", "username": "u", "password": "p" },
+                "body": {
+                    "mimeType": "multipart/form-data",
+                    "params": [
+                        { "name": "note", "value": "hi" },
+                        { "name": "file", "value": "", "type": "file", "fileName": "/tmp/a.pdf" }
+                    ]
+                }
+            },
+            {
+                "_id": "req_4", "_type": "request", "parentId": "wrk_1", "name": "Query",
+                "method": "POST", "url": "https://example.com/graphql",
+                "headers": [], "parameters": [], "authentication": {},
+                "body": { "mimeType": "application/graphql", "text": "{\"query\":\"{ me { id } }\",\"variables\":{\"a\":1}}" }
+            },
+            {
+                "_id": "env_base", "_type": "environment", "parentId": "wrk_1", "name": "Base Environment",
+                "data": { "baseUrl": "https://api.example.com", "tenant": { "id": "acme", "region": "eu" } }
+            },
+            {
+                "_id": "env_dev", "_type": "environment", "parentId": "env_base", "name": "Dev",
+                "data": { "baseUrl": "http://localhost:8080", "token": "dev-token", "retries": 3 }
+            },
+            {
+                "_id": "env_prod", "_type": "environment", "parentId": "env_base", "name": "Prod",
+                "data": { "token": "prod-token" }
+            },
+            { "_id": "jar_1", "_type": "cookie_jar", "parentId": "wrk_1", "name": "Default Jar" }
+        ]
+    }"#;
+
+    #[test]
+    fn import_workspace_structure() {
+        let (db, wid) = setup_test_db();
+        let result = import_insomnia_export(&db, SAMPLE_EXPORT, &wid).unwrap();
+
+        assert_eq!(result.collections.len(), 1);
+        assert_eq!(result.collections[0].collection_name, "Billing API");
+        assert_eq!(result.collections[0].folder_count, 2);
+        assert_eq!(result.collections[0].request_count, 4);
+
+        let colls = db.list_collections(&wid).unwrap();
+        let tree = db.get_collection_tree(&colls[0].id).unwrap();
+        assert_eq!(tree.root_requests.len(), 2);
+        let invoices = &tree.root_folders[0];
+        assert_eq!(invoices.folder.name, "Invoices");
+        assert_eq!(invoices.children[0].folder.name, "Drafts");
+
+        let list = &invoices.requests[0];
+        assert_eq!(list.url, "{{baseUrl}}/invoices");
+        assert_eq!(list.headers[0].value, "{{tenant.id}}");
+        assert_eq!(list.headers[1].value, "Bearer {{token}}");
+        assert!(!list.query_params[1].enabled);
+
+        let draft = &invoices.children[0].requests[0];
+        assert_eq!(draft.body_type.as_str(), "json");
+    }
+
+    #[test]
+    fn import_body_variants() {
+        let (db, wid) = setup_test_db();
+        import_insomnia_export(&db, SAMPLE_EXPORT, &wid).unwrap();
+
+        let colls = db.list_collections(&wid).unwrap();
+        let tree = db.get_collection_tree(&colls[0].id).unwrap();
+
+        let upload = tree
+            .root_requests
+            .iter()
+            .find(|r| r.name == "Upload")
+            .unwrap();
+        assert_eq!(upload.body_type.as_str(), "multipart");
+        assert_eq!(
+            upload.body_content.as_deref(),
+            Some("note=hi&file=@/tmp/a.pdf")
+        );
+        assert_eq!(upload.headers[0].value, "Basic dTpw");
+
+        let query = tree
+            .root_requests
+            .iter()
+            .find(|r| r.name == "Query")
+            .unwrap();
+        assert_eq!(query.body_type.as_str(), "graphql");
+        let content: serde_json::Value =
+            serde_json::from_str(query.body_content.as_deref().unwrap()).unwrap();
+        assert_eq!(content["query"], "{ me { id } }");
+    }
+
+    #[test]
+    fn import_environments_with_inheritance() {
+        let (db, wid) = setup_test_db();
+        let result = import_insomnia_export(&db, SAMPLE_EXPORT, &wid).unwrap();
+        assert_eq!(result.environment_count, 2);
+
+        let envs = db.list_environments(&wid).unwrap();
+        let dev = envs.iter().find(|e| e.name == "Dev").unwrap();
+        let value = |key: &str| {
+            dev.variables
+                .iter()
+                .find(|v| v.key == key)
+                .map(|v| v.value.clone())
+        };
+        assert_eq!(value("baseUrl").as_deref(), Some("http://localhost:8080"));
+        assert_eq!(value("tenant.id").as_deref(), Some("acme"));
+        assert_eq!(value("tenant.region").as_deref(), Some("eu"));
+        assert_eq!(value("retries").as_deref(), Some("3"));
+
+        let prod = envs.iter().find(|e| e.name == "Prod").unwrap();
+        assert!(prod
+            .variables
+            .iter()
+            .any(|v| v.key == "baseUrl" && v.value == "https://api.example.com"));
+    }
+
+    #[test]
+    fn convert_template_syntax() {
+        assert_eq!(convert_template("{{ _.baseUrl }}/a"), "{{baseUrl}}/a");
+        assert_eq!(convert_template("{{_.a.b}}-{{ c }}"), "{{a.b}}-{{c}}");
+        assert_eq!(convert_template("plain {{ unclosed"), "plain {{ unclosed");
+    }
+
+    #[test]
+    fn import_rejects_other_formats() {
+        let (db, wid) = setup_test_db();
+        assert!(
+            import_insomnia_export(&db, r#"{"__export_format": 3, "resources": []}"#, &wid)
+                .is_err()
+        );
+        assert!(import_insomnia_export(&db, "not json", &wid).is_err());
+    }
+}
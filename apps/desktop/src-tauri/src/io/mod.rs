@@ -1 +1,154 @@
+use serde::{Deserialize, Serialize};
+
+use crate::models::request::KeyValue;
+
+pub mod bruno;
+pub mod curl;
+pub mod har;
+pub mod http_file;
+pub mod insomnia;
+pub mod openapi;
 pub mod postman;
+pub mod postman_v1;
+
+/// Summary of a collection import, shared by all importers.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportResult {
+    pub collection_name: String,
+    pub request_count: usize,
+    pub folder_count: usize,
+    /// Parts of the source file that could not be mapped to Steq.
+    #[serde(default)]
+    pub warnings: Vec<String>,
+}
+
+/// Turns a display name into a file name that is valid on every platform.
+pub(crate) fn file_stem(name: &str) -> String {
+    let stem: String = name
+        .chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '-',
+            c if c.is_control() => '-',
+            c => c,
+        })
+        .collect();
+    let stem = stem.trim().trim_end_matches('.').to_string();
+    if stem.is_empty() {
+        "untitled".to_string()
+    } else {
+        stem
+    }
+}
+
+/// The path of a request URL, without its scheme, host, query string or
+/// fragment. A leading `{{baseUrl}}`-style variable stands for the host.
+pub(crate) fn url_path(url: &str) -> String {
+    let url = url.split(['?', '#']).next().unwrap_or_default();
+    let after_scheme = url.split_once("://").map_or(url, |(_, rest)| rest);
+    let host_end = match after_scheme.strip_prefix("{{").and_then(|v| v.find("}}")) {
+        Some(end) => end + 4,
+        None => 0,
+    };
+    match after_scheme[host_end..].find('/') {
+        Some(i) => after_scheme[host_end + i..].to_string(),
+        None => "/".to_string(),
+    }
+}
+
+/// Splits the query string off a URL into decoded key/value pairs, dropping any
+/// fragment.
+pub(crate) fn split_query(url: &str) -> (String, Vec<KeyValue>) {
+    let (without_fragment, _) = url.split_once('#').unwrap_or((url, ""));
+    let Some((base, query)) = without_fragment.split_once('?') else {
+        return (without_fragment.to_string(), vec![]);
+    };
+
+    let params = query
+        .split('&')
+        .filter(|p| !p.is_empty())
+        .map(|pair| {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            KeyValue {
+                key: percent_decode(key),
+                value: percent_decode(value),
+                enabled: true,
+            }
+        })
+        .collect();
+    (base.to_string(), params)
+}
+
+pub(crate) fn percent_decode(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'%' if i + 2 < bytes.len() => {
+                let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).ok();
+                match hex.and_then(|h| u8::from_str_radix(h, 16).ok()) {
+                    Some(b) => {
+                        out.push(b);
+                        i += 3;
+                        continue;
+                    }
+                    None => out.push(b'%'),
+                }
+            }
+            b'+' => out.push(b' '),
+            b => out.push(b),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).to_string()
+}
+
+/// Converts a GraphQL-over-JSON payload (`{"query": "...", "variables": {...}}`)
+/// into the editor's stored structure, where variables are kept as an editable
+/// JSON string.
+pub(crate) fn graphql_content(body: &str) -> String {
+    let Ok(value) = serde_json::from_str::<serde_json::Value>(body) else {
+        return body.to_string();
+    };
+    let variables = match value.get("variables") {
+        Some(v) if !v.is_null() => serde_json::to_string_pretty(v).unwrap_or_default(),
+        _ => String::new(),
+    };
+    serde_json::json!({
+        "query": value.get("query").and_then(|q| q.as_str()).unwrap_or_default(),
+        "variables": variables,
+        "operationName": value.get("operationName").and_then(|o| o.as_str()).unwrap_or_default(),
+    })
+    .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn url_path_drops_host_query_and_fragment() {
+        assert_eq!(url_path("https://api.test/users?x=1"), "/users");
+        assert_eq!(url_path("https://api.test?next=/home"), "/");
+        assert_eq!(url_path("{{baseUrl}}/users/:id"), "/users/:id");
+        assert_eq!(url_path("api.test/v1/items#top"), "/v1/items");
+        assert_eq!(url_path("{{baseUrl}}"), "/");
+        assert_eq!(url_path("{{api/base}}/items"), "/items");
+        assert_eq!(
+            url_path("https://{{host}}/v{{version}}/x"),
+            "/v{{version}}/x"
+        );
+    }
+
+    #[test]
+    fn split_query_decodes_pairs_and_drops_fragment() {
+        let (base, params) = split_query("https://api.test/search?q=a%20b&tag=x+y&flag#results");
+        assert_eq!(base, "https://api.test/search");
+        let pairs: Vec<(&str, &str)> = params
+            .iter()
+            .map(|p| (p.key.as_str(), p.value.as_str()))
+            .collect();
+        assert_eq!(pairs, [("q", "a b"), ("tag", "x y"), ("flag", "")]);
+        assert_eq!(split_query("{{baseUrl}}/users#top").0, "{{baseUrl}}/users");
+    }
+}
@@ -0,0 +1,717 @@
+use std::collections::{BTreeMap, HashSet};
+
+use serde_json::{json, Map, Value};
+
+use crate::db::Database;
+use crate::models::collection::CollectionTreeNode;
+use crate::models::history::HistoryEntry;
+use crate::models::request::{ApiRequest, BodyType, KeyValue};
+
+/// Number of history entries inspected per request when inferring response schemas.
+const HISTORY_SAMPLE_SIZE: i64 = 20;
+
+/// Headers that OpenAPI describes through dedicated fields rather than parameters.
+const RESERVED_HEADERS: [&str; 3] = ["accept", "content-type", "authorization"];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OpenApiFormat {
+    Json,
+    Yaml,
+}
+
+impl OpenApiFormat {
+    pub fn from_str(s: &str) -> Result<Self, String> {
+        match s.to_lowercase().as_str() {
+            "json" => Ok(OpenApiFormat::Json),
+            "yaml" | "yml" => Ok(OpenApiFormat::Yaml),
+            other => Err(format!("Unknown OpenAPI format: {}", other)),
+        }
+    }
+
+    /// Picks a format from a file path's extension, defaulting to YAML.
+    pub fn from_path(path: &str) -> Self {
+        if path.to_lowercase().ends_with(".json") {
+            OpenApiFormat::Json
+        } else {
+            OpenApiFormat::Yaml
+        }
+    }
+}
+
+/// Generates a starter OpenAPI 3.0 document from a Steq collection.
+pub fn export_openapi_document(
+    db: &Database,
+    collection_id: &str,
+    format: OpenApiFormat,
+) -> Result<String, String> {
+    let tree = db.get_collection_tree(collection_id)?;
+    let variables: BTreeMap<String, String> = db
+        .get_exported_variables(&tree.collection.workspace_id)?
+        .into_iter()
+        .collect();
+
+    let mut requests: Vec<(Option<String>, &ApiRequest)> =
+        tree.root_requests.iter().map(|r| (None, r)).collect();
+    collect_folder_requests(&tree.root_folders, &mut requests);
+
+    let mut paths = Map::new();
+    let mut servers: Vec<String> = Vec::new();
+    let mut tags: Vec<String> = Vec::new();
+    let mut operation_ids: HashSet<String> = HashSet::new();
+
+    for (tag, req) in requests {
+        let (server, path) = split_url(&req.url);
+        if let Some(server) = server {
+            if !servers.contains(&server) {
+                servers.push(server);
+            }
+        }
+        let (path, path_params) = templatize_path(&path);
+
+        let history = db.list_history_by_request(&req.id, HISTORY_SAMPLE_SIZE)?;
+        let mut operation = build_operation(req, &path_params, &history);
+
+        let operation_id = unique_operation_id(&req.name, &mut operation_ids);
+        operation["operationId"] = json!(operation_id);
+        if let Some(tag) = tag {
+            operation["tags"] = json!([tag]);
+            if !tags.contains(&tag) {
+                tags.push(tag);
+            }
+        }
+
+        let path_item = paths
+            .entry(path)
+            .or_insert_with(|| Value::Object(Map::new()))
+            .as_object_mut()
+            .ok_or_else(|| "Invalid path item".to_string())?;
+        let method = req.method.as_str().to_lowercase();
+        // First request wins when a collection holds duplicates of the same operation
+        path_item.entry(method).or_insert(operation);
+    }
+
+    let mut info = json!({
+        "title": tree.collection.name,
+        "version": "1.0.0",
+    });
+    if let Some(description) = &tree.collection.description {
+        info["description"] = json!(description);
+    }
+
+    let mut document = json!({
+        "openapi": "3.0.3",
+        "info": info,
+        "paths": paths,
+    });
+
+    if !servers.is_empty() {
+        document["servers"] = Value::Array(
+            servers
+                .iter()
+                .map(|s| build_server(s, &variables))
+                .collect(),
+        );
+    }
+    if !tags.is_empty() {
+        document["tags"] = Value::Array(tags.iter().map(|t| json!({ "name": t })).collect());
+    }
+
+    match format {
+        OpenApiFormat::Json => serde_json::to_string_pretty(&document)
+            .map_err(|e| format!("Failed to serialize: {}", e)),
+        OpenApiFormat::Yaml => {
+            serde_yaml::to_string(&document).map_err(|e| format!("Failed to serialize: {}", e))
+        }
+    }
+}
+
+fn collect_folder_requests<'a>(
+    folders: &'a [CollectionTreeNode],
+    out: &mut Vec<(Option<String>, &'a ApiRequest)>,
+) {
+    for node in folders {
+        for req in &node.requests {
+            out.push((Some(node.folder.name.clone()), req));
+        }
+        collect_folder_requests(&node.children, out);
+    }
+}
+
+/// Splits a request URL into its server part and its path, dropping any query string.
+/// A leading `{{variable}}` is treated as the server, since that is how base URLs are
+/// usually shared between requests.
+fn split_url(url: &str) -> (Option<String>, String) {
+    let without_query = url.split(['?', '#']).next().unwrap_or("").trim();
+
+    let (server, path) = if let Some(scheme_end) = without_query.find("://") {
+        let after_scheme = &without_query[scheme_end + 3..];
+        match after_scheme.find('/') {
+            Some(slash) => (
+                Some(without_query[..scheme_end + 3 + slash].to_string()),
+                after_scheme[slash..].to_string(),
+            ),
+            None => (Some(without_query.to_string()), String::new()),
+        }
+    } else if without_query.starts_with("{{") {
+        match without_query.find("}}") {
+            Some(end) => (
+                Some(without_query[..end + 2].to_string()),
+                without_query[end + 2..].to_string(),
+            ),
+            None => (None, without_query.to_string()),
+        }
+    } else {
+        (None, without_query.to_string())
+    };
+
+    let path = if path.starts_with('/') {
+        path
+    } else {
+        format!("/{}", path)
+    };
+    (server, path)
+}
+
+/// Rewrites `:param` and `{{param}}` path segments into OpenAPI `{param}` templates.
+fn templatize_path(path: &str) -> (String, Vec<String>) {
+    let mut params = Vec::new();
+    let segments: Vec<String> = path
+        .split('/')
+        .map(|segment| {
+            let name = if let Some(name) = segment.strip_prefix(':') {
+                Some(name)
+            } else if segment.starts_with("{{") && segment.ends_with("}}") && segment.len() > 4 {
+                Some(&segment[2..segment.len() - 2])
+            } else {
+                None
+            };
+            match name {
+                Some(name) if !name.is_empty() => {
+                    let name = name.trim().to_string();
+                    let templated = format!("{{{}}}", name);
+                    if !params.contains(&name) {
+                        params.push(name);
+                    }
+                    templated
+                }
+                _ => segment.to_string(),
+            }
+        })
+        .collect();
+    (segments.join("/"), params)
+}
+
+fn build_server(server: &str, variables: &BTreeMap<String, String>) -> Value {
+    let mut url = String::new();
+    let mut server_vars = Map::new();
+    let mut rest = server;
+
+    while let Some(start) = rest.find("{{") {
+        let Some(end) = rest[start..].find("}}") else {
+            break;
+        };
+        let name = rest[start + 2..start + end].trim();
+        url.push_str(&rest[..start]);
+        url.push_str(&format!("{{{}}}", name));
+        server_vars.insert(
+            name.to_string(),
+            json!({ "default": variables.get(name).cloned().unwrap_or_default() }),
+        );
+        rest = &rest[start + end + 2..];
+    }
+    url.push_str(rest);
+
+    let mut value = json!({ "url": url });
+    if !server_vars.is_empty() {
+        value["variables"] = Value::Object(server_vars);
+    }
+    value
+}
+
+fn build_operation(req: &ApiRequest, path_params: &[String], history: &[HistoryEntry]) -> Value {
+    let mut parameters: Vec<Value> = path_params
+        .iter()
+        .map(|name| {
+            json!({
+                "name": name,
+                "in": "path",
+                "required": true,
+                "schema": { "type": "string" }
+            })
+        })
+        .collect();
+
+    for q in req.query_params.iter().filter(|q| !q.key.trim().is_empty()) {
+        parameters.push(json!({
+            "name": q.key,
+            "in": "query",
+            "required": false,
+            "schema": { "type": "string" },
+            "example": q.value
+        }));
+    }
+
+    for h in req.headers.iter().filter(|h| !h.key.trim().is_empty()) {
+        if RESERVED_HEADERS.contains(&h.key.to_lowercase().as_str()) {
+            continue;
+        }
+        parameters.push(json!({
+            "name": h.key,
+            "in": "header",
+            "required": h.enabled,
+            "schema": { "type": "string" },
+            "example": h.value
+        }));
+    }
+
+    let mut operation = json!({
+        "summary": req.name,
+        "responses": build_responses(history),
+    });
+    if !parameters.is_empty() {
+        operation["parameters"] = Value::Array(parameters);
+    }
+    if let Some(body) = build_request_body(req) {
+        operation["requestBody"] = body;
+    }
+    operation
+}
+
+fn build_request_body(req: &ApiRequest) -> Option<Value> {
+    let content = req.body_content.as_deref().unwrap_or("");
+    let (media_type, media) = match req.body_type {
+        BodyType::None => return None,
+        BodyType::Json => {
+            let media = match serde_json::from_str::<Value>(content) {
+                Ok(value) => json!({ "schema": infer_schema(&value), "example": value }),
+                Err(_) => json!({ "schema": { "type": "object" } }),
+            };
+            ("application/json", media)
+        }
+        BodyType::Text => (
+            "text/plain",
+            json!({ "schema": { "type": "string" }, "example": content }),
+        ),
+        BodyType::FormUrlEncoded => (
+            "application/x-www-form-urlencoded",
+            json!({ "schema": form_schema(content) }),
+        ),
+        BodyType::Multipart => ("multipart/form-data", json!({ "schema": form_schema(content) })),
+        BodyType::GraphQL => (
+            "application/json",
+            json!({
+                "schema": {
+                    "type": "object",
+                    "required": ["query"],
+                    "properties": {
+                        "query": { "type": "string" },
+                        "variables": { "type": "object" },
+                        "operationName": { "type": "string" }
+                    }
+                }
+            }),
+        ),
+    };
+
+    Some(json!({
+        "required": true,
+        "content": { media_type: media }
+    }))
+}
+
+/// Builds an object schema from `key=value&...` or newline separated form content.
+fn form_schema(content: &str) -> Value {
+    let mut properties = Map::new();
+    for pair in content.split(['&', '\n']) {
+        let key = pair.split(['=', ':']).next().unwrap_or("").trim();
+        if !key.is_empty() {
+            properties.insert(key.to_string(), json!({ "type": "string" }));
+        }
+    }
+    json!({ "type": "object", "properties": properties })
+}
+
+/// Builds the responses object from saved history, keeping the most recent
+/// response for each status code.
+fn build_responses(history: &[HistoryEntry]) -> Value {
+    let mut responses = Map::new();
+
+    for entry in history {
+        let Some(status) = entry.response_status else {
+            continue;
+        };
+        let code = status.to_string();
+        if responses.contains_key(&code) {
+            continue;
+        }
+
+        let mut response = json!({ "description": status_description(status) });
+        if let Some(body) = entry.response_body.as_deref().filter(|b| !b.trim().is_empty()) {
+            let content_type = entry
+                .response_headers
+                .as_deref()
+                .and_then(|h| serde_json::from_str::<Vec<KeyValue>>(h).ok())
+                .and_then(|headers| {
+                    headers
+                        .into_iter()
+                        .find(|h| h.key.eq_ignore_ascii_case("content-type"))
+                        .map(|h| h.value)
+                });
+            let media_type = content_type
+                .as_deref()
+                .and_then(|ct| ct.split(';').next())
+                .map(|ct| ct.trim().to_string());
+
+            let media = match serde_json::from_str::<Value>(body) {
+                Ok(value) if value.is_object() || value.is_array() => Some((
+                    media_type.unwrap_or_else(|| "application/json".to_string()),
+                    json!({ "schema": infer_schema(&value) }),
+                )),
+                _ => media_type.map(|mt| (mt, json!({ "schema": { "type": "string" } }))),
+            };
+            if let Some((media_type, media)) = media {
+                response["content"] = json!({ media_type: media });
+            }
+        }
+        responses.insert(code, response);
+    }
+
+    if responses.is_empty() {
+        responses.insert("default".to_string(), json!({ "description": "Default response" }));
+    }
+    Value::Object(responses)
+}
+
+fn status_description(status: i32) -> String {
+    u16::try_from(status)
+        .ok()
+        .and_then(|s| reqwest::StatusCode::from_u16(s).ok())
+        .and_then(|s| s.canonical_reason())
+        .unwrap_or("Response")
+        .to_string()
+}
+
+/// Infers a JSON Schema from an example value.
+pub fn infer_schema(value: &Value) -> Value {
+    match value {
+        Value::Null => json!({ "nullable": true }),
+        Value::Bool(_) => json!({ "type": "boolean" }),
+        Value::Number(n) => {
+            if n.is_i64() || n.is_u64() {
+                json!({ "type": "integer" })
+            } else {
+                json!({ "type": "number" })
+            }
+        }
+        Value::String(s) => {
+            if chrono::DateTime::parse_from_rfc3339(s).is_ok() {
+                json!({ "type": "string", "format": "date-time" })
+            } else {
+                json!({ "type": "string" })
+            }
+        }
+        Value::Array(items) => match items.first() {
+            Some(first) => json!({ "type": "array", "items": infer_schema(first) }),
+            None => json!({ "type": "array", "items": {} }),
+        },
+        Value::Object(map) => {
+            let properties: Map<String, Value> = map
+                .iter()
+                .map(|(k, v)| (k.clone(), infer_schema(v)))
+                .collect();
+            let required: Vec<&String> = map.iter().filter(|(_, v)| !v.is_null()).map(|(k, _)| k).collect();
+            let mut schema = json!({ "type": "object", "properties": properties });
+            if !required.is_empty() {
+                schema["required"] = json!(required);
+            }
+            schema
+        }
+    }
+}
+
+/// Builds a camelCase operationId from a request name, suffixed to stay unique.
+fn unique_operation_id(name: &str, used: &mut HashSet<String>) -> String {
+    let mut base = String::new();
+    for (i, word) in name
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .enumerate()
+    {
+        let mut chars = word.chars();
+        if let Some(first) = chars.next() {
+            if i == 0 {
+                base.push(first.to_ascii_lowercase());
+            } else {
+                base.push(first.to_ascii_uppercase());
+            }
+            base.push_str(chars.as_str());
+        }
+    }
+    if base.is_empty() {
+        base = "operation".to_string();
+    }
+
+    let mut candidate = base.clone();
+    let mut n = 2;
+    while used.contains(&candidate) {
+        candidate = format!("{}{}", base, n);
+        n += 1;
+    }
+    used.insert(candidate.clone());
+    candidate
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::collection::{CreateCollectionInput, CreateFolderInput};
+    use crate::models::environment::{CreateEnvironmentInput, UpdateEnvironmentInput, Variable};
+    use crate::models::request::{CreateRequestInput, HttpMethod, RequestKind};
+    use rusqlite::Connection;
+
+    fn setup_test_db() -> (Database, String) {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch("PRAGMA foreign_keys=ON;").unwrap();
+        let db = Database { conn };
+        db.run_migrations().unwrap();
+        let workspace = db.get_current_workspace().unwrap();
+        (db, workspace.id)
+    }
+
+    fn make_request(
+        db: &Database,
+        collection_id: &str,
+        folder_id: Option<&str>,
+        name: &str,
+        method: HttpMethod,
+        url: &str,
+        body: Option<(BodyType, &str)>,
+    ) -> ApiRequest {
+        let (body_type, body_content) = match body {
+            Some((bt, content)) => (bt, Some(content.to_string())),
+            None => (BodyType::None, None),
+        };
+        db.create_request(CreateRequestInput {
+            name: name.to_string(),
+            method,
+            url: url.to_string(),
+            headers: vec![KeyValue {
+                key: "X-Tenant".to_string(),
+                value: "acme".to_string(),
+                enabled: true,
+            }],
+            query_params: vec![],
+            body_type,
+            body_content,
+            collection_id: Some(collection_id.to_string()),
+            folder_id: folder_id.map(|s| s.to_string()),
+            description: None,
+            path_params: vec![],
+            scripts: None,
+            kind: RequestKind::Http,
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn split_url_variants() {
+        assert_eq!(
+            split_url("https://api.example.com/users?page=1"),
+            (Some("https://api.example.com".to_string()), "/users".to_string())
+        );
+        assert_eq!(
+            split_url("{{baseUrl}}/users/:id"),
+            (Some("{{baseUrl}}".to_string()), "/users/:id".to_string())
+        );
+        assert_eq!(
+            split_url("https://api.example.com"),
+            (Some("https://api.example.com".to_string()), "/".to_string())
+        );
+        assert_eq!(split_url("users"), (None, "/users".to_string()));
+    }
+
+    #[test]
+    fn templatize_path_variables() {
+        let (path, params) = templatize_path("/users/:userId/posts/{{postId}}");
+        assert_eq!(path, "/users/{userId}/posts/{postId}");
+        assert_eq!(params, vec!["userId".to_string(), "postId".to_string()]);
+    }
+
+    #[test]
+    fn infer_schema_nested() {
+        let value: Value = serde_json::from_str(
+            r#"{"id":1,"name":"a","score":1.5,"tags":["x"],"createdAt":"2024-01-01T00:00:00Z"}"#,
+        )
+        .unwrap();
+        let schema = infer_schema(&value);
+        assert_eq!(schema["type"], "object");
+        assert_eq!(schema["properties"]["id"]["type"], "integer");
+        assert_eq!(schema["properties"]["score"]["type"], "number");
+        assert_eq!(schema["properties"]["tags"]["items"]["type"], "string");
+        assert_eq!(schema["properties"]["createdAt"]["format"], "date-time");
+    }
+
+    #[test]
+    fn unique_operation_ids() {
+        let mut used = HashSet::new();
+        assert_eq!(unique_operation_id("Get Users", &mut used), "getUsers");
+        assert_eq!(unique_operation_id("get users", &mut used), "getUsers2");
+        assert_eq!(unique_operation_id("!!!", &mut used), "operation");
+    }
+
+    #[test]
+    fn export_collection_to_openapi() {
+        let (db, wid) = setup_test_db();
+        let coll = db
+            .create_collection(CreateCollectionInput {
+                workspace_id: wid.clone(),
+                name: "Users API".to_string(),
+                description: Some("User service".to_string()),
+            })
+            .unwrap();
+        let folder = db
+            .create_folder(CreateFolderInput {
+                collection_id: coll.id.clone(),
+                parent_folder_id: None,
+                name: "Users".to_string(),
+                description: None,
+            })
+            .unwrap();
+
+        let get_user = make_request(
+            &db,
+            &coll.id,
+            Some(&folder.id),
+            "Get User",
+            HttpMethod::GET,
+            "{{baseUrl}}/users/:id",
+            None,
+        );
+        make_request(
+            &db,
+            &coll.id,
+            None,
+            "Create User",
+            HttpMethod::POST,
+            "{{baseUrl}}/users",
+            Some((BodyType::Json, r#"{"name":"Ada","age":36}"#)),
+        );
+
+        db.save_history_entry(
+            &wid,
+            Some(&get_user.id),
+            "GET",
+            "https://api.example.com/users/1",
+            "{}",
+            Some(200),
+            Some(r#"[{"key":"content-type","value":"application/json; charset=utf-8","enabled":true}]"#),
+            Some(r#"{"id":1,"name":"Ada"}"#),
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+
+        let exported = export_openapi_document(&db, &coll.id, OpenApiFormat::Json).unwrap();
+        let doc: Value = serde_json::from_str(&exported).unwrap();
+
+        assert_eq!(doc["openapi"], "3.0.3");
+        assert_eq!(doc["info"]["title"], "Users API");
+        assert_eq!(doc["servers"][0]["url"], "{baseUrl}");
+
+        let get_op = &doc["paths"]["/users/{id}"]["get"];
+        assert_eq!(get_op["operationId"], "getUser");
+        assert_eq!(get_op["tags"][0], "Users");
+        let params = get_op["parameters"].as_array().unwrap();
+        assert!(params.iter().any(|p| p["name"] == "id" && p["in"] == "path"));
+        assert!(params.iter().any(|p| p["name"] == "X-Tenant" && p["in"] == "header"));
+        let schema = &get_op["responses"]["200"]["content"]["application/json"]["schema"];
+        assert_eq!(schema["properties"]["name"]["type"], "string");
+
+        let post_op = &doc["paths"]["/users"]["post"];
+        let body_schema = &post_op["requestBody"]["content"]["application/json"]["schema"];
+        assert_eq!(body_schema["properties"]["age"]["type"], "integer");
+        assert!(post_op["responses"]["default"].is_object());
+    }
+
+    #[test]
+    fn export_keeps_secret_variables_out() {
+        let (db, wid) = setup_test_db();
+        let env = db
+            .create_environment(&CreateEnvironmentInput {
+                workspace_id: wid.clone(),
+                name: "Prod".to_string(),
+            })
+            .unwrap();
+        let variable = |key: &str, value: &str, is_secret: bool| Variable {
+            id: String::new(),
+            environment_id: env.id.clone(),
+            key: key.to_string(),
+            value: value.to_string(),
+            is_secret,
+            enabled: true,
+            sort_order: 0,
+            created_at: String::new(),
+            updated_at: String::new(),
+        };
+        db.update_environment(&UpdateEnvironmentInput {
+            id: env.id.clone(),
+            name: None,
+            variables: Some(vec![
+                variable("scheme", "https", false),
+                variable("tenantHost", "tk-live-123.example.com", true),
+            ]),
+        })
+        .unwrap();
+        db.set_active_environment(&env.id, &wid).unwrap();
+        let coll = db
+            .create_collection(CreateCollectionInput {
+                workspace_id: wid,
+                name: "Tenant API".to_string(),
+                description: None,
+            })
+            .unwrap();
+        make_request(
+            &db,
+            &coll.id,
+            None,
+            "Health",
+            HttpMethod::GET,
+            "{{scheme}}://{{tenantHost}}/health",
+            None,
+        );
+
+        let exported = export_openapi_document(&db, &coll.id, OpenApiFormat::Json).unwrap();
+        assert!(!exported.contains("tk-live-123"));
+        let doc: Value = serde_json::from_str(&exported).unwrap();
+        let variables = &doc["servers"][0]["variables"];
+        assert_eq!(variables["scheme"]["default"], "https");
+        assert_eq!(variables["tenantHost"]["default"], "{{tenantHost}}");
+    }
+
+    #[test]
+    fn export_yaml() {
+        let (db, wid) = setup_test_db();
+        let coll = db
+            .create_collection(CreateCollectionInput {
+                workspace_id: wid,
+                name: "Yaml API".to_string(),
+                description: None,
+            })
+            .unwrap();
+        make_request(
+            &db,
+            &coll.id,
+            None,
+            "Health",
+            HttpMethod::GET,
+            "https://api.example.com/health",
+            None,
+        );
+
+        let exported = export_openapi_document(&db, &coll.id, OpenApiFormat::Yaml).unwrap();
+        assert!(exported.contains("openapi: 3.0.3"));
+        assert!(exported.contains("/health:"));
+        assert!(exported.contains("url: https://api.example.com"));
+    }
+}
@@ -1,20 +1,60 @@
-use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use serde::Deserialize;
+
+use super::postman_v1::{self, PostmanSchema};
+use super::ImportResult;
 use crate::db::Database;
-use crate::models::request::{BodyType, CreateRequestInput, HttpMethod, KeyValue};
+use crate::models::collection::{CollectionTreeNode, CreateCollectionInput, CreateFolderInput};
+use crate::models::environment::{
+    CreateEnvironmentInput, Environment, UpdateEnvironmentInput, Variable,
+};
+use crate::models::example::{CreateExampleInput, RequestExample};
+use crate::models::request::{
+    ApiRequest, BodyType, CreateRequestInput, ExecuteRequestInput, HttpMethod, KeyValue,
+    RequestKind, RequestScripts,
+};
 
 /// Postman Collection v2.1 format types
 #[derive(Debug, Deserialize)]
 pub struct PostmanCollection {
     pub info: PostmanInfo,
     pub item: Vec<PostmanItem>,
+    #[serde(default)]
+    pub variable: Vec<PostmanVariable>,
+    #[serde(default)]
+    pub auth: Option<PostmanAuth>,
+    #[serde(default)]
+    pub event: Vec<PostmanEvent>,
 }
 
 #[derive(Debug, Deserialize)]
 pub struct PostmanInfo {
     pub name: String,
     #[serde(default)]
-    pub description: Option<String>,
+    pub description: Option<PostmanDescription>,
+}
+
+/// Descriptions are either a plain string or `{ "content": ..., "type": ... }`.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+pub enum PostmanDescription {
+    Text(String),
+    Object {
+        #[serde(default)]
+        content: Option<String>,
+    },
+}
+
+impl PostmanDescription {
+    pub fn text(&self) -> Option<String> {
+        match self {
+            PostmanDescription::Text(s) => Some(s.clone()),
+            PostmanDescription::Object { content } => content.clone(),
+        }
+        .filter(|s| !s.is_empty())
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -23,13 +63,39 @@ pub enum PostmanItem {
     Folder {
         name: String,
         item: Vec<PostmanItem>,
+        #[serde(default)]
+        description: Option<PostmanDescription>,
+        #[serde(default)]
+        auth: Option<PostmanAuth>,
+        #[serde(default)]
+        event: Vec<PostmanEvent>,
     },
     Request {
         name: String,
         request: PostmanRequest,
+        #[serde(default)]
+        event: Vec<PostmanEvent>,
+        #[serde(default)]
+        response: Vec<PostmanResponse>,
     },
 }
 
+/// A saved example response.
+#[derive(Debug, Deserialize)]
+pub struct PostmanResponse {
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(rename = "originalRequest", default)]
+    pub original_request: Option<PostmanRequest>,
+    #[serde(default)]
+    pub code: Option<i32>,
+    /// An array of headers, or occasionally a raw header string or null.
+    #[serde(default)]
+    pub header: Option<serde_json::Value>,
+    #[serde(default)]
+    pub body: Option<String>,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct PostmanRequest {
     pub method: Option<String>,
@@ -38,6 +104,10 @@ pub struct PostmanRequest {
     pub header: Vec<PostmanHeader>,
     #[serde(default)]
     pub body: Option<PostmanBody>,
+    #[serde(default)]
+    pub auth: Option<PostmanAuth>,
+    #[serde(default)]
+    pub description: Option<PostmanDescription>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -54,6 +124,8 @@ pub enum PostmanUrl {
         path: Option<serde_json::Value>,
         #[serde(default)]
         query: Option<Vec<PostmanQueryParam>>,
+        #[serde(default)]
+        variable: Option<Vec<PostmanQueryParam>>,
     },
 }
 
@@ -71,6 +143,13 @@ impl PostmanUrl {
             PostmanUrl::Structured { query, .. } => query.clone().unwrap_or_default(),
         }
     }
+
+    pub fn path_variables(&self) -> Vec<PostmanQueryParam> {
+        match self {
+            PostmanUrl::Simple(_) => vec![],
+            PostmanUrl::Structured { variable, .. } => variable.clone().unwrap_or_default(),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -95,9 +174,45 @@ pub struct PostmanBody {
     pub mode: Option<String>,
     pub raw: Option<String>,
     #[serde(default)]
+    pub urlencoded: Vec<PostmanFormParam>,
+    #[serde(default)]
+    pub formdata: Vec<PostmanFormParam>,
+    #[serde(default)]
+    pub file: Option<PostmanFile>,
+    #[serde(default)]
+    pub graphql: Option<PostmanGraphQL>,
+    #[serde(default)]
     pub options: Option<PostmanBodyOptions>,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct PostmanFormParam {
+    pub key: String,
+    #[serde(default)]
+    pub value: Option<String>,
+    #[serde(default)]
+    pub disabled: Option<bool>,
+    #[serde(rename = "type", default)]
+    pub param_type: Option<String>,
+    /// A single path or, for multi-file fields, an array of paths.
+    #[serde(default)]
+    pub src: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PostmanFile {
+    #[serde(default)]
+    pub src: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PostmanGraphQL {
+    #[serde(default)]
+    pub query: Option<String>,
+    #[serde(default)]
+    pub variables: Option<String>,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct PostmanBodyOptions {
     pub raw: Option<PostmanRawOptions>,
@@ -108,46 +223,111 @@ pub struct PostmanRawOptions {
     pub language: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct ImportResult {
-    pub collection_name: String,
-    pub request_count: usize,
-    pub folder_count: usize,
+/// Auth settings; the parameters live under a key named after the type,
+/// e.g. `{ "type": "bearer", "bearer": [{ "key": "token", "value": "..." }] }`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct PostmanAuth {
+    #[serde(rename = "type")]
+    pub auth_type: String,
+    #[serde(flatten)]
+    pub params: HashMap<String, serde_json::Value>,
+}
+
+impl PostmanAuth {
+    fn param(&self, key: &str) -> Option<String> {
+        let value = self.params.get(&self.auth_type)?;
+        match value {
+            // v2.1: array of { key, value }
+            serde_json::Value::Array(attrs) => attrs
+                .iter()
+                .find(|a| a.get("key").and_then(|k| k.as_str()) == Some(key))
+                .and_then(|a| a.get("value"))
+                .map(json_to_string),
+            // v2.0: plain object
+            serde_json::Value::Object(map) => map.get(key).map(json_to_string),
+            _ => None,
+        }
+    }
 }
 
-/// Imports a Postman Collection v2.1 JSON file into the database.
+#[derive(Debug, Deserialize)]
+pub struct PostmanEvent {
+    pub listen: String,
+    #[serde(default)]
+    pub script: Option<PostmanScript>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PostmanScript {
+    #[serde(default)]
+    pub exec: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PostmanVariable {
+    pub key: String,
+    #[serde(default)]
+    pub value: serde_json::Value,
+    #[serde(default)]
+    pub disabled: Option<bool>,
+}
+
+fn json_to_string(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::String(s) => s.clone(),
+        serde_json::Value::Null => String::new(),
+        other => other.to_string(),
+    }
+}
+
+/// Imports a Postman collection (v2.1, v2.0 or v1) into the database. Collection
+/// variables are stored in a new environment named after the collection.
 pub fn import_postman_collection(
     db: &Database,
     json_content: &str,
     workspace_id: &str,
 ) -> Result<ImportResult, String> {
-    let collection: PostmanCollection =
+    let value: serde_json::Value =
         serde_json::from_str(json_content).map_err(|e| format!("Invalid Postman JSON: {}", e))?;
+    // v2.0 differs from v2.1 only in shapes the serde model already accepts
+    let mut warnings = Vec::new();
+    let value = match postman_v1::detect_schema(&value)? {
+        PostmanSchema::V1 => postman_v1::convert_v1(&value, &mut warnings),
+        PostmanSchema::V20 | PostmanSchema::V21 => value,
+    };
+    let collection: PostmanCollection =
+        serde_json::from_value(value).map_err(|e| format!("Invalid Postman JSON: {}", e))?;
 
-    let coll_input = crate::models::collection::CreateCollectionInput {
+    let coll_input = CreateCollectionInput {
         workspace_id: workspace_id.to_string(),
         name: collection.info.name.clone(),
-        description: collection.info.description.clone(),
+        description: collection.info.description.as_ref().and_then(|d| d.text()),
     };
     let created_coll = db.create_collection(coll_input)?;
 
-    let mut request_count = 0;
-    let mut folder_count = 0;
+    let mut result = ImportResult {
+        collection_name: collection.info.name.clone(),
+        request_count: 0,
+        folder_count: 0,
+        warnings,
+    };
+
+    warn_unsupported_events(&collection.event, "collection", &mut result.warnings);
 
     import_items(
         db,
         &collection.item,
         &created_coll.id,
         None,
-        &mut request_count,
-        &mut folder_count,
+        collection.auth.as_ref(),
+        &mut result,
     )?;
 
-    Ok(ImportResult {
-        collection_name: collection.info.name,
-        request_count,
-        folder_count,
-    })
+    if !collection.variable.is_empty() {
+        import_collection_variables(db, workspace_id, &collection.info.name, &collection.variable)?;
+    }
+
+    Ok(result)
 }
 
 fn import_items(
@@ -155,188 +335,758 @@ fn import_items(
     items: &[PostmanItem],
     collection_id: &str,
     parent_folder_id: Option<&str>,
-    request_count: &mut usize,
-    folder_count: &mut usize,
+    inherited_auth: Option<&PostmanAuth>,
+    result: &mut ImportResult,
 ) -> Result<(), String> {
     for item in items {
         match item {
-            PostmanItem::Folder { name, item: children } => {
-                let folder = db.create_folder(crate::models::collection::CreateFolderInput {
+            PostmanItem::Folder {
+                name,
+                item: children,
+                description,
+                auth,
+                event,
+            } => {
+                let folder = db.create_folder(CreateFolderInput {
                     collection_id: collection_id.to_string(),
                     parent_folder_id: parent_folder_id.map(|s| s.to_string()),
                     name: name.clone(),
+                    description: description.as_ref().and_then(|d| d.text()),
                 })?;
-                *folder_count += 1;
-                import_items(db, children, collection_id, Some(&folder.id), request_count, folder_count)?;
+                result.folder_count += 1;
+                warn_unsupported_events(event, &format!("folder '{}'", name), &mut result.warnings);
+                import_items(
+                    db,
+                    children,
+                    collection_id,
+                    Some(&folder.id),
+                    effective_auth(auth.as_ref(), inherited_auth),
+                    result,
+                )?;
             }
-            PostmanItem::Request { name, request } => {
-                let method_str = request.method.as_deref().unwrap_or("GET");
-                let method = HttpMethod::from_str(method_str)
-                    .unwrap_or(HttpMethod::GET);
-                let url = request.url.raw_url();
-
-                let headers: Vec<KeyValue> = request
-                    .header
-                    .iter()
-                    .map(|h| KeyValue {
-                        key: h.key.clone(),
-                        value: h.value.clone(),
-                        enabled: !h.disabled.unwrap_or(false),
-                    })
-                    .collect();
-
-                let query_params: Vec<KeyValue> = request
-                    .url
-                    .query_params()
-                    .iter()
-                    .map(|q| KeyValue {
-                        key: q.key.clone(),
-                        value: q.value.clone().unwrap_or_default(),
-                        enabled: !q.disabled.unwrap_or(false),
-                    })
-                    .collect();
-
-                let (body_type, body_content) = match &request.body {
-                    Some(body) => {
-                        let mode = body.mode.as_deref().unwrap_or("none");
-                        let lang = body
-                            .options
-                            .as_ref()
-                            .and_then(|o| o.raw.as_ref())
-                            .and_then(|r| r.language.as_deref());
-                        let bt = match mode {
-                            "raw" => {
-                                if lang == Some("json") {
-                                    BodyType::Json
-                                } else {
-                                    BodyType::Text
-                                }
-                            }
-                            "urlencoded" => BodyType::FormUrlEncoded,
-                            _ => BodyType::None,
-                        };
-                        (bt, body.raw.clone())
-                    }
-                    None => (BodyType::None, None),
-                };
+            PostmanItem::Request {
+                name,
+                request,
+                event,
+                response,
+            } => {
+                let mut input = request_to_input(name, request, &mut result.warnings);
+                input.scripts = events_to_scripts(event);
+
+                if let Some(auth) = effective_auth(request.auth.as_ref(), inherited_auth) {
+                    apply_auth(auth, &mut input, &mut result.warnings);
+                }
 
-                let input = CreateRequestInput {
-                    name: name.clone(),
-                    method,
-                    url,
-                    headers,
-                    query_params,
-                    body_type,
-                    body_content,
-                    collection_id: Some(collection_id.to_string()),
-                    folder_id: parent_folder_id.map(|s| s.to_string()),
-                };
-
-                db.create_request(input)?;
-                *request_count += 1;
+                input.collection_id = Some(collection_id.to_string());
+                input.folder_id = parent_folder_id.map(|s| s.to_string());
+                let created = db.create_request(input)?;
+                result.request_count += 1;
+
+                for example in response {
+                    db.create_example(response_to_example(
+                        &created.id,
+                        example,
+                        &mut result.warnings,
+                    ))?;
+                }
             }
         }
     }
     Ok(())
 }
 
-/// Exports a Steq collection to Postman v2.1 JSON format.
+fn request_to_input(
+    name: &str,
+    request: &PostmanRequest,
+    warnings: &mut Vec<String>,
+) -> CreateRequestInput {
+    let method_str = request.method.as_deref().unwrap_or("GET");
+    let method = HttpMethod::from_str(method_str).unwrap_or(HttpMethod::GET);
+
+    let query_params: Vec<KeyValue> = request
+        .url
+        .query_params()
+        .iter()
+        .map(|q| KeyValue {
+            key: q.key.clone(),
+            value: q.value.clone().unwrap_or_default(),
+            enabled: !q.disabled.unwrap_or(false),
+        })
+        .collect();
+
+    // The raw URL repeats the query; keep it only in query_params
+    let raw = request.url.raw_url();
+    let url = if query_params.is_empty() {
+        raw
+    } else {
+        raw.split('?').next().unwrap_or_default().to_string()
+    };
+
+    let headers: Vec<KeyValue> = request
+        .header
+        .iter()
+        .map(|h| KeyValue {
+            key: h.key.clone(),
+            value: h.value.clone(),
+            enabled: !h.disabled.unwrap_or(false),
+        })
+        .collect();
+
+    let path_params: Vec<KeyValue> = request
+        .url
+        .path_variables()
+        .iter()
+        .map(|v| KeyValue {
+            key: v.key.clone(),
+            value: v.value.clone().unwrap_or_default(),
+            enabled: !v.disabled.unwrap_or(false),
+        })
+        .collect();
+
+    let (body_type, body_content) = match &request.body {
+        Some(body) => body_to_steq(name, body, warnings),
+        None => (BodyType::None, None),
+    };
+
+    CreateRequestInput {
+        name: name.to_string(),
+        method,
+        url,
+        headers,
+        query_params,
+        body_type,
+        body_content,
+        description: request.description.as_ref().and_then(|d| d.text()),
+        path_params,
+        scripts: None,
+        kind: RequestKind::Http,
+        collection_id: None,
+        folder_id: None,
+    }
+}
+
+fn response_to_example(
+    request_id: &str,
+    response: &PostmanResponse,
+    warnings: &mut Vec<String>,
+) -> CreateExampleInput {
+    let name = response
+        .name
+        .clone()
+        .filter(|n| !n.is_empty())
+        .or_else(|| response.code.map(|c| c.to_string()))
+        .unwrap_or_else(|| "Example".to_string());
+    let headers = match &response.header {
+        Some(serde_json::Value::Array(items)) => items
+            .iter()
+            .filter_map(|h| {
+                Some(KeyValue {
+                    key: h.get("key")?.as_str()?.to_string(),
+                    value: h.get("value").map(json_to_string).unwrap_or_default(),
+                    enabled: !h.get("disabled").and_then(|d| d.as_bool()).unwrap_or(false),
+                })
+            })
+            .collect(),
+        _ => vec![],
+    };
+    let original_request = response.original_request.as_ref().map(|request| {
+        let input = request_to_input(&name, request, warnings);
+        ExecuteRequestInput {
+            method: input.method,
+            url: input.url,
+            headers: input.headers,
+            query_params: input.query_params,
+            path_params: input.path_params,
+            body_type: input.body_type,
+            body_content: input.body_content,
+        }
+    });
+    CreateExampleInput {
+        request_id: request_id.to_string(),
+        name,
+        status: response.code,
+        headers,
+        body: response.body.clone(),
+        original_request,
+    }
+}
+
+fn body_to_steq(
+    name: &str,
+    body: &PostmanBody,
+    warnings: &mut Vec<String>,
+) -> (BodyType, Option<String>) {
+    let mode = body.mode.as_deref().unwrap_or("none");
+    match mode {
+        "raw" => {
+            let lang = body
+                .options
+                .as_ref()
+                .and_then(|o| o.raw.as_ref())
+                .and_then(|r| r.language.as_deref());
+            let bt = if lang == Some("json") {
+                BodyType::Json
+            } else {
+                BodyType::Text
+            };
+            (bt, body.raw.clone())
+        }
+        "urlencoded" => {
+            if body.urlencoded.is_empty() {
+                return (BodyType::FormUrlEncoded, body.raw.clone());
+            }
+            let fields = form_fields(name, &body.urlencoded, warnings);
+            (BodyType::FormUrlEncoded, Some(fields.join("&")))
+        }
+        "formdata" => {
+            let fields = form_fields(name, &body.formdata, warnings);
+            (BodyType::Multipart, Some(fields.join("&")))
+        }
+        "graphql" => {
+            let graphql = body.graphql.as_ref();
+            let content = serde_json::json!({
+                "query": graphql.and_then(|g| g.query.clone()).unwrap_or_default(),
+                "variables": graphql.and_then(|g| g.variables.clone()).unwrap_or_default(),
+                "operationName": "",
+            });
+            (BodyType::GraphQL, Some(content.to_string()))
+        }
+        "file" => {
+            let src = body.file.as_ref().and_then(|f| f.src.clone()).unwrap_or_default();
+            warnings.push(format!(
+                "Request '{}': binary file body ({}) is not supported and was dropped",
+                name, src
+            ));
+            (BodyType::None, None)
+        }
+        _ => (BodyType::None, None),
+    }
+}
+
+/// Converts form params into Steq's `key=value` / `key=@path` fields. Steq
+/// has no per-field enabled flag, so disabled fields are dropped.
+fn form_fields(name: &str, params: &[PostmanFormParam], warnings: &mut Vec<String>) -> Vec<String> {
+    let mut fields = Vec::new();
+    for param in params {
+        if param.disabled.unwrap_or(false) {
+            warnings.push(format!(
+                "Request '{}': disabled form field '{}' was dropped",
+                name, param.key
+            ));
+            continue;
+        }
+        if param.param_type.as_deref() == Some("file") {
+            let paths: Vec<String> = match &param.src {
+                Some(serde_json::Value::Array(items)) => items.iter().map(json_to_string).collect(),
+                Some(value) => vec![json_to_string(value)],
+                None => vec![],
+            };
+            for path in paths {
+                fields.push(format!("{}=@{}", param.key, path));
+            }
+        } else {
+            fields.push(format!(
+                "{}={}",
+                param.key,
+                param.value.as_deref().unwrap_or_default()
+            ));
+        }
+    }
+    fields
+}
+
+/// Requests without their own auth inherit from the closest folder or the collection.
+fn effective_auth<'a>(
+    own: Option<&'a PostmanAuth>,
+    inherited: Option<&'a PostmanAuth>,
+) -> Option<&'a PostmanAuth> {
+    match own {
+        Some(auth) if auth.auth_type == "inherit" => inherited,
+        Some(auth) => Some(auth),
+        None => inherited,
+    }
+}
+
+/// Steq has no auth settings, so supported schemes become headers or query params.
+fn apply_auth(auth: &PostmanAuth, input: &mut CreateRequestInput, warnings: &mut Vec<String>) {
+    let has_authorization = input
+        .headers
+        .iter()
+        .any(|h| h.key.eq_ignore_ascii_case("authorization"));
+
+    match auth.auth_type.as_str() {
+        "noauth" => {}
+        "bearer" if !has_authorization => input.headers.push(KeyValue {
+            key: "Authorization".to_string(),
+            value: format!("Bearer {}", auth.param("token").unwrap_or_default()),
+            enabled: true,
+        }),
+        "basic" if !has_authorization => {
+            let username = auth.param("username").unwrap_or_default();
+            let password = auth.param("password").unwrap_or_default();
+            if username.contains("{{") || password.contains("{{") {
+                warnings.push(format!(
+                    "Request '{}': basic auth uses variables and could not be encoded",
+                    input.name
+                ));
+                return;
+            }
+            let credentials = BASE64.encode(format!("{}:{}", username, password));
+            input.headers.push(KeyValue {
+                key: "Authorization".to_string(),
+                value: format!("Basic {}", credentials),
+                enabled: true,
+            });
+        }
+        "apikey" => {
+            let entry = KeyValue {
+                key: auth.param("key").unwrap_or_default(),
+                value: auth.param("value").unwrap_or_default(),
+                enabled: true,
+            };
+            if auth.param("in").as_deref() == Some("query") {
+                input.query_params.push(entry);
+            } else {
+                input.headers.push(entry);
+            }
+        }
+        "bearer" | "basic" => {}
+        other => warnings.push(format!(
+            "Request '{}': {} auth is not supported",
+            input.name, other
+        )),
+    }
+}
+
+fn script_text(event: &PostmanEvent) -> Option<String> {
+    let exec = event.script.as_ref()?.exec.as_ref()?;
+    let text = match exec {
+        serde_json::Value::Array(lines) => lines
+            .iter()
+            .map(json_to_string)
+            .collect::<Vec<_>>()
+            .join("\n"),
+        other => json_to_string(other),
+    };
+    (!text.trim().is_empty()).then_some(text)
+}
+
+fn events_to_scripts(events: &[PostmanEvent]) -> Option<RequestScripts> {
+    let find = |listen: &str| {
+        events
+            .iter()
+            .find(|e| e.listen == listen)
+            .and_then(script_text)
+    };
+    let scripts = RequestScripts {
+        pre_request: find("prerequest"),
+        post_response: find("test"),
+    };
+    (scripts.pre_request.is_some() || scripts.post_response.is_some()).then_some(scripts)
+}
+
+fn warn_unsupported_events(events: &[PostmanEvent], owner: &str, warnings: &mut Vec<String>) {
+    for event in events.iter().filter(|e| script_text(e).is_some()) {
+        warnings.push(format!(
+            "The {} script on {} was not imported; scripts are only kept on requests",
+            event.listen, owner
+        ));
+    }
+}
+
+fn import_collection_variables(
+    db: &Database,
+    workspace_id: &str,
+    collection_name: &str,
+    variables: &[PostmanVariable],
+) -> Result<(), String> {
+    let env = db.create_environment(&CreateEnvironmentInput {
+        workspace_id: workspace_id.to_string(),
+        name: collection_name.to_string(),
+    })?;
+
+    let variables = variables
+        .iter()
+        .filter(|v| !v.key.is_empty())
+        .enumerate()
+        .map(|(i, v)| Variable {
+            id: String::new(),
+            environment_id: env.id.clone(),
+            key: v.key.clone(),
+            value: json_to_string(&v.value),
+            is_secret: false,
+            enabled: !v.disabled.unwrap_or(false),
+            sort_order: i as i32,
+            created_at: String::new(),
+            updated_at: String::new(),
+        })
+        .collect();
+
+    db.update_environment(&UpdateEnvironmentInput {
+        id: env.id,
+        name: None,
+        variables: Some(variables),
+    })?;
+    Ok(())
+}
+
+/// Exports a Steq collection to Postman v2.1 JSON format. When `environment_id`
+/// is given, its non-secret variables are written as collection variables.
 pub fn export_postman_collection(
     db: &Database,
     collection_id: &str,
+    environment_id: Option<&str>,
 ) -> Result<String, String> {
     let tree = db.get_collection_tree(collection_id)?;
+    let mut examples: HashMap<String, Vec<RequestExample>> = HashMap::new();
+    for example in db.list_collection_examples(collection_id)? {
+        examples
+            .entry(example.request_id.clone())
+            .or_default()
+            .push(example);
+    }
 
-    let items = build_postman_items(&tree.root_folders, &tree.root_requests);
+    let items = build_postman_items(&tree.root_folders, &tree.root_requests, &examples);
 
-    let postman = serde_json::json!({
-        "info": {
-            "name": tree.collection.name,
-            "schema": "https://schema.getpostman.com/json/collection/v2.1.0/collection.json"
-        },
+    let mut info = serde_json::json!({
+        "name": tree.collection.name,
+        "schema": "https://schema.getpostman.com/json/collection/v2.1.0/collection.json"
+    });
+    if let Some(description) = &tree.collection.description {
+        info["description"] = serde_json::json!(description);
+    }
+
+    let mut postman = serde_json::json!({
+        "info": info,
         "item": items
     });
 
+    if let Some(id) = environment_id {
+        let env = db
+            .get_environment(id)?
+            .ok_or_else(|| "Environment not found".to_string())?;
+        let variables: Vec<serde_json::Value> = env
+            .variables
+            .iter()
+            .filter(|v| !v.is_secret)
+            .map(|v| {
+                serde_json::json!({
+                    "key": v.key,
+                    "value": v.value,
+                    "disabled": !v.enabled
+                })
+            })
+            .collect();
+        postman["variable"] = serde_json::json!(variables);
+    }
+
     serde_json::to_string_pretty(&postman).map_err(|e| format!("Failed to serialize: {}", e))
 }
 
 fn build_postman_items(
-    folders: &[crate::models::collection::CollectionTreeNode],
-    requests: &[crate::models::request::ApiRequest],
+    folders: &[CollectionTreeNode],
+    requests: &[ApiRequest],
+    examples: &HashMap<String, Vec<RequestExample>>,
 ) -> Vec<serde_json::Value> {
     let mut items = Vec::new();
 
     for folder in folders {
-        let children = build_postman_items(&folder.children, &folder.requests);
-        items.push(serde_json::json!({
+        let children = build_postman_items(&folder.children, &folder.requests, examples);
+        let mut item = serde_json::json!({
             "name": folder.folder.name,
             "item": children
-        }));
+        });
+        if let Some(description) = &folder.folder.description {
+            item["description"] = serde_json::json!(description);
+        }
+        items.push(item);
     }
 
     for req in requests {
-        let headers: Vec<serde_json::Value> = req
-            .headers
-            .iter()
-            .map(|h| {
-                serde_json::json!({
-                    "key": h.key,
-                    "value": h.value,
-                    "disabled": !h.enabled
-                })
-            })
-            .collect();
+        let mut item = request_to_postman_item(req);
+        if let Some(examples) = examples.get(&req.id) {
+            item["response"] = serde_json::json!(examples
+                .iter()
+                .map(example_to_postman_response)
+                .collect::<Vec<_>>());
+        }
+        items.push(item);
+    }
 
-        let query: Vec<serde_json::Value> = req
-            .query_params
-            .iter()
-            .map(|q| {
-                serde_json::json!({
-                    "key": q.key,
-                    "value": q.value,
-                    "disabled": !q.enabled
-                })
+    items
+}
+
+fn key_values(pairs: &[KeyValue]) -> Vec<serde_json::Value> {
+    pairs
+        .iter()
+        .map(|kv| {
+            serde_json::json!({
+                "key": kv.key,
+                "value": kv.value,
+                "disabled": !kv.enabled
             })
-            .collect();
+        })
+        .collect()
+}
 
-        let mut request_obj = serde_json::json!({
-            "method": req.method.as_str(),
-            "url": {
-                "raw": req.url,
-                "query": query
-            },
-            "header": headers
-        });
+/// Builds a Postman request object without the description, which only saved
+/// requests have.
+fn build_postman_request(req: &ExecuteRequestInput) -> serde_json::Value {
+    let enabled_query: Vec<String> = req
+        .query_params
+        .iter()
+        .filter(|q| q.enabled)
+        .map(|q| format!("{}={}", q.key, q.value))
+        .collect();
+    let raw = if enabled_query.is_empty() {
+        req.url.clone()
+    } else {
+        format!("{}?{}", req.url, enabled_query.join("&"))
+    };
 
-        match req.body_type {
-            BodyType::None => {}
-            _ => {
-                let (mode, language) = match req.body_type {
-                    BodyType::Json => ("raw", Some("json")),
-                    BodyType::Text => ("raw", Some("text")),
-                    BodyType::FormUrlEncoded => ("urlencoded", None),
-                    _ => ("none", None),
-                };
-                let mut body = serde_json::json!({
-                    "mode": mode,
-                    "raw": req.body_content
-                });
-                if let Some(lang) = language {
-                    body["options"] = serde_json::json!({
-                        "raw": { "language": lang }
-                    });
-                }
-                request_obj["body"] = body;
+    let mut url = serde_json::json!({
+        "raw": raw,
+        "query": key_values(&req.query_params)
+    });
+    if !req.path_params.is_empty() {
+        url["variable"] = serde_json::json!(key_values(&req.path_params));
+    }
+
+    let mut request_obj = serde_json::json!({
+        "method": req.method.as_str(),
+        "url": url,
+        "header": key_values(&req.headers)
+    });
+    if let Some(body) = build_postman_body(&req.body_type, req.body_content.as_deref()) {
+        request_obj["body"] = body;
+    }
+    request_obj
+}
+
+fn request_to_postman_item(req: &ApiRequest) -> serde_json::Value {
+    let mut request_obj = build_postman_request(&ExecuteRequestInput {
+        method: req.method.clone(),
+        url: req.url.clone(),
+        headers: req.headers.clone(),
+        query_params: req.query_params.clone(),
+        path_params: req.path_params.clone(),
+        body_type: req.body_type.clone(),
+        body_content: req.body_content.clone(),
+    });
+    if let Some(description) = &req.description {
+        request_obj["description"] = serde_json::json!(description);
+    }
+
+    let mut item = serde_json::json!({
+        "name": req.name,
+        "request": request_obj
+    });
+
+    if let Some(scripts) = &req.scripts {
+        let mut events = Vec::new();
+        for (listen, script) in [
+            ("prerequest", &scripts.pre_request),
+            ("test", &scripts.post_response),
+        ] {
+            if let Some(script) = script {
+                events.push(serde_json::json!({
+                    "listen": listen,
+                    "script": {
+                        "type": "text/javascript",
+                        "exec": script.lines().collect::<Vec<_>>()
+                    }
+                }));
             }
         }
+        if !events.is_empty() {
+            item["event"] = serde_json::json!(events);
+        }
+    }
 
-        items.push(serde_json::json!({
-            "name": req.name,
-            "request": request_obj
-        }));
+    item
+}
+
+fn example_to_postman_response(example: &RequestExample) -> serde_json::Value {
+    let mut response = serde_json::json!({
+        "name": example.name,
+        "header": key_values(&example.headers),
+        "body": example.body.as_deref().unwrap_or_default()
+    });
+    if let Some(code) = example.status {
+        response["code"] = serde_json::json!(code);
+        if let Some(reason) = u16::try_from(code)
+            .ok()
+            .and_then(|c| reqwest::StatusCode::from_u16(c).ok())
+            .and_then(|c| c.canonical_reason())
+        {
+            response["status"] = serde_json::json!(reason);
+        }
+    }
+    if let Some(original) = &example.original_request {
+        response["originalRequest"] = build_postman_request(original);
     }
+    response
+}
 
-    items
+fn build_postman_body(body_type: &BodyType, body_content: Option<&str>) -> Option<serde_json::Value> {
+    let content = body_content.unwrap_or_default();
+    let fields = || {
+        content
+            .split('&')
+            .filter(|f| !f.is_empty())
+            .map(|field| field.split_once('=').unwrap_or((field, "")))
+    };
+
+    let body = match body_type {
+        BodyType::None => return None,
+        BodyType::Json | BodyType::Text => {
+            let language = if matches!(body_type, BodyType::Json) {
+                "json"
+            } else {
+                "text"
+            };
+            serde_json::json!({
+                "mode": "raw",
+                "raw": body_content,
+                "options": { "raw": { "language": language } }
+            })
+        }
+        BodyType::FormUrlEncoded => {
+            let params: Vec<serde_json::Value> = fields()
+                .map(|(key, value)| serde_json::json!({ "key": key, "value": value }))
+                .collect();
+            serde_json::json!({ "mode": "urlencoded", "urlencoded": params })
+        }
+        BodyType::Multipart => {
+            let params: Vec<serde_json::Value> = fields()
+                .map(|(key, value)| match value.strip_prefix('@') {
+                    Some(path) => serde_json::json!({ "key": key, "type": "file", "src": path }),
+                    None => serde_json::json!({ "key": key, "value": value, "type": "text" }),
+                })
+                .collect();
+            serde_json::json!({ "mode": "formdata", "formdata": params })
+        }
+        BodyType::GraphQL => {
+            let value: serde_json::Value = serde_json::from_str(content).unwrap_or_default();
+            serde_json::json!({
+                "mode": "graphql",
+                "graphql": {
+                    "query": value["query"].as_str().unwrap_or(content),
+                    "variables": value["variables"].as_str().unwrap_or_default()
+                }
+            })
+        }
+    };
+    Some(body)
+}
+
+/// Postman environment / globals export format
+#[derive(Debug, Deserialize)]
+pub struct PostmanEnvironment {
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub values: Vec<PostmanEnvironmentValue>,
+    #[serde(rename = "_postman_variable_scope", default)]
+    pub scope: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PostmanEnvironmentValue {
+    pub key: String,
+    #[serde(default)]
+    pub value: serde_json::Value,
+    #[serde(rename = "type", default)]
+    pub value_type: Option<String>,
+    #[serde(default)]
+    pub enabled: Option<bool>,
+}
+
+/// Imports a Postman environment or globals JSON file as a new environment.
+/// Values of `type: secret` are stored as secret (encrypted) variables.
+pub fn import_postman_environment(
+    db: &Database,
+    json_content: &str,
+    workspace_id: &str,
+) -> Result<Environment, String> {
+    let postman: PostmanEnvironment = serde_json::from_str(json_content)
+        .map_err(|e| format!("Invalid Postman environment JSON: {}", e))?;
+
+    let name = match postman.name.filter(|n| !n.is_empty()) {
+        Some(name) => name,
+        None if postman.scope.as_deref() == Some("globals") => "Globals".to_string(),
+        None => "Postman Environment".to_string(),
+    };
+
+    let env = db.create_environment(&CreateEnvironmentInput {
+        workspace_id: workspace_id.to_string(),
+        name,
+    })?;
+
+    let variables = postman
+        .values
+        .iter()
+        .filter(|v| !v.key.is_empty())
+        .enumerate()
+        .map(|(i, v)| Variable {
+            id: String::new(),
+            environment_id: env.id.clone(),
+            key: v.key.clone(),
+            value: json_to_string(&v.value),
+            is_secret: v.value_type.as_deref() == Some("secret"),
+            enabled: v.enabled.unwrap_or(true),
+            sort_order: i as i32,
+            created_at: String::new(),
+            updated_at: String::new(),
+        })
+        .collect();
+
+    db.update_environment(&UpdateEnvironmentInput {
+        id: env.id,
+        name: None,
+        variables: Some(variables),
+    })
+}
+
+/// Exports a Steq environment to Postman environment JSON. Secret values are
+/// blanked unless `include_secrets` is set.
+pub fn export_postman_environment(
+    db: &Database,
+    environment_id: &str,
+    include_secrets: bool,
+) -> Result<String, String> {
+    let env = db
+        .get_environment(environment_id)?
+        .ok_or_else(|| "Environment not found".to_string())?;
+
+    let values: Vec<serde_json::Value> = env
+        .variables
+        .iter()
+        .map(|v| {
+            let value = if v.is_secret && !include_secrets {
+                ""
+            } else {
+                v.value.as_str()
+            };
+            serde_json::json!({
+                "key": v.key,
+                "value": value,
+                "type": if v.is_secret { "secret" } else { "default" },
+                "enabled": v.enabled
+            })
+        })
+        .collect();
+
+    let postman = serde_json::json!({
+        "id": env.id,
+        "name": env.name,
+        "values": values,
+        "_postman_variable_scope": "environment",
+        "_postman_exported_at": chrono::Utc::now().to_rfc3339(),
+        "_postman_exported_using": "Steq"
+    });
+
+    serde_json::to_string_pretty(&postman).map_err(|e| format!("Failed to serialize: {}", e))
 }
 
 #[cfg(test)]
@@ -637,7 +1387,7 @@ mod tests {
         let coll_id = &colls[0].id;
 
         // Export
-        let exported = export_postman_collection(&db, coll_id).unwrap();
+        let exported = export_postman_collection(&db, coll_id, None).unwrap();
         let parsed: serde_json::Value = serde_json::from_str(&exported).unwrap();
 
         assert_eq!(parsed["info"]["name"], "Roundtrip");
@@ -659,4 +1409,446 @@ mod tests {
         assert_eq!(import_result.request_count, 2);
         assert_eq!(import_result.folder_count, 1);
     }
+
+    const FULL_FIDELITY: &str = r#"{
+        "info": {
+            "name": "Fidelity",
+            "description": { "content": "Collection docs", "type": "text/markdown" },
+            "schema": "https://schema.getpostman.com/json/collection/v2.1.0/collection.json"
+        },
+        "auth": { "type": "bearer", "bearer": [{ "key": "token", "value": "{{token}}", "type": "string" }] },
+        "variable": [
+            { "key": "baseUrl", "value": "https://api.example.com" },
+            { "key": "limit", "value": 10, "disabled": true }
+        ],
+        "event": [{ "listen": "prerequest", "script": { "exec": ["console.log('collection')"] } }],
+        "item": [
+            {
+                "name": "Users",
+                "description": "User endpoints",
+                "auth": { "type": "apikey", "apikey": [
+                    { "key": "key", "value": "X-Api-Key" },
+                    { "key": "value", "value": "k1" },
+                    { "key": "in", "value": "header" }
+                ] },
+                "item": [
+                    {
+                        "name": "Get User",
+                        "event": [
+                            { "listen": "prerequest", "script": { "exec": ["pm.variables.set('a', 1);", "// done"] } },
+                            { "listen": "test", "script": { "exec": "pm.test('ok', () => {});" } }
+                        ],
+                        "request": {
+                            "method": "GET",
+                            "description": "Fetch one user",
+                            "url": {
+                                "raw": "{{baseUrl}}/users/:id?expand=true",
+                                "query": [{ "key": "expand", "value": "true" }],
+                                "variable": [{ "key": "id", "value": "42" }]
+                            },
+                            "header": []
+                        }
+                    },
+                    {
+                        "name": "Upload Avatar",
+                        "request": {
+                            "method": "POST",
+                            "auth": { "type": "noauth" },
+                            "url": "{{baseUrl}}/users/avatar",
+                            "header": [],
+                            "body": {
+                                "mode": "formdata",
+                                "formdata": [
+                                    { "key": "caption", "value": "me", "type": "text" },
+                                    { "key": "file", "type": "file", "src": "/tmp/me.png" },
+                                    { "key": "old", "value": "x", "type": "text", "disabled": true }
+                                ]
+                            }
+                        }
+                    }
+                ]
+            },
+            {
+                "name": "Login",
+                "request": {
+                    "method": "POST",
+                    "auth": { "type": "basic", "basic": [
+                        { "key": "username", "value": "ada" },
+                        { "key": "password", "value": "pw" }
+                    ] },
+                    "url": "{{baseUrl}}/login",
+                    "header": [],
+                    "body": {
+                        "mode": "urlencoded",
+                        "urlencoded": [
+                            { "key": "grant_type", "value": "password" },
+                            { "key": "scope", "value": "all" }
+                        ]
+                    }
+                }
+            },
+            {
+                "name": "Query",
+                "request": {
+                    "method": "POST",
+                    "auth": { "type": "oauth2", "oauth2": [] },
+                    "url": "{{baseUrl}}/graphql",
+                    "header": [],
+                    "body": {
+                        "mode": "graphql",
+                        "graphql": { "query": "{ me { id } }", "variables": "{\"a\": 1}" }
+                    }
+                }
+            },
+            {
+                "name": "Binary",
+                "request": {
+                    "method": "PUT",
+                    "url": "{{baseUrl}}/blob",
+                    "header": [],
+                    "body": { "mode": "file", "file": { "src": "/tmp/blob.bin" } }
+                }
+            }
+        ]
+    }"#;
+
+    fn find_request<'a>(requests: &'a [ApiRequest], name: &str) -> &'a ApiRequest {
+        requests.iter().find(|r| r.name == name).unwrap()
+    }
+
+    #[test]
+    fn import_full_fidelity_bodies_and_auth() {
+        let (db, wid) = setup_test_db();
+        let result = import_postman_collection(&db, FULL_FIDELITY, &wid).unwrap();
+        assert_eq!(result.request_count, 5);
+
+        let colls = db.list_collections(&wid).unwrap();
+        assert_eq!(colls[0].description.as_deref(), Some("Collection docs"));
+        let tree = db.get_collection_tree(&colls[0].id).unwrap();
+        let users = &tree.root_folders[0];
+        assert_eq!(users.folder.description.as_deref(), Some("User endpoints"));
+
+        let get_user = find_request(&users.requests, "Get User");
+        assert_eq!(get_user.url, "{{baseUrl}}/users/:id");
+        assert_eq!(get_user.path_params[0].key, "id");
+        assert_eq!(get_user.path_params[0].value, "42");
+        assert_eq!(get_user.description.as_deref(), Some("Fetch one user"));
+        // Folder apikey auth overrides the collection bearer token
+        assert_eq!(get_user.headers[0].key, "X-Api-Key");
+        assert_eq!(get_user.headers.len(), 1);
+        let scripts = get_user.scripts.as_ref().unwrap();
+        assert_eq!(
+            scripts.pre_request.as_deref(),
+            Some("pm.variables.set('a', 1);\n// done")
+        );
+        assert_eq!(scripts.post_response.as_deref(), Some("pm.test('ok', () => {});"));
+
+        let upload = find_request(&users.requests, "Upload Avatar");
+        assert_eq!(upload.body_type.as_str(), "multipart");
+        assert_eq!(upload.body_content.as_deref(), Some("caption=me&file=@/tmp/me.png"));
+        assert!(upload.headers.is_empty());
+
+        let login = find_request(&tree.root_requests, "Login");
+        assert_eq!(login.body_type.as_str(), "form_url_encoded");
+        assert_eq!(login.body_content.as_deref(), Some("grant_type=password&scope=all"));
+        assert_eq!(login.headers[0].value, "Basic YWRhOnB3");
+
+        let query = find_request(&tree.root_requests, "Query");
+        assert_eq!(query.body_type.as_str(), "graphql");
+        let content: serde_json::Value =
+            serde_json::from_str(query.body_content.as_deref().unwrap()).unwrap();
+        assert_eq!(content["query"], "{ me { id } }");
+        assert_eq!(content["variables"], "{\"a\": 1}");
+
+        let binary = find_request(&tree.root_requests, "Binary");
+        assert_eq!(binary.body_type.as_str(), "none");
+        assert_eq!(binary.headers[0].value, "Bearer {{token}}");
+    }
+
+    #[test]
+    fn import_reports_warnings_and_variables() {
+        let (db, wid) = setup_test_db();
+        let result = import_postman_collection(&db, FULL_FIDELITY, &wid).unwrap();
+
+        let warnings = result.warnings.join("\n");
+        assert_eq!(result.warnings.len(), 4, "{}", warnings);
+        assert!(warnings.contains("prerequest script on collection"));
+        assert!(warnings.contains("disabled form field 'old'"));
+        assert!(warnings.contains("oauth2 auth"));
+        assert!(warnings.contains("/tmp/blob.bin"));
+
+        let envs = db.list_environments(&wid).unwrap();
+        let env = envs.iter().find(|e| e.name == "Fidelity").unwrap();
+        assert_eq!(env.variables[0].value, "https://api.example.com");
+        assert_eq!(env.variables[1].value, "10");
+        assert!(!env.variables[1].enabled);
+    }
+
+    #[test]
+    fn export_full_fidelity_roundtrip() {
+        let (db, wid) = setup_test_db();
+        import_postman_collection(&db, FULL_FIDELITY, &wid).unwrap();
+        let coll = db.list_collections(&wid).unwrap().remove(0);
+        let env = db.list_environments(&wid).unwrap().remove(0);
+
+        let exported = export_postman_collection(&db, &coll.id, Some(&env.id)).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&exported).unwrap();
+        assert_eq!(parsed["info"]["description"], "Collection docs");
+        assert_eq!(parsed["variable"][0]["key"], "baseUrl");
+
+        let users = &parsed["item"][0];
+        assert_eq!(users["description"], "User endpoints");
+        let get_user = users["item"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|i| i["name"] == "Get User")
+            .unwrap();
+        assert_eq!(get_user["request"]["url"]["raw"], "{{baseUrl}}/users/:id?expand=true");
+        assert_eq!(get_user["request"]["url"]["variable"][0]["value"], "42");
+        assert_eq!(get_user["event"][0]["listen"], "prerequest");
+
+        // Importing the export again yields the same requests
+        let (db2, wid2) = setup_test_db();
+        let result = import_postman_collection(&db2, &exported, &wid2).unwrap();
+        assert_eq!(result.request_count, 5);
+        let coll2 = db2.list_collections(&wid2).unwrap().remove(0);
+        let tree2 = db2.get_collection_tree(&coll2.id).unwrap();
+        let tree1 = db.get_collection_tree(&coll.id).unwrap();
+
+        let mut before: Vec<&ApiRequest> = tree1.root_requests.iter().collect();
+        before.extend(&tree1.root_folders[0].requests);
+        for original in before {
+            let all: Vec<ApiRequest> = tree2
+                .root_requests
+                .iter()
+                .chain(&tree2.root_folders[0].requests)
+                .cloned()
+                .collect();
+            let copy = find_request(&all, &original.name);
+            assert_eq!(copy.url, original.url);
+            assert_eq!(copy.body_type.as_str(), original.body_type.as_str());
+            assert_eq!(copy.body_content, original.body_content);
+            assert_eq!(copy.headers.len(), original.headers.len());
+            assert_eq!(copy.query_params.len(), original.query_params.len());
+            assert_eq!(copy.path_params.len(), original.path_params.len());
+            assert_eq!(copy.description, original.description);
+            assert_eq!(
+                copy.scripts.as_ref().and_then(|s| s.pre_request.clone()),
+                original.scripts.as_ref().and_then(|s| s.pre_request.clone())
+            );
+        }
+    }
+
+    #[test]
+    fn examples_round_trip() {
+        let (db, wid) = setup_test_db();
+        let json = r#"{
+            "info": { "name": "Examples" },
+            "item": [{
+                "name": "Get User",
+                "request": { "method": "GET", "url": "https://api.test/users/1" },
+                "response": [
+                    {
+                        "name": "Found",
+                        "originalRequest": {
+                            "method": "GET",
+                            "url": { "raw": "https://api.test/users/1?full=1", "query": [{ "key": "full", "value": "1" }] },
+                            "header": [{ "key": "Accept", "value": "application/json" }]
+                        },
+                        "status": "OK",
+                        "code": 200,
+                        "header": [{ "key": "Content-Type", "value": "application/json" }],
+                        "body": "{\"id\":1}"
+                    },
+                    { "name": "", "code": 404, "header": null, "body": "" }
+                ]
+            }]
+        }"#;
+
+        import_postman_collection(&db, json, &wid).unwrap();
+        let coll = db.list_collections(&wid).unwrap().remove(0);
+        let examples = db.list_collection_examples(&coll.id).unwrap();
+        assert_eq!(examples.len(), 2);
+        assert_eq!(examples[0].name, "Found");
+        assert_eq!(examples[0].headers[0].key, "Content-Type");
+        let original = examples[0].original_request.as_ref().unwrap();
+        assert_eq!(original.url, "https://api.test/users/1");
+        assert_eq!(original.query_params[0].key, "full");
+        assert_eq!(examples[1].name, "404");
+        assert!(examples[1].original_request.is_none());
+
+        let exported = export_postman_collection(&db, &coll.id, None).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&exported).unwrap();
+        let responses = &parsed["item"][0]["response"];
+        assert_eq!(responses[0]["code"], 200);
+        assert_eq!(responses[0]["status"], "OK");
+        assert_eq!(responses[0]["body"], "{\"id\":1}");
+        assert_eq!(
+            responses[0]["originalRequest"]["url"]["raw"],
+            "https://api.test/users/1?full=1"
+        );
+        assert_eq!(responses[1]["status"], "Not Found");
+
+        let (db2, wid2) = setup_test_db();
+        import_postman_collection(&db2, &exported, &wid2).unwrap();
+        let coll2 = db2.list_collections(&wid2).unwrap().remove(0);
+        assert_eq!(db2.list_collection_examples(&coll2.id).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn import_v20_collection() {
+        let (db, wid) = setup_test_db();
+        let json = r#"{
+            "info": {
+                "name": "Old v2.0",
+                "schema": "https://schema.getpostman.com/json/collection/v2.0.0/collection.json"
+            },
+            "item": [
+                {
+                    "name": "Me",
+                    "request": {
+                        "method": "GET",
+                        "url": "https://example.com/me",
+                        "auth": { "type": "bearer", "bearer": { "token": "abc" } }
+                    }
+                }
+            ]
+        }"#;
+
+        let result = import_postman_collection(&db, json, &wid).unwrap();
+        assert_eq!(result.request_count, 1);
+        let colls = db.list_collections(&wid).unwrap();
+        let tree = db.get_collection_tree(&colls[0].id).unwrap();
+        assert_eq!(tree.root_requests[0].headers[0].value, "Bearer abc");
+    }
+
+    #[test]
+    fn import_v1_collection() {
+        let (db, wid) = setup_test_db();
+        let json = r#"{
+            "id": "8f3a",
+            "name": "Legacy API",
+            "description": "From 2016",
+            "order": ["r3"],
+            "folders_order": ["f1"],
+            "folders": [
+                { "id": "f1", "name": "Users", "order": ["r2", "r1"], "folders_order": [] }
+            ],
+            "requests": [
+                {
+                    "id": "r1", "name": "Create User", "method": "POST",
+                    "url": "https://api.example.com/users",
+                    "headers": "Content-Type: application/json\n",
+                    "dataMode": "raw", "rawModeData": "{\"name\":\"Ada\"}",
+                    "tests": "tests['created'] = responseCode.code === 201;"
+                },
+                {
+                    "id": "r2", "name": "List Users", "method": "GET",
+                    "url": "https://api.example.com/users?page=2",
+                    "queryParams": [{ "key": "page", "value": "2", "equals": true, "enabled": true }],
+                    "headers": ""
+                },
+                {
+                    "id": "r3", "name": "Login", "method": "POST",
+                    "url": "https://api.example.com/login",
+                    "headers": "",
+                    "dataMode": "urlencoded",
+                    "data": [{ "key": "user", "value": "ada", "type": "text", "enabled": true }]
+                }
+            ]
+        }"#;
+
+        let result = import_postman_collection(&db, json, &wid).unwrap();
+        assert_eq!(result.collection_name, "Legacy API");
+        assert_eq!(result.folder_count, 1);
+        assert_eq!(result.request_count, 3);
+
+        let colls = db.list_collections(&wid).unwrap();
+        assert_eq!(colls[0].description.as_deref(), Some("From 2016"));
+        let tree = db.get_collection_tree(&colls[0].id).unwrap();
+        let users = &tree.root_folders[0];
+        let create = find_request(&users.requests, "Create User");
+        assert_eq!(create.body_type.as_str(), "json");
+        assert!(create.scripts.as_ref().unwrap().post_response.is_some());
+        let list = find_request(&users.requests, "List Users");
+        assert_eq!(list.url, "https://api.example.com/users");
+        assert_eq!(list.query_params[0].value, "2");
+        let login = find_request(&tree.root_requests, "Login");
+        assert_eq!(login.body_content.as_deref(), Some("user=ada"));
+    }
+
+    #[test]
+    fn import_unknown_schema_fails() {
+        let (db, wid) = setup_test_db();
+        let json = r#"{ "info": { "name": "x", "schema": "https://schema.getpostman.com/json/collection/v3.0.0/" }, "item": [] }"#;
+        let err = import_postman_collection(&db, json, &wid).unwrap_err();
+        assert!(err.contains("Unsupported Postman schema"));
+    }
+
+    const POSTMAN_ENVIRONMENT: &str = r#"{
+        "id": "5f1c",
+        "name": "Staging",
+        "values": [
+            { "key": "baseUrl", "value": "https://staging.example.com", "type": "default", "enabled": true },
+            { "key": "apiKey", "value": "s3cret", "type": "secret", "enabled": true },
+            { "key": "retries", "value": 3, "enabled": false }
+        ],
+        "_postman_variable_scope": "environment"
+    }"#;
+
+    #[test]
+    fn import_environment_with_secrets() {
+        let (db, wid) = setup_test_db();
+        let env = import_postman_environment(&db, POSTMAN_ENVIRONMENT, &wid).unwrap();
+        assert_eq!(env.name, "Staging");
+        assert_eq!(env.variables.len(), 3);
+
+        let api_key = &env.variables[1];
+        assert!(api_key.is_secret);
+        assert_eq!(api_key.value, "s3cret");
+        assert_eq!(env.variables[2].value, "3");
+        assert!(!env.variables[2].enabled);
+
+        let stored: String = db
+            .conn
+            .query_row(
+                "SELECT value FROM variables WHERE key = 'apiKey'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_ne!(stored, "s3cret");
+    }
+
+    #[test]
+    fn import_globals_defaults_name() {
+        let (db, wid) = setup_test_db();
+        let json = r#"{
+            "values": [{ "key": "token", "value": "t" }],
+            "_postman_variable_scope": "globals"
+        }"#;
+        let env = import_postman_environment(&db, json, &wid).unwrap();
+        assert_eq!(env.name, "Globals");
+        assert!(env.variables[0].enabled);
+    }
+
+    #[test]
+    fn export_environment_secrets_optional() {
+        let (db, wid) = setup_test_db();
+        let env = import_postman_environment(&db, POSTMAN_ENVIRONMENT, &wid).unwrap();
+
+        let masked: serde_json::Value =
+            serde_json::from_str(&export_postman_environment(&db, &env.id, false).unwrap())
+                .unwrap();
+        assert_eq!(masked["name"], "Staging");
+        assert_eq!(masked["values"][1]["type"], "secret");
+        assert_eq!(masked["values"][1]["value"], "");
+        assert_eq!(masked["values"][0]["value"], "https://staging.example.com");
+
+        let full: serde_json::Value =
+            serde_json::from_str(&export_postman_environment(&db, &env.id, true).unwrap())
+                .unwrap();
+        assert_eq!(full["values"][1]["value"], "s3cret");
+    }
 }
@@ -0,0 +1,499 @@
+use serde_json::{json, Value};
+
+/// Postman collection schema versions the importer understands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PostmanSchema {
+    V1,
+    V20,
+    V21,
+}
+
+/// Detects the collection format from `info.schema`, falling back to the shape
+/// of the document (v1 has a top-level `requests` list and no `info`).
+pub fn detect_schema(value: &Value) -> Result<PostmanSchema, String> {
+    if let Some(schema) = value.pointer("/info/schema").and_then(|s| s.as_str()) {
+        if schema.contains("v2.0") {
+            return Ok(PostmanSchema::V20);
+        }
+        if schema.contains("v2.1") {
+            return Ok(PostmanSchema::V21);
+        }
+        return Err(format!("Unsupported Postman schema: {}", schema));
+    }
+    if value.get("info").is_some() {
+        return Ok(PostmanSchema::V21);
+    }
+    if value.get("requests").is_some_and(|r| r.is_array()) {
+        return Ok(PostmanSchema::V1);
+    }
+    Err("Unrecognized Postman collection format".to_string())
+}
+
+/// Converts a v1 collection into the v2.1 layout so it can go through the
+/// regular import path. Folder and request order follow the v1 `order` and
+/// `folders_order` arrays; requests no folder references end up at the root,
+/// as do folders only reachable through a nesting cycle.
+pub fn convert_v1(v1: &Value, warnings: &mut Vec<String>) -> Value {
+    let requests: Vec<&Value> = array(v1, "requests").collect();
+    let folders: Vec<&Value> = array(v1, "folders").collect();
+
+    // Folders listed in another folder's `folders_order` (or pointing at a parent
+    // via `folder`) are nested; the rest sit at the root.
+    let child_folder_ids: Vec<&str> = folders
+        .iter()
+        .flat_map(|f| ids(f, "folders_order"))
+        .chain(
+            folders
+                .iter()
+                .filter(|f| str_field(f, "folder").is_some())
+                .filter_map(|f| str_field(f, "id")),
+        )
+        .collect();
+    let mut root_folder_ids: Vec<&str> = ids(v1, "folders_order").collect();
+    for id in folders.iter().filter_map(|f| str_field(f, "id")) {
+        if !root_folder_ids.contains(&id) {
+            root_folder_ids.push(id);
+        }
+    }
+    root_folder_ids.retain(|id| !child_folder_ids.contains(id));
+
+    let mut placed: Vec<&str> = Vec::new();
+    let mut visited: Vec<&str> = Vec::new();
+    let mut items = Vec::new();
+    let unreached: Vec<&str> = folders.iter().filter_map(|f| str_field(f, "id")).collect();
+    for id in root_folder_ids.into_iter().chain(unreached) {
+        if visited.contains(&id) {
+            continue;
+        }
+        if let Some(folder) = find_by_id(&folders, id) {
+            items.push(convert_folder(
+                folder,
+                &folders,
+                &requests,
+                &mut placed,
+                &mut visited,
+                warnings,
+            ));
+        }
+    }
+
+    let mut root_request_ids: Vec<&str> = ids(v1, "order").collect();
+    for id in requests.iter().filter_map(|r| str_field(r, "id")) {
+        if !root_request_ids.contains(&id) {
+            root_request_ids.push(id);
+        }
+    }
+    for id in root_request_ids {
+        if placed.contains(&id) {
+            continue;
+        }
+        if let Some(request) = find_by_id(&requests, id) {
+            placed.push(id);
+            items.push(convert_request(request));
+        }
+    }
+
+    let mut info = json!({
+        "name": str_field(v1, "name").unwrap_or("Imported Collection"),
+        "schema": "https://schema.getpostman.com/json/collection/v2.1.0/collection.json",
+    });
+    copy_field(v1, "description", &mut info);
+
+    let mut collection = json!({ "info": info, "item": items });
+    if let Some(variables) = v1.get("variables").filter(|v| v.is_array()) {
+        collection["variable"] = variables.clone();
+    }
+    if let Some(auth) = v1.get("auth").filter(|a| a.is_object()) {
+        collection["auth"] = auth.clone();
+    }
+    collection
+}
+
+/// `visited` holds the folders already converted, so a folder nested in
+/// itself, directly or through others, is only converted once.
+fn convert_folder<'a>(
+    folder: &'a Value,
+    folders: &[&'a Value],
+    requests: &[&'a Value],
+    placed: &mut Vec<&'a str>,
+    visited: &mut Vec<&'a str>,
+    warnings: &mut Vec<String>,
+) -> Value {
+    let folder_id = str_field(folder, "id").unwrap_or_default();
+    visited.push(folder_id);
+
+    let mut subfolder_ids: Vec<&str> = ids(folder, "folders_order").collect();
+    for other in folders {
+        if str_field(other, "folder") == Some(folder_id) {
+            if let Some(id) = str_field(other, "id").filter(|id| !subfolder_ids.contains(id)) {
+                subfolder_ids.push(id);
+            }
+        }
+    }
+
+    let mut items = Vec::new();
+    for id in subfolder_ids {
+        let Some(sub) = find_by_id(folders, id) else {
+            continue;
+        };
+        if visited.contains(&id) {
+            warnings.push(format!(
+                "Folder '{}' is nested more than once; kept its first place only",
+                str_field(sub, "name").unwrap_or(id)
+            ));
+            continue;
+        }
+        items.push(convert_folder(
+            sub, folders, requests, placed, visited, warnings,
+        ));
+    }
+    for id in ids(folder, "order") {
+        if let Some(request) = find_by_id(requests, id) {
+            placed.push(id);
+            items.push(convert_request(request));
+        }
+    }
+
+    let mut item = json!({
+        "name": str_field(folder, "name").unwrap_or_default(),
+        "item": items,
+    });
+    copy_field(folder, "description", &mut item);
+    if let Some(auth) = folder.get("auth").filter(|a| a.is_object()) {
+        item["auth"] = auth.clone();
+    }
+    item
+}
+
+fn convert_request(request: &Value) -> Value {
+    let headers = convert_headers(request);
+    let url = str_field(request, "url").unwrap_or_default();
+
+    let mut url_obj = json!({ "raw": url });
+    if let Some(query) = request.get("queryParams").and_then(|q| q.as_array()) {
+        let query: Vec<Value> = query.iter().map(convert_pair).collect();
+        url_obj["query"] = json!(query);
+    }
+    let path_variables: Vec<Value> =
+        match request.get("pathVariableData").and_then(|p| p.as_array()) {
+            Some(data) => data.iter().map(convert_pair).collect(),
+            None => request
+                .get("pathVariables")
+                .and_then(|p| p.as_object())
+                .map(|vars| {
+                    vars.iter()
+                        .map(|(k, v)| json!({ "key": k, "value": text(v) }))
+                        .collect()
+                })
+                .unwrap_or_default(),
+        };
+    if !path_variables.is_empty() {
+        url_obj["variable"] = json!(path_variables);
+    }
+
+    let mut req = json!({
+        "method": str_field(request, "method").unwrap_or("GET"),
+        "url": url_obj,
+        "header": headers,
+    });
+    copy_field(request, "description", &mut req);
+    if let Some(body) = convert_body(request, &headers) {
+        req["body"] = body;
+    }
+    if let Some(auth) = convert_auth(request) {
+        req["auth"] = auth;
+    }
+
+    let mut item = json!({
+        "name": str_field(request, "name").unwrap_or_default(),
+        "request": req,
+    });
+
+    let mut events = Vec::new();
+    for (field, listen) in [("preRequestScript", "prerequest"), ("tests", "test")] {
+        if let Some(script) = str_field(request, field).filter(|s| !s.trim().is_empty()) {
+            events.push(json!({
+                "listen": listen,
+                "script": { "type": "text/javascript", "exec": script.lines().collect::<Vec<_>>() },
+            }));
+        }
+    }
+    if !events.is_empty() {
+        item["event"] = json!(events);
+    }
+    item
+}
+
+/// v1 stores headers as `headerData` pairs or as a newline-separated string where
+/// disabled lines are commented out with `//`.
+fn convert_headers(request: &Value) -> Vec<Value> {
+    if let Some(data) = request.get("headerData").and_then(|h| h.as_array()) {
+        return data.iter().map(convert_pair).collect();
+    }
+    str_field(request, "headers")
+        .unwrap_or_default()
+        .lines()
+        .filter_map(|line| {
+            let trimmed = line.trim();
+            let (disabled, line) = match trimmed.strip_prefix("//") {
+                Some(rest) => (true, rest.trim()),
+                None => (false, trimmed),
+            };
+            let (key, value) = line.split_once(':')?;
+            Some(json!({ "key": key.trim(), "value": value.trim(), "disabled": disabled }))
+        })
+        .collect()
+}
+
+fn convert_body(request: &Value, headers: &[Value]) -> Option<Value> {
+    let mode = str_field(request, "dataMode")?;
+    let body = match mode {
+        "raw" => {
+            let raw = str_field(request, "rawModeData").unwrap_or_default();
+            if raw.is_empty() {
+                return None;
+            }
+            let is_json = headers.iter().any(|h| {
+                h["key"]
+                    .as_str()
+                    .is_some_and(|k| k.eq_ignore_ascii_case("content-type"))
+                    && h["value"].as_str().is_some_and(|v| v.contains("json"))
+            });
+            json!({
+                "mode": "raw",
+                "raw": raw,
+                "options": { "raw": { "language": if is_json { "json" } else { "text" } } },
+            })
+        }
+        "urlencoded" => {
+            let params: Vec<Value> = array(request, "data").map(convert_pair).collect();
+            json!({ "mode": "urlencoded", "urlencoded": params })
+        }
+        "params" => {
+            let params: Vec<Value> = array(request, "data")
+                .map(|p| {
+                    let mut param = convert_pair(p);
+                    if str_field(p, "type") == Some("file") {
+                        param["type"] = json!("file");
+                        param["src"] = p.get("value").cloned().unwrap_or(Value::Null);
+                    } else {
+                        param["type"] = json!("text");
+                    }
+                    param
+                })
+                .collect();
+            json!({ "mode": "formdata", "formdata": params })
+        }
+        "graphql" => {
+            let data = request.get("graphqlModeData").cloned().unwrap_or(json!({}));
+            let variables = match data.get("variables") {
+                Some(Value::String(s)) => s.clone(),
+                Some(Value::Null) | None => String::new(),
+                Some(other) => other.to_string(),
+            };
+            json!({
+                "mode": "graphql",
+                "graphql": { "query": data.get("query").cloned().unwrap_or(json!("")), "variables": variables },
+            })
+        }
+        "binary" => json!({ "mode": "file", "file": { "src": Value::Null } }),
+        _ => return None,
+    };
+    Some(body)
+}
+
+/// Newer v1 exports carry a v2-style `auth` object; older ones use helpers.
+fn convert_auth(request: &Value) -> Option<Value> {
+    if let Some(auth) = request.get("auth").filter(|a| a.is_object()) {
+        return Some(auth.clone());
+    }
+    let attrs = request
+        .get("helperAttributes")
+        .cloned()
+        .unwrap_or(json!({}));
+    match str_field(request, "currentHelper")? {
+        "basicAuth" => Some(json!({ "type": "basic", "basic": attrs })),
+        "bearerAuth" => Some(json!({ "type": "bearer", "bearer": attrs })),
+        "normal" => None,
+        other => Some(json!({ "type": other })),
+    }
+}
+
+fn convert_pair(pair: &Value) -> Value {
+    json!({
+        "key": str_field(pair, "key").unwrap_or_default(),
+        "value": pair.get("value").map(text).unwrap_or_default(),
+        "disabled": pair.get("enabled").and_then(|e| e.as_bool()) == Some(false),
+    })
+}
+
+fn array<'a>(value: &'a Value, key: &str) -> impl Iterator<Item = &'a Value> {
+    value
+        .get(key)
+        .and_then(|v| v.as_array())
+        .into_iter()
+        .flatten()
+}
+
+fn ids<'a>(value: &'a Value, key: &str) -> impl Iterator<Item = &'a str> {
+    array(value, key).filter_map(|v| v.as_str())
+}
+
+fn find_by_id<'a>(values: &[&'a Value], id: &str) -> Option<&'a Value> {
+    values
+        .iter()
+        .copied()
+        .find(|v| str_field(v, "id") == Some(id))
+}
+
+fn str_field<'a>(value: &'a Value, key: &str) -> Option<&'a str> {
+    value.get(key).and_then(|v| v.as_str())
+}
+
+fn text(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        Value::Null => String::new(),
+        other => other.to_string(),
+    }
+}
+
+fn copy_field(from: &Value, key: &str, to: &mut Value) {
+    if let Some(value) = from.get(key).filter(|v| !v.is_null()) {
+        if let Value::Object(map) = to {
+            map.insert(key.to_string(), value.clone());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detect_schema_versions() {
+        let v21 = json!({ "info": { "name": "a", "schema": "https://schema.getpostman.com/json/collection/v2.1.0/collection.json" }, "item": [] });
+        let v20 = json!({ "info": { "name": "a", "schema": "https://schema.getpostman.com/json/collection/v2.0.0/collection.json" }, "item": [] });
+        let v1 = json!({ "id": "1", "name": "a", "requests": [] });
+        assert_eq!(detect_schema(&v21).unwrap(), PostmanSchema::V21);
+        assert_eq!(detect_schema(&v20).unwrap(), PostmanSchema::V20);
+        assert_eq!(detect_schema(&v1).unwrap(), PostmanSchema::V1);
+        assert_eq!(
+            detect_schema(&json!({ "info": { "name": "a" } })).unwrap(),
+            PostmanSchema::V21
+        );
+        assert!(detect_schema(&json!({ "foo": 1 })).is_err());
+        assert!(detect_schema(&json!({ "info": { "schema": "v3.0" } })).is_err());
+    }
+
+    #[test]
+    fn convert_v1_orders_folders_and_requests() {
+        let v1 = json!({
+            "id": "c1",
+            "name": "Legacy",
+            "order": ["r3", "r4"],
+            "folders_order": ["f1"],
+            "folders": [
+                { "id": "f1", "name": "Accounts", "order": ["r2", "r1"], "folders_order": ["f2"] },
+                { "id": "f2", "name": "Admin", "order": ["r5"] }
+            ],
+            "requests": [
+                { "id": "r1", "name": "One", "method": "GET", "url": "https://x.test/1" },
+                { "id": "r2", "name": "Two", "method": "GET", "url": "https://x.test/2" },
+                { "id": "r3", "name": "Three", "method": "GET", "url": "https://x.test/3" },
+                { "id": "r4", "name": "Four", "method": "GET", "url": "https://x.test/4" },
+                { "id": "r5", "name": "Five", "method": "GET", "url": "https://x.test/5" },
+                { "id": "r6", "name": "Orphan", "method": "GET", "url": "https://x.test/6" }
+            ]
+        });
+        let v21 = convert_v1(&v1, &mut Vec::new());
+        let names = |items: &Value| -> Vec<String> {
+            items
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|i| i["name"].as_str().unwrap().to_string())
+                .collect()
+        };
+        assert_eq!(v21["info"]["name"], "Legacy");
+        assert_eq!(
+            names(&v21["item"]),
+            vec!["Accounts", "Three", "Four", "Orphan"]
+        );
+        assert_eq!(names(&v21["item"][0]["item"]), vec!["Admin", "Two", "One"]);
+        assert_eq!(names(&v21["item"][0]["item"][0]["item"]), vec!["Five"]);
+    }
+
+    #[test]
+    fn convert_v1_breaks_folder_cycles() {
+        let v1 = json!({
+            "id": "c1",
+            "name": "Loops",
+            "folders_order": ["f1"],
+            "folders": [
+                { "id": "f1", "name": "Self", "order": ["r1"], "folders_order": ["f1"] },
+                { "id": "f2", "name": "Ping", "folders_order": ["f3"] },
+                { "id": "f3", "name": "Pong", "order": ["r2"], "folders_order": ["f2"] }
+            ],
+            "requests": [
+                { "id": "r1", "name": "One", "method": "GET", "url": "https://x.test/1" },
+                { "id": "r2", "name": "Two", "method": "GET", "url": "https://x.test/2" }
+            ]
+        });
+        let mut warnings = Vec::new();
+        let v21 = convert_v1(&v1, &mut warnings);
+
+        assert_eq!(v21["item"][0]["name"], "Self");
+        assert_eq!(v21["item"][0]["item"].as_array().unwrap().len(), 1);
+        assert_eq!(v21["item"][0]["item"][0]["name"], "One");
+        assert_eq!(v21["item"][1]["name"], "Ping");
+        assert_eq!(v21["item"][1]["item"][0]["name"], "Pong");
+        assert_eq!(v21["item"][1]["item"][0]["item"][0]["name"], "Two");
+        assert_eq!(v21["item"].as_array().unwrap().len(), 2);
+        assert_eq!(warnings.len(), 2);
+        assert!(warnings[0].contains("'Self'"));
+    }
+
+    #[test]
+    fn convert_v1_request_fields() {
+        let request = json!({
+            "id": "r1",
+            "name": "Create",
+            "method": "POST",
+            "url": "https://x.test/users/:id?verbose=1",
+            "headers": "Content-Type: application/json\n// X-Old: 1\n",
+            "queryParams": [{ "key": "verbose", "value": "1", "equals": true, "enabled": true }],
+            "pathVariables": { "id": "7" },
+            "dataMode": "raw",
+            "rawModeData": "{\"a\":1}",
+            "preRequestScript": "console.log(1);",
+            "tests": "tests['ok'] = true;",
+            "currentHelper": "basicAuth",
+            "helperAttributes": { "username": "u", "password": "p" }
+        });
+        let item = convert_request(&request);
+        let req = &item["request"];
+        assert_eq!(req["header"][0]["key"], "Content-Type");
+        assert_eq!(req["header"][1]["disabled"], true);
+        assert_eq!(req["url"]["query"][0]["key"], "verbose");
+        assert_eq!(req["url"]["variable"][0]["value"], "7");
+        assert_eq!(req["body"]["options"]["raw"]["language"], "json");
+        assert_eq!(req["auth"]["basic"]["username"], "u");
+        assert_eq!(item["event"][0]["listen"], "prerequest");
+        assert_eq!(item["event"][1]["listen"], "test");
+
+        let form = json!({
+            "name": "Upload",
+            "url": "https://x.test",
+            "dataMode": "params",
+            "data": [
+                { "key": "f", "value": "a.png", "type": "file", "enabled": true },
+                { "key": "n", "value": "1", "type": "text", "enabled": false }
+            ]
+        });
+        let body = &convert_request(&form)["request"]["body"];
+        assert_eq!(body["mode"], "formdata");
+        assert_eq!(body["formdata"][0]["src"], "a.png");
+        assert_eq!(body["formdata"][1]["disabled"], true);
+    }
+}
@@ -1,19 +1,37 @@
+mod codegen;
 mod commands;
 mod db;
+mod graphql;
+mod grpc;
+mod history;
 mod http;
 mod crypto;
 mod io;
+mod mock;
 mod models;
+mod proxy;
+mod redact;
+mod storage;
+mod websocket;
 
 use std::sync::Mutex;
 
 use tauri::Manager;
 
+use commands::codegen_cmd::*;
 use commands::collection_cmd::*;
 use commands::environment_cmd::*;
+use commands::example_cmd::*;
+use commands::graphql_cmd::*;
+use commands::grpc_cmd::*;
 use commands::history_cmd::*;
 use commands::io_cmd::*;
+use commands::mock_cmd::*;
+use commands::proxy_cmd::*;
 use commands::request_cmd::*;
+use commands::search_cmd::*;
+use commands::storage_cmd::*;
+use commands::websocket_cmd::*;
 use commands::workspace_cmd::*;
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
@@ -37,8 +55,30 @@ pub fn run() {
             database
                 .run_migrations()
                 .expect("Failed to run migrations");
+            // The proxy and WebSocket connections don't survive a restart, so
+            // neither do their sessions
+            let _ = database.close_open_capture_sessions();
+            let _ = database.close_open_websocket_sessions();
 
             app.manage(Mutex::new(database));
+            app.manage(storage::watcher::StorageWatchers::default());
+            app.manage(mock::MockServers::default());
+            app.manage(proxy::ProxyState::default());
+            app.manage(websocket::WebSocketSessions::default());
+            app.manage(http::sse::EventStreams::default());
+            let handle = app.handle().clone();
+            app.manage(history::retention::HistoryPruner::start(move |workspace_id| {
+                if let Ok(db) = handle.state::<Mutex<db::Database>>().lock() {
+                    let _ = db.prune_history(workspace_id);
+                }
+            }));
+            resume_storage(app.handle()).expect("Failed to resume collection storage");
+
+            // Retention may have been tightened or history imported since the last run
+            let handle = app.handle().clone();
+            std::thread::spawn(move || {
+                let _ = history::retention::prune_all(&handle.state::<Mutex<db::Database>>());
+            });
 
             Ok(())
         })
@@ -49,6 +89,8 @@ pub fn run() {
             delete_request,
             list_requests_by_collection,
             execute_request,
+            execute_request_stream,
+            stop_request_stream,
             get_current_workspace,
             create_collection,
             list_collections,
@@ -70,6 +112,63 @@ pub fn run() {
             delete_history_entry,
             import_postman,
             export_postman,
+            import_postman_environment,
+            export_postman_environment,
+            export_openapi,
+            import_har,
+            export_har,
+            parse_curl,
+            import_insomnia,
+            import_bruno,
+            export_bruno,
+            import_http_file,
+            export_http_file,
+            generate_code,
+            link_collection_directory,
+            open_collection_directory,
+            unlink_collection_directory,
+            get_collection_storage,
+            sync_collection_directory,
+            search,
+            get_history_retention,
+            set_history_retention,
+            vacuum,
+            get_history_redaction,
+            set_history_redaction,
+            reveal_history_entry,
+            replay_history_entry,
+            diff_history_entries,
+            save_history_to_collection,
+            list_examples,
+            create_example,
+            update_example,
+            delete_example,
+            start_mock_server,
+            stop_mock_server,
+            list_mock_servers,
+            get_mock_log,
+            clear_mock_log,
+            start_proxy,
+            stop_proxy,
+            get_proxy_status,
+            get_proxy_ca_certificate,
+            list_capture_sessions,
+            list_capture_entries,
+            delete_capture_session,
+            capture_session_to_collection,
+            connect_websocket,
+            send_websocket_message,
+            ping_websocket,
+            close_websocket,
+            list_websocket_sessions,
+            get_websocket_transcript,
+            delete_websocket_session,
+            list_grpc_services,
+            execute_grpc,
+            introspect_graphql_schema,
+            get_graphql_schema,
+            delete_graphql_schema,
+            validate_graphql,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -0,0 +1,574 @@
+pub mod routes;
+pub mod template;
+
+use std::collections::{HashMap, VecDeque};
+use std::convert::Infallible;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use http_body_util::{BodyExt, Full};
+use hyper::body::{Bytes, Incoming};
+use hyper::server::conn::http1;
+use hyper::service::service_fn;
+use hyper::{Request, Response};
+use hyper_util::rt::TokioIo;
+use tokio::net::TcpListener;
+use tokio::sync::watch;
+
+use crate::models::mock::{MockLogEntry, MockResponseSource, MockServerInfo};
+use crate::models::request::KeyValue;
+use routes::MockRoute;
+
+/// Older entries are dropped once a server has logged this many requests.
+const LOG_CAPACITY: usize = 500;
+
+/// Example headers that describe the original transfer rather than the body
+/// being served, so they would be wrong if replayed.
+const SKIPPED_HEADERS: &[&str] = &[
+    "content-length",
+    "transfer-encoding",
+    "content-encoding",
+    "connection",
+];
+
+/// Running mock servers, keyed by collection id. Dropping a server stops it.
+#[derive(Default)]
+pub struct MockServers {
+    servers: Mutex<HashMap<String, MockServer>>,
+}
+
+impl MockServers {
+    /// Adds a started server, stopping any other server for the same collection.
+    pub fn insert(&self, server: MockServer) -> Result<MockServerInfo, String> {
+        let mut servers = self
+            .servers
+            .lock()
+            .map_err(|e| format!("Lock error: {}", e))?;
+        let info = server.info.clone();
+        servers.insert(info.collection_id.clone(), server);
+        Ok(info)
+    }
+
+    pub fn remove(&self, collection_id: &str) -> Result<(), String> {
+        let mut servers = self
+            .servers
+            .lock()
+            .map_err(|e| format!("Lock error: {}", e))?;
+        servers.remove(collection_id);
+        Ok(())
+    }
+
+    pub fn list(&self) -> Result<Vec<MockServerInfo>, String> {
+        let servers = self
+            .servers
+            .lock()
+            .map_err(|e| format!("Lock error: {}", e))?;
+        Ok(servers.values().map(|s| s.info.clone()).collect())
+    }
+
+    pub fn log(&self, collection_id: &str) -> Result<Vec<MockLogEntry>, String> {
+        let servers = self
+            .servers
+            .lock()
+            .map_err(|e| format!("Lock error: {}", e))?;
+        match servers.get(collection_id) {
+            Some(server) => server.log(),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    pub fn clear_log(&self, collection_id: &str) -> Result<(), String> {
+        let servers = self
+            .servers
+            .lock()
+            .map_err(|e| format!("Lock error: {}", e))?;
+        if let Some(server) = servers.get(collection_id) {
+            server
+                .log
+                .lock()
+                .map_err(|e| format!("Lock error: {}", e))?
+                .clear();
+        }
+        Ok(())
+    }
+}
+
+/// A listening mock server. The accept loop and open connections end when this
+/// is dropped.
+pub struct MockServer {
+    pub info: MockServerInfo,
+    log: Arc<Mutex<VecDeque<MockLogEntry>>>,
+    _shutdown: watch::Sender<()>,
+}
+
+impl MockServer {
+    pub fn log(&self) -> Result<Vec<MockLogEntry>, String> {
+        let log = self.log.lock().map_err(|e| format!("Lock error: {}", e))?;
+        Ok(log.iter().cloned().collect())
+    }
+}
+
+struct ServerState {
+    collection_id: String,
+    routes: Vec<MockRoute>,
+    delay_ms: u64,
+    log: Arc<Mutex<VecDeque<MockLogEntry>>>,
+    on_request: Box<dyn Fn(&MockLogEntry) + Send + Sync>,
+}
+
+/// Binds `127.0.0.1:port` (any free port when `None`) and serves `routes` on the
+/// current Tokio runtime. `on_request` is called with each request as it is answered.
+pub async fn start<F>(
+    collection_id: &str,
+    port: Option<u16>,
+    routes: Vec<MockRoute>,
+    delay_ms: u64,
+    on_request: F,
+) -> Result<MockServer, String>
+where
+    F: Fn(&MockLogEntry) + Send + Sync + 'static,
+{
+    let listener = TcpListener::bind(("127.0.0.1", port.unwrap_or(0)))
+        .await
+        .map_err(|e| format!("Failed to bind mock server: {}", e))?;
+    let port = listener
+        .local_addr()
+        .map_err(|e| format!("Failed to read mock server address: {}", e))?
+        .port();
+
+    let log = Arc::new(Mutex::new(VecDeque::new()));
+    let info = MockServerInfo {
+        collection_id: collection_id.to_string(),
+        port,
+        url: format!("http://127.0.0.1:{}", port),
+        route_count: routes.len(),
+        started_at: chrono::Utc::now().to_rfc3339(),
+    };
+    let state = Arc::new(ServerState {
+        collection_id: collection_id.to_string(),
+        routes,
+        delay_ms,
+        log: log.clone(),
+        on_request: Box::new(on_request),
+    });
+    let (shutdown, stopped) = watch::channel(());
+    tokio::spawn(accept_loop(listener, state, stopped));
+
+    Ok(MockServer {
+        info,
+        log,
+        _shutdown: shutdown,
+    })
+}
+
+async fn accept_loop(
+    listener: TcpListener,
+    state: Arc<ServerState>,
+    mut stopped: watch::Receiver<()>,
+) {
+    loop {
+        let stream = tokio::select! {
+            _ = stopped.changed() => return,
+            accepted = listener.accept() => match accepted {
+                Ok((stream, _)) => stream,
+                Err(_) => continue,
+            },
+        };
+        let state = state.clone();
+        let mut stopped = stopped.clone();
+        tokio::spawn(async move {
+            let service = service_fn(move |request| {
+                let state = state.clone();
+                async move { Ok::<_, Infallible>(handle(&state, request).await) }
+            });
+            let connection = http1::Builder::new().serve_connection(TokioIo::new(stream), service);
+            tokio::select! {
+                _ = connection => {}
+                _ = stopped.changed() => {}
+            }
+        });
+    }
+}
+
+async fn handle(state: &ServerState, request: Request<Incoming>) -> Response<Full<Bytes>> {
+    let started = Instant::now();
+    let received_at = chrono::Utc::now().to_rfc3339();
+    let method = request.method().as_str().to_string();
+    let path = request.uri().path().to_string();
+    let query = request.uri().query().map(|q| q.to_string());
+    let headers: Vec<(String, String)> = request
+        .headers()
+        .iter()
+        .map(|(name, value)| {
+            (
+                name.as_str().to_string(),
+                String::from_utf8_lossy(value.as_bytes()).into_owned(),
+            )
+        })
+        .collect();
+    let body = match request.into_body().collect().await {
+        Ok(collected) => String::from_utf8_lossy(&collected.to_bytes()).into_owned(),
+        Err(_) => String::new(),
+    };
+
+    let reply = respond(
+        &state.routes,
+        &method,
+        &path,
+        query.as_deref(),
+        &headers,
+        &body,
+    );
+    let delay = reply.delay_ms.unwrap_or(state.delay_ms);
+    if delay > 0 {
+        tokio::time::sleep(Duration::from_millis(delay)).await;
+    }
+
+    let entry = MockLogEntry {
+        id: uuid::Uuid::now_v7().to_string(),
+        collection_id: state.collection_id.clone(),
+        method,
+        path,
+        query,
+        request_headers: headers
+            .into_iter()
+            .map(|(key, value)| KeyValue {
+                key,
+                value,
+                enabled: true,
+            })
+            .collect(),
+        request_body: (!body.is_empty()).then_some(body),
+        request_id: reply.request_id.clone(),
+        example_name: reply.example_name.clone(),
+        source: reply.source,
+        status: reply.status,
+        duration_ms: started.elapsed().as_millis() as u64,
+        received_at,
+    };
+    if let Ok(mut log) = state.log.lock() {
+        if log.len() == LOG_CAPACITY {
+            log.pop_front();
+        }
+        log.push_back(entry.clone());
+    }
+    (state.on_request)(&entry);
+
+    reply.into_response()
+}
+
+/// What a mock server answers to one request.
+#[derive(Debug)]
+pub struct MockReply {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+    /// Overrides the server-wide delay.
+    pub delay_ms: Option<u64>,
+    pub request_id: Option<String>,
+    pub example_name: Option<String>,
+    pub source: MockResponseSource,
+}
+
+impl MockReply {
+    fn error(status: u16, message: &str, source: MockResponseSource) -> Self {
+        MockReply {
+            status,
+            headers: vec![("content-type".to_string(), "application/json".to_string())],
+            body: serde_json::json!({ "error": message }).to_string(),
+            delay_ms: None,
+            request_id: None,
+            example_name: None,
+            source,
+        }
+    }
+
+    fn into_response(self) -> Response<Full<Bytes>> {
+        let mut builder = Response::builder().status(self.status);
+        let mut has_cors = false;
+        for (name, value) in &self.headers {
+            if SKIPPED_HEADERS.contains(&name.to_lowercase().as_str()) {
+                continue;
+            }
+            has_cors |= name.eq_ignore_ascii_case("access-control-allow-origin");
+            builder = builder.header(name.as_str(), value.as_str());
+        }
+        // Frontends under development usually run on another origin
+        if !has_cors {
+            builder = builder.header("access-control-allow-origin", "*");
+        }
+        builder
+            .body(Full::new(Bytes::from(self.body)))
+            .unwrap_or_else(|e| {
+                let mut response = Response::new(Full::new(Bytes::from(format!(
+                    "Invalid mock response: {}",
+                    e
+                ))));
+                *response.status_mut() = hyper::StatusCode::INTERNAL_SERVER_ERROR;
+                response
+            })
+    }
+}
+
+/// Picks the response for a request. Clients can choose an example with the
+/// `x-mock-response-name` or `x-mock-response-code` header; otherwise the route's
+/// configured response, its configured example or its first example is used.
+pub fn respond(
+    routes: &[MockRoute],
+    method: &str,
+    path: &str,
+    query: Option<&str>,
+    headers: &[(String, String)],
+    body: &str,
+) -> MockReply {
+    let Some((route, params)) = routes::find_route(routes, method, path) else {
+        if method.eq_ignore_ascii_case("OPTIONS") {
+            return preflight(headers);
+        }
+        return MockReply::error(
+            404,
+            &format!("No mock route for {} {}", method, path),
+            MockResponseSource::NotFound,
+        );
+    };
+    let header = |name: &str| {
+        headers
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    };
+    let context = template::request_context(method, path, query, headers, body, &params);
+    let config = route.config.as_ref();
+
+    let requested = header("x-mock-response-name")
+        .and_then(|name| route.examples.iter().find(|e| e.name == name))
+        .or_else(|| {
+            let code: i32 = header("x-mock-response-code")?.parse().ok()?;
+            route.examples.iter().find(|e| e.status == Some(code))
+        });
+    let (status, response_headers, template, example_name, source) =
+        match (requested, config.and_then(|c| c.response.as_ref())) {
+            (None, Some(response)) => (
+                response.status,
+                response.headers.clone(),
+                response.body.clone(),
+                None,
+                MockResponseSource::Configured,
+            ),
+            (requested, _) => {
+                let configured = config
+                    .and_then(|c| c.example_id.as_ref())
+                    .and_then(|id| route.examples.iter().find(|e| &e.id == id));
+                let Some(example) = requested.or(configured).or(route.examples.first()) else {
+                    let mut reply = MockReply::error(
+                        501,
+                        "This request has no examples or configured mock response",
+                        MockResponseSource::Missing,
+                    );
+                    reply.request_id = Some(route.request_id.clone());
+                    return reply;
+                };
+                (
+                    example
+                        .status
+                        .and_then(|s| u16::try_from(s).ok())
+                        .unwrap_or(200),
+                    example.headers.clone(),
+                    example.body.clone().unwrap_or_default(),
+                    Some(example.name.clone()),
+                    MockResponseSource::Example,
+                )
+            }
+        };
+
+    MockReply {
+        status,
+        headers: response_headers
+            .into_iter()
+            .filter(|h| h.enabled)
+            .map(|h| (h.key, h.value))
+            .collect(),
+        body: template::render(&template, &context),
+        delay_ms: config.and_then(|c| c.delay_ms),
+        request_id: Some(route.request_id.clone()),
+        example_name,
+        source,
+    }
+}
+
+/// Allows whatever the browser asks for, since every route is meant to be reachable.
+fn preflight(headers: &[(String, String)]) -> MockReply {
+    let requested = |name: &str| {
+        headers
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.clone())
+    };
+    MockReply {
+        status: 204,
+        headers: vec![
+            (
+                "access-control-allow-methods".to_string(),
+                requested("access-control-request-method")
+                    .unwrap_or_else(|| "GET, POST, PUT, PATCH, DELETE".to_string()),
+            ),
+            (
+                "access-control-allow-headers".to_string(),
+                requested("access-control-request-headers").unwrap_or_else(|| "*".to_string()),
+            ),
+        ],
+        body: String::new(),
+        delay_ms: None,
+        request_id: None,
+        example_name: None,
+        source: MockResponseSource::Preflight,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::example::RequestExample;
+    use crate::models::mock::{MockResponse, MockRouteConfig};
+    use crate::models::request::{ApiRequest, BodyType, HttpMethod, RequestKind};
+
+    fn route(
+        url: &str,
+        examples: Vec<RequestExample>,
+        config: Option<MockRouteConfig>,
+    ) -> MockRoute {
+        let request = ApiRequest {
+            id: "req".to_string(),
+            name: "Get user".to_string(),
+            method: HttpMethod::GET,
+            url: url.to_string(),
+            headers: vec![],
+            query_params: vec![],
+            body_type: BodyType::None,
+            body_content: None,
+            description: None,
+            path_params: vec![],
+            scripts: None,
+            kind: RequestKind::Http,
+            collection_id: None,
+            folder_id: None,
+            sort_order: 0,
+            created_at: String::new(),
+            updated_at: String::new(),
+        };
+        MockRoute::new(&request, examples, config)
+    }
+
+    fn example(id: &str, name: &str, status: i32, body: &str) -> RequestExample {
+        RequestExample {
+            id: id.to_string(),
+            request_id: "req".to_string(),
+            name: name.to_string(),
+            status: Some(status),
+            headers: vec![KeyValue {
+                key: "Content-Type".to_string(),
+                value: "application/json".to_string(),
+                enabled: true,
+            }],
+            body: Some(body.to_string()),
+            original_request: None,
+            sort_order: 0,
+            created_at: String::new(),
+            updated_at: String::new(),
+        }
+    }
+
+    #[test]
+    fn serves_examples_and_configured_responses() {
+        let examples = vec![
+            example("ok", "Found", 200, r#"{"id":"{{id}}"}"#),
+            example("missing", "Missing", 404, "{}"),
+        ];
+        let routes = vec![route("{{baseUrl}}/users/:id", examples.clone(), None)];
+
+        let reply = respond(&routes, "GET", "/users/7", None, &[], "");
+        assert_eq!((reply.status, reply.body.as_str()), (200, r#"{"id":"7"}"#));
+        assert_eq!(reply.example_name.as_deref(), Some("Found"));
+
+        let pick = [("X-Mock-Response-Code".to_string(), "404".to_string())];
+        assert_eq!(
+            respond(&routes, "GET", "/users/7", None, &pick, "").status,
+            404
+        );
+
+        let configured = vec![route(
+            "{{baseUrl}}/users/:id",
+            examples.clone(),
+            Some(MockRouteConfig {
+                request_id: "req".to_string(),
+                example_id: None,
+                response: Some(MockResponse {
+                    status: 503,
+                    headers: vec![],
+                    body: "down for {{query.reason}}".to_string(),
+                }),
+                delay_ms: Some(250),
+            }),
+        )];
+        let reply = respond(
+            &configured,
+            "GET",
+            "/users/7",
+            Some("reason=maintenance"),
+            &[],
+            "",
+        );
+        assert_eq!(reply.status, 503);
+        assert_eq!(reply.body, "down for maintenance");
+        assert_eq!(reply.delay_ms, Some(250));
+        assert_eq!(reply.source, MockResponseSource::Configured);
+
+        let empty = vec![route("/users/:id", vec![], None)];
+        let reply = respond(&empty, "GET", "/users/7", None, &[], "");
+        assert_eq!(
+            (reply.status, reply.source),
+            (501, MockResponseSource::Missing)
+        );
+        let reply = respond(&empty, "GET", "/orders", None, &[], "");
+        assert_eq!(
+            (reply.status, reply.source),
+            (404, MockResponseSource::NotFound)
+        );
+        assert_eq!(
+            respond(&empty, "OPTIONS", "/users/7", None, &[], "").status,
+            204
+        );
+    }
+
+    #[tokio::test]
+    async fn serves_over_http_until_dropped() {
+        let routes = vec![route(
+            "https://api.test/users/:id",
+            vec![example("ok", "Found", 201, r#"{"id":"{{id}}"}"#)],
+            None,
+        )];
+        let (tx, rx) = std::sync::mpsc::channel();
+        let server = start("coll", None, routes, 0, move |entry| {
+            let _ = tx.send(entry.clone());
+        })
+        .await
+        .unwrap();
+
+        let response = reqwest::get(format!("{}/users/9", server.info.url))
+            .await
+            .unwrap();
+        assert_eq!(response.status().as_u16(), 201);
+        assert_eq!(response.headers()["access-control-allow-origin"], "*");
+        assert_eq!(response.text().await.unwrap(), r#"{"id":"9"}"#);
+
+        let entry = rx.recv_timeout(Duration::from_secs(5)).unwrap();
+        assert_eq!(entry.path, "/users/9");
+        assert_eq!(entry.request_id.as_deref(), Some("req"));
+        assert_eq!(server.log().unwrap().len(), 1);
+
+        let url = server.info.url.clone();
+        drop(server);
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(reqwest::get(format!("{}/users/9", url)).await.is_err());
+    }
+}
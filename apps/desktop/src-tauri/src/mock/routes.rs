@@ -0,0 +1,239 @@
+use std::collections::HashMap;
+
+use crate::io::url_path;
+use crate::models::collection::{CollectionTree, CollectionTreeNode};
+use crate::models::example::RequestExample;
+use crate::models::mock::MockRouteConfig;
+use crate::models::request::{ApiRequest, RequestKind};
+use crate::redact::glob_match;
+
+/// A saved request served by a mock server.
+#[derive(Debug, Clone)]
+pub struct MockRoute {
+    pub request_id: String,
+    pub method: String,
+    segments: Vec<Segment>,
+    pub examples: Vec<RequestExample>,
+    pub config: Option<MockRouteConfig>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Segment {
+    Literal(String),
+    /// `:name` or a whole `{{name}}` segment; the matched text is captured.
+    Param(String),
+    /// A segment with `{{var}}` inside literal text, matched as a `*` glob.
+    Pattern(String),
+}
+
+impl MockRoute {
+    pub fn new(
+        request: &ApiRequest,
+        examples: Vec<RequestExample>,
+        config: Option<MockRouteConfig>,
+    ) -> Self {
+        MockRoute {
+            request_id: request.id.clone(),
+            method: request.method.as_str().to_string(),
+            segments: parse_segments(&url_path(&request.url)),
+            examples,
+            config,
+        }
+    }
+
+    /// Returns the captured parameters if `path` matches.
+    fn captures(&self, path: &str) -> Option<Vec<(String, String)>> {
+        let parts: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+        if parts.len() != self.segments.len() {
+            return None;
+        }
+        let mut params = Vec::new();
+        for (segment, part) in self.segments.iter().zip(parts) {
+            match segment {
+                Segment::Literal(literal) if literal == part => {}
+                Segment::Param(name) => params.push((name.clone(), part.to_string())),
+                Segment::Pattern(pattern) if glob_match(pattern, part) => {}
+                _ => return None,
+            }
+        }
+        Some(params)
+    }
+
+    /// Routes with more literal segments win over wildcards.
+    fn specificity(&self) -> (usize, usize) {
+        let literals = self
+            .segments
+            .iter()
+            .filter(|s| matches!(s, Segment::Literal(_)))
+            .count();
+        let patterns = self
+            .segments
+            .iter()
+            .filter(|s| matches!(s, Segment::Pattern(_)))
+            .count();
+        (literals, patterns)
+    }
+}
+
+/// Builds routes for every HTTP request in the collection, in tree order.
+/// Requests are matched in that order when their specificity is equal.
+pub fn build_routes(
+    tree: &CollectionTree,
+    examples: Vec<RequestExample>,
+    configs: &[MockRouteConfig],
+) -> Vec<MockRoute> {
+    let mut requests: Vec<&ApiRequest> = tree.root_requests.iter().collect();
+    collect_requests(&tree.root_folders, &mut requests);
+
+    let mut by_request: HashMap<String, Vec<RequestExample>> = HashMap::new();
+    for example in examples {
+        by_request
+            .entry(example.request_id.clone())
+            .or_default()
+            .push(example);
+    }
+    requests
+        .into_iter()
+        .filter(|request| request.kind == RequestKind::Http)
+        .map(|request| {
+            MockRoute::new(
+                request,
+                by_request.remove(&request.id).unwrap_or_default(),
+                configs.iter().find(|c| c.request_id == request.id).cloned(),
+            )
+        })
+        .collect()
+}
+
+fn collect_requests<'a>(nodes: &'a [CollectionTreeNode], requests: &mut Vec<&'a ApiRequest>) {
+    for node in nodes {
+        requests.extend(node.requests.iter());
+        collect_requests(&node.children, requests);
+    }
+}
+
+pub fn find_route<'a>(
+    routes: &'a [MockRoute],
+    method: &str,
+    path: &str,
+) -> Option<(&'a MockRoute, Vec<(String, String)>)> {
+    let mut best: Option<(&MockRoute, Vec<(String, String)>)> = None;
+    for route in routes
+        .iter()
+        .filter(|r| r.method.eq_ignore_ascii_case(method))
+    {
+        let Some(params) = route.captures(path) else {
+            continue;
+        };
+        let better = match &best {
+            Some((current, _)) => route.specificity() > current.specificity(),
+            None => true,
+        };
+        if better {
+            best = Some((route, params));
+        }
+    }
+    best
+}
+
+fn parse_segments(path: &str) -> Vec<Segment> {
+    path.split('/')
+        .filter(|s| !s.is_empty())
+        .map(|segment| {
+            if let Some(name) = segment.strip_prefix(':') {
+                return Segment::Param(name.to_string());
+            }
+            let whole_variable = segment
+                .strip_prefix("{{")
+                .and_then(|s| s.strip_suffix("}}"))
+                .filter(|name| !name.contains("{{") && !name.contains("}}"));
+            if let Some(name) = whole_variable {
+                return Segment::Param(name.trim().to_string());
+            }
+            if segment.contains("{{") {
+                return Segment::Pattern(variables_to_glob(segment));
+            }
+            Segment::Literal(segment.to_string())
+        })
+        .collect()
+}
+
+fn variables_to_glob(segment: &str) -> String {
+    let mut glob = String::new();
+    let mut rest = segment;
+    while let Some(start) = rest.find("{{") {
+        glob.push_str(&rest[..start]);
+        match rest[start..].find("}}") {
+            Some(end) => {
+                glob.push('*');
+                rest = &rest[start + end + 2..];
+            }
+            None => {
+                glob.push_str(&rest[start..]);
+                rest = "";
+            }
+        }
+    }
+    glob.push_str(rest);
+    glob
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::request::{BodyType, HttpMethod};
+
+    fn request(id: &str, method: HttpMethod, url: &str) -> ApiRequest {
+        ApiRequest {
+            id: id.to_string(),
+            name: id.to_string(),
+            method,
+            url: url.to_string(),
+            headers: vec![],
+            query_params: vec![],
+            body_type: BodyType::None,
+            body_content: None,
+            description: None,
+            path_params: vec![],
+            scripts: None,
+            kind: RequestKind::Http,
+            collection_id: None,
+            folder_id: None,
+            sort_order: 0,
+            created_at: String::new(),
+            updated_at: String::new(),
+        }
+    }
+
+    #[test]
+    fn matches_wildcards_and_prefers_literals() {
+        let routes: Vec<MockRoute> = [
+            request("by-id", HttpMethod::GET, "{{baseUrl}}/users/:id"),
+            request("me", HttpMethod::GET, "{{baseUrl}}/users/me"),
+            request("by-var", HttpMethod::DELETE, "{{baseUrl}}/users/{{userId}}"),
+            request(
+                "versioned",
+                HttpMethod::GET,
+                "https://api.test/v{{n}}/status",
+            ),
+        ]
+        .iter()
+        .map(|r| MockRoute::new(r, vec![], None))
+        .collect();
+
+        let (route, params) = find_route(&routes, "GET", "/users/42/").unwrap();
+        assert_eq!(route.request_id, "by-id");
+        assert_eq!(params, vec![("id".to_string(), "42".to_string())]);
+
+        let (route, _) = find_route(&routes, "GET", "/users/me").unwrap();
+        assert_eq!(route.request_id, "me");
+
+        let (route, params) = find_route(&routes, "delete", "/users/7").unwrap();
+        assert_eq!(route.request_id, "by-var");
+        assert_eq!(params[0].0, "userId");
+
+        assert!(find_route(&routes, "GET", "/v2/status").is_some());
+        assert!(find_route(&routes, "GET", "/x2/status").is_none());
+        assert!(find_route(&routes, "POST", "/users/me").is_none());
+    }
+}
@@ -0,0 +1,96 @@
+use rand::Rng;
+
+/// Renders `{{name}}` placeholders in a mock response body. Names come from the
+/// incoming request (see `request_context`) plus a few dynamic values:
+/// `$timestamp`, `$isoTimestamp`, `$randomUUID` and `$randomInt`. Unknown names
+/// are left untouched so bodies that happen to contain braces survive.
+pub fn render(template: &str, context: &[(String, String)]) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let resolved = after
+            .find("}}")
+            .and_then(|end| lookup(after[..end].trim(), context).map(|value| (value, end)));
+        match resolved {
+            Some((value, end)) => {
+                out.push_str(&value);
+                rest = &after[end + 2..];
+            }
+            None => {
+                out.push_str("{{");
+                rest = after;
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+fn lookup(name: &str, context: &[(String, String)]) -> Option<String> {
+    match name {
+        "$timestamp" => Some(chrono::Utc::now().timestamp().to_string()),
+        "$isoTimestamp" => Some(chrono::Utc::now().to_rfc3339()),
+        "$randomUUID" => Some(uuid::Uuid::now_v7().to_string()),
+        "$randomInt" => Some(rand::thread_rng().gen_range(0..1000).to_string()),
+        _ => context
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.clone()),
+    }
+}
+
+/// Template names for a request: path parameters by name, `query.<name>`,
+/// `header.<name>` (lowercase), `method`, `path` and `body`.
+pub fn request_context(
+    method: &str,
+    path: &str,
+    query: Option<&str>,
+    headers: &[(String, String)],
+    body: &str,
+    params: &[(String, String)],
+) -> Vec<(String, String)> {
+    let mut context: Vec<(String, String)> = params.to_vec();
+    for pair in query
+        .unwrap_or_default()
+        .split('&')
+        .filter(|p| !p.is_empty())
+    {
+        let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+        context.push((format!("query.{}", key), value.to_string()));
+    }
+    for (name, value) in headers {
+        context.push((format!("header.{}", name.to_lowercase()), value.clone()));
+    }
+    context.push(("method".to_string(), method.to_string()));
+    context.push(("path".to_string(), path.to_string()));
+    context.push(("body".to_string(), body.to_string()));
+    context
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_request_values_and_keeps_unknown_names() {
+        let context = request_context(
+            "GET",
+            "/users/42",
+            Some("fields=name&debug"),
+            &[("X-Tenant".to_string(), "acme".to_string())],
+            "",
+            &[("id".to_string(), "42".to_string())],
+        );
+        assert_eq!(
+            render(
+                r#"{"id":{{id}},"fields":"{{ query.fields }}","tenant":"{{header.x-tenant}}","x":"{{other}}"}"#,
+                &context
+            ),
+            r#"{"id":42,"fields":"name","tenant":"acme","x":"{{other}}"}"#
+        );
+        assert_eq!(render("{{query.debug}}|{{", &context), "|{{");
+        assert_eq!(render("{{$randomUUID}}", &context).len(), 36);
+    }
+}
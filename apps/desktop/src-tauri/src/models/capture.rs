@@ -0,0 +1,60 @@
+use serde::{Deserialize, Serialize};
+
+/// The history entries recorded while the proxy was running once.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CaptureSession {
+    pub id: String,
+    pub workspace_id: String,
+    pub name: String,
+    pub started_at: String,
+    /// `None` while the proxy is still capturing.
+    pub stopped_at: Option<String>,
+    pub entry_count: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StartProxyInput {
+    pub workspace_id: String,
+    /// Any free port when omitted.
+    #[serde(default)]
+    pub port: Option<u16>,
+    /// Decrypt HTTPS with certificates issued by the local CA. Clients must
+    /// trust that CA; without it HTTPS is tunnelled and not captured.
+    #[serde(default)]
+    pub intercept_https: bool,
+    /// Listen on all interfaces so other devices, such as phones, can connect.
+    #[serde(default)]
+    pub listen_on_network: bool,
+    #[serde(default)]
+    pub session_name: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProxyInfo {
+    pub session_id: String,
+    pub workspace_id: String,
+    pub port: u16,
+    /// The address clients should use, e.g. `127.0.0.1:8899`.
+    pub address: String,
+    /// Set when listening on the network. Clients on other machines send it as
+    /// the proxy password, e.g. `http://steq:<token>@192.168.1.20:8899`.
+    pub token: Option<String>,
+    pub intercept_https: bool,
+    pub started_at: String,
+}
+
+/// Converts a capture session into a new collection.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CaptureToCollectionInput {
+    pub session_id: String,
+    /// Defaults to the session name.
+    #[serde(default)]
+    pub name: Option<String>,
+    /// Attach each distinct response status as an example.
+    #[serde(default = "default_true")]
+    pub save_responses: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
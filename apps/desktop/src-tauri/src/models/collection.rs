@@ -18,6 +18,7 @@ pub struct Folder {
     pub collection_id: String,
     pub parent_folder_id: Option<String>,
     pub name: String,
+    pub description: Option<String>,
     pub sort_order: i32,
     pub created_at: String,
     pub updated_at: String,
@@ -49,6 +50,8 @@ pub struct CreateFolderInput {
     pub collection_id: String,
     pub parent_folder_id: Option<String>,
     pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -0,0 +1,136 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// What to leave out when comparing two responses. Patterns are case-insensitive
+/// with `*` wildcards.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiffOptions {
+    /// JSON field names matched at any depth, or JSON Pointer paths when the
+    /// pattern starts with `/`, e.g. `/meta/*`.
+    #[serde(default = "default_ignore_fields")]
+    pub ignore_fields: Vec<String>,
+    #[serde(default = "default_ignore_headers")]
+    pub ignore_headers: Vec<String>,
+}
+
+impl Default for DiffOptions {
+    fn default() -> Self {
+        DiffOptions {
+            ignore_fields: default_ignore_fields(),
+            ignore_headers: default_ignore_headers(),
+        }
+    }
+}
+
+fn default_ignore_fields() -> Vec<String> {
+    [
+        "*timestamp*",
+        "created_at",
+        "createdat",
+        "updated_at",
+        "updatedat",
+        "date",
+        "time",
+        "*request_id",
+        "*requestid",
+        "trace_id",
+        "traceid",
+        "nonce",
+    ]
+    .iter()
+    .map(|f| f.to_string())
+    .collect()
+}
+
+fn default_ignore_headers() -> Vec<String> {
+    [
+        "date",
+        "age",
+        "expires",
+        "last-modified",
+        "etag",
+        "x-request-id",
+        "x-amzn-requestid",
+        "x-amzn-trace-id",
+        "cf-ray",
+        "server-timing",
+        "report-to",
+        "nel",
+    ]
+    .iter()
+    .map(|h| h.to_string())
+    .collect()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResponseDiff {
+    pub identical: bool,
+    /// Set when the status differs; `None` on either side means no response.
+    pub status: Option<StatusChange>,
+    pub headers: Vec<HeaderChange>,
+    pub body: BodyDiff,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StatusChange {
+    pub before: Option<i32>,
+    pub after: Option<i32>,
+}
+
+/// Header names are lowercased; repeated headers are joined with `, `.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HeaderChange {
+    pub name: String,
+    pub before: Option<String>,
+    pub after: Option<String>,
+}
+
+/// JSON bodies are compared structurally; anything else line by line.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum BodyDiff {
+    Json { changes: Vec<JsonChange> },
+    Text { changes: Vec<TextChange> },
+}
+
+impl BodyDiff {
+    pub fn is_empty(&self) -> bool {
+        match self {
+            BodyDiff::Json { changes } => changes.is_empty(),
+            BodyDiff::Text { changes } => changes.is_empty(),
+        }
+    }
+}
+
+/// A value that was added (`before` is `None`), removed (`after` is `None`) or
+/// changed at a JSON Pointer path.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JsonChange {
+    pub path: String,
+    pub before: Option<Value>,
+    pub after: Option<Value>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LineChangeKind {
+    Added,
+    Removed,
+}
+
+/// A changed line, with 1-based line numbers in the side it belongs to.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TextChange {
+    pub kind: LineChangeKind,
+    pub line: usize,
+    pub text: String,
+}
+
+/// The outcome of re-sending a history entry.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReplayResult {
+    pub result: super::execution::ExecutionResult,
+    /// The new history entry, if it could be recorded.
+    pub entry: Option<super::history::HistoryEntry>,
+    pub diff: ResponseDiff,
+}
@@ -0,0 +1,43 @@
+use serde::{Deserialize, Serialize};
+
+use super::request::{ExecuteRequestInput, KeyValue};
+
+/// A saved response attached to a request, used as documentation and mock data.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RequestExample {
+    pub id: String,
+    pub request_id: String,
+    pub name: String,
+    pub status: Option<i32>,
+    pub headers: Vec<KeyValue>,
+    pub body: Option<String>,
+    /// The request variant that produced this response, when it was recorded.
+    pub original_request: Option<ExecuteRequestInput>,
+    pub sort_order: i32,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateExampleInput {
+    pub request_id: String,
+    pub name: String,
+    pub status: Option<i32>,
+    #[serde(default)]
+    pub headers: Vec<KeyValue>,
+    pub body: Option<String>,
+    #[serde(default)]
+    pub original_request: Option<ExecuteRequestInput>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateExampleInput {
+    pub id: String,
+    pub name: Option<String>,
+    pub status: Option<i32>,
+    pub headers: Option<Vec<KeyValue>>,
+    pub body: Option<String>,
+    #[serde(default)]
+    pub original_request: Option<ExecuteRequestInput>,
+    pub sort_order: Option<i32>,
+}
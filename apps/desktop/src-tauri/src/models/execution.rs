@@ -21,3 +21,24 @@ pub struct ExecutionResult {
     pub timing: ExecutionTiming,
     pub error: Option<String>,
 }
+
+/// One Server-Sent Event from a streamed response.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SseEvent {
+    /// The last event id the server set, which carries over to later events.
+    pub id: Option<String>,
+    /// `message` unless the server named the event.
+    pub event: String,
+    pub data: String,
+    /// The reconnection delay the server asked for.
+    pub retry_ms: Option<u64>,
+    /// Time since the request was sent.
+    pub received_ms: f64,
+}
+
+/// Sent as the `sse-event` event while a stream is open.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StreamEvent {
+    pub stream_id: String,
+    pub event: SseEvent,
+}
@@ -0,0 +1,44 @@
+use serde::{Deserialize, Serialize};
+
+/// An introspected schema, cached per workspace and endpoint URL.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GraphQLSchema {
+    pub workspace_id: String,
+    pub url: String,
+    /// The `data` of the introspection response, `{"__schema": ...}`, which is
+    /// what editors build a client schema from.
+    pub introspection: serde_json::Value,
+    pub fetched_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ValidateGraphQLInput {
+    pub workspace_id: String,
+    /// Picks the cached schema. Without one only syntax and variables are
+    /// checked.
+    pub url: String,
+    pub query: String,
+    /// The variables as the editor holds them: a JSON object, or empty.
+    #[serde(default)]
+    pub variables: String,
+    #[serde(default)]
+    pub operation_name: String,
+}
+
+/// Which editor a validation error points into.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GraphQLErrorSource {
+    Query,
+    Variables,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GraphQLError {
+    pub message: String,
+    pub source: GraphQLErrorSource,
+    /// One-based.
+    pub line: usize,
+    /// One-based.
+    pub column: usize,
+}
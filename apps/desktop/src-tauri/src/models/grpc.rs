@@ -0,0 +1,92 @@
+use serde::{Deserialize, Serialize};
+
+use super::execution::ExecutionTiming;
+use super::request::KeyValue;
+
+/// Where the service definitions for a gRPC call come from.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum GrpcSchemaSource {
+    /// Ask the server through the gRPC reflection service.
+    Reflection,
+    /// Parse `.proto` files from disk. Imports are resolved against the include
+    /// paths, then the directory of each file.
+    ProtoFiles {
+        files: Vec<String>,
+        #[serde(default)]
+        include_paths: Vec<String>,
+    },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GrpcServicesInput {
+    /// The server, e.g. `http://localhost:50051`. Only used with reflection.
+    #[serde(default)]
+    pub url: String,
+    /// Sent with reflection requests.
+    #[serde(default)]
+    pub metadata: Vec<KeyValue>,
+    pub source: GrpcSchemaSource,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GrpcServiceInfo {
+    /// The fully qualified name, e.g. `helloworld.Greeter`.
+    pub name: String,
+    pub methods: Vec<GrpcMethodInfo>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GrpcMethodInfo {
+    pub name: String,
+    pub input_type: String,
+    pub output_type: String,
+    pub client_streaming: bool,
+    pub server_streaming: bool,
+    /// The input message as JSON with every field at its default, to start
+    /// editing from.
+    pub input_template: serde_json::Value,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GrpcCallInput {
+    /// `http://` for plaintext, `https://` for TLS. `grpc://` and `grpcs://`
+    /// are accepted, and a bare `host:port` is plaintext.
+    pub url: String,
+    pub service: String,
+    pub method: String,
+    /// The request message as JSON.
+    #[serde(default)]
+    pub message: String,
+    /// Sent as request headers. Values of `-bin` keys must be base64.
+    #[serde(default)]
+    pub metadata: Vec<KeyValue>,
+    pub source: GrpcSchemaSource,
+    /// Sent as the call deadline.
+    #[serde(default)]
+    pub timeout_ms: Option<u64>,
+}
+
+/// The outcome of a unary or server-streaming call.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GrpcResult {
+    /// The gRPC status code; 0 is OK.
+    pub status_code: i32,
+    /// The code's name, e.g. `NotFound`.
+    pub status_name: String,
+    pub status_message: String,
+    pub headers: Vec<KeyValue>,
+    pub trailers: Vec<KeyValue>,
+    /// Response messages as JSON, in the order received.
+    pub messages: Vec<serde_json::Value>,
+    /// Encoded size of the response messages.
+    pub size_bytes: u64,
+    pub timing: ExecutionTiming,
+}
+
+/// Sent as the `grpc-message` event as each response message arrives.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GrpcStreamMessage {
+    pub stream_id: String,
+    pub message: serde_json::Value,
+}
@@ -11,15 +11,156 @@ pub struct HistoryEntry {
     pub response_status: Option<i32>,
     pub response_headers: Option<String>,
     pub response_body: Option<String>,
+    /// Set when the stored body was cut to the workspace's large body limit.
+    #[serde(default)]
+    pub response_body_truncated: bool,
     pub response_size: Option<i64>,
     pub duration_ms: Option<i64>,
     pub error: Option<String>,
     pub executed_at: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct HistoryQuery {
     pub workspace_id: String,
     pub limit: Option<i64>,
     pub offset: Option<i64>,
+    /// Matches any of these methods, case-insensitively.
+    #[serde(default)]
+    pub methods: Vec<String>,
+    /// Exact status codes, e.g. `404`. Combined with `status_classes`, an entry
+    /// matches if either list matches.
+    #[serde(default)]
+    pub status_codes: Vec<i32>,
+    /// Status classes by leading digit, e.g. `4` for 4xx.
+    #[serde(default)]
+    pub status_classes: Vec<i32>,
+    #[serde(default)]
+    pub url_contains: Option<String>,
+    /// Host name, optionally with a port. `api.test` also matches `api.test:8080`.
+    #[serde(default)]
+    pub host: Option<String>,
+    #[serde(default)]
+    pub request_id: Option<String>,
+    /// RFC 3339 timestamps; both bounds are inclusive.
+    #[serde(default)]
+    pub executed_after: Option<String>,
+    #[serde(default)]
+    pub executed_before: Option<String>,
+    /// Only entries where the request failed without a response.
+    #[serde(default)]
+    pub errors_only: bool,
+    #[serde(default)]
+    pub min_duration_ms: Option<i64>,
+    #[serde(default)]
+    pub sort: HistorySort,
+    /// Continues after this entry in `sort` order. Cheaper than `offset` on
+    /// large histories and stable while new entries are being recorded.
+    #[serde(default)]
+    pub after: Option<HistoryCursor>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HistorySort {
+    #[default]
+    NewestFirst,
+    OldestFirst,
+}
+
+/// Position of the last entry of the previous page.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryCursor {
+    pub executed_at: String,
+    pub id: String,
+}
+
+/// Per-workspace limits on how much history is kept. `None` disables a limit.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HistoryRetention {
+    pub workspace_id: String,
+    pub max_entries: Option<i64>,
+    pub max_age_days: Option<i64>,
+    /// Total stored size of response bodies; the oldest entries go first.
+    pub max_body_bytes: Option<i64>,
+    /// Bodies larger than this are compressed or truncated before storing.
+    pub large_body_bytes: i64,
+    pub large_body_mode: LargeBodyMode,
+}
+
+impl HistoryRetention {
+    pub fn default_for(workspace_id: &str) -> Self {
+        HistoryRetention {
+            workspace_id: workspace_id.to_string(),
+            max_entries: Some(10_000),
+            max_age_days: None,
+            max_body_bytes: Some(512 * 1024 * 1024),
+            large_body_bytes: 256 * 1024,
+            large_body_mode: LargeBodyMode::Compress,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LargeBodyMode {
+    Compress,
+    Truncate,
+}
+
+/// Per-workspace rules for keeping credentials out of history. `Authorization`,
+/// `Proxy-Authorization`, `Cookie` and `Set-Cookie` headers and secret variable
+/// values are always redacted; the patterns add to them.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HistoryRedaction {
+    pub workspace_id: String,
+    /// Header names, case-insensitive, with `*` wildcards, e.g. `x-api-*`.
+    pub header_patterns: Vec<String>,
+    /// JSON body fields, form fields and query parameters, matched like headers.
+    pub field_patterns: Vec<String>,
+    pub mode: RedactionMode,
+}
+
+impl HistoryRedaction {
+    pub fn default_for(workspace_id: &str) -> Self {
+        HistoryRedaction {
+            workspace_id: workspace_id.to_string(),
+            header_patterns: vec!["x-api-key".to_string()],
+            field_patterns: ["password", "*secret*", "*token*", "api_key", "apikey"]
+                .iter()
+                .map(|p| p.to_string())
+                .collect(),
+            mode: RedactionMode::Mask,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RedactionMode {
+    /// Replaced with a fixed mask; the original value is gone.
+    Mask,
+    /// Encrypted with the local secret key so it can be revealed on request.
+    Encrypt,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VacuumResult {
+    pub entries_pruned: usize,
+    pub bytes_before: i64,
+    pub bytes_after: i64,
+    pub bytes_reclaimed: i64,
+}
+
+/// Promotes a history entry into a collection as a saved request.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SaveHistoryInput {
+    pub history_id: String,
+    pub collection_id: String,
+    pub folder_id: Option<String>,
+    /// Defaults to the method and URL path.
+    pub name: Option<String>,
+    /// Also attach the recorded response to the new request as an example.
+    #[serde(default)]
+    pub save_response: bool,
 }
@@ -0,0 +1,83 @@
+use serde::{Deserialize, Serialize};
+
+use super::request::KeyValue;
+
+/// A fixed response served instead of the request's examples. The body may use
+/// `{{...}}` templates, see `mock::template`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MockResponse {
+    pub status: u16,
+    #[serde(default)]
+    pub headers: Vec<KeyValue>,
+    #[serde(default)]
+    pub body: String,
+}
+
+/// Overrides for one saved request. Without one, the first example is served.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MockRouteConfig {
+    pub request_id: String,
+    /// Serve this example instead of the first one.
+    #[serde(default)]
+    pub example_id: Option<String>,
+    /// Serve this response instead of any example.
+    #[serde(default)]
+    pub response: Option<MockResponse>,
+    /// Replaces the server-wide delay for this route.
+    #[serde(default)]
+    pub delay_ms: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StartMockInput {
+    pub collection_id: String,
+    /// Any free port when omitted.
+    #[serde(default)]
+    pub port: Option<u16>,
+    #[serde(default)]
+    pub routes: Vec<MockRouteConfig>,
+    #[serde(default)]
+    pub delay_ms: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MockServerInfo {
+    pub collection_id: String,
+    pub port: u16,
+    pub url: String,
+    pub route_count: usize,
+    pub started_at: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MockResponseSource {
+    /// A response from the route's `MockRouteConfig`.
+    Configured,
+    Example,
+    /// A route matched but has no examples or configured response.
+    Missing,
+    /// No route matched.
+    NotFound,
+    /// A CORS preflight answered by the server itself.
+    Preflight,
+}
+
+/// One request received by a mock server, also sent as the `mock-request` event.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MockLogEntry {
+    pub id: String,
+    pub collection_id: String,
+    pub method: String,
+    pub path: String,
+    pub query: Option<String>,
+    pub request_headers: Vec<KeyValue>,
+    pub request_body: Option<String>,
+    /// The saved request whose route matched.
+    pub request_id: Option<String>,
+    pub example_name: Option<String>,
+    pub source: MockResponseSource,
+    pub status: u16,
+    pub duration_ms: u64,
+    pub received_at: String,
+}
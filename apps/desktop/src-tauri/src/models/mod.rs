@@ -4,3 +4,12 @@ pub mod collection;
 pub mod environment;
 pub mod workspace;
 pub mod history;
+pub mod storage;
+pub mod search;
+pub mod diff;
+pub mod example;
+pub mod mock;
+pub mod capture;
+pub mod websocket;
+pub mod grpc;
+pub mod graphql;
@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 
+use super::grpc::GrpcSchemaSource;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum HttpMethod {
     GET,
@@ -85,6 +87,37 @@ impl BodyType {
     }
 }
 
+/// Scripts attached to a request. Steq does not run them yet; they are stored so
+/// imported collections export back without losing them.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RequestScripts {
+    #[serde(default)]
+    pub pre_request: Option<String>,
+    #[serde(default)]
+    pub post_response: Option<String>,
+}
+
+/// What a saved request talks to. HTTP requests are sent by the executor; other
+/// kinds open a session using the request's URL, headers and query parameters.
+/// gRPC requests send their body as the JSON message and their headers as
+/// metadata.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RequestKind {
+    #[default]
+    Http,
+    #[serde(rename = "websocket")]
+    WebSocket {
+        #[serde(default)]
+        subprotocols: Vec<String>,
+    },
+    Grpc {
+        service: String,
+        method: String,
+        source: GrpcSchemaSource,
+    },
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ApiRequest {
     pub id: String,
@@ -95,6 +128,11 @@ pub struct ApiRequest {
     pub query_params: Vec<KeyValue>,
     pub body_type: BodyType,
     pub body_content: Option<String>,
+    pub description: Option<String>,
+    /// Values for `:name` segments in the URL path.
+    pub path_params: Vec<KeyValue>,
+    pub scripts: Option<RequestScripts>,
+    pub kind: RequestKind,
     pub collection_id: Option<String>,
     pub folder_id: Option<String>,
     pub sort_order: i32,
@@ -111,6 +149,14 @@ pub struct CreateRequestInput {
     pub query_params: Vec<KeyValue>,
     pub body_type: BodyType,
     pub body_content: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub path_params: Vec<KeyValue>,
+    #[serde(default)]
+    pub scripts: Option<RequestScripts>,
+    #[serde(default)]
+    pub kind: RequestKind,
     pub collection_id: Option<String>,
     pub folder_id: Option<String>,
 }
@@ -125,6 +171,14 @@ pub struct UpdateRequestInput {
     pub query_params: Option<Vec<KeyValue>>,
     pub body_type: Option<BodyType>,
     pub body_content: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub path_params: Option<Vec<KeyValue>>,
+    #[serde(default)]
+    pub scripts: Option<RequestScripts>,
+    #[serde(default)]
+    pub kind: Option<RequestKind>,
     pub collection_id: Option<String>,
     pub folder_id: Option<String>,
     pub sort_order: Option<i32>,
@@ -136,10 +190,22 @@ pub struct ExecuteRequestInput {
     pub url: String,
     pub headers: Vec<KeyValue>,
     pub query_params: Vec<KeyValue>,
+    #[serde(default)]
+    pub path_params: Vec<KeyValue>,
     pub body_type: BodyType,
     pub body_content: Option<String>,
 }
 
+/// A request sent with `execute_request_stream`. Events are tagged with
+/// `stream_id`, which `stop_request_stream` also takes.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExecuteStreamInput {
+    pub request: ExecuteRequestInput,
+    pub workspace_id: String,
+    pub request_id: Option<String>,
+    pub stream_id: String,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,39 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SearchHitKind {
+    Request,
+    Collection,
+    History,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchQuery {
+    pub workspace_id: String,
+    pub query: String,
+    /// Restricts results to these kinds; all kinds when omitted.
+    pub kinds: Option<Vec<SearchHitKind>>,
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchHit {
+    pub kind: SearchHitKind,
+    pub id: String,
+    pub title: String,
+    /// Method and URL for requests, execution time for history entries.
+    pub subtitle: Option<String>,
+    pub collection_id: Option<String>,
+    /// The best matching excerpt, split so matched terms can be highlighted
+    /// without the frontend having to parse markup.
+    pub snippet: Vec<SnippetSegment>,
+    /// BM25 score; lower is a better match.
+    pub rank: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SnippetSegment {
+    pub text: String,
+    pub highlight: bool,
+}
@@ -0,0 +1,18 @@
+use serde::{Deserialize, Serialize};
+
+/// A collection mirrored to a directory on disk.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CollectionStorage {
+    pub collection_id: String,
+    pub directory: String,
+    pub synced_at: String,
+}
+
+/// Payload of the `collection-storage-synced` event, sent after a watched
+/// directory has been read back into the database, or when mirroring an edit
+/// to the directory failed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StorageSyncEvent {
+    pub collection_id: String,
+    pub error: Option<String>,
+}
@@ -0,0 +1,102 @@
+use serde::{Deserialize, Serialize};
+
+use super::request::KeyValue;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConnectWebSocketInput {
+    pub workspace_id: String,
+    /// The saved request this session was opened from, if any.
+    #[serde(default)]
+    pub request_id: Option<String>,
+    /// A `ws://` or `wss://` URL. `http(s)://` is accepted and switched over.
+    pub url: String,
+    #[serde(default)]
+    pub headers: Vec<KeyValue>,
+    #[serde(default)]
+    pub query_params: Vec<KeyValue>,
+    /// Offered in `Sec-WebSocket-Protocol`; the server picks at most one.
+    #[serde(default)]
+    pub subprotocols: Vec<String>,
+}
+
+/// A WebSocket connection and its transcript.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebSocketSession {
+    pub id: String,
+    pub workspace_id: String,
+    pub request_id: Option<String>,
+    pub url: String,
+    /// The subprotocol the server accepted.
+    pub subprotocol: Option<String>,
+    pub opened_at: String,
+    /// `None` while the connection is open.
+    pub closed_at: Option<String>,
+    pub close_code: Option<u16>,
+    pub close_reason: Option<String>,
+    /// Set when the connection failed rather than closed cleanly.
+    pub error: Option<String>,
+    pub message_count: i64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MessageDirection {
+    Sent,
+    Received,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MessageKind {
+    Text,
+    Binary,
+    Ping,
+    Pong,
+    Close,
+}
+
+impl MessageKind {
+    pub fn as_str(&self) -> &str {
+        match self {
+            MessageKind::Text => "text",
+            MessageKind::Binary => "binary",
+            MessageKind::Ping => "ping",
+            MessageKind::Pong => "pong",
+            MessageKind::Close => "close",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Self {
+        match s {
+            "binary" => MessageKind::Binary,
+            "ping" => MessageKind::Ping,
+            "pong" => MessageKind::Pong,
+            "close" => MessageKind::Close,
+            _ => MessageKind::Text,
+        }
+    }
+}
+
+/// One frame in a transcript, also sent as the `websocket-message` event.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebSocketMessage {
+    pub id: String,
+    pub session_id: String,
+    pub direction: MessageDirection,
+    pub kind: MessageKind,
+    /// Text as is; binary, ping and pong payloads base64-encoded. Close frames
+    /// hold the reason.
+    pub data: String,
+    /// Payload size in bytes.
+    pub size: i64,
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SendWebSocketInput {
+    pub session_id: String,
+    /// `text` or `binary`.
+    pub kind: MessageKind,
+    /// Text as is; binary payloads base64-encoded.
+    pub data: String,
+}
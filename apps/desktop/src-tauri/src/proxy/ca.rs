@@ -0,0 +1,150 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use chrono::Datelike;
+use rcgen::{
+    BasicConstraints, Certificate, CertificateParams, DistinguishedName, DnType,
+    ExtendedKeyUsagePurpose, IsCa, KeyPair, KeyUsagePurpose,
+};
+use tokio_rustls::rustls::crypto::ring;
+use tokio_rustls::rustls::pki_types::{CertificateDer, PrivateKeyDer, PrivatePkcs8KeyDer};
+use tokio_rustls::rustls::ServerConfig;
+
+use crate::db::Database;
+
+const CA_NAME: &str = "Steq Local Proxy CA";
+
+/// The certificate authority that signs the certificates the proxy presents
+/// when intercepting HTTPS. It is generated once and kept in the database;
+/// clients only trust intercepted traffic after installing its certificate.
+pub struct LocalCa {
+    certificate_pem: String,
+    certificate_der: CertificateDer<'static>,
+    /// Signs leaf certificates. It's rebuilt from the stored key, and only its
+    /// name and key are used when signing, so it need not match the stored
+    /// certificate byte for byte.
+    issuer: Certificate,
+    key: KeyPair,
+    leaves: Mutex<HashMap<String, Arc<ServerConfig>>>,
+}
+
+impl LocalCa {
+    /// Loads the stored CA, generating and saving one on first use.
+    pub fn load_or_create(db: &Database) -> Result<LocalCa, String> {
+        if let Some((certificate, key)) = db.get_proxy_ca()? {
+            return LocalCa::from_pem(&certificate, &key);
+        }
+        let key = KeyPair::generate().map_err(|e| format!("Failed to generate CA key: {}", e))?;
+        let certificate = ca_params()
+            .self_signed(&key)
+            .map_err(|e| format!("Failed to create CA certificate: {}", e))?;
+        db.set_proxy_ca(&certificate.pem(), &key.serialize_pem())?;
+        LocalCa::from_pem(&certificate.pem(), &key.serialize_pem())
+    }
+
+    pub fn from_pem(certificate_pem: &str, key_pem: &str) -> Result<LocalCa, String> {
+        let key =
+            KeyPair::from_pem(key_pem).map_err(|e| format!("Failed to read CA key: {}", e))?;
+        let issuer = ca_params()
+            .self_signed(&key)
+            .map_err(|e| format!("Failed to load CA certificate: {}", e))?;
+        let certificate_der = pem_to_der(certificate_pem)?;
+        Ok(LocalCa {
+            certificate_pem: certificate_pem.to_string(),
+            certificate_der,
+            issuer,
+            key,
+            leaves: Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// The PEM certificate users install as a trusted root.
+    pub fn certificate_pem(&self) -> &str {
+        &self.certificate_pem
+    }
+
+    /// A TLS config presenting a certificate for `host`, signed by this CA.
+    /// Certificates are issued once per host and reused.
+    pub fn server_config(&self, host: &str) -> Result<Arc<ServerConfig>, String> {
+        let mut leaves = self
+            .leaves
+            .lock()
+            .map_err(|e| format!("Lock error: {}", e))?;
+        if let Some(config) = leaves.get(host) {
+            return Ok(config.clone());
+        }
+
+        let key = KeyPair::generate().map_err(|e| format!("Failed to generate key: {}", e))?;
+        let mut params = CertificateParams::new(vec![host.to_string()])
+            .map_err(|e| format!("Invalid host {}: {}", host, e))?;
+        params.distinguished_name = DistinguishedName::new();
+        params.distinguished_name.push(DnType::CommonName, host);
+        params.key_usages = vec![
+            KeyUsagePurpose::DigitalSignature,
+            KeyUsagePurpose::KeyEncipherment,
+        ];
+        params.extended_key_usages = vec![ExtendedKeyUsagePurpose::ServerAuth];
+        params.use_authority_key_identifier_extension = true;
+        set_validity(&mut params, 365);
+        let leaf = params
+            .signed_by(&key, &self.issuer, &self.key)
+            .map_err(|e| format!("Failed to sign certificate for {}: {}", host, e))?;
+
+        let mut config = ServerConfig::builder_with_provider(Arc::new(ring::default_provider()))
+            .with_safe_default_protocol_versions()
+            .map_err(|e| format!("Failed to configure TLS: {}", e))?
+            .with_no_client_auth()
+            .with_single_cert(
+                vec![leaf.der().clone(), self.certificate_der.clone()],
+                PrivateKeyDer::Pkcs8(PrivatePkcs8KeyDer::from(key.serialize_der())),
+            )
+            .map_err(|e| format!("Failed to configure TLS: {}", e))?;
+        // Intercepted connections are served over HTTP/1.1 only
+        config.alpn_protocols = vec![b"http/1.1".to_vec()];
+
+        let config = Arc::new(config);
+        leaves.insert(host.to_string(), config.clone());
+        Ok(config)
+    }
+}
+
+/// Fixed so the issuer can be rebuilt from the stored key alone.
+fn ca_params() -> CertificateParams {
+    let mut params = CertificateParams::default();
+    params.distinguished_name = DistinguishedName::new();
+    params.distinguished_name.push(DnType::CommonName, CA_NAME);
+    params
+        .distinguished_name
+        .push(DnType::OrganizationName, "Steq");
+    params.is_ca = IsCa::Ca(BasicConstraints::Constrained(0));
+    params.key_usages = vec![
+        KeyUsagePurpose::KeyCertSign,
+        KeyUsagePurpose::CrlSign,
+        KeyUsagePurpose::DigitalSignature,
+    ];
+    set_validity(&mut params, 3650);
+    params
+}
+
+/// Valid from yesterday, so clients with a slightly slow clock accept it.
+fn set_validity(params: &mut CertificateParams, days: i64) {
+    let date = |offset: i64| {
+        let date = chrono::Utc::now().date_naive() + chrono::Duration::days(offset);
+        rcgen::date_time_ymd(date.year(), date.month() as u8, date.day() as u8)
+    };
+    params.not_before = date(-1);
+    params.not_after = date(days);
+}
+
+fn pem_to_der(pem: &str) -> Result<CertificateDer<'static>, String> {
+    use base64::Engine;
+
+    let body: String = pem
+        .lines()
+        .filter(|line| !line.starts_with("-----"))
+        .collect();
+    let der = base64::engine::general_purpose::STANDARD
+        .decode(body.trim())
+        .map_err(|e| format!("Failed to read CA certificate: {}", e))?;
+    Ok(CertificateDer::from(der))
+}
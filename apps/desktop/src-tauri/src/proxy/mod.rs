@@ -0,0 +1,732 @@
+pub mod ca;
+
+use std::convert::Infallible;
+use std::net::{IpAddr, SocketAddr, UdpSocket};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use http_body_util::{BodyExt, Full};
+use hyper::body::{Bytes, Incoming};
+use hyper::header::{HeaderMap, HeaderName, HeaderValue};
+use hyper::http::uri::Authority;
+use hyper::server::conn::http1;
+use hyper::service::service_fn;
+use hyper::upgrade::Upgraded;
+use hyper::{Method, Request, Response, StatusCode};
+use hyper_util::rt::TokioIo;
+use rand::RngCore;
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::watch;
+use tokio_rustls::TlsAcceptor;
+
+use crate::models::capture::ProxyInfo;
+use crate::models::execution::{ExecutionResult, ExecutionTiming};
+use crate::models::request::{BodyType, ExecuteRequestInput, HttpMethod, KeyValue};
+use ca::LocalCa;
+
+/// Headers that describe a single connection and must not be passed on.
+/// `host` and `content-length` are recomputed for each hop.
+const HOP_BY_HOP_HEADERS: &[&str] = &[
+    "connection",
+    "proxy-connection",
+    "keep-alive",
+    "proxy-authenticate",
+    "proxy-authorization",
+    "te",
+    "trailer",
+    "transfer-encoding",
+    "upgrade",
+    "host",
+    "content-length",
+];
+
+/// The running capture proxy, if any. Dropping it stops the proxy.
+#[derive(Default)]
+pub struct ProxyState {
+    proxy: Mutex<Option<Proxy>>,
+}
+
+impl ProxyState {
+    pub fn set(&self, proxy: Proxy) -> Result<ProxyInfo, String> {
+        let mut current = self
+            .proxy
+            .lock()
+            .map_err(|e| format!("Lock error: {}", e))?;
+        let info = proxy.info.clone();
+        *current = Some(proxy);
+        Ok(info)
+    }
+
+    /// Stops the proxy, returning what it was running.
+    pub fn take(&self) -> Result<Option<ProxyInfo>, String> {
+        let mut current = self
+            .proxy
+            .lock()
+            .map_err(|e| format!("Lock error: {}", e))?;
+        Ok(current.take().map(|proxy| proxy.info))
+    }
+
+    pub fn info(&self) -> Result<Option<ProxyInfo>, String> {
+        let current = self
+            .proxy
+            .lock()
+            .map_err(|e| format!("Lock error: {}", e))?;
+        Ok(current.as_ref().map(|proxy| proxy.info.clone()))
+    }
+}
+
+/// A listening proxy. The accept loop, open connections and tunnels end when
+/// this is dropped.
+pub struct Proxy {
+    pub info: ProxyInfo,
+    _shutdown: watch::Sender<()>,
+}
+
+struct ProxyShared {
+    client: reqwest::Client,
+    /// Intercepts HTTPS when set; otherwise `CONNECT` tunnels are passed through
+    /// unread.
+    ca: Option<LocalCa>,
+    /// Required from clients on other machines when listening on the network.
+    token: Option<String>,
+    stopped: watch::Receiver<()>,
+    on_exchange: ExchangeHandler,
+}
+
+type ExchangeHandler = Box<dyn Fn(&ExecuteRequestInput, &ExecutionResult) + Send + Sync>;
+
+/// Binds the proxy on `127.0.0.1`, or on all interfaces with `listen_on_network`,
+/// and runs it on the current Tokio runtime. `on_exchange` is called with each
+/// forwarded request and its response, or the error forwarding it.
+///
+/// On the network, other machines must authenticate with a token generated for
+/// this start and can't reach services on this machine or link-local addresses
+/// through the proxy.
+pub async fn start<F>(
+    session_id: &str,
+    workspace_id: &str,
+    port: Option<u16>,
+    listen_on_network: bool,
+    ca: Option<LocalCa>,
+    on_exchange: F,
+) -> Result<Proxy, String>
+where
+    F: Fn(&ExecuteRequestInput, &ExecutionResult) + Send + Sync + 'static,
+{
+    let host = if listen_on_network {
+        "0.0.0.0"
+    } else {
+        "127.0.0.1"
+    };
+    let listener = TcpListener::bind((host, port.unwrap_or(0)))
+        .await
+        .map_err(|e| format!("Failed to bind proxy: {}", e))?;
+    let port = listener
+        .local_addr()
+        .map_err(|e| format!("Failed to read proxy address: {}", e))?
+        .port();
+    let address = match listen_on_network {
+        true => network_address().map_or_else(|| host.to_string(), |ip| ip.to_string()),
+        false => host.to_string(),
+    };
+
+    // Redirects go back to the client, and the proxy must never proxy itself
+    let client = reqwest::Client::builder()
+        .redirect(reqwest::redirect::Policy::none())
+        .no_proxy()
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+
+    let token = listen_on_network.then(|| {
+        let mut bytes = [0u8; 18];
+        rand::thread_rng().fill_bytes(&mut bytes);
+        base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(bytes)
+    });
+    let info = ProxyInfo {
+        session_id: session_id.to_string(),
+        workspace_id: workspace_id.to_string(),
+        port,
+        address: format!("{}:{}", address, port),
+        token: token.clone(),
+        intercept_https: ca.is_some(),
+        started_at: chrono::Utc::now().to_rfc3339(),
+    };
+    let (shutdown, stopped) = watch::channel(());
+    let shared = Arc::new(ProxyShared {
+        client,
+        ca,
+        token,
+        stopped,
+        on_exchange: Box::new(on_exchange),
+    });
+    tokio::spawn(accept_loop(listener, shared));
+
+    Ok(Proxy {
+        info,
+        _shutdown: shutdown,
+    })
+}
+
+/// The address other devices can use to reach this machine, found by asking
+/// the OS which interface routes outwards. No packets are sent.
+fn network_address() -> Option<IpAddr> {
+    let socket = UdpSocket::bind("0.0.0.0:0").ok()?;
+    socket.connect("192.0.2.1:80").ok()?;
+    socket.local_addr().ok().map(|addr| addr.ip())
+}
+
+async fn accept_loop(listener: TcpListener, shared: Arc<ProxyShared>) {
+    let mut stopped = shared.stopped.clone();
+    loop {
+        let (stream, peer) = tokio::select! {
+            _ = stopped.changed() => return,
+            accepted = listener.accept() => match accepted {
+                Ok(accepted) => accepted,
+                Err(_) => continue,
+            },
+        };
+        let shared = shared.clone();
+        let mut stopped = stopped.clone();
+        tokio::spawn(async move {
+            let service = service_fn(move |request| {
+                let shared = shared.clone();
+                async move { Ok::<_, Infallible>(handle(shared, request, None, peer).await) }
+            });
+            let connection = http1::Builder::new()
+                .serve_connection(TokioIo::new(stream), service)
+                .with_upgrades();
+            tokio::select! {
+                _ = connection => {}
+                _ = stopped.changed() => {}
+            }
+        });
+    }
+}
+
+/// `origin` is `https://host[:port]` for requests read from an intercepted
+/// tunnel; requests sent to the proxy directly carry an absolute URL. Access is
+/// checked when the request or tunnel reaches the proxy, so requests inside an
+/// intercepted tunnel aren't checked again.
+async fn handle(
+    shared: Arc<ProxyShared>,
+    request: Request<Incoming>,
+    origin: Option<String>,
+    peer: SocketAddr,
+) -> Response<Full<Bytes>> {
+    if origin.is_none() {
+        if !authorized(shared.token.as_deref(), peer.ip(), request.headers()) {
+            let mut response = text_response(
+                StatusCode::PROXY_AUTHENTICATION_REQUIRED,
+                "Use the token shown in Steq as the proxy password.",
+            );
+            response.headers_mut().insert(
+                hyper::header::PROXY_AUTHENTICATE,
+                HeaderValue::from_static("Basic realm=\"Steq capture proxy\""),
+            );
+            return response;
+        }
+        let uri = request.uri();
+        let default_port = match uri.scheme_str() {
+            Some("https") => 443,
+            _ => 80,
+        };
+        if let Some(host) = uri.host() {
+            let port = uri.port_u16().unwrap_or(default_port);
+            if !upstream_allowed(peer.ip(), host, port).await {
+                return text_response(
+                    StatusCode::FORBIDDEN,
+                    "Other devices can't reach this machine's local services through the proxy.",
+                );
+            }
+        }
+    }
+    let url = match &origin {
+        Some(origin) => format!(
+            "{}{}",
+            origin,
+            request
+                .uri()
+                .path_and_query()
+                .map(|p| p.as_str())
+                .unwrap_or("/")
+        ),
+        None if request.method() == Method::CONNECT => return connect(shared, request, peer),
+        None if request.uri().scheme().is_some() => request.uri().to_string(),
+        None => {
+            return text_response(
+                StatusCode::BAD_REQUEST,
+                "This is an HTTP proxy. Configure it as your client's proxy instead of requesting it directly.",
+            )
+        }
+    };
+    forward(&shared, request, url).await
+}
+
+/// Accepts a `CONNECT` and, once the client switches to the tunnel, either
+/// decrypts it or relays it untouched.
+fn connect(
+    shared: Arc<ProxyShared>,
+    request: Request<Incoming>,
+    peer: SocketAddr,
+) -> Response<Full<Bytes>> {
+    let Some(authority) = request.uri().authority().cloned() else {
+        return text_response(StatusCode::BAD_REQUEST, "CONNECT requires host:port");
+    };
+    let mut stopped = shared.stopped.clone();
+    tokio::spawn(async move {
+        let Ok(upgraded) = hyper::upgrade::on(request).await else {
+            return;
+        };
+        let io = TokioIo::new(upgraded);
+        let session = async {
+            match shared.ca.is_some() {
+                true => intercept(shared.clone(), io, authority, peer).await,
+                false => tunnel(io, authority).await,
+            }
+        };
+        tokio::select! {
+            _ = session => {}
+            _ = stopped.changed() => {}
+        }
+    });
+    Response::new(Full::new(Bytes::new()))
+}
+
+async fn tunnel(mut io: TokioIo<Upgraded>, authority: Authority) -> Result<(), String> {
+    let mut server = TcpStream::connect(authority.as_str())
+        .await
+        .map_err(|e| format!("Failed to connect to {}: {}", authority, e))?;
+    tokio::io::copy_bidirectional(&mut io, &mut server)
+        .await
+        .map_err(|e| format!("Tunnel to {} failed: {}", authority, e))?;
+    Ok(())
+}
+
+/// Terminates TLS with a certificate for the requested host and serves the
+/// decrypted requests like any others.
+async fn intercept(
+    shared: Arc<ProxyShared>,
+    io: TokioIo<Upgraded>,
+    authority: Authority,
+    peer: SocketAddr,
+) -> Result<(), String> {
+    let ca = shared.ca.as_ref().ok_or("HTTPS interception is off")?;
+    let host = authority
+        .host()
+        .trim_start_matches('[')
+        .trim_end_matches(']');
+    let tls = TlsAcceptor::from(ca.server_config(host)?)
+        .accept(io)
+        .await
+        .map_err(|e| format!("TLS handshake with client failed: {}", e))?;
+
+    let origin = match authority.port_u16() {
+        None | Some(443) => format!("https://{}", authority.host()),
+        Some(port) => format!("https://{}:{}", authority.host(), port),
+    };
+    let service = service_fn(move |request| {
+        let shared = shared.clone();
+        let origin = origin.clone();
+        async move { Ok::<_, Infallible>(handle(shared, request, Some(origin), peer).await) }
+    });
+    http1::Builder::new()
+        .serve_connection(TokioIo::new(tls), service)
+        .await
+        .map_err(|e| format!("Intercepted connection failed: {}", e))
+}
+
+async fn forward(
+    shared: &ProxyShared,
+    request: Request<Incoming>,
+    url: String,
+) -> Response<Full<Bytes>> {
+    let started = Instant::now();
+    let (parts, body) = request.into_parts();
+    let body = match body.collect().await {
+        Ok(collected) => collected.to_bytes(),
+        Err(e) => {
+            return text_response(
+                StatusCode::BAD_REQUEST,
+                &format!("Failed to read request body: {}", e),
+            )
+        }
+    };
+    let headers: Vec<(&HeaderName, &HeaderValue)> = parts
+        .headers
+        .iter()
+        .filter(|(name, _)| !HOP_BY_HOP_HEADERS.contains(&name.as_str()))
+        .collect();
+    let input = capture_input(&parts.method, &url, &headers, &body);
+
+    let mut outgoing = shared.client.request(parts.method.clone(), &url).body(body);
+    for (name, value) in &headers {
+        // Ask for an uncompressed response so the captured body is readable
+        if *name != hyper::header::ACCEPT_ENCODING {
+            outgoing = outgoing.header(*name, *value);
+        }
+    }
+
+    let response = match outgoing.send().await {
+        Ok(response) => response,
+        Err(e) => return failed(shared, input, started, format!("{}", e)),
+    };
+    let first_byte_ms = started.elapsed().as_secs_f64() * 1000.0;
+    let status = response.status();
+    let response_headers = response.headers().clone();
+    let bytes = match response.bytes().await {
+        Ok(bytes) => bytes,
+        Err(e) => return failed(shared, input, started, format!("{}", e)),
+    };
+
+    if let Some(input) = &input {
+        let result = ExecutionResult {
+            status: status.as_u16(),
+            status_text: status.canonical_reason().unwrap_or("").to_string(),
+            headers: response_headers
+                .iter()
+                .map(|(name, value)| KeyValue {
+                    key: name.to_string(),
+                    value: value.to_str().unwrap_or("").to_string(),
+                    enabled: true,
+                })
+                .collect(),
+            body: String::from_utf8_lossy(&bytes).to_string(),
+            size_bytes: bytes.len() as u64,
+            timing: ExecutionTiming {
+                dns_ms: None,
+                connect_ms: None,
+                tls_ms: None,
+                first_byte_ms,
+                total_ms: started.elapsed().as_secs_f64() * 1000.0,
+            },
+            error: None,
+        };
+        (shared.on_exchange)(input, &result);
+    }
+
+    let mut reply = Response::new(Full::new(bytes));
+    *reply.status_mut() = status;
+    for (name, value) in &response_headers {
+        if !HOP_BY_HOP_HEADERS.contains(&name.as_str()) {
+            reply.headers_mut().append(name, value.clone());
+        }
+    }
+    reply
+}
+
+/// Records a request that got no response and tells the client why.
+fn failed(
+    shared: &ProxyShared,
+    input: Option<ExecuteRequestInput>,
+    started: Instant,
+    error: String,
+) -> Response<Full<Bytes>> {
+    if let Some(input) = &input {
+        let total_ms = started.elapsed().as_secs_f64() * 1000.0;
+        let result = ExecutionResult {
+            status: 0,
+            status_text: String::new(),
+            headers: vec![],
+            body: String::new(),
+            size_bytes: 0,
+            timing: ExecutionTiming {
+                dns_ms: None,
+                connect_ms: None,
+                tls_ms: None,
+                first_byte_ms: total_ms,
+                total_ms,
+            },
+            error: Some(error.clone()),
+        };
+        (shared.on_exchange)(input, &result);
+    }
+    text_response(
+        StatusCode::BAD_GATEWAY,
+        &format!("Proxy request failed: {}", error),
+    )
+}
+
+/// The request as a saved request would send it. Methods requests can't use,
+/// such as `TRACE`, are forwarded but not captured.
+fn capture_input(
+    method: &Method,
+    url: &str,
+    headers: &[(&HeaderName, &HeaderValue)],
+    body: &[u8],
+) -> Option<ExecuteRequestInput> {
+    let method = HttpMethod::from_str(method.as_str()).ok()?;
+    let content_type = headers
+        .iter()
+        .find(|(name, _)| **name == hyper::header::CONTENT_TYPE)
+        .and_then(|(_, value)| value.to_str().ok())
+        .unwrap_or("")
+        .to_lowercase();
+    let body_type = if body.is_empty() {
+        BodyType::None
+    } else if content_type.contains("json") {
+        BodyType::Json
+    } else if content_type.contains("x-www-form-urlencoded") {
+        BodyType::FormUrlEncoded
+    } else {
+        BodyType::Text
+    };
+    Some(ExecuteRequestInput {
+        method,
+        url: url.to_string(),
+        headers: headers
+            .iter()
+            .map(|(name, value)| KeyValue {
+                key: name.to_string(),
+                value: String::from_utf8_lossy(value.as_bytes()).to_string(),
+                enabled: true,
+            })
+            .collect(),
+        query_params: vec![],
+        path_params: vec![],
+        body_type,
+        body_content: (!body.is_empty()).then(|| String::from_utf8_lossy(body).to_string()),
+    })
+}
+
+/// Clients on other machines must send the token as the password of Basic
+/// `Proxy-Authorization` credentials; the username is ignored. Clients on this
+/// machine could send the requests themselves, so they don't need it.
+fn authorized(token: Option<&str>, peer: IpAddr, headers: &HeaderMap) -> bool {
+    let Some(token) = token else {
+        return true;
+    };
+    if peer.to_canonical().is_loopback() {
+        return true;
+    }
+    headers
+        .get(hyper::header::PROXY_AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Basic "))
+        .and_then(|credentials| BASE64.decode(credentials.trim()).ok())
+        .and_then(|credentials| String::from_utf8(credentials).ok())
+        .is_some_and(|credentials| {
+            credentials
+                .split_once(':')
+                .is_some_and(|(_, password)| password == token)
+        })
+}
+
+/// Whether `peer` may reach `host`. Clients on other machines can't reach
+/// this machine's loopback services or link-local addresses such as cloud
+/// metadata endpoints. Hosts that don't resolve are left to fail when
+/// forwarded.
+async fn upstream_allowed(peer: IpAddr, host: &str, port: u16) -> bool {
+    if peer.to_canonical().is_loopback() {
+        return true;
+    }
+    let host = host.trim_start_matches('[').trim_end_matches(']');
+    match tokio::net::lookup_host((host, port)).await {
+        Ok(mut addresses) => !addresses.any(|address| is_local(address.ip())),
+        Err(_) => true,
+    }
+}
+
+fn is_local(ip: IpAddr) -> bool {
+    match ip.to_canonical() {
+        IpAddr::V4(ip) => ip.is_loopback() || ip.is_link_local() || ip.is_unspecified(),
+        IpAddr::V6(ip) => ip.is_loopback() || ip.is_unspecified() || ip.is_unicast_link_local(),
+    }
+}
+
+fn text_response(status: StatusCode, message: &str) -> Response<Full<Bytes>> {
+    let mut response = Response::new(Full::new(Bytes::from(message.to_string())));
+    *response.status_mut() = status;
+    response.headers_mut().insert(
+        hyper::header::CONTENT_TYPE,
+        HeaderValue::from_static("text/plain; charset=utf-8"),
+    );
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::Database;
+    use crate::mock;
+    use crate::mock::routes::MockRoute;
+    use crate::models::example::RequestExample;
+    use crate::models::request::{ApiRequest, RequestKind};
+    use std::sync::mpsc;
+    use std::time::Duration;
+
+    async fn upstream() -> mock::MockServer {
+        let request = ApiRequest {
+            id: "req".to_string(),
+            name: "Get user".to_string(),
+            method: HttpMethod::POST,
+            url: "/users/:id".to_string(),
+            headers: vec![],
+            query_params: vec![],
+            body_type: BodyType::None,
+            body_content: None,
+            description: None,
+            path_params: vec![],
+            scripts: None,
+            kind: RequestKind::Http,
+            collection_id: None,
+            folder_id: None,
+            sort_order: 0,
+            created_at: String::new(),
+            updated_at: String::new(),
+        };
+        let example = RequestExample {
+            id: "ok".to_string(),
+            request_id: "req".to_string(),
+            name: "Created".to_string(),
+            status: Some(201),
+            headers: vec![],
+            body: Some(r#"{"id":"{{id}}","echo":{{body}}}"#.to_string()),
+            original_request: None,
+            sort_order: 0,
+            created_at: String::new(),
+            updated_at: String::new(),
+        };
+        let routes = vec![MockRoute::new(&request, vec![example], None)];
+        mock::start("coll", None, routes, 0, |_| {}).await.unwrap()
+    }
+
+    async fn capture(
+        ca: Option<LocalCa>,
+    ) -> (
+        Proxy,
+        mpsc::Receiver<(ExecuteRequestInput, ExecutionResult)>,
+    ) {
+        let (tx, rx) = mpsc::channel();
+        let proxy = start("session", "ws", None, false, ca, move |input, result| {
+            let _ = tx.send((input.clone(), result.clone()));
+        })
+        .await
+        .unwrap();
+        (proxy, rx)
+    }
+
+    #[tokio::test]
+    async fn forwards_and_captures_http() {
+        let upstream = upstream().await;
+        let (proxy, captured) = capture(None).await;
+        let client = reqwest::Client::builder()
+            .proxy(reqwest::Proxy::all(format!("http://{}", proxy.info.address)).unwrap())
+            .build()
+            .unwrap();
+
+        let response = client
+            .post(format!("{}/users/7?full=1", upstream.info.url))
+            .header("Content-Type", "application/json")
+            .body(r#"{"name":"Ada"}"#)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status().as_u16(), 201);
+        assert_eq!(
+            response.text().await.unwrap(),
+            r#"{"id":"7","echo":{"name":"Ada"}}"#
+        );
+
+        let (input, result) = captured.recv_timeout(Duration::from_secs(5)).unwrap();
+        assert_eq!(input.method.as_str(), "POST");
+        assert_eq!(input.url, format!("{}/users/7?full=1", upstream.info.url));
+        assert!(matches!(input.body_type, BodyType::Json));
+        assert!(!input.headers.iter().any(|h| h.key == "host"));
+        assert_eq!(result.status, 201);
+        assert_eq!(result.body, r#"{"id":"7","echo":{"name":"Ada"}}"#);
+
+        // Requests for the proxy itself aren't forwarded
+        let direct = reqwest::get(format!("http://{}/", proxy.info.address))
+            .await
+            .unwrap();
+        assert_eq!(direct.status().as_u16(), 400);
+    }
+
+    #[tokio::test]
+    async fn refuses_local_upstreams_for_network_clients() {
+        let phone: IpAddr = "192.168.1.20".parse().unwrap();
+        for host in [
+            "127.0.0.1",
+            "localhost",
+            "[::1]",
+            "169.254.169.254",
+            "0.0.0.0",
+        ] {
+            assert!(!upstream_allowed(phone, host, 80).await, "{}", host);
+        }
+        assert!(upstream_allowed(phone, "203.0.113.7", 443).await);
+        assert!(upstream_allowed("127.0.0.1".parse().unwrap(), "localhost", 80).await);
+
+        let mut headers = HeaderMap::new();
+        assert!(!authorized(Some("secret"), phone, &headers));
+        assert!(authorized(Some("secret"), "::1".parse().unwrap(), &headers));
+        assert!(authorized(None, phone, &headers));
+        let basic = |credentials: &str| {
+            HeaderValue::from_str(&format!("Basic {}", BASE64.encode(credentials))).unwrap()
+        };
+        headers.insert(hyper::header::PROXY_AUTHORIZATION, basic("steq:wrong"));
+        assert!(!authorized(Some("secret"), phone, &headers));
+        headers.insert(hyper::header::PROXY_AUTHORIZATION, basic("steq:secret"));
+        assert!(authorized(Some("secret"), phone, &headers));
+
+        // Clients on this machine don't need the token
+        let (tx, _captured) = mpsc::channel();
+        let proxy = start("session", "ws", None, true, None, move |input, _| {
+            let _ = tx.send(input.clone());
+        })
+        .await
+        .unwrap();
+        assert!(proxy.info.token.is_some());
+        let client = reqwest::Client::builder()
+            .proxy(reqwest::Proxy::all(format!("http://127.0.0.1:{}", proxy.info.port)).unwrap())
+            .build()
+            .unwrap();
+        let upstream = upstream().await;
+        let response = client
+            .post(format!("{}/users/7", upstream.info.url))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status().as_u16(), 201);
+    }
+
+    #[tokio::test]
+    async fn intercepts_https_with_local_ca() {
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        let db = Database { conn };
+        db.run_migrations().unwrap();
+        let ca = LocalCa::load_or_create(&db).unwrap();
+        let pem = ca.certificate_pem().to_string();
+        // The same CA comes back once stored
+        assert_eq!(LocalCa::load_or_create(&db).unwrap().certificate_pem(), pem);
+
+        // The upstream only speaks plain HTTP, so the decrypted request fails
+        // upstream, which is still captured
+        let upstream = upstream().await;
+        let (proxy, captured) = capture(Some(ca)).await;
+        let client = reqwest::Client::builder()
+            .proxy(reqwest::Proxy::all(format!("http://{}", proxy.info.address)).unwrap())
+            .add_root_certificate(reqwest::Certificate::from_pem(pem.as_bytes()).unwrap())
+            .build()
+            .unwrap();
+        let https_url = format!(
+            "{}/users/7",
+            upstream.info.url.replace("http://", "https://")
+        );
+        let response = client.get(&https_url).send().await.unwrap();
+        assert_eq!(response.status().as_u16(), 502);
+
+        let (input, result) = captured.recv_timeout(Duration::from_secs(5)).unwrap();
+        assert_eq!(input.url, https_url);
+        assert_eq!(result.status, 0);
+        assert!(result.error.is_some());
+
+        // Untrusted clients can't be intercepted
+        let untrusted = reqwest::Client::builder()
+            .proxy(reqwest::Proxy::all(format!("http://{}", proxy.info.address)).unwrap())
+            .build()
+            .unwrap();
+        assert!(untrusted.get(&https_url).send().await.is_err());
+    }
+}
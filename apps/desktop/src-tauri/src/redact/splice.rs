@@ -0,0 +1,146 @@
+//! Edits values in JSON text without re-serializing it, so key order and
+//! whitespace stay as they were. Positions are only meaningful for text that
+//! already parsed as JSON.
+
+use std::ops::Range;
+
+use serde_json::Value;
+
+/// A value's byte range and the JSON text that replaces it.
+pub type Edit = (Range<usize>, String);
+
+pub fn skip_whitespace(json: &str, mut i: usize) -> usize {
+    let bytes = json.as_bytes();
+    while i < bytes.len() && bytes[i].is_ascii_whitespace() {
+        i += 1;
+    }
+    i
+}
+
+/// The range of the value starting at `start`.
+pub fn value_at(json: &str, start: usize) -> Range<usize> {
+    let bytes = json.as_bytes();
+    let mut i = start;
+    match bytes[start] {
+        b'"' => {
+            i += 1;
+            loop {
+                match bytes[i] {
+                    b'\\' => i += 2,
+                    b'"' => return start..i + 1,
+                    _ => i += 1,
+                }
+            }
+        }
+        b'{' | b'[' => {
+            let mut depth = 0;
+            loop {
+                match bytes[i] {
+                    b'"' => {
+                        i = value_at(json, i).end;
+                        continue;
+                    }
+                    b'{' | b'[' => depth += 1,
+                    b'}' | b']' => {
+                        depth -= 1;
+                        if depth == 0 {
+                            return start..i + 1;
+                        }
+                    }
+                    _ => {}
+                }
+                i += 1;
+            }
+        }
+        _ => {
+            while i < bytes.len()
+                && !matches!(bytes[i], b',' | b'}' | b']')
+                && !bytes[i].is_ascii_whitespace()
+            {
+                i += 1;
+            }
+            start..i
+        }
+    }
+}
+
+/// The keys and value ranges of the object starting at `start`.
+pub fn members(json: &str, start: usize) -> Vec<(String, Range<usize>)> {
+    let bytes = json.as_bytes();
+    let mut members = Vec::new();
+    let mut i = skip_whitespace(json, start + 1);
+    while bytes[i] == b'"' {
+        let key = value_at(json, i);
+        let colon = skip_whitespace(json, key.end);
+        let value = value_at(json, skip_whitespace(json, colon + 1));
+        i = skip_whitespace(json, value.end);
+        if bytes[i] == b',' {
+            i = skip_whitespace(json, i + 1);
+        }
+        let key = serde_json::from_str(&json[key]).unwrap_or_default();
+        members.push((key, value));
+    }
+    members
+}
+
+/// The value ranges of the array starting at `start`.
+pub fn items(json: &str, start: usize) -> Vec<Range<usize>> {
+    let bytes = json.as_bytes();
+    let mut items = Vec::new();
+    let mut i = skip_whitespace(json, start + 1);
+    while bytes[i] != b']' {
+        let item = value_at(json, i);
+        i = skip_whitespace(json, item.end);
+        if bytes[i] == b',' {
+            i = skip_whitespace(json, i + 1);
+        }
+        items.push(item);
+    }
+    items
+}
+
+pub fn string(value: &str) -> String {
+    Value::from(value).to_string()
+}
+
+/// Applies edits given in document order.
+pub fn apply(json: &str, edits: Vec<Edit>) -> String {
+    let mut out = String::with_capacity(json.len());
+    let mut last = 0;
+    for (range, text) in edits {
+        out.push_str(&json[last..range.start]);
+        out.push_str(&text);
+        last = range.end;
+    }
+    out.push_str(&json[last..]);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_members_and_items_in_formatted_text() {
+        let json = "{ \"b\" : [1, \"x\\\"]\" ,{}], \"a\":null ,\"é\":\"ü\" }";
+        let found = members(json, 0);
+        let keys: Vec<&str> = found.iter().map(|(k, _)| k.as_str()).collect();
+        assert_eq!(keys, ["b", "a", "é"]);
+        assert_eq!(&json[found[1].1.clone()], "null");
+        assert_eq!(&json[found[2].1.clone()], "\"ü\"");
+
+        let list: Vec<&str> = items(json, found[0].1.start)
+            .into_iter()
+            .map(|r| &json[r])
+            .collect();
+        assert_eq!(list, ["1", "\"x\\\"]\"", "{}"]);
+        assert!(members("{}", 0).is_empty());
+        assert!(items("[ ]", 0).is_empty());
+
+        let edited = apply(json, vec![(found[1].1.clone(), string("n/a"))]);
+        assert_eq!(
+            edited,
+            "{ \"b\" : [1, \"x\\\"]\" ,{}], \"a\":\"n/a\" ,\"é\":\"ü\" }"
+        );
+    }
+}
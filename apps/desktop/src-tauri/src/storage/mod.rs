@@ -0,0 +1,780 @@
+//! File-system storage for collections: a directory tree with one YAML file per
+//! request, a `folder.yaml` per folder and a `collection.yaml` at the root. Field
+//! order is fixed by the structs below so diffs stay minimal.
+
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::db::Database;
+use crate::io::file_stem;
+use crate::models::collection::{Collection, CollectionTree, CollectionTreeNode, Folder};
+use crate::models::request::{ApiRequest, BodyType, HttpMethod, KeyValue, RequestKind, RequestScripts};
+
+pub mod watcher;
+
+const COLLECTION_FILE: &str = "collection.yaml";
+const FOLDER_FILE: &str = "folder.yaml";
+const REQUEST_SUFFIX: &str = ".request.yaml";
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CollectionFile {
+    name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct FolderFile {
+    #[serde(default)]
+    id: Option<String>,
+    name: String,
+    #[serde(default)]
+    seq: i32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct RequestFile {
+    #[serde(default)]
+    id: Option<String>,
+    name: String,
+    #[serde(default)]
+    seq: i32,
+    method: String,
+    url: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    path_params: Vec<KeyValue>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    query_params: Vec<KeyValue>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    headers: Vec<KeyValue>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    body: Option<BodyFile>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pre_request_script: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    post_response_script: Option<String>,
+    /// Omitted for HTTP requests.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    kind: Option<RequestKind>,
+}
+
+/// GraphQL bodies are split into their fields instead of the stored JSON string,
+/// so queries show up as readable multi-line text.
+#[derive(Debug, Serialize, Deserialize)]
+struct BodyFile {
+    #[serde(rename = "type")]
+    body_type: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    content: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    query: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    variables: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    operation_name: Option<String>,
+}
+
+/// The stored GraphQL body, with keys in the order the editor writes them.
+#[derive(Serialize)]
+struct GraphQLContent {
+    query: String,
+    variables: String,
+    #[serde(rename = "operationName")]
+    operation_name: String,
+}
+
+/// Writes a collection to `dir`. Only files whose content changed are touched, and
+/// request/folder files that no longer belong to the collection are removed; other
+/// files (READMEs, `.git`) are left alone.
+pub fn write_collection(db: &Database, collection_id: &str, dir: &Path) -> Result<(), String> {
+    let tree = db.get_collection_tree(collection_id)?;
+
+    let mut files = BTreeMap::new();
+    let collection_file = CollectionFile {
+        name: tree.collection.name.clone(),
+        description: tree.collection.description.clone(),
+    };
+    files.insert(PathBuf::from(COLLECTION_FILE), to_yaml(&collection_file)?);
+    render_items(
+        Path::new(""),
+        &tree.root_folders,
+        &tree.root_requests,
+        &mut files,
+    )?;
+
+    for (relative, content) in &files {
+        let path = dir.join(relative);
+        if std::fs::read_to_string(&path).ok().as_deref() == Some(content.as_str()) {
+            continue;
+        }
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+        }
+        std::fs::write(&path, content)
+            .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    }
+
+    remove_stale(dir, dir, &files)?;
+    Ok(())
+}
+
+fn render_items(
+    dir: &Path,
+    folders: &[CollectionTreeNode],
+    requests: &[ApiRequest],
+    files: &mut BTreeMap<PathBuf, String>,
+) -> Result<(), String> {
+    let mut folder_names = UniqueNames::default();
+    for node in folders {
+        let folder_dir = dir.join(folder_names.claim(&file_stem(&node.folder.name)));
+        let folder_file = FolderFile {
+            id: Some(node.folder.id.clone()),
+            name: node.folder.name.clone(),
+            seq: node.folder.sort_order,
+            description: node.folder.description.clone(),
+        };
+        files.insert(folder_dir.join(FOLDER_FILE), to_yaml(&folder_file)?);
+        render_items(&folder_dir, &node.children, &node.requests, files)?;
+    }
+
+    let mut request_names = UniqueNames::default();
+    for request in requests {
+        let name = request_names.claim(&file_stem(&request.name));
+        let path = dir.join(format!("{}{}", name, REQUEST_SUFFIX));
+        files.insert(path, to_yaml(&request_to_file(request))?);
+    }
+    Ok(())
+}
+
+fn request_to_file(request: &ApiRequest) -> RequestFile {
+    let body = request
+        .body_content
+        .as_ref()
+        .filter(|_| !matches!(request.body_type, BodyType::None))
+        .map(|content| {
+            let mut body = BodyFile {
+                body_type: request.body_type.as_str().to_string(),
+                content: Some(content.clone()),
+                query: None,
+                variables: None,
+                operation_name: None,
+            };
+            if matches!(request.body_type, BodyType::GraphQL) {
+                if let Ok(value) = serde_json::from_str::<serde_json::Value>(content) {
+                    let field = |key: &str| {
+                        value[key]
+                            .as_str()
+                            .filter(|s| !s.is_empty())
+                            .map(|s| s.to_string())
+                    };
+                    body.content = None;
+                    body.query = field("query").or(Some(String::new()));
+                    body.variables = field("variables");
+                    body.operation_name = field("operationName");
+                }
+            }
+            body
+        });
+    let scripts = request.scripts.clone().unwrap_or_default();
+
+    RequestFile {
+        id: Some(request.id.clone()),
+        name: request.name.clone(),
+        seq: request.sort_order,
+        method: request.method.as_str().to_string(),
+        url: request.url.clone(),
+        description: request.description.clone(),
+        path_params: request.path_params.clone(),
+        query_params: request.query_params.clone(),
+        headers: request.headers.clone(),
+        body,
+        pre_request_script: scripts.pre_request,
+        post_response_script: scripts.post_response,
+        kind: Some(request.kind.clone()).filter(|kind| *kind != RequestKind::Http),
+    }
+}
+
+/// Removes request and folder files under `dir` that are not in `keep`, then any
+/// directories left empty.
+fn remove_stale(root: &Path, dir: &Path, keep: &BTreeMap<PathBuf, String>) -> Result<(), String> {
+    for path in read_dir_sorted(dir)? {
+        let name = file_name(&path);
+        if name.starts_with('.') {
+            continue;
+        }
+        if path.is_dir() {
+            remove_stale(root, &path, keep)?;
+            let is_empty = std::fs::read_dir(&path)
+                .map(|mut entries| entries.next().is_none())
+                .unwrap_or(false);
+            if is_empty {
+                let _ = std::fs::remove_dir(&path);
+            }
+        } else if name == FOLDER_FILE || name.ends_with(REQUEST_SUFFIX) {
+            let relative = path.strip_prefix(root).unwrap_or(&path);
+            if !keep.contains_key(relative) {
+                std::fs::remove_file(&path)
+                    .map_err(|e| format!("Failed to remove {}: {}", path.display(), e))?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Reads the directory into a tree for `collection`. Files without an id, or
+/// with an id already used in the tree or in `taken`, get a new one. A directory
+/// counts as a folder when it has a `folder.yaml` or at least one request file.
+pub fn read_collection(
+    dir: &Path,
+    collection: &Collection,
+    taken: HashSet<String>,
+) -> Result<CollectionTree, String> {
+    let now = chrono::Utc::now().to_rfc3339();
+    let mut collection = collection.clone();
+    let collection_path = dir.join(COLLECTION_FILE);
+    if collection_path.is_file() {
+        let file: CollectionFile = read_yaml(&collection_path)?;
+        if file.name != collection.name || file.description != collection.description {
+            collection.name = file.name;
+            collection.description = file.description;
+            collection.updated_at = now.clone();
+        }
+    }
+
+    let mut reader = TreeReader {
+        collection_id: collection.id.clone(),
+        now,
+        seen_ids: taken,
+    };
+    let (root_folders, root_requests) = reader.read_dir(dir, None)?;
+
+    Ok(CollectionTree {
+        collection,
+        root_folders,
+        root_requests,
+    })
+}
+
+struct TreeReader {
+    collection_id: String,
+    now: String,
+    seen_ids: HashSet<String>,
+}
+
+impl TreeReader {
+    fn read_dir(
+        &mut self,
+        dir: &Path,
+        folder_id: Option<&str>,
+    ) -> Result<(Vec<CollectionTreeNode>, Vec<ApiRequest>), String> {
+        let mut folders = Vec::new();
+        let mut requests = Vec::new();
+
+        for path in read_dir_sorted(dir)? {
+            let name = file_name(&path);
+            if name.starts_with('.') {
+                continue;
+            }
+            if path.is_dir() {
+                if let Some(node) = self.read_folder(&path, folder_id)? {
+                    folders.push(node);
+                }
+            } else if let Some(stem) = name.strip_suffix(REQUEST_SUFFIX) {
+                let file: RequestFile = read_yaml(&path)?;
+                requests.push(self.request_from_file(file, stem, folder_id)?);
+            }
+        }
+
+        folders.sort_by(|a, b| {
+            (a.folder.sort_order, &a.folder.name).cmp(&(b.folder.sort_order, &b.folder.name))
+        });
+        requests.sort_by(|a, b| (a.sort_order, &a.name).cmp(&(b.sort_order, &b.name)));
+        Ok((folders, requests))
+    }
+
+    fn read_folder(
+        &mut self,
+        dir: &Path,
+        parent_id: Option<&str>,
+    ) -> Result<Option<CollectionTreeNode>, String> {
+        let folder_path = dir.join(FOLDER_FILE);
+        let file: FolderFile = if folder_path.is_file() {
+            read_yaml(&folder_path)?
+        } else {
+            let has_requests = read_dir_sorted(dir)?
+                .iter()
+                .any(|p| file_name(p).ends_with(REQUEST_SUFFIX));
+            if !has_requests {
+                return Ok(None);
+            }
+            FolderFile {
+                id: None,
+                name: file_name(dir),
+                seq: 0,
+                description: None,
+            }
+        };
+
+        let id = self.claim_id(file.id);
+        let (children, requests) = self.read_dir(dir, Some(&id))?;
+        Ok(Some(CollectionTreeNode {
+            folder: Folder {
+                id,
+                collection_id: self.collection_id.clone(),
+                parent_folder_id: parent_id.map(|s| s.to_string()),
+                name: file.name,
+                description: file.description,
+                sort_order: file.seq,
+                created_at: self.now.clone(),
+                updated_at: self.now.clone(),
+            },
+            children,
+            requests,
+        }))
+    }
+
+    fn request_from_file(
+        &mut self,
+        file: RequestFile,
+        stem: &str,
+        folder_id: Option<&str>,
+    ) -> Result<ApiRequest, String> {
+        let method = HttpMethod::from_str(&file.method)
+            .map_err(|e| format!("{}{}: {}", stem, REQUEST_SUFFIX, e))?;
+        let (body_type, body_content) = match file.body {
+            None => (BodyType::None, None),
+            Some(body) => {
+                let body_type = BodyType::from_str(&body.body_type);
+                let content = if matches!(body_type, BodyType::GraphQL) && body.content.is_none() {
+                    let graphql = GraphQLContent {
+                        query: body.query.unwrap_or_default(),
+                        variables: body.variables.unwrap_or_default(),
+                        operation_name: body.operation_name.unwrap_or_default(),
+                    };
+                    serde_json::to_string(&graphql).ok()
+                } else {
+                    body.content
+                };
+                (body_type, content)
+            }
+        };
+        let scripts = (file.pre_request_script.is_some() || file.post_response_script.is_some())
+            .then_some(RequestScripts {
+                pre_request: file.pre_request_script,
+                post_response: file.post_response_script,
+            });
+
+        Ok(ApiRequest {
+            id: self.claim_id(file.id),
+            name: file.name,
+            method,
+            url: file.url,
+            headers: file.headers,
+            query_params: file.query_params,
+            body_type,
+            body_content,
+            description: file.description,
+            path_params: file.path_params,
+            scripts,
+            kind: file.kind.unwrap_or_default(),
+            collection_id: Some(self.collection_id.clone()),
+            folder_id: folder_id.map(|s| s.to_string()),
+            sort_order: file.seq,
+            created_at: self.now.clone(),
+            updated_at: self.now.clone(),
+        })
+    }
+
+    fn claim_id(&mut self, id: Option<String>) -> String {
+        match id.filter(|id| !id.is_empty() && !self.seen_ids.contains(id)) {
+            Some(id) => {
+                self.seen_ids.insert(id.clone());
+                id
+            }
+            None => {
+                let id = uuid::Uuid::now_v7().to_string();
+                self.seen_ids.insert(id.clone());
+                id
+            }
+        }
+    }
+}
+
+/// Mirrors a collection to a directory for the first time and records the link.
+pub fn link_directory(db: &Database, collection_id: &str, dir: &Path) -> Result<(), String> {
+    std::fs::create_dir_all(dir)
+        .map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    write_collection(db, collection_id, dir)?;
+    db.link_collection_directory(collection_id, &dir.to_string_lossy())?;
+    Ok(())
+}
+
+/// Creates a new collection from a directory written by Steq (for example a
+/// teammate's checkout) and links it.
+pub fn open_directory(db: &Database, workspace_id: &str, dir: &Path) -> Result<Collection, String> {
+    let collection_path = dir.join(COLLECTION_FILE);
+    if !collection_path.is_file() {
+        return Err(format!(
+            "Not a Steq collection: {} is missing",
+            collection_path.display()
+        ));
+    }
+    let file: CollectionFile = read_yaml(&collection_path)?;
+    let collection = db.create_collection(crate::models::collection::CreateCollectionInput {
+        workspace_id: workspace_id.to_string(),
+        name: file.name,
+        description: file.description,
+    })?;
+    db.link_collection_directory(&collection.id, &dir.to_string_lossy())?;
+    sync_from_directory(db, &collection.id)?;
+    Ok(collection)
+}
+
+/// Re-reads a linked directory into SQLite, then writes back so new files get ids.
+pub fn sync_from_directory(db: &Database, collection_id: &str) -> Result<(), String> {
+    let storage = db
+        .get_collection_storage(collection_id)?
+        .ok_or_else(|| format!("Collection is not stored in a directory: {}", collection_id))?;
+    let dir = Path::new(&storage.directory);
+    let current = db.get_collection_tree(collection_id)?;
+    // A copy of a directory linked to another collection gets ids of its own
+    let taken = db.ids_outside_collection(collection_id)?;
+    let tree = read_collection(dir, &current.collection, taken)?;
+    db.replace_collection_contents(&tree)?;
+    write_collection(db, collection_id, dir)?;
+    db.mark_collection_synced(collection_id)
+}
+
+/// Rewrites the directory of a linked collection after an edit in the app. Does
+/// nothing for collections that only live in SQLite.
+pub fn write_if_linked(db: &Database, collection_id: Option<&str>) -> Result<(), String> {
+    let Some(collection_id) = collection_id else {
+        return Ok(());
+    };
+    match db.get_collection_storage(collection_id)? {
+        Some(storage) => {
+            write_collection(db, collection_id, Path::new(&storage.directory))?;
+            db.mark_collection_synced(collection_id)
+        }
+        None => Ok(()),
+    }
+}
+
+#[derive(Default)]
+struct UniqueNames(Vec<String>);
+
+impl UniqueNames {
+    fn claim(&mut self, stem: &str) -> String {
+        let mut candidate = stem.to_string();
+        let mut n = 2;
+        while self.0.iter().any(|u| u.eq_ignore_ascii_case(&candidate)) {
+            candidate = format!("{} {}", stem, n);
+            n += 1;
+        }
+        self.0.push(candidate.clone());
+        candidate
+    }
+}
+
+fn to_yaml<T: Serialize>(value: &T) -> Result<String, String> {
+    serde_yaml::to_string(value).map_err(|e| format!("Failed to serialize: {}", e))
+}
+
+fn read_yaml<T: for<'de> Deserialize<'de>>(path: &Path) -> Result<T, String> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    serde_yaml::from_str(&content).map_err(|e| format!("Invalid {}: {}", path.display(), e))
+}
+
+fn read_dir_sorted(dir: &Path) -> Result<Vec<PathBuf>, String> {
+    let mut entries: Vec<PathBuf> = std::fs::read_dir(dir)
+        .map_err(|e| format!("Failed to read {}: {}", dir.display(), e))?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .collect();
+    entries.sort();
+    Ok(entries)
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::collection::{CreateCollectionInput, CreateFolderInput};
+    use crate::models::request::{CreateRequestInput, UpdateRequestInput};
+    use rusqlite::Connection;
+
+    fn setup_test_db() -> (Database, String) {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch("PRAGMA foreign_keys=ON;").unwrap();
+        let db = Database { conn };
+        db.run_migrations().unwrap();
+        let workspace = db.get_current_workspace().unwrap();
+        (db, workspace.id)
+    }
+
+    fn temp_dir() -> PathBuf {
+        std::env::temp_dir().join(format!("steq-storage-{}", uuid::Uuid::now_v7()))
+    }
+
+    fn request_input(
+        name: &str,
+        collection_id: &str,
+        folder_id: Option<&str>,
+    ) -> CreateRequestInput {
+        CreateRequestInput {
+            name: name.to_string(),
+            method: HttpMethod::POST,
+            url: "{{baseUrl}}/users".to_string(),
+            headers: vec![KeyValue {
+                key: "Accept".to_string(),
+                value: "application/json".to_string(),
+                enabled: true,
+            }],
+            query_params: vec![],
+            body_type: BodyType::Json,
+            body_content: Some("{\n  \"name\": \"Ada\"\n}".to_string()),
+            description: None,
+            path_params: vec![],
+            scripts: None,
+            kind: RequestKind::Http,
+            collection_id: Some(collection_id.to_string()),
+            folder_id: folder_id.map(|s| s.to_string()),
+        }
+    }
+
+    fn sample_collection(db: &Database, workspace_id: &str) -> String {
+        let coll = db
+            .create_collection(CreateCollectionInput {
+                workspace_id: workspace_id.to_string(),
+                name: "Users API".to_string(),
+                description: Some("Team collection".to_string()),
+            })
+            .unwrap();
+        let folder = db
+            .create_folder(CreateFolderInput {
+                collection_id: coll.id.clone(),
+                parent_folder_id: None,
+                name: "Admin/Ops".to_string(),
+                description: None,
+            })
+            .unwrap();
+        db.create_request(request_input("Create User", &coll.id, Some(&folder.id)))
+            .unwrap();
+        db.create_request(request_input("Create User", &coll.id, None))
+            .unwrap();
+        let mut graphql = request_input("Me", &coll.id, None);
+        graphql.body_type = BodyType::GraphQL;
+        graphql.body_content = Some(
+            r#"{"query":"query {\n  me { id }\n}","variables":"","operationName":""}"#.to_string(),
+        );
+        db.create_request(graphql).unwrap();
+        coll.id
+    }
+
+    #[test]
+    fn write_produces_stable_readable_files() {
+        let (db, wid) = setup_test_db();
+        let coll_id = sample_collection(&db, &wid);
+        let dir = temp_dir();
+
+        write_collection(&db, &coll_id, &dir).unwrap();
+        assert!(dir.join("collection.yaml").is_file());
+        assert!(dir.join("Admin-Ops/folder.yaml").is_file());
+        assert!(dir.join("Admin-Ops/Create User.request.yaml").is_file());
+        assert!(dir.join("Create User.request.yaml").is_file());
+
+        let me = std::fs::read_to_string(dir.join("Me.request.yaml")).unwrap();
+        let keys: Vec<&str> = me
+            .lines()
+            .filter(|l| !l.starts_with(' ') && !l.starts_with('-'))
+            .filter_map(|l| l.split(':').next())
+            .collect();
+        assert_eq!(
+            keys,
+            vec!["id", "name", "seq", "method", "url", "headers", "body"]
+        );
+        assert!(me.contains("  type: graphql\n  query: |-\n    query {\n      me { id }\n    }\n"));
+
+        // Writing again is a no-op
+        let before = std::fs::metadata(dir.join("Me.request.yaml"))
+            .unwrap()
+            .modified()
+            .unwrap();
+        write_collection(&db, &coll_id, &dir).unwrap();
+        let after = std::fs::metadata(dir.join("Me.request.yaml"))
+            .unwrap()
+            .modified()
+            .unwrap();
+        assert_eq!(before, after);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn write_removes_stale_files_but_keeps_others() {
+        let (db, wid) = setup_test_db();
+        let coll_id = sample_collection(&db, &wid);
+        let dir = temp_dir();
+        write_collection(&db, &coll_id, &dir).unwrap();
+        std::fs::write(dir.join("README.md"), "docs").unwrap();
+
+        let tree = db.get_collection_tree(&coll_id).unwrap();
+        db.delete_folder(&tree.root_folders[0].folder.id).unwrap();
+        let me = tree.root_requests.iter().find(|r| r.name == "Me").unwrap();
+        db.update_request(UpdateRequestInput {
+            id: me.id.clone(),
+            name: Some("Current User".to_string()),
+            method: None,
+            url: None,
+            headers: None,
+            query_params: None,
+            body_type: None,
+            body_content: None,
+            description: None,
+            path_params: None,
+            scripts: None,
+            kind: None,
+            collection_id: None,
+            folder_id: None,
+            sort_order: None,
+        })
+        .unwrap();
+        write_collection(&db, &coll_id, &dir).unwrap();
+
+        assert!(!dir.join("Me.request.yaml").exists());
+        assert!(dir.join("Current User.request.yaml").is_file());
+        // The folder's request moved to the root, so the directory is gone
+        assert!(!dir.join("Admin-Ops").exists());
+        assert!(dir.join("README.md").is_file());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn sync_applies_external_edits() {
+        let (db, wid) = setup_test_db();
+        let coll_id = sample_collection(&db, &wid);
+        let dir = temp_dir();
+        link_directory(&db, &coll_id, &dir).unwrap();
+        let tree = db.get_collection_tree(&coll_id).unwrap();
+        let folder_request_id = tree.root_folders[0].requests[0].id.clone();
+
+        // Edit a request, add one by hand without an id, delete another
+        let path = dir.join("Create User.request.yaml");
+        let edited = std::fs::read_to_string(&path)
+            .unwrap()
+            .replace("method: POST", "method: PUT");
+        std::fs::write(&path, edited).unwrap();
+        std::fs::write(
+            dir.join("Admin-Ops/Health.request.yaml"),
+            "name: Health\nmethod: GET\nurl: https://example.com/health\n",
+        )
+        .unwrap();
+        std::fs::remove_file(dir.join("Me.request.yaml")).unwrap();
+        std::fs::write(dir.join("collection.yaml"), "name: Renamed\n").unwrap();
+
+        sync_from_directory(&db, &coll_id).unwrap();
+
+        let tree = db.get_collection_tree(&coll_id).unwrap();
+        assert_eq!(tree.collection.name, "Renamed");
+        assert!(tree.collection.description.is_none());
+        assert_eq!(tree.root_requests.len(), 1);
+        assert_eq!(tree.root_requests[0].method.as_str(), "PUT");
+        let folder = &tree.root_folders[0];
+        assert_eq!(folder.requests.len(), 2);
+        assert!(folder.requests.iter().any(|r| r.id == folder_request_id));
+        let health = folder.requests.iter().find(|r| r.name == "Health").unwrap();
+        assert_eq!(health.body_type.as_str(), "none");
+
+        // The new file was written back with its generated id
+        let health_file =
+            std::fs::read_to_string(dir.join("Admin-Ops/Health.request.yaml")).unwrap();
+        assert!(health_file.starts_with(&format!("id: {}\n", health.id)));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn open_directory_creates_linked_collection() {
+        let (db, wid) = setup_test_db();
+        let coll_id = sample_collection(&db, &wid);
+        let dir = temp_dir();
+        write_collection(&db, &coll_id, &dir).unwrap();
+
+        let (db2, wid2) = setup_test_db();
+        let opened = open_directory(&db2, &wid2, &dir).unwrap();
+        assert_eq!(opened.name, "Users API");
+        assert!(db2.get_collection_storage(&opened.id).unwrap().is_some());
+
+        let original = db.get_collection_tree(&coll_id).unwrap();
+        let copy = db2.get_collection_tree(&opened.id).unwrap();
+        assert_eq!(copy.root_requests.len(), original.root_requests.len());
+        for (a, b) in original.root_requests.iter().zip(&copy.root_requests) {
+            assert_eq!(a.id, b.id);
+            assert_eq!(a.body_content, b.body_content);
+            assert_eq!(a.headers.len(), b.headers.len());
+        }
+        assert_eq!(copy.root_folders[0].folder.name, "Admin/Ops");
+
+        assert!(open_directory(&db2, &wid2, &temp_dir()).is_err());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn opening_a_linked_directory_again_leaves_the_first_collection_alone() {
+        let (db, wid) = setup_test_db();
+        let coll_id = sample_collection(&db, &wid);
+        let dir = temp_dir();
+        link_directory(&db, &coll_id, &dir).unwrap();
+        let before = db.get_collection_tree(&coll_id).unwrap();
+        let ids = |tree: &CollectionTree| {
+            let mut folders = Vec::new();
+            let mut requests: Vec<&ApiRequest> = tree.root_requests.iter().collect();
+            for node in &tree.root_folders {
+                folders.push(node.folder.id.clone());
+                requests.extend(&node.requests);
+            }
+            let mut ids: Vec<String> = requests.iter().map(|r| r.id.clone()).collect();
+            ids.extend(folders);
+            ids.sort();
+            ids
+        };
+
+        let opened = open_directory(&db, &wid, &dir).unwrap();
+
+        let after = db.get_collection_tree(&coll_id).unwrap();
+        assert_eq!(ids(&after), ids(&before));
+        assert!(after
+            .root_requests
+            .iter()
+            .all(|r| r.collection_id.as_deref() == Some(coll_id.as_str())));
+        let copy = db.get_collection_tree(&opened.id).unwrap();
+        assert_eq!(copy.root_requests.len(), before.root_requests.len());
+        assert!(ids(&copy).iter().all(|id| !ids(&before).contains(id)));
+
+        // Even a tree that reuses the ids can't take the rows over
+        let mut stolen = before.clone();
+        stolen.collection = copy.collection.clone();
+        for request in &mut stolen.root_requests {
+            request.name = "Stolen".to_string();
+        }
+        db.replace_collection_contents(&stolen).unwrap();
+        let after = db.get_collection_tree(&coll_id).unwrap();
+        assert_eq!(ids(&after), ids(&before));
+        assert!(after.root_requests.iter().all(|r| r.name != "Stolen"));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,139 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+
+/// Editors and `git checkout` touch many files at once; changes are reported once
+/// the directory has been quiet for this long.
+const DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// Active directory watchers, keyed by collection id. Dropping a watcher stops it.
+#[derive(Default)]
+pub struct StorageWatchers {
+    watchers: Mutex<HashMap<String, RecommendedWatcher>>,
+}
+
+impl StorageWatchers {
+    pub fn insert(&self, collection_id: &str, watcher: RecommendedWatcher) -> Result<(), String> {
+        let mut watchers = self
+            .watchers
+            .lock()
+            .map_err(|e| format!("Lock error: {}", e))?;
+        watchers.insert(collection_id.to_string(), watcher);
+        Ok(())
+    }
+
+    pub fn remove(&self, collection_id: &str) -> Result<(), String> {
+        let mut watchers = self
+            .watchers
+            .lock()
+            .map_err(|e| format!("Lock error: {}", e))?;
+        watchers.remove(collection_id);
+        Ok(())
+    }
+}
+
+/// Watches `dir` recursively and calls `on_change` on a background thread after
+/// each burst of changes to collection files.
+pub fn watch_directory<F>(dir: &Path, on_change: F) -> Result<RecommendedWatcher, String>
+where
+    F: Fn() + Send + 'static,
+{
+    let (tx, rx) = mpsc::channel::<()>();
+    let roots = [
+        dir.to_path_buf(),
+        dir.canonicalize().unwrap_or_else(|_| dir.to_path_buf()),
+    ];
+    let mut watcher = notify::recommended_watcher(move |result: notify::Result<Event>| {
+        if let Ok(event) = result {
+            if is_relevant(&roots, &event) {
+                let _ = tx.send(());
+            }
+        }
+    })
+    .map_err(|e| format!("Failed to create watcher: {}", e))?;
+    watcher
+        .watch(dir, RecursiveMode::Recursive)
+        .map_err(|e| format!("Failed to watch {}: {}", dir.display(), e))?;
+
+    // The channel closes when the watcher is dropped, which ends this thread
+    std::thread::spawn(move || {
+        while rx.recv().is_ok() {
+            loop {
+                match rx.recv_timeout(DEBOUNCE) {
+                    Ok(()) => continue,
+                    Err(RecvTimeoutError::Timeout) => break,
+                    Err(RecvTimeoutError::Disconnected) => return,
+                }
+            }
+            on_change();
+        }
+    });
+
+    Ok(watcher)
+}
+
+/// Ignores reads and anything inside hidden directories such as `.git`. Only the
+/// part of the path below the watched directory is checked.
+fn is_relevant(roots: &[PathBuf], event: &Event) -> bool {
+    if matches!(event.kind, EventKind::Access(_)) {
+        return false;
+    }
+    event.paths.iter().any(|path| {
+        let relative = roots
+            .iter()
+            .find_map(|root| path.strip_prefix(root).ok())
+            .unwrap_or(path);
+        !relative
+            .components()
+            .any(|c| c.as_os_str().to_string_lossy().starts_with('.'))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ignores_access_and_hidden_paths() {
+        let modify = |path: &str| {
+            Event::new(EventKind::Modify(notify::event::ModifyKind::Any))
+                .add_path(PathBuf::from(path))
+        };
+        let roots = [PathBuf::from("/home/me/.config/api")];
+        assert!(is_relevant(
+            &roots,
+            &modify("/home/me/.config/api/Get.request.yaml")
+        ));
+        assert!(!is_relevant(
+            &roots,
+            &modify("/home/me/.config/api/.git/index")
+        ));
+        let access = Event::new(EventKind::Access(notify::event::AccessKind::Any))
+            .add_path(PathBuf::from("/home/me/.config/api/Get.request.yaml"));
+        assert!(!is_relevant(&roots, &access));
+    }
+
+    #[test]
+    fn reports_changes_once_per_burst() {
+        let dir = std::env::temp_dir().join(format!("steq-watch-{}", uuid::Uuid::now_v7()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let (tx, rx) = mpsc::channel();
+        let watcher = watch_directory(&dir, move || {
+            let _ = tx.send(());
+        })
+        .unwrap();
+
+        for i in 0..3 {
+            std::fs::write(dir.join(format!("{}.request.yaml", i)), "name: x\n").unwrap();
+        }
+        assert!(rx.recv_timeout(Duration::from_secs(5)).is_ok());
+        assert!(rx.recv_timeout(DEBOUNCE * 2).is_err());
+
+        drop(watcher);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,416 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use base64::Engine;
+use futures_util::{SinkExt, StreamExt};
+use tokio::net::TcpStream;
+use tokio::sync::mpsc;
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::http::{HeaderName, HeaderValue};
+use tokio_tungstenite::tungstenite::protocol::frame::coding::CloseCode;
+use tokio_tungstenite::tungstenite::protocol::CloseFrame;
+use tokio_tungstenite::tungstenite::{Error as WsError, Message};
+use tokio_tungstenite::{MaybeTlsStream, WebSocketStream};
+
+use crate::models::websocket::{ConnectWebSocketInput, MessageDirection, MessageKind};
+
+/// How long to wait for the server to answer a close frame before dropping the
+/// connection.
+const CLOSE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Open WebSocket connections by session id. A session is removed when its
+/// connection ends; dropping its handle closes the connection.
+#[derive(Default)]
+pub struct WebSocketSessions {
+    sessions: Mutex<HashMap<String, SessionHandle>>,
+}
+
+impl WebSocketSessions {
+    pub fn insert(&self, session_id: &str, handle: SessionHandle) -> Result<(), String> {
+        let mut sessions = self
+            .sessions
+            .lock()
+            .map_err(|e| format!("Lock error: {}", e))?;
+        sessions.insert(session_id.to_string(), handle);
+        Ok(())
+    }
+
+    pub fn remove(&self, session_id: &str) -> Result<(), String> {
+        let mut sessions = self
+            .sessions
+            .lock()
+            .map_err(|e| format!("Lock error: {}", e))?;
+        sessions.remove(session_id);
+        Ok(())
+    }
+
+    pub fn send(&self, session_id: &str, command: Command) -> Result<(), String> {
+        let sessions = self
+            .sessions
+            .lock()
+            .map_err(|e| format!("Lock error: {}", e))?;
+        let handle = sessions
+            .get(session_id)
+            .ok_or_else(|| format!("WebSocket session {} is not connected", session_id))?;
+        handle
+            .commands
+            .send(command)
+            .map_err(|_| format!("WebSocket session {} is closing", session_id))
+    }
+}
+
+pub struct SessionHandle {
+    commands: mpsc::UnboundedSender<Command>,
+}
+
+pub enum Command {
+    Send(Message),
+    Close { code: u16, reason: String },
+}
+
+/// What happened on a connection, in order. `Closed` is always last.
+#[derive(Debug)]
+pub enum SessionEvent {
+    Message {
+        direction: MessageDirection,
+        kind: MessageKind,
+        data: String,
+        size: usize,
+    },
+    Closed {
+        code: Option<u16>,
+        reason: Option<String>,
+        error: Option<String>,
+    },
+}
+
+/// A connection that has completed its handshake but isn't running yet.
+pub struct Connection {
+    stream: WebSocketStream<MaybeTlsStream<TcpStream>>,
+    /// The subprotocol the server accepted.
+    pub subprotocol: Option<String>,
+}
+
+/// Opens a connection with the input's enabled headers and query parameters,
+/// offering its subprotocols.
+pub async fn connect(input: &ConnectWebSocketInput) -> Result<Connection, String> {
+    let mut request = build_url(input)?
+        .into_client_request()
+        .map_err(|e| format!("Invalid WebSocket URL: {}", e))?;
+    let headers = request.headers_mut();
+    for kv in input.headers.iter().filter(|kv| kv.enabled) {
+        let name = HeaderName::from_bytes(kv.key.as_bytes())
+            .map_err(|e| format!("Invalid header name {}: {}", kv.key, e))?;
+        let value = HeaderValue::from_str(&kv.value)
+            .map_err(|e| format!("Invalid value for header {}: {}", kv.key, e))?;
+        headers.insert(name, value);
+    }
+    let subprotocols: Vec<&str> = input
+        .subprotocols
+        .iter()
+        .map(|p| p.trim())
+        .filter(|p| !p.is_empty())
+        .collect();
+    if !subprotocols.is_empty() {
+        // No space after the comma: the handshake matches the server's choice
+        // against the offered names split on commas alone
+        let value = HeaderValue::from_str(&subprotocols.join(","))
+            .map_err(|e| format!("Invalid subprotocol: {}", e))?;
+        headers.insert("sec-websocket-protocol", value);
+    }
+
+    let (stream, response) = tokio_tungstenite::connect_async(request)
+        .await
+        .map_err(|e| format!("WebSocket connection failed: {}", e))?;
+    let subprotocol = response
+        .headers()
+        .get("sec-websocket-protocol")
+        .and_then(|v| v.to_str().ok())
+        .map(String::from);
+    Ok(Connection {
+        stream,
+        subprotocol,
+    })
+}
+
+impl Connection {
+    /// Adds the connection to `sessions` under `session_id`, then runs it in the
+    /// background, reporting every frame sent or received to `on_event`. It's
+    /// registered before the first event, so handling `Closed` can remove it
+    /// however soon the connection ends. The connection closes when the session
+    /// is removed.
+    pub fn run<F>(
+        self,
+        sessions: &WebSocketSessions,
+        session_id: &str,
+        on_event: F,
+    ) -> Result<(), String>
+    where
+        F: Fn(SessionEvent) + Send + 'static,
+    {
+        let (commands, receiver) = mpsc::unbounded_channel();
+        sessions.insert(session_id, SessionHandle { commands })?;
+        tokio::spawn(session_loop(self.stream, receiver, on_event));
+        Ok(())
+    }
+}
+
+async fn session_loop<F>(
+    stream: WebSocketStream<MaybeTlsStream<TcpStream>>,
+    mut commands: mpsc::UnboundedReceiver<Command>,
+    on_event: F,
+) where
+    F: Fn(SessionEvent) + Send + 'static,
+{
+    let (mut sink, mut incoming) = stream.split();
+    // The close frame that ended the session, from whichever side sent it first
+    let mut close: Option<(Option<u16>, Option<String>)> = None;
+    let mut close_deadline = None;
+
+    let error = loop {
+        let timeout = async {
+            match close_deadline {
+                Some(deadline) => tokio::time::sleep_until(deadline).await,
+                None => std::future::pending().await,
+            }
+        };
+        tokio::select! {
+            command = commands.recv(), if close_deadline.is_none() => {
+                let message = match command {
+                    Some(Command::Send(message)) => message,
+                    Some(Command::Close { code, reason }) => close_message(code, reason),
+                    // Every handle is gone, e.g. the app is quitting
+                    None => close_message(1001, String::new()),
+                };
+                if let Message::Close(frame) = &message {
+                    close.get_or_insert_with(|| close_details(frame.as_ref()));
+                    close_deadline = Some(tokio::time::Instant::now() + CLOSE_TIMEOUT);
+                }
+                let event = message_event(MessageDirection::Sent, &message);
+                if let Err(e) = sink.send(message).await {
+                    break close_error(e);
+                }
+                if let Some(event) = event {
+                    on_event(event);
+                }
+            }
+            received = incoming.next() => match received {
+                Some(Ok(message)) => {
+                    if let Message::Close(frame) = &message {
+                        close.get_or_insert_with(|| close_details(frame.as_ref()));
+                    }
+                    if let Some(event) = message_event(MessageDirection::Received, &message) {
+                        on_event(event);
+                    }
+                }
+                Some(Err(e)) => break close_error(e),
+                None => break None,
+            },
+            _ = timeout => break None,
+        }
+    };
+
+    let (code, reason) = close.unwrap_or_default();
+    on_event(SessionEvent::Closed {
+        code,
+        reason,
+        error,
+    });
+}
+
+/// `http(s)` URLs are switched to `ws(s)`; enabled query parameters are appended.
+fn build_url(input: &ConnectWebSocketInput) -> Result<String, String> {
+    let mut url = match input.url.trim() {
+        u if u.starts_with("https://") => format!("wss://{}", &u["https://".len()..]),
+        u if u.starts_with("http://") => format!("ws://{}", &u["http://".len()..]),
+        u => u.to_string(),
+    };
+    let mut parsed =
+        reqwest::Url::parse(&url).map_err(|e| format!("Invalid WebSocket URL {}: {}", url, e))?;
+    if !matches!(parsed.scheme(), "ws" | "wss") {
+        return Err(format!(
+            "Unsupported WebSocket URL scheme: {}",
+            parsed.scheme()
+        ));
+    }
+    let enabled: Vec<_> = input.query_params.iter().filter(|kv| kv.enabled).collect();
+    if !enabled.is_empty() {
+        let mut pairs = parsed.query_pairs_mut();
+        for kv in enabled {
+            pairs.append_pair(&kv.key, &kv.value);
+        }
+        drop(pairs);
+        url = parsed.to_string();
+    }
+    Ok(url)
+}
+
+fn close_message(code: u16, reason: String) -> Message {
+    Message::Close(Some(CloseFrame {
+        code: CloseCode::from(code),
+        reason: reason.into(),
+    }))
+}
+
+fn close_details(frame: Option<&CloseFrame>) -> (Option<u16>, Option<String>) {
+    match frame {
+        Some(frame) => (
+            Some(u16::from(frame.code)),
+            Some(frame.reason.to_string()).filter(|r| !r.is_empty()),
+        ),
+        None => (None, None),
+    }
+}
+
+/// Errors after the close handshake are just the connection going away.
+fn close_error(error: WsError) -> Option<String> {
+    match error {
+        WsError::ConnectionClosed | WsError::AlreadyClosed => None,
+        e => Some(e.to_string()),
+    }
+}
+
+fn message_event(direction: MessageDirection, message: &Message) -> Option<SessionEvent> {
+    let encode = |bytes: &[u8]| base64::engine::general_purpose::STANDARD.encode(bytes);
+    let (kind, data, size) = match message {
+        Message::Text(text) => (MessageKind::Text, text.to_string(), text.len()),
+        Message::Binary(bytes) => (MessageKind::Binary, encode(bytes), bytes.len()),
+        Message::Ping(bytes) => (MessageKind::Ping, encode(bytes), bytes.len()),
+        Message::Pong(bytes) => (MessageKind::Pong, encode(bytes), bytes.len()),
+        Message::Close(frame) => {
+            let reason = frame
+                .as_ref()
+                .map(|f| f.reason.to_string())
+                .unwrap_or_default();
+            let size = reason.len();
+            (MessageKind::Close, reason, size)
+        }
+        Message::Frame(_) => return None,
+    };
+    Some(SessionEvent::Message {
+        direction,
+        kind,
+        data,
+        size,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::request::KeyValue;
+    use tokio::net::TcpListener;
+    use tokio_tungstenite::tungstenite::handshake::server::{Request, Response};
+
+    /// Echoes data frames, accepting the `echo` subprotocol and recording the
+    /// `x-token` header and query string it was opened with.
+    #[allow(clippy::result_large_err)] // The handshake callback's signature is fixed
+    async fn echo_server() -> (String, tokio::sync::oneshot::Receiver<(String, String)>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}/socket", listener.local_addr().unwrap());
+        let (seen_tx, seen_rx) = tokio::sync::oneshot::channel();
+        tokio::spawn(async move {
+            let (socket, _) = listener.accept().await.unwrap();
+            let callback = |request: &Request, mut response: Response| {
+                let token = request.headers()["x-token"].to_str().unwrap().to_string();
+                let query = request.uri().query().unwrap_or_default().to_string();
+                let _ = seen_tx.send((token, query));
+                response
+                    .headers_mut()
+                    .insert("sec-websocket-protocol", HeaderValue::from_static("echo"));
+                Ok(response)
+            };
+            let mut ws = tokio_tungstenite::accept_hdr_async(socket, callback)
+                .await
+                .unwrap();
+            while let Some(Ok(message)) = ws.next().await {
+                if message.is_text() || message.is_binary() {
+                    ws.send(message).await.unwrap();
+                }
+            }
+        });
+        (url, seen_rx)
+    }
+
+    #[tokio::test]
+    async fn test_session_round_trip() {
+        let (url, seen) = echo_server().await;
+        let input = ConnectWebSocketInput {
+            workspace_id: "w".to_string(),
+            request_id: None,
+            url: url.replace("ws://", "http://"),
+            headers: vec![KeyValue {
+                key: "X-Token".to_string(),
+                value: "abc".to_string(),
+                enabled: true,
+            }],
+            query_params: vec![KeyValue {
+                key: "room".to_string(),
+                value: "a b".to_string(),
+                enabled: true,
+            }],
+            subprotocols: vec!["chat".to_string(), "echo".to_string()],
+        };
+        let connection = connect(&input).await.unwrap();
+        assert_eq!(connection.subprotocol.as_deref(), Some("echo"));
+        assert_eq!(
+            seen.await.unwrap(),
+            ("abc".to_string(), "room=a+b".to_string())
+        );
+
+        let sessions = WebSocketSessions::default();
+        let (events_tx, mut events) = mpsc::unbounded_channel();
+        connection
+            .run(&sessions, "s", move |event| {
+                let _ = events_tx.send(event);
+            })
+            .unwrap();
+        sessions
+            .send("s", Command::Send(Message::Text("hello".into())))
+            .unwrap();
+        sessions
+            .send("s", Command::Send(Message::Binary(vec![1, 2])))
+            .unwrap();
+
+        let mut received = Vec::new();
+        while received.len() < 2 {
+            if let Some(SessionEvent::Message {
+                direction: MessageDirection::Received,
+                kind,
+                data,
+                ..
+            }) = events.recv().await
+            {
+                received.push((kind, data));
+            }
+        }
+        assert_eq!(received[0], (MessageKind::Text, "hello".to_string()));
+        assert_eq!(received[1], (MessageKind::Binary, "AQI=".to_string()));
+
+        sessions
+            .send(
+                "s",
+                Command::Close {
+                    code: 4000,
+                    reason: "done".to_string(),
+                },
+            )
+            .unwrap();
+        loop {
+            match events.recv().await.unwrap() {
+                SessionEvent::Closed {
+                    code,
+                    reason,
+                    error,
+                } => {
+                    assert_eq!(code, Some(4000));
+                    assert_eq!(reason.as_deref(), Some("done"));
+                    assert!(error.is_none(), "{:?}", error);
+                    break;
+                }
+                SessionEvent::Message { .. } => {}
+            }
+        }
+    }
+}
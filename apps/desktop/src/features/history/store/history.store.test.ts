@@ -26,6 +26,7 @@ const mockEntry: HistoryEntry = {
   response_status: 200,
   response_headers: null,
   response_body: '{"data":[]}',
+  response_body_truncated: false,
   response_size: 12,
   duration_ms: 150,
   error: null,
@@ -82,6 +82,10 @@ describe('request-editor.store', () => {
       query_params: [{ key: 'page', value: '1', enabled: true }],
       body_type: 'json',
       body_content: '{"key":"val"}',
+      description: null,
+      path_params: [],
+      scripts: null,
+      kind: { type: 'http' },
       collection_id: 'coll-1',
       folder_id: null,
       sort_order: 0,
@@ -147,6 +151,10 @@ describe('requestToDraft', () => {
     query_params: [{ key: 'q', value: 'test', enabled: true }],
     body_type: 'json',
     body_content: '{"a":1}',
+    description: null,
+    path_params: [],
+    scripts: null,
+    kind: { type: 'http' },
     collection_id: 'coll-1',
     folder_id: 'folder-1',
     sort_order: 0,
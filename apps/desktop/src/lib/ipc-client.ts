@@ -4,6 +4,7 @@ import type {
   CreateRequestInput,
   UpdateRequestInput,
   ExecuteRequestInput,
+  ExecuteStreamInput,
   ExecutionResult,
   Workspace,
   Collection,
@@ -17,8 +18,59 @@ import type {
   UpdateEnvironmentInput,
   HistoryEntry,
   HistoryQuery,
+  HistoryRedaction,
+  HistoryRetention,
+  SaveHistoryInput,
+  VacuumResult,
+  SearchHit,
+  SearchQuery,
+  DiffOptions,
+  ReplayResult,
+  ResponseDiff,
+  RequestExample,
+  CreateExampleInput,
+  UpdateExampleInput,
+  MockLogEntry,
+  MockServerInfo,
+  StartMockInput,
+  CaptureSession,
+  CaptureToCollectionInput,
+  ProxyInfo,
+  StartProxyInput,
+  ConnectWebSocketInput,
+  SendWebSocketInput,
+  WebSocketMessage,
+  WebSocketSession,
+  GrpcCallInput,
+  GrpcResult,
+  GrpcServiceInfo,
+  GrpcServicesInput,
+  GraphQLError,
+  GraphQLSchema,
+  ValidateGraphQLInput,
 } from '@steq/domain';
 
+export interface CollectionStorage {
+  collection_id: string;
+  directory: string;
+  synced_at: string;
+}
+
+/** Payload of the `collection-storage-synced` event. */
+export interface StorageSyncEvent {
+  collection_id: string;
+  error: string | null;
+}
+
+export type CodegenTarget =
+  | 'python_requests'
+  | 'go_net_http'
+  | 'node_axios'
+  | 'rust_reqwest'
+  | 'java_http_client'
+  | 'csharp_http_client'
+  | 'powershell';
+
 export const ipc = {
   request: {
     create: (input: CreateRequestInput) => invoke<ApiRequest>('create_request', { input }),
@@ -28,12 +80,95 @@ export const ipc = {
         workspaceId,
         requestId: requestId ?? null,
       }),
+    /**
+     * Resolves when the stream ends. Events arrive as `sse-event` with the given
+     * `streamId`, and the result body is the JSON list of events.
+     */
+    executeStream: (
+      input: ExecuteRequestInput,
+      workspaceId: string,
+      streamId: string,
+      requestId?: string | null,
+    ) => {
+      const stream: ExecuteStreamInput = {
+        request: input,
+        workspace_id: workspaceId,
+        request_id: requestId ?? null,
+        stream_id: streamId,
+      };
+      return invoke<ExecutionResult>('execute_request_stream', { input: stream });
+    },
+    stopStream: (streamId: string) => invoke<void>('stop_request_stream', { streamId }),
     update: (input: UpdateRequestInput) => invoke<ApiRequest>('update_request', { input }),
     delete: (id: string) => invoke<void>('delete_request', { id }),
     getById: (id: string) => invoke<ApiRequest | null>('get_request', { id }),
     listByCollection: (collectionId: string) =>
       invoke<ApiRequest[]>('list_requests_by_collection', { collectionId }),
   },
+  example: {
+    list: (requestId: string) => invoke<RequestExample[]>('list_examples', { requestId }),
+    create: (input: CreateExampleInput) => invoke<RequestExample>('create_example', { input }),
+    update: (input: UpdateExampleInput) => invoke<RequestExample>('update_example', { input }),
+    delete: (id: string) => invoke<void>('delete_example', { id }),
+  },
+  mock: {
+    start: (input: StartMockInput) => invoke<MockServerInfo>('start_mock_server', { input }),
+    stop: (collectionId: string) => invoke<void>('stop_mock_server', { collectionId }),
+    list: () => invoke<MockServerInfo[]>('list_mock_servers'),
+    log: (collectionId: string) => invoke<MockLogEntry[]>('get_mock_log', { collectionId }),
+    clearLog: (collectionId: string) => invoke<void>('clear_mock_log', { collectionId }),
+  },
+  proxy: {
+    start: (input: StartProxyInput) => invoke<ProxyInfo>('start_proxy', { input }),
+    stop: () => invoke<void>('stop_proxy'),
+    status: () => invoke<ProxyInfo | null>('get_proxy_status'),
+    caCertificate: () => invoke<string>('get_proxy_ca_certificate'),
+    listSessions: (workspaceId: string) =>
+      invoke<CaptureSession[]>('list_capture_sessions', { workspaceId }),
+    listEntries: (sessionId: string) =>
+      invoke<HistoryEntry[]>('list_capture_entries', { sessionId }),
+    deleteSession: (id: string) => invoke<void>('delete_capture_session', { id }),
+    toCollection: (input: CaptureToCollectionInput) =>
+      invoke<Collection>('capture_session_to_collection', { input }),
+  },
+  websocket: {
+    connect: (input: ConnectWebSocketInput) =>
+      invoke<WebSocketSession>('connect_websocket', { input }),
+    send: (input: SendWebSocketInput) => invoke<void>('send_websocket_message', { input }),
+    ping: (sessionId: string, payload?: string) =>
+      invoke<void>('ping_websocket', { sessionId, payload: payload ?? null }),
+    close: (sessionId: string, code?: number, reason?: string) =>
+      invoke<void>('close_websocket', { sessionId, code: code ?? null, reason: reason ?? null }),
+    listSessions: (workspaceId: string, requestId?: string) =>
+      invoke<WebSocketSession[]>('list_websocket_sessions', {
+        workspaceId,
+        requestId: requestId ?? null,
+      }),
+    transcript: (sessionId: string) =>
+      invoke<WebSocketMessage[]>('get_websocket_transcript', { sessionId }),
+    deleteSession: (id: string) => invoke<void>('delete_websocket_session', { id }),
+  },
+  grpc: {
+    listServices: (input: GrpcServicesInput) =>
+      invoke<GrpcServiceInfo[]>('list_grpc_services', { input }),
+    /**
+     * With a `streamId`, response messages also arrive as `grpc-message` events,
+     * and `request.stopStream` ends the call early.
+     */
+    execute: (input: GrpcCallInput, streamId?: string) =>
+      invoke<GrpcResult>('execute_grpc', { input, streamId: streamId ?? null }),
+  },
+  graphql: {
+    /** Sends the introspection query with the request's URL and headers. */
+    introspect: (input: ExecuteRequestInput, workspaceId: string) =>
+      invoke<GraphQLSchema>('introspect_graphql_schema', { input, workspaceId }),
+    getSchema: (workspaceId: string, url: string) =>
+      invoke<GraphQLSchema | null>('get_graphql_schema', { workspaceId, url }),
+    deleteSchema: (workspaceId: string, url: string) =>
+      invoke<void>('delete_graphql_schema', { workspaceId, url }),
+    validate: (input: ValidateGraphQLInput) =>
+      invoke<GraphQLError[]>('validate_graphql', { input }),
+  },
   collection: {
     create: (input: CreateCollectionInput) =>
       invoke<Collection>('create_collection', { input }),
@@ -43,6 +178,17 @@ export const ipc = {
     rename: (input: RenameInput) => invoke<void>('rename_collection', { input }),
     delete: (id: string) => invoke<void>('delete_collection', { id }),
   },
+  storage: {
+    link: (collectionId: string, directory: string) =>
+      invoke<CollectionStorage>('link_collection_directory', { collectionId, directory }),
+    open: (workspaceId: string, directory: string) =>
+      invoke<Collection>('open_collection_directory', { workspaceId, directory }),
+    unlink: (collectionId: string) =>
+      invoke<void>('unlink_collection_directory', { collectionId }),
+    get: (collectionId: string) =>
+      invoke<CollectionStorage | null>('get_collection_storage', { collectionId }),
+    sync: (collectionId: string) => invoke<void>('sync_collection_directory', { collectionId }),
+  },
   folder: {
     create: (input: CreateFolderInput) => invoke<Folder>('create_folder', { input }),
     rename: (input: RenameInput) => invoke<void>('rename_folder', { input }),
@@ -67,15 +213,92 @@ export const ipc = {
     list: (query: HistoryQuery) => invoke<HistoryEntry[]>('list_history', { query }),
     clear: (workspaceId: string) => invoke<void>('clear_history', { workspaceId }),
     delete: (id: string) => invoke<void>('delete_history_entry', { id }),
+    getRetention: (workspaceId: string) =>
+      invoke<HistoryRetention>('get_history_retention', { workspaceId }),
+    setRetention: (retention: HistoryRetention) =>
+      invoke<void>('set_history_retention', { retention }),
+    vacuum: () => invoke<VacuumResult>('vacuum'),
+    getRedaction: (workspaceId: string) =>
+      invoke<HistoryRedaction>('get_history_redaction', { workspaceId }),
+    setRedaction: (redaction: HistoryRedaction) =>
+      invoke<void>('set_history_redaction', { redaction }),
+    reveal: (id: string) => invoke<HistoryEntry>('reveal_history_entry', { id }),
+    replay: (id: string, options?: DiffOptions) =>
+      invoke<ReplayResult>('replay_history_entry', { id, options }),
+    diff: (beforeId: string, afterId: string, options?: DiffOptions) =>
+      invoke<ResponseDiff>('diff_history_entries', { beforeId, afterId, options }),
+    saveToCollection: (input: SaveHistoryInput) =>
+      invoke<ApiRequest>('save_history_to_collection', { input }),
   },
+  search: (query: SearchQuery) => invoke<SearchHit[]>('search', { query }),
   io: {
     importPostman: (filePath: string, workspaceId: string) =>
-      invoke<{ collection_name: string; request_count: number; folder_count: number }>(
+      invoke<{ collection_name: string; request_count: number; folder_count: number; warnings: string[] }>(
         'import_postman',
         { filePath, workspaceId },
       ),
-    exportPostman: (collectionId: string, filePath: string) =>
-      invoke<void>('export_postman', { collectionId, filePath }),
+    exportPostman: (collectionId: string, filePath: string, environmentId?: string | null) =>
+      invoke<void>('export_postman', { collectionId, filePath, environmentId: environmentId ?? null }),
+    importPostmanEnvironment: (filePath: string, workspaceId: string) =>
+      invoke<Environment>('import_postman_environment', { filePath, workspaceId }),
+    exportPostmanEnvironment: (environmentId: string, filePath: string, includeSecrets: boolean) =>
+      invoke<void>('export_postman_environment', { environmentId, filePath, includeSecrets }),
+    importHar: (
+      filePath: string,
+      workspaceId: string,
+      options: { collection_name: string | null; group_by_host: boolean },
+    ) =>
+      invoke<{ collection_name: string; request_count: number; folder_count: number; warnings: string[] }>(
+        'import_har',
+        { filePath, workspaceId, options },
+      ),
+    exportHar: (workspaceId: string, entryIds: string[], filePath: string) =>
+      invoke<void>('export_har', { workspaceId, entryIds, filePath }),
+    importInsomnia: (filePath: string, workspaceId: string) =>
+      invoke<{
+        collections: { collection_name: string; request_count: number; folder_count: number; warnings: string[] }[];
+        environment_count: number;
+      }>('import_insomnia', { filePath, workspaceId }),
+    importHttpFile: (
+      filePath: string,
+      workspaceId: string,
+      options: {
+        collection_name?: string | null;
+        collection_id?: string | null;
+        folder_id?: string | null;
+        environment_id?: string | null;
+      } = {},
+    ) =>
+      invoke<{ collection_name: string; request_count: number; folder_count: number; warnings: string[] }>(
+        'import_http_file',
+        { filePath, workspaceId, options },
+      ),
+    exportHttpFile: (
+      collectionId: string,
+      filePath: string,
+      folderId?: string | null,
+      environmentId?: string | null,
+    ) =>
+      invoke<void>('export_http_file', {
+        collectionId,
+        folderId: folderId ?? null,
+        environmentId: environmentId ?? null,
+        filePath,
+      }),
+    importBruno: (dirPath: string, workspaceId: string) =>
+      invoke<{ collection_name: string; request_count: number; folder_count: number; warnings: string[] }>(
+        'import_bruno',
+        { dirPath, workspaceId },
+      ),
+    exportBruno: (collectionId: string, dirPath: string, environmentIds: string[] = []) =>
+      invoke<void>('export_bruno', { collectionId, dirPath, environmentIds }),
+    parseCurl: (command: string) => invoke<CreateRequestInput>('parse_curl', { command }),
+    exportOpenApi: (collectionId: string, filePath: string, format?: 'json' | 'yaml') =>
+      invoke<void>('export_openapi', { collectionId, filePath, format: format ?? null }),
+  },
+  codegen: {
+    generate: (requestId: string, target: CodegenTarget, workspaceId?: string | null) =>
+      invoke<string>('generate_code', { requestId, target, workspaceId: workspaceId ?? null }),
   },
   workspace: {
     getCurrent: () => invoke<Workspace>('get_current_workspace'),
@@ -0,0 +1,47 @@
+/** The history entries recorded while the proxy was running once. */
+export interface CaptureSession {
+  id: string;
+  workspace_id: string;
+  name: string;
+  started_at: string;
+  /** `null` while the proxy is still capturing. */
+  stopped_at: string | null;
+  entry_count: number;
+}
+
+export interface StartProxyInput {
+  workspace_id: string;
+  /** Any free port when omitted. */
+  port?: number | null;
+  /**
+   * Decrypt HTTPS with certificates issued by the local CA. Clients must trust
+   * that CA; without it HTTPS is tunnelled and not captured.
+   */
+  intercept_https?: boolean;
+  /** Listen on all interfaces so other devices, such as phones, can connect. */
+  listen_on_network?: boolean;
+  session_name?: string | null;
+}
+
+export interface ProxyInfo {
+  session_id: string;
+  workspace_id: string;
+  port: number;
+  /** The address clients should use, e.g. `127.0.0.1:8899`. */
+  address: string;
+  /**
+   * Set when listening on the network. Clients on other machines send it as
+   * the proxy password, e.g. `http://steq:<token>@192.168.1.20:8899`.
+   */
+  token: string | null;
+  intercept_https: boolean;
+  started_at: string;
+}
+
+export interface CaptureToCollectionInput {
+  session_id: string;
+  /** Defaults to the session name. */
+  name?: string | null;
+  /** Attach each distinct response status as an example. Defaults to true. */
+  save_responses?: boolean;
+}
@@ -14,6 +14,7 @@ export interface Folder {
   collection_id: string;
   parent_folder_id: string | null;
   name: string;
+  description: string | null;
   sort_order: number;
   created_at: string;
   updated_at: string;
@@ -41,6 +42,7 @@ export interface CreateFolderInput {
   collection_id: string;
   parent_folder_id: string | null;
   name: string;
+  description?: string | null;
 }
 
 export interface RenameInput {
@@ -0,0 +1,51 @@
+import type { ExecutionResult } from '../types/http';
+import type { HistoryEntry } from './history';
+
+/** Case-insensitive patterns with `*` wildcards. Omitted lists use the defaults. */
+export interface DiffOptions {
+  /** Field names at any depth, or JSON Pointer paths when starting with `/`. */
+  ignore_fields?: string[];
+  ignore_headers?: string[];
+}
+
+export interface StatusChange {
+  before: number | null;
+  after: number | null;
+}
+
+export interface HeaderChange {
+  name: string;
+  before: string | null;
+  after: string | null;
+}
+
+export interface JsonChange {
+  path: string;
+  before?: unknown;
+  after?: unknown;
+}
+
+export type LineChangeKind = 'added' | 'removed';
+
+export interface TextChange {
+  kind: LineChangeKind;
+  line: number;
+  text: string;
+}
+
+export type BodyDiff =
+  | { type: 'json'; changes: JsonChange[] }
+  | { type: 'text'; changes: TextChange[] };
+
+export interface ResponseDiff {
+  identical: boolean;
+  status: StatusChange | null;
+  headers: HeaderChange[];
+  body: BodyDiff;
+}
+
+export interface ReplayResult {
+  result: ExecutionResult;
+  entry: HistoryEntry | null;
+  diff: ResponseDiff;
+}
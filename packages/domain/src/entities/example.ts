@@ -0,0 +1,36 @@
+import type { KeyValue } from '../types/http';
+import type { ExecuteRequestInput } from './request';
+
+/** A saved response attached to a request, used as documentation and mock data. */
+export interface RequestExample {
+  id: string;
+  request_id: string;
+  name: string;
+  status: number | null;
+  headers: KeyValue[];
+  body: string | null;
+  /** The request variant that produced this response, when it was recorded. */
+  original_request: ExecuteRequestInput | null;
+  sort_order: number;
+  created_at: string;
+  updated_at: string;
+}
+
+export interface CreateExampleInput {
+  request_id: string;
+  name: string;
+  status: number | null;
+  headers?: KeyValue[];
+  body: string | null;
+  original_request?: ExecuteRequestInput | null;
+}
+
+export interface UpdateExampleInput {
+  id: string;
+  name?: string;
+  status?: number;
+  headers?: KeyValue[];
+  body?: string;
+  original_request?: ExecuteRequestInput;
+  sort_order?: number;
+}
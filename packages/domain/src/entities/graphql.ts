@@ -0,0 +1,30 @@
+/** An introspected schema, cached per workspace and endpoint URL. */
+export interface GraphQLSchema {
+  workspace_id: string;
+  url: string;
+  /** The introspection response's `data`, ready for `buildClientSchema`. */
+  introspection: { __schema: unknown };
+  fetched_at: string;
+}
+
+export interface ValidateGraphQLInput {
+  workspace_id: string;
+  /** Picks the cached schema. Without one only syntax and variables are checked. */
+  url: string;
+  query: string;
+  /** The variables as the editor holds them: a JSON object, or empty. */
+  variables?: string;
+  operation_name?: string;
+}
+
+/** Which editor a validation error points into. */
+export type GraphQLErrorSource = 'query' | 'variables';
+
+export interface GraphQLError {
+  message: string;
+  source: GraphQLErrorSource;
+  /** One-based. */
+  line: number;
+  /** One-based. */
+  column: number;
+}
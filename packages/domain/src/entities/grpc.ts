@@ -0,0 +1,70 @@
+import type { ExecutionTiming, KeyValue } from '../types/http';
+
+/** Where the service definitions for a gRPC call come from. */
+export type GrpcSchemaSource =
+  | { type: 'reflection' }
+  | {
+      type: 'proto_files';
+      files: string[];
+      /** Searched for imports before the directory of each file. */
+      include_paths?: string[];
+    };
+
+export interface GrpcServicesInput {
+  /** The server; only used with reflection. */
+  url?: string;
+  metadata?: KeyValue[];
+  source: GrpcSchemaSource;
+}
+
+export interface GrpcMethodInfo {
+  name: string;
+  input_type: string;
+  output_type: string;
+  client_streaming: boolean;
+  server_streaming: boolean;
+  /** The input message with every field at its default, to start editing from. */
+  input_template: unknown;
+}
+
+export interface GrpcServiceInfo {
+  /** The fully qualified name, e.g. `helloworld.Greeter`. */
+  name: string;
+  methods: GrpcMethodInfo[];
+}
+
+export interface GrpcCallInput {
+  /**
+   * `http://` for plaintext, `https://` for TLS. `grpc://` and `grpcs://` are
+   * accepted, and a bare `host:port` is plaintext.
+   */
+  url: string;
+  service: string;
+  method: string;
+  /** The request message as JSON. */
+  message?: string;
+  /** Values of `-bin` keys must be base64. */
+  metadata?: KeyValue[];
+  source: GrpcSchemaSource;
+  timeout_ms?: number | null;
+}
+
+/** The outcome of a unary or server-streaming call. */
+export interface GrpcResult {
+  /** The gRPC status code; 0 is OK. */
+  status_code: number;
+  status_name: string;
+  status_message: string;
+  headers: KeyValue[];
+  trailers: KeyValue[];
+  /** Response messages, in the order received. */
+  messages: unknown[];
+  size_bytes: number;
+  timing: ExecutionTiming;
+}
+
+/** Sent as the `grpc-message` event as each response message arrives. */
+export interface GrpcStreamMessage {
+  stream_id: string;
+  message: unknown;
+}
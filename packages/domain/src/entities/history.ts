@@ -8,14 +8,83 @@ export interface HistoryEntry {
   response_status: number | null;
   response_headers: string | null;
   response_body: string | null;
+  /** Set when the stored body was cut to the workspace's large body limit. */
+  response_body_truncated: boolean;
   response_size: number | null;
   duration_ms: number | null;
   error: string | null;
   executed_at: string;
 }
 
+export type HistorySort = 'newest_first' | 'oldest_first';
+
+/** Position of the last entry of the previous page, for keyset pagination. */
+export interface HistoryCursor {
+  executed_at: string;
+  id: string;
+}
+
 export interface HistoryQuery {
   workspace_id: string;
   limit?: number;
   offset?: number;
+  methods?: string[];
+  /** Exact codes such as 404; an entry matches if it matches these or `status_classes`. */
+  status_codes?: number[];
+  /** Leading digit of the status, e.g. 4 for 4xx. */
+  status_classes?: number[];
+  url_contains?: string;
+  host?: string;
+  request_id?: string;
+  /** Inclusive RFC 3339 bounds. */
+  executed_after?: string;
+  executed_before?: string;
+  errors_only?: boolean;
+  min_duration_ms?: number;
+  sort?: HistorySort;
+  after?: HistoryCursor;
+}
+
+export type LargeBodyMode = 'compress' | 'truncate';
+
+/** Per-workspace history limits; `null` disables a limit. */
+export interface HistoryRetention {
+  workspace_id: string;
+  max_entries: number | null;
+  max_age_days: number | null;
+  max_body_bytes: number | null;
+  large_body_bytes: number;
+  large_body_mode: LargeBodyMode;
+}
+
+export type RedactionMode = 'mask' | 'encrypt';
+
+/**
+ * Per-workspace rules for keeping credentials out of history. Authorization,
+ * Proxy-Authorization, Cookie and Set-Cookie headers and secret variable values
+ * are always redacted. Patterns are case-insensitive and support `*`.
+ */
+export interface HistoryRedaction {
+  workspace_id: string;
+  header_patterns: string[];
+  /** JSON body fields, form fields and query parameters. */
+  field_patterns: string[];
+  mode: RedactionMode;
+}
+
+export interface VacuumResult {
+  entries_pruned: number;
+  bytes_before: number;
+  bytes_after: number;
+  bytes_reclaimed: number;
+}
+
+export interface SaveHistoryInput {
+  history_id: string;
+  collection_id: string;
+  folder_id: string | null;
+  /** Defaults to the method and URL path. */
+  name?: string | null;
+  /** Also attach the recorded response to the new request as an example. */
+  save_response?: boolean;
 }
@@ -0,0 +1,57 @@
+import type { KeyValue } from '../types/http';
+
+/**
+ * A fixed response served instead of the request's examples. The body may use
+ * `{{id}}` path parameters, `{{query.name}}`, `{{header.name}}`, `{{method}}`,
+ * `{{path}}`, `{{body}}`, `{{$timestamp}}`, `{{$isoTimestamp}}`,
+ * `{{$randomUUID}}` and `{{$randomInt}}`.
+ */
+export interface MockResponse {
+  status: number;
+  headers?: KeyValue[];
+  body?: string;
+}
+
+/** Overrides for one saved request. Without one, the first example is served. */
+export interface MockRouteConfig {
+  request_id: string;
+  example_id?: string | null;
+  response?: MockResponse | null;
+  /** Replaces the server-wide delay for this route. */
+  delay_ms?: number | null;
+}
+
+export interface StartMockInput {
+  collection_id: string;
+  /** Any free port when omitted. */
+  port?: number | null;
+  routes?: MockRouteConfig[];
+  delay_ms?: number;
+}
+
+export interface MockServerInfo {
+  collection_id: string;
+  port: number;
+  url: string;
+  route_count: number;
+  started_at: string;
+}
+
+export type MockResponseSource = 'configured' | 'example' | 'missing' | 'not_found' | 'preflight';
+
+/** One request received by a mock server, also sent as the `mock-request` event. */
+export interface MockLogEntry {
+  id: string;
+  collection_id: string;
+  method: string;
+  path: string;
+  query: string | null;
+  request_headers: KeyValue[];
+  request_body: string | null;
+  request_id: string | null;
+  example_name: string | null;
+  source: MockResponseSource;
+  status: number;
+  duration_ms: number;
+  received_at: string;
+}
@@ -1,4 +1,22 @@
 import type { HttpMethod, KeyValue, BodyType } from '../types/http';
+import type { GrpcSchemaSource } from './grpc';
+
+/** Scripts kept for import/export fidelity; Steq does not run them yet. */
+export interface RequestScripts {
+  pre_request: string | null;
+  post_response: string | null;
+}
+
+/**
+ * What a saved request talks to. HTTP requests are sent by the executor; other
+ * kinds open a session using the request's URL, headers and query parameters.
+ * gRPC requests send their body as the JSON message and their headers as
+ * metadata.
+ */
+export type RequestKind =
+  | { type: 'http' }
+  | { type: 'websocket'; subprotocols: string[] }
+  | { type: 'grpc'; service: string; method: string; source: GrpcSchemaSource };
 
 export interface ApiRequest {
   id: string;
@@ -9,6 +27,10 @@ export interface ApiRequest {
   query_params: KeyValue[];
   body_type: BodyType;
   body_content: string | null;
+  description: string | null;
+  path_params: KeyValue[];
+  scripts: RequestScripts | null;
+  kind: RequestKind;
   collection_id: string | null;
   folder_id: string | null;
   sort_order: number;
@@ -24,6 +46,11 @@ export interface CreateRequestInput {
   query_params: KeyValue[];
   body_type: BodyType;
   body_content: string | null;
+  description?: string | null;
+  path_params?: KeyValue[];
+  scripts?: RequestScripts | null;
+  /** Defaults to HTTP. */
+  kind?: RequestKind;
   collection_id: string | null;
   folder_id: string | null;
 }
@@ -37,6 +64,10 @@ export interface UpdateRequestInput {
   query_params?: KeyValue[];
   body_type?: BodyType;
   body_content?: string | null;
+  description?: string | null;
+  path_params?: KeyValue[];
+  scripts?: RequestScripts | null;
+  kind?: RequestKind;
   collection_id?: string | null;
   folder_id?: string | null;
   sort_order?: number;
@@ -47,6 +78,18 @@ export interface ExecuteRequestInput {
   url: string;
   headers: KeyValue[];
   query_params: KeyValue[];
+  path_params?: KeyValue[];
   body_type: BodyType;
   body_content: string | null;
 }
+
+/**
+ * A request sent with `execute_request_stream`. Events are tagged with
+ * `stream_id`, which `stop_request_stream` also takes.
+ */
+export interface ExecuteStreamInput {
+  request: ExecuteRequestInput;
+  workspace_id: string;
+  request_id: string | null;
+  stream_id: string;
+}
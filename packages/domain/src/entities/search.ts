@@ -0,0 +1,23 @@
+export type SearchHitKind = 'request' | 'collection' | 'history';
+
+export interface SearchQuery {
+  workspace_id: string;
+  query: string;
+  kinds?: SearchHitKind[];
+  limit?: number;
+}
+
+export interface SnippetSegment {
+  text: string;
+  highlight: boolean;
+}
+
+export interface SearchHit {
+  kind: SearchHitKind;
+  id: string;
+  title: string;
+  subtitle: string | null;
+  collection_id: string | null;
+  snippet: SnippetSegment[];
+  rank: number;
+}
@@ -0,0 +1,58 @@
+import type { KeyValue } from '../types/http';
+
+export interface ConnectWebSocketInput {
+  workspace_id: string;
+  /** The saved request this session was opened from, if any. */
+  request_id?: string | null;
+  /** A `ws://` or `wss://` URL. `http(s)://` is accepted and switched over. */
+  url: string;
+  headers?: KeyValue[];
+  query_params?: KeyValue[];
+  /** Offered in `Sec-WebSocket-Protocol`; the server picks at most one. */
+  subprotocols?: string[];
+}
+
+/** A WebSocket connection and its transcript. */
+export interface WebSocketSession {
+  id: string;
+  workspace_id: string;
+  request_id: string | null;
+  url: string;
+  /** The subprotocol the server accepted. */
+  subprotocol: string | null;
+  opened_at: string;
+  /** `null` while the connection is open. */
+  closed_at: string | null;
+  close_code: number | null;
+  close_reason: string | null;
+  /** Set when the connection failed rather than closed cleanly. */
+  error: string | null;
+  message_count: number;
+}
+
+export type WebSocketMessageDirection = 'sent' | 'received';
+
+export type WebSocketMessageKind = 'text' | 'binary' | 'ping' | 'pong' | 'close';
+
+/** One frame in a transcript, also sent as the `websocket-message` event. */
+export interface WebSocketMessage {
+  id: string;
+  session_id: string;
+  direction: WebSocketMessageDirection;
+  kind: WebSocketMessageKind;
+  /**
+   * Text as is; binary, ping and pong payloads base64-encoded. Close frames
+   * hold the reason.
+   */
+  data: string;
+  /** Payload size in bytes. */
+  size: number;
+  created_at: string;
+}
+
+export interface SendWebSocketInput {
+  session_id: string;
+  kind: 'text' | 'binary';
+  /** Text as is; binary payloads base64-encoded. */
+  data: string;
+}
@@ -3,6 +3,14 @@ export * from './entities/collection';
 export * from './entities/environment';
 export * from './entities/workspace';
 export * from './entities/history';
+export * from './entities/search';
+export * from './entities/diff';
+export * from './entities/example';
+export * from './entities/mock';
+export * from './entities/capture';
+export * from './entities/websocket';
+export * from './entities/grpc';
+export * from './entities/graphql';
 export * from './types/http';
 export * from './operations/resolve-variables';
 export * from './operations/export-request';
@@ -31,3 +31,22 @@ export interface ExecutionResult {
   timing: ExecutionTiming;
   error: string | null;
 }
+
+/** One Server-Sent Event from a streamed response. */
+export interface SseEvent {
+  /** The last event id the server set, which carries over to later events. */
+  id: string | null;
+  /** `message` unless the server named the event. */
+  event: string;
+  data: string;
+  /** The reconnection delay the server asked for. */
+  retry_ms: number | null;
+  /** Time since the request was sent. */
+  received_ms: number;
+}
+
+/** Payload of the `sse-event` event while a stream is open. */
+export interface StreamEvent {
+  stream_id: string;
+  event: SseEvent;
+}